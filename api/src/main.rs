@@ -0,0 +1,70 @@
+use std::error::Error;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use async_graphql::http::GraphiQLSource;
+use async_graphql_axum::{GraphQL, GraphQLSubscription};
+use axum::{
+    response::{Html, IntoResponse},
+    routing::get,
+    Router, Server,
+};
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+
+use crate::schema::{build_schema, ApiContext};
+
+mod schema;
+
+#[derive(Parser)]
+#[command(name = "auction-api", about = "Serve AuctionPal auctions over GraphQL")]
+struct Args {
+    /// RPC endpoint of the cluster
+    #[arg(long, env = "RPC_URL", default_value = "http://localhost:8899")]
+    rpc_url: String,
+    /// WebSocket endpoint of the cluster, used by subscriptions
+    #[arg(long, env = "WS_URL", default_value = "ws://localhost:8900")]
+    ws_url: String,
+    /// Commitment level used for queries
+    #[arg(long, default_value = "confirmed")]
+    commitment: String,
+    /// Address of the deployed AuctionPal program
+    #[arg(long, env = "AUCTION_PROGRAM_ID")]
+    program_id: Pubkey,
+    /// Port of the HTTP server
+    #[arg(long, default_value_t = 3000)]
+    port: u16,
+}
+
+async fn graphiql() -> impl IntoResponse {
+    Html(
+        GraphiQLSource::build()
+            .endpoint("/")
+            .subscription_endpoint("/ws")
+            .finish(),
+    )
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let commitment: CommitmentConfig = args.commitment.parse()?;
+    let schema = build_schema(ApiContext {
+        rpc: Arc::new(RpcClient::new_with_commitment(args.rpc_url, commitment)),
+        ws_url: args.ws_url,
+        program_id: args.program_id,
+    });
+
+    let app = Router::new()
+        .route(
+            "/",
+            get(graphiql).post_service(GraphQL::new(schema.clone())),
+        )
+        .route_service("/ws", GraphQLSubscription::new(schema));
+
+    let addr = SocketAddr::from(([0, 0, 0, 0], args.port));
+    println!("GraphQL API listening on http://{}", addr);
+    Server::bind(&addr).serve(app.into_make_service()).await?;
+    Ok(())
+}
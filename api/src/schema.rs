@@ -0,0 +1,516 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_graphql::{Context, EmptyMutation, Object, Result, Schema, SimpleObject, Subscription};
+use auction_pal::event::BidEvent;
+use auction_pal::state::{Auction, BidHistory, ExhibitorProfile};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use futures_util::{Stream, StreamExt};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    nonblocking::{pubsub_client::PubsubClient, rpc_client::RpcClient},
+    rpc_config::{
+        RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionLogsConfig,
+        RpcTransactionLogsFilter,
+    },
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_program::{clock::UnixTimestamp, program_pack::Pack, pubkey::Pubkey};
+
+/// Offset of `exhibitor_pubkey` in an `Auction` account
+const EXHIBITOR_OFFSET: usize = 1;
+/// Offset of `highest_bidder_pubkey` in an `Auction` account
+const HIGHEST_BIDDER_OFFSET: usize = 113;
+
+pub type AuctionSchema = Schema<QueryRoot, EmptyMutation, SubscriptionRoot>;
+
+pub struct ApiContext {
+    pub rpc: Arc<RpcClient>,
+    pub ws_url: String,
+    pub program_id: Pubkey,
+}
+
+pub fn build_schema(api_context: ApiContext) -> AuctionSchema {
+    Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .data(api_context)
+        .finish()
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "Auction")]
+pub struct AuctionObject {
+    /// Escrow account pubkey of the auction
+    pub escrow: String,
+    /// Wallet pubkey of exhibitor
+    pub exhibitor: String,
+    /// Exhibiting NFT pubkey of exhibitor in escrow
+    pub exhibiting_nft_temp: String,
+    /// FT pubkey of exhibitor to get token into
+    pub exhibitor_ft_receiving: String,
+    /// Current price, 0 when bids are hidden
+    pub price: u64,
+    /// Auction start time
+    pub starts_at: i64,
+    /// Auction end time
+    pub end_at: i64,
+    /// Wallet pubkey of highest bidder, null without bids
+    pub highest_bidder: Option<String>,
+    /// Lowest price the exhibitor accepts
+    pub floor_price: u64,
+    /// Category of the exhibited NFT
+    pub category: String,
+    /// Mint of the FT the auction is priced in
+    pub ft_mint: String,
+    /// Maximum number of unique bidders, 0 when unlimited
+    pub max_bidders: u8,
+    /// Number of unique bidders so far
+    pub unique_bidder_count: u8,
+    /// Whether the exhibitor accepted the auction terms
+    pub terms_accepted: bool,
+    /// Whether bid prices are visible
+    pub bids_visible: bool,
+    /// Whether the auction settles itself on the last bid after expiry
+    pub auto_close: bool,
+    /// Whether the NFT is frozen in the winner's account once the auction closes
+    pub is_soulbound: bool,
+    /// Version of the account layout
+    pub version: u8,
+}
+
+impl AuctionObject {
+    fn new(escrow: &Pubkey, auction: &Auction) -> Self {
+        AuctionObject {
+            escrow: escrow.to_string(),
+            exhibitor: auction.exhibitor_pubkey.to_string(),
+            exhibiting_nft_temp: auction.exhibiting_nft_temp_pubkey.to_string(),
+            exhibitor_ft_receiving: auction.exhibitor_ft_receiving_pubkey.to_string(),
+            price: auction.visible_price(),
+            starts_at: auction.starts_at,
+            end_at: auction.end_at,
+            highest_bidder: (auction.highest_bidder_pubkey != Pubkey::default())
+                .then(|| auction.highest_bidder_pubkey.to_string()),
+            floor_price: auction.floor_price,
+            category: format!("{:?}", auction.category),
+            ft_mint: auction.ft_mint.to_string(),
+            max_bidders: auction.max_bidders,
+            unique_bidder_count: auction.unique_bidder_count,
+            terms_accepted: auction.terms_accepted,
+            bids_visible: auction.bids_visible,
+            auto_close: auction.auto_close,
+            is_soulbound: auction.is_soulbound,
+            version: auction.version,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "BidRecord")]
+pub struct BidRecordObject {
+    /// Wallet pubkey of the bidder
+    pub bidder: String,
+    /// Bid price
+    pub price: u64,
+    /// Time of the bid
+    pub bid_at: i64,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "BidHistory")]
+pub struct BidHistoryObject {
+    /// Number of bids ever added to the history
+    pub total_count: u64,
+    /// Latest bids, oldest first
+    pub bids: Vec<BidRecordObject>,
+}
+
+impl From<BidHistory> for BidHistoryObject {
+    fn from(history: BidHistory) -> Self {
+        let stored = (history.total_count as usize).min(history.bids.len());
+        let oldest = (history.total_count as usize).saturating_sub(stored);
+        let bids = (oldest..oldest + stored)
+            .map(|index| &history.bids[index % history.bids.len()])
+            .map(|bid| BidRecordObject {
+                bidder: bid.bidder.to_string(),
+                price: bid.price,
+                bid_at: bid.bid_at,
+            })
+            .collect();
+        BidHistoryObject {
+            total_count: history.total_count,
+            bids,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "ExhibitorProfile")]
+pub struct ExhibitorProfileObject {
+    /// Wallet pubkey of the exhibitor
+    pub exhibitor: String,
+    /// Number of auctions started by the exhibitor
+    pub auction_count: u32,
+    /// Escrow pubkey of the latest auction started by the exhibitor
+    pub latest_auction: String,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "BidEvent")]
+pub struct BidEventObject {
+    /// Signature of the transaction placing the bid
+    pub signature: String,
+    /// Escrow account pubkey of the auction
+    pub escrow: String,
+    /// Wallet pubkey of the new highest bidder
+    pub bidder: String,
+    /// Bid price, 0 when bids are hidden
+    pub price: u64,
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value).map_err(|_| format!("invalid pubkey {}", value).into())
+}
+
+/// Fetches the auctions matching `filter` on top of the `Auction` size filter
+async fn find_auctions(api: &ApiContext, filter: RpcFilterType) -> Result<Vec<(Pubkey, Auction)>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::DataSize(Auction::LEN as u64), filter]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let accounts = api
+        .rpc
+        .get_program_accounts_with_config(&api.program_id, config)
+        .await?;
+    Ok(accounts
+        .into_iter()
+        .filter_map(|(pubkey, account)| {
+            Auction::unpack(&account.data)
+                .ok()
+                .map(|auction| (pubkey, auction))
+        })
+        .collect())
+}
+
+/// Decodes a `Program data:` log line carrying a `Bid` event
+fn decode_bid_event(signature: &str, log: &str) -> Option<BidEventObject> {
+    let mut fields = log.strip_prefix("Program data: ")?.split(' ');
+    if STANDARD.decode(fields.next()?).ok()? != BidEvent::NAME {
+        return None;
+    }
+    let escrow = Pubkey::try_from(STANDARD.decode(fields.next()?).ok()?.as_slice()).ok()?;
+    let bidder = Pubkey::try_from(STANDARD.decode(fields.next()?).ok()?.as_slice()).ok()?;
+    let price = STANDARD.decode(fields.next()?).ok()?.try_into().ok()?;
+    Some(BidEventObject {
+        signature: signature.to_string(),
+        escrow: escrow.to_string(),
+        bidder: bidder.to_string(),
+        price: u64::from_le_bytes(price),
+    })
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Auction stored in the given escrow account
+    async fn auction_by_escrow(&self, ctx: &Context<'_>, escrow: String) -> Result<AuctionObject> {
+        let api = ctx.data::<ApiContext>()?;
+        let escrow = parse_pubkey(&escrow)?;
+        let auction = Auction::unpack(&api.rpc.get_account_data(&escrow).await?)?;
+        Ok(AuctionObject::new(&escrow, &auction))
+    }
+
+    /// Auctions exhibited by the given wallet
+    async fn auctions_by_exhibitor(
+        &self,
+        ctx: &Context<'_>,
+        exhibitor: String,
+    ) -> Result<Vec<AuctionObject>> {
+        let api = ctx.data::<ApiContext>()?;
+        let exhibitor = parse_pubkey(&exhibitor)?;
+        let filter = RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            EXHIBITOR_OFFSET,
+            exhibitor.as_ref(),
+        ));
+        Ok(find_auctions(api, filter)
+            .await?
+            .iter()
+            .map(|(escrow, auction)| AuctionObject::new(escrow, auction))
+            .collect())
+    }
+
+    /// Running auctions in which the given wallet holds the highest bid
+    async fn active_bids_for_bidder(
+        &self,
+        ctx: &Context<'_>,
+        bidder: String,
+    ) -> Result<Vec<AuctionObject>> {
+        let api = ctx.data::<ApiContext>()?;
+        let bidder = parse_pubkey(&bidder)?;
+        let filter = RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            HIGHEST_BIDDER_OFFSET,
+            bidder.as_ref(),
+        ));
+        let slot = api.rpc.get_slot().await?;
+        let now: UnixTimestamp = api.rpc.get_block_time(slot).await?;
+        Ok(find_auctions(api, filter)
+            .await?
+            .iter()
+            .filter(|(_, auction)| auction.end_at > now)
+            .map(|(escrow, auction)| AuctionObject::new(escrow, auction))
+            .collect())
+    }
+
+    /// Latest bids of the given auction, null when the history was never initialized
+    async fn bid_history(
+        &self,
+        ctx: &Context<'_>,
+        escrow: String,
+    ) -> Result<Option<BidHistoryObject>> {
+        let api = ctx.data::<ApiContext>()?;
+        let escrow = parse_pubkey(&escrow)?;
+        let (bid_history, _) =
+            Pubkey::find_program_address(&[b"bid_history", escrow.as_ref()], &api.program_id);
+        let account = api
+            .rpc
+            .get_account_with_commitment(&bid_history, api.rpc.commitment())
+            .await?
+            .value;
+        match account {
+            Some(account) => Ok(Some(BidHistory::unpack(&account.data)?.into())),
+            None => Ok(None),
+        }
+    }
+
+    /// Profile of the given exhibitor, null before their first auction
+    async fn exhibitor_profile(
+        &self,
+        ctx: &Context<'_>,
+        exhibitor: String,
+    ) -> Result<Option<ExhibitorProfileObject>> {
+        let api = ctx.data::<ApiContext>()?;
+        let exhibitor = parse_pubkey(&exhibitor)?;
+        let (profile, _) = Pubkey::find_program_address(
+            &[b"exhibitor_profile", exhibitor.as_ref()],
+            &api.program_id,
+        );
+        let account = api
+            .rpc
+            .get_account_with_commitment(&profile, api.rpc.commitment())
+            .await?
+            .value;
+        match account {
+            Some(account) => {
+                let profile = ExhibitorProfile::unpack(&account.data)?;
+                Ok(Some(ExhibitorProfileObject {
+                    exhibitor: exhibitor.to_string(),
+                    auction_count: profile.auction_count,
+                    latest_auction: profile.latest_auction.to_string(),
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+pub struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    /// Bids placed on any auction, or only on `escrow` when given
+    async fn bid_events(
+        &self,
+        ctx: &Context<'_>,
+        escrow: Option<String>,
+    ) -> Result<impl Stream<Item = BidEventObject>> {
+        let api = ctx.data::<ApiContext>()?;
+        let escrow = escrow.as_deref().map(parse_pubkey).transpose()?;
+        let ws_url = api.ws_url.clone();
+        let program_id = api.program_id;
+        let pubsub = PubsubClient::new(&ws_url).await?;
+
+        Ok(async_stream::stream! {
+            let subscription = pubsub
+                .logs_subscribe(
+                    RpcTransactionLogsFilter::Mentions(vec![program_id.to_string()]),
+                    RpcTransactionLogsConfig { commitment: None },
+                )
+                .await;
+            let Ok((mut logs, _unsubscribe)) = subscription else {
+                return;
+            };
+            while let Some(response) = logs.next().await {
+                if response.value.err.is_some() {
+                    continue;
+                }
+                for log in &response.value.logs {
+                    if let Some(event) = decode_bid_event(&response.value.signature, log) {
+                        if escrow.map_or(true, |escrow| escrow.to_string() == event.escrow) {
+                            yield event;
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::value;
+    use serde_json::{json, Value};
+    use solana_client::{mock_sender::Mocks, rpc_request::RpcRequest};
+
+    const ESCROW: &str = "11111114d3RrygbPdAtMuFnDmzsN8T5fYKVQ7FVr7";
+    const EXHIBITOR: &str = "11111112D1oxKts8YPdTJRG5FzxTNpMtWmq8hkVx3";
+    const BIDDER: &str = "111111152P2r5yt6odmBLPsFCLBrFisJ3aS7LqLAT";
+
+    fn auction_data() -> Vec<u8> {
+        let mut auction = Auction::unpack_unchecked(&[0; Auction::LEN]).unwrap();
+        auction.is_initialized = true;
+        auction.exhibitor_pubkey = parse_pubkey(EXHIBITOR).unwrap();
+        auction.highest_bidder_pubkey = parse_pubkey(BIDDER).unwrap();
+        auction.price = 250;
+        auction.bids_visible = true;
+        auction.starts_at = 1_700_000_000;
+        auction.end_at = 1_700_003_600;
+        auction.version = 3;
+        let mut data = vec![0; Auction::LEN];
+        Auction::pack(auction, &mut data).unwrap();
+        data
+    }
+
+    fn ui_account(data: &[u8]) -> Value {
+        json!({
+            "lamports": 1_000_000,
+            "data": [STANDARD.encode(data), "base64"],
+            "owner": Pubkey::default().to_string(),
+            "executable": false,
+            "rentEpoch": 0,
+            "space": data.len(),
+        })
+    }
+
+    /// Schema whose RPC client answers each request with its mocked response
+    fn schema(mocks: Vec<(RpcRequest, Value)>) -> AuctionSchema {
+        let mocks: Mocks = mocks.into_iter().collect();
+        build_schema(ApiContext {
+            rpc: Arc::new(RpcClient::new_mock_with_mocks(
+                "succeeds".to_string(),
+                mocks,
+            )),
+            ws_url: String::new(),
+            program_id: Pubkey::new_unique(),
+        })
+    }
+
+    #[tokio::test]
+    async fn auction_by_escrow_decodes_the_escrow_account() {
+        let schema = schema(vec![(
+            RpcRequest::GetAccountInfo,
+            json!({ "context": { "slot": 1 }, "value": ui_account(&auction_data()) }),
+        )]);
+
+        let response = schema
+            .execute(format!(
+                r#"{{ auctionByEscrow(escrow: "{}") {{ escrow exhibitor price endAt highestBidder version }} }}"#,
+                ESCROW
+            ))
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data,
+            value!({
+                "auctionByEscrow": {
+                    "escrow": ESCROW,
+                    "exhibitor": EXHIBITOR,
+                    "price": 250,
+                    "endAt": 1_700_003_600,
+                    "highestBidder": BIDDER,
+                    "version": 3,
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_pubkeys_are_reported() {
+        let response = schema(vec![])
+            .execute(r#"{ auctionByEscrow(escrow: "not-a-pubkey") { escrow } }"#)
+            .await;
+        assert_eq!(response.errors.len(), 1);
+        assert_eq!(response.errors[0].message, "invalid pubkey not-a-pubkey");
+    }
+
+    #[tokio::test]
+    async fn auctions_by_exhibitor_lists_the_matching_accounts() {
+        let schema = schema(vec![(
+            RpcRequest::GetProgramAccounts,
+            json!([{ "pubkey": ESCROW, "account": ui_account(&auction_data()) }]),
+        )]);
+
+        let response = schema
+            .execute(format!(
+                r#"{{ auctionsByExhibitor(exhibitor: "{}") {{ escrow exhibitor }} }}"#,
+                EXHIBITOR
+            ))
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data,
+            value!({
+                "auctionsByExhibitor": [{ "escrow": ESCROW, "exhibitor": EXHIBITOR }]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn bid_history_is_null_before_the_first_bid() {
+        let schema = schema(vec![(
+            RpcRequest::GetAccountInfo,
+            json!({ "context": { "slot": 1 }, "value": null }),
+        )]);
+
+        let response = schema
+            .execute(format!(
+                r#"{{ bidHistory(escrow: "{}") {{ totalCount }} }}"#,
+                ESCROW
+            ))
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(response.data, value!({ "bidHistory": null }));
+    }
+
+    #[test]
+    fn bid_event_logs_are_decoded() {
+        let escrow = parse_pubkey(ESCROW).unwrap();
+        let bidder = parse_pubkey(BIDDER).unwrap();
+        let log = format!(
+            "Program data: {} {} {} {}",
+            STANDARD.encode(BidEvent::NAME),
+            STANDARD.encode(escrow),
+            STANDARD.encode(bidder),
+            STANDARD.encode(250u64.to_le_bytes()),
+        );
+
+        let event = decode_bid_event("signature", &log).unwrap();
+        assert_eq!(event.signature, "signature");
+        assert_eq!(event.escrow, ESCROW);
+        assert_eq!(event.bidder, BIDDER);
+        assert_eq!(event.price, 250);
+        assert!(decode_bid_event("signature", "Program log: Bid").is_none());
+        assert!(decode_bid_event(
+            "signature",
+            &log.replacen(
+                &STANDARD.encode(BidEvent::NAME),
+                &STANDARD.encode(b"Cancel"),
+                1
+            )
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,435 @@
+use std::error::Error;
+use std::str::FromStr;
+
+use auction_pal::state::{Auction, AuctionCategory, GlobalConfig};
+use clap::{Parser, Subcommand};
+use solana_client::rpc_client::RpcClient;
+use solana_program::program_pack::Pack;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signature, Signer},
+    system_instruction, system_program, sysvar,
+    transaction::Transaction,
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+use spl_token::state::Account as TokenAccount;
+
+type CliResult<T> = Result<T, Box<dyn Error>>;
+
+#[derive(Parser)]
+#[command(
+    name = "auction-cli",
+    about = "Manage AuctionPal auctions from the command line"
+)]
+struct Cli {
+    /// RPC endpoint of the cluster
+    #[arg(long, default_value = "http://localhost:8899")]
+    rpc_url: String,
+    /// Commitment level used for queries and confirmations
+    #[arg(long, default_value = "confirmed")]
+    commitment: String,
+    /// Address of the deployed AuctionPal program
+    #[arg(long, env = "AUCTION_PROGRAM_ID")]
+    program_id: Pubkey,
+    /// Keypair signing the transactions, defaults to ~/.config/solana/id.json
+    #[arg(long)]
+    keypair: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Starts an auction for an NFT held in the keypair's associated token account
+    Exhibit {
+        /// Mint of the NFT to exhibit
+        #[arg(long)]
+        nft: Pubkey,
+        /// Initial price in the smallest FT unit
+        #[arg(long)]
+        price: u64,
+        /// Auction duration in seconds
+        #[arg(long)]
+        duration: u64,
+        /// Mint of the FT the auction is priced in
+        #[arg(long)]
+        ft_mint: Pubkey,
+        /// Auction category, from 0 (Art) to 5 (Other)
+        #[arg(long, default_value_t = 5)]
+        category: u8,
+    },
+    /// Bids on an auction from the keypair's associated FT account
+    Bid {
+        /// Escrow account of the auction
+        #[arg(long)]
+        escrow: Pubkey,
+        /// Bid price in the smallest FT unit
+        #[arg(long)]
+        price: u64,
+    },
+    /// Cancels an auction without bids and returns the NFT to the exhibitor
+    Cancel {
+        /// Escrow account of the auction
+        #[arg(long)]
+        escrow: Pubkey,
+    },
+    /// Closes an expired auction as its winner
+    Close {
+        /// Escrow account of the auction
+        #[arg(long)]
+        escrow: Pubkey,
+    },
+    /// Prints the state of an auction
+    Status {
+        /// Escrow account of the auction
+        #[arg(long)]
+        escrow: Pubkey,
+    },
+}
+
+fn main() {
+    if let Err(err) = run(Cli::parse()) {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> CliResult<()> {
+    let commitment = CommitmentConfig::from_str(&cli.commitment)?;
+    let client = RpcClient::new_with_commitment(cli.rpc_url, commitment);
+    let program_id = cli.program_id;
+
+    if let Command::Status { escrow } = cli.command {
+        return status(&client, &escrow);
+    }
+
+    let payer = load_keypair(cli.keypair.as_deref())?;
+    let signature = match cli.command {
+        Command::Exhibit {
+            nft,
+            price,
+            duration,
+            ft_mint,
+            category,
+        } => exhibit(
+            &client,
+            &program_id,
+            &payer,
+            nft,
+            price,
+            duration,
+            ft_mint,
+            category,
+        )?,
+        Command::Bid { escrow, price } => bid(&client, &program_id, &payer, escrow, price)?,
+        Command::Cancel { escrow } => cancel(&client, &program_id, &payer, escrow)?,
+        Command::Close { escrow } => close(&client, &program_id, &payer, escrow)?,
+        Command::Status { .. } => unreachable!(),
+    };
+    println!("Signature: {}", signature);
+    Ok(())
+}
+
+fn load_keypair(path: Option<&str>) -> CliResult<Keypair> {
+    let path = match path {
+        Some(path) => path.to_string(),
+        None => format!("{}/.config/solana/id.json", std::env::var("HOME")?),
+    };
+    read_keypair_file(&path)
+        .map_err(|err| format!("failed to read keypair {}: {}", path, err).into())
+}
+
+fn fetch_auction(client: &RpcClient, escrow: &Pubkey) -> CliResult<Auction> {
+    Ok(Auction::unpack(&client.get_account_data(escrow)?)?)
+}
+
+fn send(
+    client: &RpcClient,
+    payer: &Keypair,
+    instructions: &[Instruction],
+    extra_signers: &[&Keypair],
+) -> CliResult<Signature> {
+    let mut signers = vec![payer];
+    signers.extend_from_slice(extra_signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &signers,
+        client.get_latest_blockhash()?,
+    );
+    Ok(client.send_and_confirm_transaction(&transaction)?)
+}
+
+fn pda(seeds: &[&[u8]], program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(seeds, program_id).0
+}
+
+#[allow(clippy::too_many_arguments)]
+fn exhibit(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    nft: Pubkey,
+    price: u64,
+    duration: u64,
+    ft_mint: Pubkey,
+    category: u8,
+) -> CliResult<Signature> {
+    let category = AuctionCategory::try_from(category)?;
+    let exhibitor = payer.pubkey();
+    let temp_nft = Keypair::new();
+    let escrow = Keypair::new();
+
+    let mut data = vec![0];
+    data.extend_from_slice(&price.to_le_bytes());
+    data.extend_from_slice(&duration.to_le_bytes());
+    data.extend_from_slice(ft_mint.as_ref());
+    data.push(category as u8);
+
+    let instructions = [
+        create_associated_token_account_idempotent(
+            &exhibitor,
+            &exhibitor,
+            &ft_mint,
+            &spl_token::id(),
+        ),
+        system_instruction::create_account(
+            &exhibitor,
+            &temp_nft.pubkey(),
+            client.get_minimum_balance_for_rent_exemption(TokenAccount::LEN)?,
+            TokenAccount::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_account3(
+            &spl_token::id(),
+            &temp_nft.pubkey(),
+            &nft,
+            &exhibitor,
+        )?,
+        system_instruction::create_account(
+            &exhibitor,
+            &escrow.pubkey(),
+            client.get_minimum_balance_for_rent_exemption(Auction::LEN)?,
+            Auction::LEN as u64,
+            program_id,
+        ),
+        Instruction::new_with_bytes(
+            *program_id,
+            &data,
+            vec![
+                AccountMeta::new(exhibitor, true),
+                AccountMeta::new(get_associated_token_address(&exhibitor, &nft), false),
+                AccountMeta::new(temp_nft.pubkey(), false),
+                AccountMeta::new_readonly(
+                    get_associated_token_address(&exhibitor, &ft_mint),
+                    false,
+                ),
+                AccountMeta::new(escrow.pubkey(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(
+                    pda(&[b"exhibitor_profile", exhibitor.as_ref()], program_id),
+                    false,
+                ),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(pda(&[b"auction_index"], program_id), false),
+                AccountMeta::new(
+                    pda(&[b"category_index", &[category as u8]], program_id),
+                    false,
+                ),
+                AccountMeta::new_readonly(ft_mint, false),
+                AccountMeta::new(pda(&[b"stats"], program_id), false),
+                AccountMeta::new_readonly(pda(&[b"global_config"], program_id), false),
+                AccountMeta::new_readonly(
+                    pda(&[b"exhibitor_config", exhibitor.as_ref()], program_id),
+                    false,
+                ),
+                AccountMeta::new_readonly(
+                    pda(&[b"tombstone", escrow.pubkey().as_ref()], program_id),
+                    false,
+                ),
+            ],
+        ),
+    ];
+    let signature = send(client, payer, &instructions, &[&temp_nft, &escrow])?;
+    println!("Escrow: {}", escrow.pubkey());
+    println!("Bids are accepted once the exhibitor has accepted the auction terms");
+    Ok(signature)
+}
+
+fn bid(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    escrow: Pubkey,
+    price: u64,
+) -> CliResult<Signature> {
+    let auction = fetch_auction(client, &escrow)?;
+    let global_config_pda = pda(&[b"global_config"], program_id);
+    let global_config = GlobalConfig::unpack(&client.get_account_data(&global_config_pda)?)?;
+    let bidder = payer.pubkey();
+    let temp_ft = Keypair::new();
+
+    let mut data = vec![1];
+    data.extend_from_slice(&price.to_le_bytes());
+
+    let mut accounts = vec![
+        AccountMeta::new(bidder, true),
+        AccountMeta::new(auction.highest_bidder_pubkey, false),
+        AccountMeta::new(auction.highest_bidder_ft_temp_pubkey, false),
+        AccountMeta::new(auction.highest_bidder_ft_returning_pubkey, false),
+        AccountMeta::new(temp_ft.pubkey(), false),
+        AccountMeta::new(
+            get_associated_token_address(&bidder, &auction.ft_mint),
+            false,
+        ),
+        AccountMeta::new(escrow, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(pda(&[b"escrow"], program_id), false),
+        AccountMeta::new_readonly(global_config_pda, false),
+        AccountMeta::new(pda(&[b"bid_history", escrow.as_ref()], program_id), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(pda(&[b"stats"], program_id), false),
+    ];
+    if global_config.insurance_fee_bps > 0 {
+        accounts.push(AccountMeta::new(
+            pda(&[b"insurance_vault", auction.ft_mint.as_ref()], program_id),
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(auction.ft_mint, false));
+    }
+    if auction.max_bidders > 0 {
+        accounts.push(AccountMeta::new(
+            pda(&[b"bidder_registry", escrow.as_ref()], program_id),
+            false,
+        ));
+    }
+    if auction.upfront_royalty_bps > 0 && !auction.upfront_royalty_paid {
+        accounts.push(AccountMeta::new(auction.royalty_creator_ft_pubkey, false));
+    }
+
+    let instructions = [
+        system_instruction::create_account(
+            &bidder,
+            &temp_ft.pubkey(),
+            client.get_minimum_balance_for_rent_exemption(TokenAccount::LEN)?,
+            TokenAccount::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_account3(
+            &spl_token::id(),
+            &temp_ft.pubkey(),
+            &auction.ft_mint,
+            &bidder,
+        )?,
+        Instruction::new_with_bytes(*program_id, &data, accounts),
+    ];
+    send(client, payer, &instructions, &[&temp_ft])
+}
+
+fn cancel(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    escrow: Pubkey,
+) -> CliResult<Signature> {
+    let auction = fetch_auction(client, &escrow)?;
+    let exhibitor = payer.pubkey();
+    let nft_mint =
+        TokenAccount::unpack(&client.get_account_data(&auction.exhibiting_nft_temp_pubkey)?)?.mint;
+
+    let mut accounts = vec![
+        AccountMeta::new(exhibitor, true),
+        AccountMeta::new(auction.exhibiting_nft_temp_pubkey, false),
+        AccountMeta::new(get_associated_token_address(&exhibitor, &nft_mint), false),
+        AccountMeta::new(escrow, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(pda(&[b"escrow"], program_id), false),
+        AccountMeta::new(pda(&[b"stats"], program_id), false),
+    ];
+    if auction.is_soulbound {
+        accounts.push(AccountMeta::new(nft_mint, false));
+    }
+
+    let instructions = [
+        create_associated_token_account_idempotent(
+            &exhibitor,
+            &exhibitor,
+            &nft_mint,
+            &spl_token::id(),
+        ),
+        Instruction::new_with_bytes(*program_id, &[2], accounts),
+    ];
+    send(client, payer, &instructions, &[])
+}
+
+fn close(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    escrow: Pubkey,
+) -> CliResult<Signature> {
+    let auction = fetch_auction(client, &escrow)?;
+    let winner = payer.pubkey();
+    let nft_mint =
+        TokenAccount::unpack(&client.get_account_data(&auction.exhibiting_nft_temp_pubkey)?)?.mint;
+
+    let mut accounts = vec![
+        AccountMeta::new(winner, true),
+        AccountMeta::new(auction.exhibitor_pubkey, false),
+        AccountMeta::new(auction.exhibiting_nft_temp_pubkey, false),
+        AccountMeta::new(auction.exhibitor_ft_receiving_pubkey, false),
+        AccountMeta::new(auction.highest_bidder_ft_temp_pubkey, false),
+        AccountMeta::new(get_associated_token_address(&winner, &nft_mint), false),
+        AccountMeta::new(escrow, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(pda(&[b"escrow"], program_id), false),
+        AccountMeta::new(pda(&[b"stats"], program_id), false),
+        AccountMeta::new(pda(&[b"tombstone", escrow.as_ref()], program_id), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    if auction.is_soulbound {
+        accounts.push(AccountMeta::new_readonly(nft_mint, false));
+    }
+
+    let instructions = [
+        create_associated_token_account_idempotent(&winner, &winner, &nft_mint, &spl_token::id()),
+        Instruction::new_with_bytes(*program_id, &[3], accounts),
+    ];
+    send(client, payer, &instructions, &[])
+}
+
+fn status(client: &RpcClient, escrow: &Pubkey) -> CliResult<()> {
+    let auction = fetch_auction(client, escrow)?;
+    let or_none = |pubkey: Pubkey| {
+        if pubkey == Pubkey::default() {
+            "none".to_string()
+        } else {
+            pubkey.to_string()
+        }
+    };
+
+    println!("Escrow: {}", escrow);
+    println!("Exhibitor: {}", auction.exhibitor_pubkey);
+    println!("Category: {:?}", auction.category);
+    println!("FT mint: {}", auction.ft_mint);
+    if auction.bids_visible {
+        println!("Price: {}", auction.price);
+    } else {
+        println!("Price: hidden");
+    }
+    println!("Floor price: {}", auction.floor_price);
+    println!("Starts at: {}", auction.starts_at);
+    println!("Ends at: {}", auction.end_at);
+    println!("Highest bidder: {}", or_none(auction.highest_bidder_pubkey));
+    println!("Terms accepted: {}", auction.terms_accepted);
+    Ok(())
+}
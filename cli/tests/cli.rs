@@ -0,0 +1,116 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{write_keypair_file, Keypair};
+
+/// Address no RPC node listens on, so commands fail as soon as they reach the network
+const UNREACHABLE_RPC_URL: &str = "http://127.0.0.1:1";
+
+fn auction_cli() -> Command {
+    let mut command = Command::cargo_bin("auction-cli").unwrap();
+    command
+        .env_remove("AUCTION_PROGRAM_ID")
+        .args(["--rpc-url", UNREACHABLE_RPC_URL])
+        .args(["--program-id", &Pubkey::new_unique().to_string()]);
+    command
+}
+
+/// Keypair file in the temporary directory, unique to the test
+fn keypair_file(name: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("auction-cli-{}-{}.json", name, std::process::id()));
+    write_keypair_file(&Keypair::new(), &path).unwrap();
+    path
+}
+
+#[test]
+fn help_lists_the_subcommands() {
+    let mut assert = Command::cargo_bin("auction-cli")
+        .unwrap()
+        .arg("--help")
+        .assert()
+        .success();
+    for subcommand in ["exhibit", "bid", "cancel", "close", "status"] {
+        assert = assert.stdout(predicate::str::contains(subcommand));
+    }
+    for flag in ["--rpc-url", "--commitment", "--program-id", "--keypair"] {
+        assert = assert.stdout(predicate::str::contains(flag));
+    }
+}
+
+#[test]
+fn bid_requires_a_price() {
+    auction_cli()
+        .args(["bid", "--escrow", &Pubkey::new_unique().to_string()])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--price"));
+}
+
+#[test]
+fn escrow_must_be_a_pubkey() {
+    auction_cli()
+        .args(["status", "--escrow", "not-a-pubkey"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--escrow"));
+}
+
+#[test]
+fn program_id_is_required() {
+    Command::cargo_bin("auction-cli")
+        .unwrap()
+        .env_remove("AUCTION_PROGRAM_ID")
+        .args(["status", "--escrow", &Pubkey::new_unique().to_string()])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--program-id"));
+}
+
+#[test]
+fn unknown_commitment_is_refused() {
+    auction_cli()
+        .args(["--commitment", "eventually"])
+        .args(["status", "--escrow", &Pubkey::new_unique().to_string()])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("Error:"));
+}
+
+#[test]
+fn missing_keypair_is_reported() {
+    let path = std::env::temp_dir().join("auction-cli-missing-keypair.json");
+    auction_cli()
+        .args(["--keypair", path.to_str().unwrap()])
+        .args(["cancel", "--escrow", &Pubkey::new_unique().to_string()])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("failed to read keypair"));
+}
+
+#[test]
+fn exhibit_refuses_an_unknown_category() {
+    let keypair = keypair_file("category");
+    auction_cli()
+        .args(["--keypair", keypair.to_str().unwrap()])
+        .args(["exhibit", "--nft", &Pubkey::new_unique().to_string()])
+        .args(["--price", "100", "--duration", "3600"])
+        .args(["--ft-mint", &Pubkey::new_unique().to_string()])
+        .args(["--category", "9"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("Error:"));
+    std::fs::remove_file(keypair).unwrap();
+}
+
+#[test]
+fn status_reports_an_unreachable_cluster() {
+    auction_cli()
+        .args(["status", "--escrow", &Pubkey::new_unique().to_string()])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::starts_with("Error:"));
+}
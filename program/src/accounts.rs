@@ -0,0 +1,435 @@
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::program_error::ProgramError;
+
+/// Number of accounts expected by the `Exhibit` instruction
+pub const EXHIBIT_ACCOUNTS_LEN: usize = 17;
+
+/// Accounts taken by `Exhibit`, `BatchExhibit` and `ExhibitWithTimestamps`, in this order
+pub struct ExhibitAccounts<'a, 'b> {
+    /// `[signer, writable]` The exhibitor
+    pub exhibitor: &'a AccountInfo<'b>,
+    /// `[writable]` NFT account of the exhibitor
+    pub exhibitor_nft_account: &'a AccountInfo<'b>,
+    /// `[writable]` Temporary NFT account handed over to the escrow
+    pub nft_temp_account: &'a AccountInfo<'b>,
+    /// `[]` FT account of the exhibitor receiving the winning bid
+    pub ft_receiving_account: &'a AccountInfo<'b>,
+    /// `[writable]` The escrow account
+    pub escrow_account: &'a AccountInfo<'b>,
+    /// `[]` The rent sysvar
+    pub rent_sysvar: &'a AccountInfo<'b>,
+    /// `[]` The clock sysvar
+    pub clock_sysvar: &'a AccountInfo<'b>,
+    /// `[]` The token program
+    pub token_program: &'a AccountInfo<'b>,
+    /// `[writable]` Profile PDA of the exhibitor
+    pub exhibitor_profile: &'a AccountInfo<'b>,
+    /// `[]` The system program
+    pub system_program: &'a AccountInfo<'b>,
+    /// `[writable]` The auction index PDA
+    pub auction_index: &'a AccountInfo<'b>,
+    /// `[writable]` The index PDA of the category
+    pub category_index: &'a AccountInfo<'b>,
+    /// `[]` The FT mint the auction is priced in
+    pub ft_mint: &'a AccountInfo<'b>,
+    /// `[writable]` The program stats PDA
+    pub program_stats: &'a AccountInfo<'b>,
+    /// `[]` The global config PDA
+    pub global_config: &'a AccountInfo<'b>,
+    /// `[]` Config PDA of the exhibitor
+    pub exhibitor_config: &'a AccountInfo<'b>,
+    /// `[]` Tombstone PDA of the escrow, written when an auction on the escrow is closed
+    pub tombstone: &'a AccountInfo<'b>,
+    /// Optional accounts following the ones above
+    pub remaining: &'a [AccountInfo<'b>],
+}
+
+impl<'a, 'b> ExhibitAccounts<'a, 'b> {
+    /// Binds the leading `EXHIBIT_ACCOUNTS_LEN` accounts by name, leaving the rest in `remaining`
+    pub fn from_accounts(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+        Ok(ExhibitAccounts {
+            exhibitor: next_account_info(account_info_iter)?,
+            exhibitor_nft_account: next_account_info(account_info_iter)?,
+            nft_temp_account: next_account_info(account_info_iter)?,
+            ft_receiving_account: next_account_info(account_info_iter)?,
+            escrow_account: next_account_info(account_info_iter)?,
+            rent_sysvar: next_account_info(account_info_iter)?,
+            clock_sysvar: next_account_info(account_info_iter)?,
+            token_program: next_account_info(account_info_iter)?,
+            exhibitor_profile: next_account_info(account_info_iter)?,
+            system_program: next_account_info(account_info_iter)?,
+            auction_index: next_account_info(account_info_iter)?,
+            category_index: next_account_info(account_info_iter)?,
+            ft_mint: next_account_info(account_info_iter)?,
+            program_stats: next_account_info(account_info_iter)?,
+            global_config: next_account_info(account_info_iter)?,
+            exhibitor_config: next_account_info(account_info_iter)?,
+            tombstone: next_account_info(account_info_iter)?,
+            remaining: accounts.get(EXHIBIT_ACCOUNTS_LEN..).unwrap_or_default(),
+        })
+    }
+}
+
+/// Number of accounts expected by the `Bid` instruction before its optional accounts
+pub const BID_ACCOUNTS_LEN: usize = 15;
+
+/// Accounts taken by `Bid` and the instructions bidding through it, in this order
+pub struct BidAccounts<'a, 'b> {
+    /// `[signer, writable]` The bidder
+    pub bidder: &'a AccountInfo<'b>,
+    /// `[writable]` The current highest bidder
+    pub highest_bidder: &'a AccountInfo<'b>,
+    /// `[writable]` Temporary FT account of the current highest bidder
+    pub highest_bidder_ft_temp_account: &'a AccountInfo<'b>,
+    /// `[writable]` FT account the current highest bidder is refunded to
+    pub highest_bidder_ft_returning_account: &'a AccountInfo<'b>,
+    /// `[writable]` Temporary FT account of the bidder handed over to the escrow
+    pub bidder_ft_temp_account: &'a AccountInfo<'b>,
+    /// `[writable]` FT account of the bidder
+    pub bidder_ft_account: &'a AccountInfo<'b>,
+    /// `[writable]` The escrow account
+    pub escrow_account: &'a AccountInfo<'b>,
+    /// `[]` The clock sysvar
+    pub clock_sysvar: &'a AccountInfo<'b>,
+    /// `[]` The token program
+    pub token_program: &'a AccountInfo<'b>,
+    /// `[]` The escrow PDA
+    pub pda_account: &'a AccountInfo<'b>,
+    /// `[]` The global config PDA
+    pub global_config: &'a AccountInfo<'b>,
+    /// `[writable]` The bid history PDA
+    pub bid_history: &'a AccountInfo<'b>,
+    /// `[]` The rent sysvar
+    pub rent_sysvar: &'a AccountInfo<'b>,
+    /// `[]` The system program
+    pub system_program: &'a AccountInfo<'b>,
+    /// `[writable]` The program stats PDA
+    pub program_stats: &'a AccountInfo<'b>,
+    /// Optional accounts following the ones above
+    pub remaining: &'a [AccountInfo<'b>],
+}
+
+impl<'a, 'b> BidAccounts<'a, 'b> {
+    /// Binds the leading `BID_ACCOUNTS_LEN` accounts by name, leaving the rest in `remaining`
+    pub fn from_accounts(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+        Ok(BidAccounts {
+            bidder: next_account_info(account_info_iter)?,
+            highest_bidder: next_account_info(account_info_iter)?,
+            highest_bidder_ft_temp_account: next_account_info(account_info_iter)?,
+            highest_bidder_ft_returning_account: next_account_info(account_info_iter)?,
+            bidder_ft_temp_account: next_account_info(account_info_iter)?,
+            bidder_ft_account: next_account_info(account_info_iter)?,
+            escrow_account: next_account_info(account_info_iter)?,
+            clock_sysvar: next_account_info(account_info_iter)?,
+            token_program: next_account_info(account_info_iter)?,
+            pda_account: next_account_info(account_info_iter)?,
+            global_config: next_account_info(account_info_iter)?,
+            bid_history: next_account_info(account_info_iter)?,
+            rent_sysvar: next_account_info(account_info_iter)?,
+            system_program: next_account_info(account_info_iter)?,
+            program_stats: next_account_info(account_info_iter)?,
+            remaining: accounts.get(BID_ACCOUNTS_LEN..).unwrap_or_default(),
+        })
+    }
+}
+
+/// Number of accounts expected by the `Cancel` instruction before its optional accounts
+pub const CANCEL_ACCOUNTS_LEN: usize = 7;
+
+/// Accounts taken by `Cancel`, in this order
+pub struct CancelAccounts<'a, 'b> {
+    /// `[signer, writable]` The exhibitor
+    pub exhibitor: &'a AccountInfo<'b>,
+    /// `[writable]` Temporary NFT account held by the escrow
+    pub nft_temp_account: &'a AccountInfo<'b>,
+    /// `[writable]` NFT account of the exhibitor the NFT is returned to
+    pub nft_returning_account: &'a AccountInfo<'b>,
+    /// `[writable]` The escrow account
+    pub escrow_account: &'a AccountInfo<'b>,
+    /// `[]` The token program
+    pub token_program: &'a AccountInfo<'b>,
+    /// `[]` The escrow PDA
+    pub pda_account: &'a AccountInfo<'b>,
+    /// `[writable]` The program stats PDA
+    pub program_stats: &'a AccountInfo<'b>,
+    /// Optional accounts following the ones above
+    pub remaining: &'a [AccountInfo<'b>],
+}
+
+impl<'a, 'b> CancelAccounts<'a, 'b> {
+    /// Binds the leading `CANCEL_ACCOUNTS_LEN` accounts by name, leaving the rest in `remaining`
+    pub fn from_accounts(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+        Ok(CancelAccounts {
+            exhibitor: next_account_info(account_info_iter)?,
+            nft_temp_account: next_account_info(account_info_iter)?,
+            nft_returning_account: next_account_info(account_info_iter)?,
+            escrow_account: next_account_info(account_info_iter)?,
+            token_program: next_account_info(account_info_iter)?,
+            pda_account: next_account_info(account_info_iter)?,
+            program_stats: next_account_info(account_info_iter)?,
+            remaining: accounts.get(CANCEL_ACCOUNTS_LEN..).unwrap_or_default(),
+        })
+    }
+}
+
+/// Number of accounts expected by the `Close` instruction before its optional accounts
+pub const CLOSE_ACCOUNTS_LEN: usize = 13;
+
+/// Accounts taken by `Close`, in this order
+pub struct CloseAccounts<'a, 'b> {
+    /// `[signer, writable]` The closer, paying for the tombstone record
+    pub closer: &'a AccountInfo<'b>,
+    /// `[writable]` The exhibitor
+    pub exhibitor: &'a AccountInfo<'b>,
+    /// `[writable]` Temporary NFT account held by the escrow
+    pub nft_temp_account: &'a AccountInfo<'b>,
+    /// `[writable]` FT account of the exhibitor receiving the winning bid
+    pub ft_receiving_account: &'a AccountInfo<'b>,
+    /// `[writable]` Temporary FT account of the highest bidder
+    pub highest_bidder_ft_temp_account: &'a AccountInfo<'b>,
+    /// `[writable]` NFT account of the highest bidder receiving the NFT
+    pub highest_bidder_nft_receiving_account: &'a AccountInfo<'b>,
+    /// `[writable]` The escrow account
+    pub escrow_account: &'a AccountInfo<'b>,
+    /// `[]` The clock sysvar
+    pub clock_sysvar: &'a AccountInfo<'b>,
+    /// `[]` The token program
+    pub token_program: &'a AccountInfo<'b>,
+    /// `[]` The escrow PDA
+    pub pda_account: &'a AccountInfo<'b>,
+    /// `[writable]` The program stats PDA
+    pub program_stats: &'a AccountInfo<'b>,
+    /// `[writable]` The tombstone PDA
+    pub tombstone: &'a AccountInfo<'b>,
+    /// `[]` The system program
+    pub system_program: &'a AccountInfo<'b>,
+    /// Optional accounts following the ones above
+    pub remaining: &'a [AccountInfo<'b>],
+}
+
+impl<'a, 'b> CloseAccounts<'a, 'b> {
+    /// Binds the leading `CLOSE_ACCOUNTS_LEN` accounts by name, leaving the rest in `remaining`
+    pub fn from_accounts(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+        Ok(CloseAccounts {
+            closer: next_account_info(account_info_iter)?,
+            exhibitor: next_account_info(account_info_iter)?,
+            nft_temp_account: next_account_info(account_info_iter)?,
+            ft_receiving_account: next_account_info(account_info_iter)?,
+            highest_bidder_ft_temp_account: next_account_info(account_info_iter)?,
+            highest_bidder_nft_receiving_account: next_account_info(account_info_iter)?,
+            escrow_account: next_account_info(account_info_iter)?,
+            clock_sysvar: next_account_info(account_info_iter)?,
+            token_program: next_account_info(account_info_iter)?,
+            pda_account: next_account_info(account_info_iter)?,
+            program_stats: next_account_info(account_info_iter)?,
+            tombstone: next_account_info(account_info_iter)?,
+            system_program: next_account_info(account_info_iter)?,
+            remaining: accounts.get(CLOSE_ACCOUNTS_LEN..).unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::pubkey::Pubkey;
+
+    /// Keys of `len` accounts, each filled with its index
+    fn keys(len: usize) -> Vec<Pubkey> {
+        (0..len as u8)
+            .map(|i| Pubkey::new_from_array([i; 32]))
+            .collect()
+    }
+
+    fn account_infos<'a>(
+        keys: &'a [Pubkey],
+        lamports: &'a mut [u64],
+        data: &'a mut [[u8; 0]],
+        owner: &'a Pubkey,
+    ) -> Vec<AccountInfo<'a>> {
+        keys.iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, owner, false, 0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn exhibit_accounts_are_bound_in_order() {
+        let keys = keys(EXHIBIT_ACCOUNTS_LEN + 1);
+        let owner = Pubkey::default();
+        let mut lamports = vec![0; keys.len()];
+        let mut data = vec![[0u8; 0]; keys.len()];
+        let accounts = account_infos(&keys, &mut lamports, &mut data, &owner);
+
+        let exhibit_accounts = ExhibitAccounts::from_accounts(&accounts).unwrap();
+        let bound = [
+            exhibit_accounts.exhibitor,
+            exhibit_accounts.exhibitor_nft_account,
+            exhibit_accounts.nft_temp_account,
+            exhibit_accounts.ft_receiving_account,
+            exhibit_accounts.escrow_account,
+            exhibit_accounts.rent_sysvar,
+            exhibit_accounts.clock_sysvar,
+            exhibit_accounts.token_program,
+            exhibit_accounts.exhibitor_profile,
+            exhibit_accounts.system_program,
+            exhibit_accounts.auction_index,
+            exhibit_accounts.category_index,
+            exhibit_accounts.ft_mint,
+            exhibit_accounts.program_stats,
+            exhibit_accounts.global_config,
+            exhibit_accounts.exhibitor_config,
+            exhibit_accounts.tombstone,
+        ];
+        for (account, key) in bound.iter().zip(&keys) {
+            assert_eq!(account.key, key);
+        }
+        assert_eq!(exhibit_accounts.remaining.len(), 1);
+        assert_eq!(
+            exhibit_accounts.remaining[0].key,
+            &keys[EXHIBIT_ACCOUNTS_LEN]
+        );
+
+        assert!(
+            ExhibitAccounts::from_accounts(&accounts[..EXHIBIT_ACCOUNTS_LEN])
+                .unwrap()
+                .remaining
+                .is_empty()
+        );
+        assert_eq!(
+            ExhibitAccounts::from_accounts(&accounts[..EXHIBIT_ACCOUNTS_LEN - 1]).err(),
+            Some(ProgramError::NotEnoughAccountKeys)
+        );
+    }
+
+    #[test]
+    fn bid_accounts_are_bound_in_order() {
+        let keys = keys(BID_ACCOUNTS_LEN + 1);
+        let owner = Pubkey::default();
+        let mut lamports = vec![0; keys.len()];
+        let mut data = vec![[0u8; 0]; keys.len()];
+        let accounts = account_infos(&keys, &mut lamports, &mut data, &owner);
+
+        let bid_accounts = BidAccounts::from_accounts(&accounts).unwrap();
+        let bound = [
+            bid_accounts.bidder,
+            bid_accounts.highest_bidder,
+            bid_accounts.highest_bidder_ft_temp_account,
+            bid_accounts.highest_bidder_ft_returning_account,
+            bid_accounts.bidder_ft_temp_account,
+            bid_accounts.bidder_ft_account,
+            bid_accounts.escrow_account,
+            bid_accounts.clock_sysvar,
+            bid_accounts.token_program,
+            bid_accounts.pda_account,
+            bid_accounts.global_config,
+            bid_accounts.bid_history,
+            bid_accounts.rent_sysvar,
+            bid_accounts.system_program,
+            bid_accounts.program_stats,
+        ];
+        assert_eq!(bound.len(), BID_ACCOUNTS_LEN);
+        for (account, key) in bound.iter().zip(&keys) {
+            assert_eq!(account.key, key);
+        }
+        assert_eq!(bid_accounts.remaining.len(), 1);
+        assert_eq!(bid_accounts.remaining[0].key, &keys[BID_ACCOUNTS_LEN]);
+
+        assert!(BidAccounts::from_accounts(&accounts[..BID_ACCOUNTS_LEN])
+            .unwrap()
+            .remaining
+            .is_empty());
+        assert_eq!(
+            BidAccounts::from_accounts(&accounts[..BID_ACCOUNTS_LEN - 1]).err(),
+            Some(ProgramError::NotEnoughAccountKeys)
+        );
+    }
+
+    #[test]
+    fn cancel_accounts_are_bound_in_order() {
+        let keys = keys(CANCEL_ACCOUNTS_LEN + 1);
+        let owner = Pubkey::default();
+        let mut lamports = vec![0; keys.len()];
+        let mut data = vec![[0u8; 0]; keys.len()];
+        let accounts = account_infos(&keys, &mut lamports, &mut data, &owner);
+
+        let cancel_accounts = CancelAccounts::from_accounts(&accounts).unwrap();
+        let bound = [
+            cancel_accounts.exhibitor,
+            cancel_accounts.nft_temp_account,
+            cancel_accounts.nft_returning_account,
+            cancel_accounts.escrow_account,
+            cancel_accounts.token_program,
+            cancel_accounts.pda_account,
+            cancel_accounts.program_stats,
+        ];
+        assert_eq!(bound.len(), CANCEL_ACCOUNTS_LEN);
+        for (account, key) in bound.iter().zip(&keys) {
+            assert_eq!(account.key, key);
+        }
+        assert_eq!(cancel_accounts.remaining.len(), 1);
+        assert_eq!(
+            cancel_accounts.remaining[0].key,
+            &keys[CANCEL_ACCOUNTS_LEN]
+        );
+
+        assert!(
+            CancelAccounts::from_accounts(&accounts[..CANCEL_ACCOUNTS_LEN])
+                .unwrap()
+                .remaining
+                .is_empty()
+        );
+        assert_eq!(
+            CancelAccounts::from_accounts(&accounts[..CANCEL_ACCOUNTS_LEN - 1]).err(),
+            Some(ProgramError::NotEnoughAccountKeys)
+        );
+    }
+
+    #[test]
+    fn close_accounts_are_bound_in_order() {
+        let keys = keys(CLOSE_ACCOUNTS_LEN + 1);
+        let owner = Pubkey::default();
+        let mut lamports = vec![0; keys.len()];
+        let mut data = vec![[0u8; 0]; keys.len()];
+        let accounts = account_infos(&keys, &mut lamports, &mut data, &owner);
+
+        let close_accounts = CloseAccounts::from_accounts(&accounts).unwrap();
+        let bound = [
+            close_accounts.closer,
+            close_accounts.exhibitor,
+            close_accounts.nft_temp_account,
+            close_accounts.ft_receiving_account,
+            close_accounts.highest_bidder_ft_temp_account,
+            close_accounts.highest_bidder_nft_receiving_account,
+            close_accounts.escrow_account,
+            close_accounts.clock_sysvar,
+            close_accounts.token_program,
+            close_accounts.pda_account,
+            close_accounts.program_stats,
+            close_accounts.tombstone,
+            close_accounts.system_program,
+        ];
+        assert_eq!(bound.len(), CLOSE_ACCOUNTS_LEN);
+        for (account, key) in bound.iter().zip(&keys) {
+            assert_eq!(account.key, key);
+        }
+        assert_eq!(close_accounts.remaining.len(), 1);
+        assert_eq!(close_accounts.remaining[0].key, &keys[CLOSE_ACCOUNTS_LEN]);
+
+        assert!(CloseAccounts::from_accounts(&accounts[..CLOSE_ACCOUNTS_LEN])
+            .unwrap()
+            .remaining
+            .is_empty());
+        assert_eq!(
+            CloseAccounts::from_accounts(&accounts[..CLOSE_ACCOUNTS_LEN - 1]).err(),
+            Some(ProgramError::NotEnoughAccountKeys)
+        );
+    }
+}
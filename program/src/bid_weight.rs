@@ -0,0 +1,37 @@
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::pubkey::Pubkey;
+
+/// Instruction tag of a bid weight query
+pub const BID_WEIGHT_QUERY_TAG: u8 = 0;
+
+/// Weight of an unweighted bid, in basis points
+pub const UNIT_BID_WEIGHT_BPS: u64 = 10_000;
+
+/// Oracle weighting bids by other criteria than their price, as called by `Bid`
+/// Instruction data is the tag followed by the bidder pubkey, the oracle sets the little endian
+/// weight in basis points as its return data
+pub trait BidWeightOracle {
+    /// Program id of the oracle
+    fn program_id(&self) -> Pubkey;
+
+    /// Queries the weight of the bids of `bidder`
+    fn weight(&self, bidder: &Pubkey) -> Instruction {
+        let mut data = Vec::with_capacity(33);
+        data.push(BID_WEIGHT_QUERY_TAG);
+        data.extend_from_slice(bidder.as_ref());
+        Instruction::new_with_bytes(
+            self.program_id(),
+            &data,
+            vec![AccountMeta::new_readonly(*bidder, false)],
+        )
+    }
+}
+
+/// Bid weight oracle implementing the interface as is
+pub struct StandardBidWeightOracle(pub Pubkey);
+
+impl BidWeightOracle for StandardBidWeightOracle {
+    fn program_id(&self) -> Pubkey {
+        self.0
+    }
+}
@@ -13,7 +13,58 @@ pub enum AuctionError {#[error("Invalid Instruction Error: The provided instruct
     AlreadyBid, #[error("Auction Inactive Error: The auction has concluded and is no longer active.")]
     InactiveAuction,#[error("Auction Active Error: The auction is still ongoing.")]
     ActiveAuction,#[error("No Bidders Error: There are no bidders participating in this auction.")]
-    NoBidderFound,
+    NoBidderFound,#[error("Auction Not Found Error: The escrow account does not hold an initialized auction.")]
+    AuctionNotFound,#[error("Already Extended Error: The highest bidder has already extended this auction.")]
+    AlreadyExtended,#[error("Below Floor Price Error: The bid amount is below the floor price set by the exhibitor.")]
+    BelowFloorPrice,#[error("Unauthorized Error: The signer is not allowed to perform this operation.")]
+    Unauthorized,#[error("Token Account Frozen Error: The token account is frozen or not initialized.")]
+    TokenAccountFrozen,#[error("Invalid Fee Error: The fee basis points exceed the allowed maximum.")]
+    InvalidFeeBasisPoints,#[error("Insurance Claimed Error: Insurance has already been claimed for this auction.")]
+    InsuranceAlreadyClaimed,#[error("Note Already Set Error: The exhibitor note of this auction can't be changed.")]
+    NoteAlreadySet,#[error("Note Hash Error: The note hash doesn't match the note content.")]
+    NoteHashMismatch,#[error("Max Bidders Error: The auction has reached its maximum number of bidders.")]
+    MaxBiddersReached,#[error("Grace Period Error: The close grace period of this auction has not elapsed yet.")]
+    GracePeriodActive,#[error("Terms Not Accepted Error: The exhibitor has not attested the auction terms yet.")]
+    TermsNotAccepted,#[error("Terms Signature Error: The exhibitor's signature over the terms hash is missing or invalid.")]
+    InvalidTermsSignature,#[error("Not Registered Error: The participant is not registered to this auction.")]
+    NotRegistered,#[error("Auto Close Error: Automatic settlement is not enabled for this auction.")]
+    AutoCloseDisabled,#[error("Insufficient Escrow Error: The highest bidder's escrowed FT no longer covers the bid.")]
+    InsufficientEscrow,#[error("Proceeds Advanced Error: The exhibitor already withdrew part of the highest bid.")]
+    ProceedsAdvanced,#[error("Invalid Expiry Error: The new auction end time is in the past or exceeds the governance extension cap.")]
+    InvalidExpiry,#[error("Unexpected NFT Mint Error: The exhibited NFT is not of the expected mint.")]
+    UnexpectedNFTMint,#[error("Auction Not Started Error: The auction does not accept bids yet.")]
+    AuctionNotStarted,#[error("Unsupported Version Error: The auction account can't be migrated to the requested layout version.")]
+    UnsupportedVersion,#[error("Auction Too Short Error: The auction is shorter than the minimum duration.")]
+    AuctionTooShort,#[error("Invalid Score Error: The feedback score must be between 1 and 5.")]
+    InvalidScore,#[error("Feedback Window Closed Error: The feedback window of the auction has passed.")]
+    FeedbackWindowClosed,#[error("Not Whitelisted Error: The bidder is not in the whitelist of the auction room.")]
+    NotWhitelisted,#[error("Settlement Pending Error: The settlement of the auction is not final yet.")]
+    SettlementPending,#[error("Dispute Window Closed Error: The settlement of the auction is already final.")]
+    DisputeWindowClosed,#[error("Mediator Required Error: The auction has no mediator to arbitrate the dispute.")]
+    MediatorRequired,#[error("Invalid Owner Shares Error: The fractional owner shares must sum to 10000 basis points.")]
+    InvalidOwnerShares,#[error("Gate Token Error: The bidder doesn't hold enough of the gate token of the auction.")]
+    GateTokenRequirementNotMet,#[error("Invalid Lot Size Error: The lot size must be positive and covered by the exhibitor's balance.")]
+    InvalidLotSize,#[error("Winner Override Error: The exhibitor selected another winner, who must close the auction.")]
+    WinnerOverrideRequired,#[error("Lifetime Bid Cap Error: The bid takes the bidder's lifetime bid volume over the global cap.")]
+    LifetimeBidCapExceeded,#[error("NFT Locked Error: The exhibitor won the NFT too recently to list it again.")]
+    NFTLockedForRelisting,#[error("Transaction Limit Error: The price exceeds the transaction limit and the compliance signer did not sign.")]
+    TransactionLimitExceeded,#[error("Mint Blacklisted Error: The platform blacklisted the FT mint.")]
+    MintBlacklisted,#[error("Not Pre-Authorized Error: The exhibitor has not allowed a fee payer to bid for this bidder.")]
+    NotPreAuthorized,#[error("Bidder Signature Error: The bidder's signature over the bid is missing or invalid.")]
+    InvalidBidderSignature,#[error("Burn Failed Error: The exhibited NFT could not be burnt.")]
+    BurnFailed,#[error("NFT Updates Disabled Error: The exhibitor has not allowed updating the NFT during the auction.")]
+    NFTUpdatesDisabled,#[error("Bid Commitment Mismatch Error: The price and salt do not match the committed bid hash.")]
+    BidCommitmentMismatch,#[error("Stale Slot Hash Error: The slot hash of the bid commitment is no longer available.")]
+    StaleSlotHash,#[error("Trustline Error: The trustline program did not verify the bidder.")]
+    TrustlineNotVerified,#[error("Sealed Bid Error: Bids on a sealed bid auction must be committed and revealed through SubmitBid.")]
+    BidNotCommitted,#[error("Bid Nonce Error: The bid nonce doesn't match the auction's, the bid was replayed or is outdated.")]
+    InvalidBidNonce,#[error("No Royalty Error: The creator's royalty vault holds no royalty to claim.")]
+    NoRoyaltyToClaim,#[error("Globally Banned Error: The platform banned the bidder from all auctions.")]
+    GloballyBanned,#[error("Same Slot Bid Error: A bid in the slot of another bidder's bid must beat it by the anti-sandwich premium.")]
+    SameSlotBidTooLow,#[error("Price Curve Error: The tiers of the price curve must be sorted by ascending minimum price, followed by the unused tiers.")]
+    InvalidPriceCurve,#[error("Bid Weight Error: The bid weight oracle didn't return a valid weight for the bidder.")]
+    InvalidBidWeight,#[error("Escrow Migrated Error: The escrowed accounts were handed to another program, which now manages the auction.")]
+    EscrowMigrated,
 }
 
 impl From<AuctionError> for ProgramError {
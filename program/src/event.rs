@@ -0,0 +1,383 @@
+use solana_program::log::sol_log_data;
+use solana_program::pubkey::Pubkey;
+
+use crate::state::{Auction, ChangelogEntry, ProgramStats};
+
+pub struct AuctionStateEvent {
+    /// Escrow account pubkey of the auction
+    pub escrow_pubkey: Pubkey,
+    /// Wallet pubkey of exhibitor
+    pub exhibitor_pubkey: Pubkey,
+    /// Exhibiting NFT pubkey of exhibitor in escrow
+    pub exhibiting_nft_temp_pubkey: Pubkey,
+    /// FT pubkey of exhibitor to get token into
+    pub exhibitor_ft_receiving_pubkey: Pubkey,
+    /// Current price
+    pub price: u64,
+    /// Auction end time
+    pub end_at: i64,
+    /// Wallet pubkey of highest bidder
+    pub highest_bidder_pubkey: Pubkey,
+    /// FT pubkey of highest bidder in escrow
+    pub highest_bidder_ft_temp_pubkey: Pubkey,
+    /// FT pubkey of highest bidder to return to
+    pub highest_bidder_ft_returning_pubkey: Pubkey,
+    /// Mint of the FT the auction is priced in
+    pub ft_mint: Pubkey,
+    /// Minimum price the exhibitor accepts
+    pub floor_price: u64,
+    /// Auction start time
+    pub starts_at: i64,
+    /// Auction type
+    pub auction_type: u8,
+    /// Auction category
+    pub category: u8,
+    /// Layout version of the escrow account
+    pub version: u8,
+    /// Whether bids are publicly visible
+    pub bids_visible: bool,
+    /// Whether bidding is locked
+    pub bid_locked: bool,
+    /// Whether the highest bidder already extended the deadline
+    pub bidder_extended: bool,
+    /// Maximum number of unique bidders, 0 when unlimited
+    pub max_bidders: u8,
+    /// Number of unique bidders so far
+    pub unique_bidder_count: u8,
+    /// Minimum amount a new bid must exceed the current price by
+    pub min_bid_increment: u64,
+    /// Lot size every bid must be a multiple of
+    pub lot_size: u64,
+    /// Upfront royalty in basis points
+    pub upfront_royalty_bps: u16,
+    /// Room fee in basis points
+    pub room_fee_bps: u16,
+    /// Time of the last bid
+    pub last_bid_at: i64,
+    /// Nonce incremented on every bid
+    pub bid_nonce: u64,
+    /// Whether the auction is finalized
+    pub is_finalized: bool,
+    /// Whether the auction is disputed
+    pub is_disputed: bool,
+}
+
+impl AuctionStateEvent {
+    pub const NAME: &'static [u8] = b"AuctionState";
+
+    /// State of the auction at `now`, whose end time is logged as `Auction::visible_end_at`
+    pub fn new(escrow_pubkey: &Pubkey, auction_info: &Auction, now: i64) -> Self {
+        AuctionStateEvent {
+            escrow_pubkey: *escrow_pubkey,
+            exhibitor_pubkey: auction_info.exhibitor_pubkey,
+            exhibiting_nft_temp_pubkey: auction_info.exhibiting_nft_temp_pubkey,
+            exhibitor_ft_receiving_pubkey: auction_info.exhibitor_ft_receiving_pubkey,
+            price: auction_info.price,
+            end_at: auction_info.visible_end_at(now),
+            highest_bidder_pubkey: auction_info.highest_bidder_pubkey,
+            highest_bidder_ft_temp_pubkey: auction_info.highest_bidder_ft_temp_pubkey,
+            highest_bidder_ft_returning_pubkey: auction_info.highest_bidder_ft_returning_pubkey,
+            ft_mint: auction_info.ft_mint,
+            floor_price: auction_info.floor_price,
+            starts_at: auction_info.starts_at,
+            auction_type: auction_info.auction_type as u8,
+            category: auction_info.category as u8,
+            version: auction_info.version,
+            bids_visible: auction_info.bids_visible,
+            bid_locked: auction_info.bid_locked,
+            bidder_extended: auction_info.bidder_extended,
+            max_bidders: auction_info.max_bidders,
+            unique_bidder_count: auction_info.unique_bidder_count,
+            min_bid_increment: auction_info.min_bid_increment,
+            lot_size: auction_info.lot_size,
+            upfront_royalty_bps: auction_info.upfront_royalty_bps,
+            room_fee_bps: auction_info.room_fee_bps,
+            last_bid_at: auction_info.last_bid_at,
+            bid_nonce: auction_info.bid_nonce,
+            is_finalized: auction_info.is_finalized,
+            is_disputed: auction_info.is_disputed,
+        }
+    }
+
+    pub fn emit(&self) {
+        sol_log_data(&[
+            Self::NAME,
+            self.escrow_pubkey.as_ref(),
+            self.exhibitor_pubkey.as_ref(),
+            self.exhibiting_nft_temp_pubkey.as_ref(),
+            self.exhibitor_ft_receiving_pubkey.as_ref(),
+            &self.price.to_le_bytes(),
+            &self.end_at.to_le_bytes(),
+            self.highest_bidder_pubkey.as_ref(),
+            self.highest_bidder_ft_temp_pubkey.as_ref(),
+            self.highest_bidder_ft_returning_pubkey.as_ref(),
+            self.ft_mint.as_ref(),
+            &self.floor_price.to_le_bytes(),
+            &self.starts_at.to_le_bytes(),
+            &[self.auction_type],
+            &[self.category],
+            &[self.version],
+            &[self.bids_visible as u8],
+            &[self.bid_locked as u8],
+            &[self.bidder_extended as u8],
+            &[self.max_bidders],
+            &[self.unique_bidder_count],
+            &self.min_bid_increment.to_le_bytes(),
+            &self.lot_size.to_le_bytes(),
+            &self.upfront_royalty_bps.to_le_bytes(),
+            &self.room_fee_bps.to_le_bytes(),
+            &self.last_bid_at.to_le_bytes(),
+            &self.bid_nonce.to_le_bytes(),
+            &[self.is_finalized as u8],
+            &[self.is_disputed as u8],
+        ]);
+    }
+}
+
+pub struct NewAuctionEvent {
+    /// Wallet pubkey of exhibitor
+    pub exhibitor: Pubkey,
+    /// Escrow account pubkey of the auction
+    pub escrow: Pubkey,
+    /// Mint of the exhibited NFT
+    pub nft_mint: Pubkey,
+    /// Initial NFT price
+    pub initial_price: u64,
+    /// Auction end time
+    pub end_at: i64,
+}
+
+impl NewAuctionEvent {
+    pub const NAME: &'static [u8] = b"NewAuction";
+
+    pub fn emit(&self) {
+        sol_log_data(&[
+            Self::NAME,
+            self.exhibitor.as_ref(),
+            self.escrow.as_ref(),
+            self.nft_mint.as_ref(),
+            &self.initial_price.to_le_bytes(),
+            &self.end_at.to_le_bytes(),
+        ]);
+    }
+}
+
+pub struct BidEvent {
+    /// Escrow account pubkey of the auction
+    pub escrow: Pubkey,
+    /// Wallet pubkey of the highest bidder
+    pub bidder: Pubkey,
+    /// Current price
+    pub price: u64,
+}
+
+impl BidEvent {
+    pub const NAME: &'static [u8] = b"Bid";
+
+    pub fn emit(&self) {
+        sol_log_data(&[
+            Self::NAME,
+            self.escrow.as_ref(),
+            self.bidder.as_ref(),
+            &self.price.to_le_bytes(),
+        ]);
+    }
+}
+
+pub struct ForfeitedAuctionEvent {
+    /// Escrow account pubkey of the auction
+    pub escrow: Pubkey,
+    /// Wallet pubkey of exhibitor
+    pub exhibitor: Pubkey,
+    /// Wallet pubkey of the unresponsive winner
+    pub winner: Pubkey,
+    /// Winning price refunded to the winner
+    pub price: u64,
+}
+
+impl ForfeitedAuctionEvent {
+    pub const NAME: &'static [u8] = b"ForfeitedAuction";
+
+    pub fn emit(&self) {
+        sol_log_data(&[
+            Self::NAME,
+            self.escrow.as_ref(),
+            self.exhibitor.as_ref(),
+            self.winner.as_ref(),
+            &self.price.to_le_bytes(),
+        ]);
+    }
+}
+
+pub struct ProgramStatsEvent {
+    /// Number of auctions ever exhibited
+    pub total_auctions: u64,
+    /// Sum of all bid prices
+    pub total_volume: u64,
+    /// Number of bids ever placed
+    pub total_bids: u64,
+    /// Number of auctions not closed yet
+    pub active_auctions: u32,
+}
+
+impl ProgramStatsEvent {
+    pub const NAME: &'static [u8] = b"ProgramStats";
+
+    pub fn new(program_stats: &ProgramStats) -> Self {
+        ProgramStatsEvent {
+            total_auctions: program_stats.total_auctions,
+            total_volume: program_stats.total_volume,
+            total_bids: program_stats.total_bids,
+            active_auctions: program_stats.active_auctions,
+        }
+    }
+
+    pub fn emit(&self) {
+        sol_log_data(&[
+            Self::NAME,
+            &self.total_auctions.to_le_bytes(),
+            &self.total_volume.to_le_bytes(),
+            &self.total_bids.to_le_bytes(),
+            &self.active_auctions.to_le_bytes(),
+        ]);
+    }
+}
+
+pub struct ExpiryUpdatedEvent {
+    /// Escrow account pubkey of the auction
+    pub escrow: Pubkey,
+    /// Auction end time before the update, `i64::MAX` while it is sealed
+    pub previous_end_at: i64,
+    /// Auction end time after the update, `i64::MAX` while it is sealed
+    pub new_end_at: i64,
+}
+
+impl ExpiryUpdatedEvent {
+    pub const NAME: &'static [u8] = b"ExpiryUpdated";
+
+    pub fn emit(&self) {
+        sol_log_data(&[
+            Self::NAME,
+            self.escrow.as_ref(),
+            &self.previous_end_at.to_le_bytes(),
+            &self.new_end_at.to_le_bytes(),
+        ]);
+    }
+}
+
+pub struct ValidationPassedEvent {
+    /// Escrow account pubkey of the auction
+    pub escrow: Pubkey,
+    /// Wallet pubkey of the prospective bidder
+    pub bidder: Pubkey,
+    /// Bid price that would be accepted
+    pub expected_price: u64,
+}
+
+impl ValidationPassedEvent {
+    pub const NAME: &'static [u8] = b"ValidationPassed";
+
+    pub fn emit(&self) {
+        sol_log_data(&[
+            Self::NAME,
+            self.escrow.as_ref(),
+            self.bidder.as_ref(),
+            &self.expected_price.to_le_bytes(),
+        ]);
+    }
+}
+
+pub struct NextBidInfoEvent {
+    /// Escrow account pubkey of the auction
+    pub escrow: Pubkey,
+    /// Minimum price the next bid must have
+    pub min_next_bid: u64,
+    /// Seconds until the auction ends, negative once ended
+    pub time_remaining: i64,
+    /// Number of bids placed so far
+    pub bid_count: u64,
+    /// Whether the exhibitor set a floor price
+    pub has_reserve: bool,
+    /// Whether the current price reaches the floor price
+    pub reserve_met: bool,
+}
+
+impl NextBidInfoEvent {
+    pub const NAME: &'static [u8] = b"NextBidInfo";
+
+    pub fn emit(&self) {
+        sol_log_data(&[
+            Self::NAME,
+            self.escrow.as_ref(),
+            &self.min_next_bid.to_le_bytes(),
+            &self.time_remaining.to_le_bytes(),
+            &self.bid_count.to_le_bytes(),
+            &[self.has_reserve as u8],
+            &[self.reserve_met as u8],
+        ]);
+    }
+}
+
+pub struct ChangelogEntryEvent {
+    /// Escrow account pubkey of the auction
+    pub escrow: Pubkey,
+    /// Position of the entry since the changelog was created
+    pub index: u64,
+    /// Tag of the executed instruction
+    pub instruction: u8,
+    /// First signer of the instruction
+    pub actor: Pubkey,
+    /// Time the instruction was executed
+    pub timestamp: i64,
+    /// Instruction specific data
+    pub extra: [u8; 8],
+}
+
+impl ChangelogEntryEvent {
+    pub const NAME: &'static [u8] = b"ChangelogEntry";
+
+    pub fn new(escrow: Pubkey, index: u64, entry: &ChangelogEntry) -> Self {
+        Self {
+            escrow,
+            index,
+            instruction: entry.instruction,
+            actor: entry.actor,
+            timestamp: entry.timestamp,
+            extra: entry.extra,
+        }
+    }
+
+    pub fn emit(&self) {
+        sol_log_data(&[
+            Self::NAME,
+            self.escrow.as_ref(),
+            &self.index.to_le_bytes(),
+            &[self.instruction],
+            self.actor.as_ref(),
+            &self.timestamp.to_le_bytes(),
+            &self.extra,
+        ]);
+    }
+}
+
+pub struct TWAPEvent {
+    /// Escrow account pubkey of the auction
+    pub escrow: Pubkey,
+    /// Time weighted average bid price, 0 before the second bid or while bids are hidden
+    pub twap: u64,
+    /// Seconds the average covers
+    pub duration_sec: u64,
+}
+
+impl TWAPEvent {
+    pub const NAME: &'static [u8] = b"TWAP";
+
+    pub fn emit(&self) {
+        sol_log_data(&[
+            Self::NAME,
+            self.escrow.as_ref(),
+            &self.twap.to_le_bytes(),
+            &self.duration_sec.to_le_bytes(),
+        ]);
+    }
+}
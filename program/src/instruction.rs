@@ -1,5 +1,24 @@
 use crate::error::AuctionError::InvalidInstruction;
+use crate::state::{
+    AuctionCategory, AuctionType, EXHIBITOR_NOTE_LEN, MAX_ACCEPTED_MINTS, MAX_BATCH_BID,
+    MAX_PRICE_TIERS, ROOM_DESCRIPTION_LEN,
+};
 use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+/// Maximum number of auctions that can be exhibited in a single `BatchExhibit`
+pub const MAX_BATCH_EXHIBIT: u8 = 4;
+
+pub struct ExhibitConfig {
+    /// Initial NFT price
+    pub initial_price: u64,
+    /// Auction duration
+    pub seconds: u64,
+    /// Mint of the FT the auction is priced in
+    pub ft_mint: Pubkey,
+    /// Auction category
+    pub category: AuctionCategory,
+}
 
 pub enum AuctionInstruction {
     /// Starts the auction by creating and populating an escrow account and transferring ownership of the given temp NFT account to the PDA
@@ -7,37 +26,128 @@ pub enum AuctionInstruction {
     ///
     /// Accounts expected:
     ///
-    /// 0. `[signer]` The account of the person starting the auction
-    /// 1. `[writable]` Temporary NFT account that should be created prior to this instruction and owned by the exhibitor
-    /// 2. `[]` The exhibitor's NFT account for the token they will receive should the trade go through
-    /// 3. `[writable]` The escrow account, it will hold all necessary info about the auction.
-    /// 4. `[]` The rent sysvar
-    /// 4. `[]` The clock sysvar
-    /// 5. `[]` The token program
+    /// 0. `[signer, writable]` The account of the person starting the auction
+    /// 1. `[writable]` The exhibitor's NFT account holding the token to exhibit
+    /// 2. `[writable]` Temporary NFT account that should be created prior to this instruction and owned by the exhibitor
+    /// 3. `[]` The exhibitor's FT account for the token they will receive should the trade go through, must be of `ft_mint`
+    /// 4. `[writable]` The escrow account, it will hold all necessary info about the auction.
+    /// 5. `[]` The rent sysvar
+    /// 6. `[]` The clock sysvar
+    /// 7. `[]` The token program
+    /// 8. `[writable]` The exhibitor profile PDA account, seeded by `[b"exhibitor_profile", exhibitor_pubkey]`
+    /// 9. `[]` The system program
+    /// 10. `[writable]` The global auction index PDA account, seeded by `[b"auction_index"]`
+    /// 11. `[writable]` The category index PDA account, seeded by `[b"category_index", category]`
+    /// 12. `[]` The FT mint the auction is priced in
+    /// 13. `[writable]` The program stats PDA account, seeded by `[b"stats"]`
+    /// 14. `[]` The global config PDA account
+    /// 15. `[]` The exhibitor config PDA account, seeded by `[b"exhibitor_config", exhibitor_pubkey]`, may be uninitialized
+    /// 16. `[]` The tombstone PDA account, seeded by `[b"tombstone", escrow_pubkey]`, must be uninitialized as a closed escrow can't be reused
+    ///
+    /// To exhibit in an auction room, the following account is also expected:
+    ///
+    /// 17. `[]` The auction room PDA account, seeded by `[b"room", room_id]`
+    ///
+    /// When the program authority blacklisted FT mints or set an NFT lock period, the following accounts are expected last:
+    ///
+    /// 1. `[]` The mint blacklist PDA account, seeded by `[b"blacklist", ft_mint]`, may be uninitialized, when mints are blacklisted
+    /// 2. `[]` The NFT lock record PDA account, seeded by `[b"nft_lock", nft_mint, exhibitor_pubkey]`, may be uninitialized, when a lock period is set
     Exhibit {
         /// Initial NFT price
         initial_price: u64,
         /// Auction duration
         seconds: u64,
+        /// Mint of the FT the auction is priced in
+        ft_mint: Pubkey,
+        /// Auction category, optional and `Other` when omitted
+        category: AuctionCategory,
+        /// Seconds before the auction accepts bids, optional and 0 when omitted
+        start_delay_sec: u64,
     },
 
     /// Bid on the auction and transfer ownership of the given temp FT account to the PDA
+    /// The nonce must match the auction's bid nonce, so the same bid transaction can't succeed twice
     ///
     ///
     /// Accounts expected:
     ///
-    /// 0. `[signer]`  The account of the person bidding on the auction
-    /// 1. `[writable]` The current highest bidder's temporary FT account
-    /// 2. `[writable]` The current highest bidder's FT account to get back to when the other person become the highest bidder
-    /// 3. `[writable]` The bidder's temporary FT account for depositing FT in escrow
-    /// 4. `[writable]` The bidder's FT account to get back to when the other person become the highest bidder
-    /// 5. `[writable]` The escrow account, it will hold all necessary info about the auction.
-    /// 6. `[]` The clock sysvar
-    /// 7. `[]` The token program
-    /// 8. `[]` The PDA account
+    /// 0. `[signer, writable]`  The account of the person bidding on the auction, pays for the bid history on the first bid
+    /// 1. `[writable]` The current highest bidder's account to return the temporary account rent to
+    /// 2. `[writable]` The current highest bidder's temporary FT account
+    /// 3. `[writable]` The current highest bidder's FT account to get back to when the other person become the highest bidder
+    /// 4. `[writable]` The bidder's temporary FT account for depositing FT in escrow
+    /// 5. `[writable]` The bidder's FT account to get back to when the other person become the highest bidder
+    /// 6. `[writable]` The escrow account, it will hold all necessary info about the auction.
+    /// 7. `[]` The clock sysvar
+    /// 8. `[]` The token program
+    /// 9. `[]` The PDA account
+    /// 10. `[]` The global config PDA account
+    /// 11. `[writable]` The bid history PDA account, seeded by `[b"bid_history", escrow_pubkey]`, created on the first bid
+    /// 12. `[]` The rent sysvar
+    /// 13. `[]` The system program
+    /// 14. `[writable]` The program stats PDA account, seeded by `[b"stats"]`
+    ///
+    /// When the global insurance fee is set, the following accounts are also expected:
+    ///
+    /// 15. `[writable]` The insurance vault PDA account, seeded by `[b"insurance_vault", ft_mint]`
+    /// 16. `[]` The FT mint
+    ///
+    /// When the exhibitor set a maximum number of bidders, the following account is expected last:
+    ///
+    /// 17. `[writable]` The bidder registry PDA account, seeded by `[b"bidder_registry", escrow_pubkey]`
+    ///
+    /// When the upfront royalty is set and not paid yet, the following account is expected last:
+    ///
+    /// 18. `[writable]` The creator's royalty vault PDA account, seeded by `[b"royalty", ft_mint, creator_pubkey]`
+    ///
+    /// When the auction belongs to a room with a fee, the following account is expected last:
+    ///
+    /// 19. `[writable]` The room organizer's FT account receiving the room fee
+    ///
+    /// When the current highest bid was withdrawn from a vault, the following accounts are expected last:
+    ///
+    /// 20. `[]` The vault program of the current highest bidder
+    /// 21. `[writable]` The current highest bidder's position account in the vault
+    /// 22. `[writable]` The FT account of the vault
+    ///
+    /// When the exhibitor gated the auction with a token, the following account is expected last:
+    ///
+    /// 23. `[]` The bidder's account of the gate token
+    ///
+    /// When the program authority capped the lifetime bid volume, the following account is expected last:
+    ///
+    /// 24. `[writable]` The bidder's lifetime bid volume PDA account, seeded by `[b"ltv", bidder_pubkey]`
+    ///
+    /// When the program authority blacklisted FT mints, the following account is expected last:
+    ///
+    /// 25. `[]` The mint blacklist PDA account of the bid mint, seeded by `[b"blacklist", mint]`, may be uninitialized
+    ///
+    /// When the program authority banned wallets, the following account is expected last:
+    ///
+    /// 26. `[]` The global ban PDA account of the bidder, seeded by `[b"global_ban", bidder_pubkey]`, may be uninitialized
+    ///
+    /// When the exhibitor gated the auction with a trustline, the following account is expected last:
+    ///
+    /// 27. `[]` The trustline program, verifying the bidder through `TrustlineVerifier::verify`
+    ///
+    /// When the exhibitor enabled cross-program bids and the bidder did not sign, the following account is expected last:
+    ///
+    /// 28. `[signer, writable]` The CPI authority, owning the bidder's FT account and paying for the created accounts
+    ///
+    /// When the exhibitor requires approval, the following account is also expected, anywhere after the accounts above:
+    ///
+    /// 29. `[writable]` The bid approval PDA account, seeded by `[b"bid_approval", escrow_pubkey, bidder_pubkey]`, only recording the bid until the bidder is approved
+    ///
+    /// When the exhibitor set a bid weight oracle, the following account is also expected, anywhere after the accounts above:
+    ///
+    /// 30. `[]` The bid weight oracle program, weighting the bid through `BidWeightOracle::weight`
     Bid {
         /// Bidding price
         price: u64,
+        /// Bid nonce of the auction
+        nonce: u64,
+        /// Merkle proof of the bidder in the room whitelist, optional and empty when omitted
+        proof: Vec<[u8; 32]>,
     },
 
     /// Cancels a auction
@@ -46,22 +156,27 @@ pub enum AuctionInstruction {
     ///
     /// Accounts expected:
     ///
-    /// 0. `[signer]` The account of the person started the auction
+    /// 0. `[signer, writable]` The account of the person started the auction
     /// 1. `[writable]` The PDA's temporary NFT account
     /// 2. `[writable]` The exhibitor's NFT account to get the token back to
     /// 3. `[writable]` The escrow account holding the escrow info
     /// 4. `[]` The token program
     /// 5. `[]` The PDA account
+    /// 6. `[writable]` The program stats PDA account, seeded by `[b"stats"]`
+    ///
+    /// When the auction is soulbound or burns on cancel, the NFT is burnt instead of returned and the following account is expected:
+    ///
+    /// 7. `[writable]` The NFT mint
     Cancel {},
 
     /// Closes a auction
-    /// Only the successful bidder can close the auction
+    /// Only the successful bidder or the close authority can close the auction until its close grace period has elapsed, anyone can close it afterwards
     ///
     ///
     /// Accounts expected:
     ///
-    /// 0. `[signer]` The account of the successful bidder
-    /// 1. `[]` The account ot the person started the auction to close the escrow
+    /// 0. `[signer, writable]` The account of the successful bidder, the close authority, or anyone once the grace period has elapsed, pays for the tombstone record
+    /// 1. `[writable]` The account ot the person started the auction to close the escrow
     /// 2. `[writable]` The temporary NFT account to send to successful bidder
     /// 3. `[writable]` The FT account to sent FT to the exhibitor'
     /// 4. `[writable]` The temporary FT account that holds the successful bidder's FT
@@ -70,32 +185,2346 @@ pub enum AuctionInstruction {
     /// 7. `[]` The clock sysvar
     /// 8. `[]` The token program
     /// 9. `[]` The PDA account
+    /// 10. `[writable]` The program stats PDA account, seeded by `[b"stats"]`
+    /// 11. `[writable]` The tombstone PDA account, seeded by `[b"tombstone", escrow_pubkey]`, written once the winner is final
+    /// 12. `[]` The system program
+    ///
+    /// When the signer is not the successful bidder, the following account is also expected:
+    ///
+    /// 13. `[writable]` The account of the successful bidder to return the temporary account rent to
+    ///
+    /// When the auction is soulbound, the following account is expected last:
+    ///
+    /// 13. `[]` The NFT mint whose freeze authority is the PDA, at index 14 when the successful bidder's account is passed
+    ///
+    /// When sponsors deposited a bonus, the following accounts are expected last:
+    ///
+    /// 1. `[writable]` The bonus vault PDA account, seeded by `[b"bonus", escrow_pubkey]`
+    /// 2. `[writable]` The successful bidder's FT account receiving the bonus
+    ///
+    /// When the exhibitor set a finality timeout, the FT stays in escrow until `FinalizeSettlement`
+    ///
+    /// When the exhibitor set fractional owners, their FT receiving accounts are expected last, in the order they were set
+    ///
+    /// When the exhibitor overrode the winner, only the selected winner can close, as the signer, and the following
+    /// accounts are expected last:
+    ///
+    /// 1. `[writable]` The highest bidder's account to return the temporary account rent to
+    /// 2. `[writable]` The highest bidder's FT account to refund the bid to
+    /// 3. `[writable]` The selected winner's FT account paying the price
+    ///
+    /// When the exhibitor set an audit oracle, the escrow stays open until `CertifyPrice`
+    ///
+    /// When the auction carries an NFT lock period, the following account is expected after the bonus accounts:
+    ///
+    /// 1. `[writable]` The winner's NFT lock record PDA account, seeded by `[b"nft_lock", nft_mint, winner_pubkey]`
+    ///
+    /// When the price exceeds the transaction limit of the auction, the following accounts are expected after those:
+    ///
+    /// 1. `[]` The global config PDA account
+    /// 2. `[signer]` The compliance signer of the global config
     Close {},
-}
 
-impl AuctionInstruction {
-    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
-        let (instruction_type, rest) = input.split_first().ok_or(InvalidInstruction)?;
-        Ok(match instruction_type {
-            0 => Self::Exhibit {
-                initial_price: Self::unpack64(rest, 0)?,
-                seconds: Self::unpack64(rest, 8)?,
-            },
-            1 => Self::Bid {
-                price: Self::unpack64(rest, 0)?,
-            },
-            2 => Self::Cancel {},
-            3 => Self::Close {},
-            _ => return Err(InvalidInstruction.into()),
-        })
-    }
+    /// Logs the current state of a auction without modifying it
+    /// The fields are emitted via `sol_log_data` as an `AuctionStateEvent`
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[]` The escrow account holding the escrow info
+    GetAuctionState {},
 
-    fn unpack64(input: &[u8], start: usize) -> Result<u64, ProgramError> {
-        let v = input
-            .get(start..start + 8)
-            .and_then(|slice| slice.try_into().ok())
-            .map(u64::from_le_bytes)
-            .ok_or(InvalidInstruction)?;
-        Ok(v)
+    /// Redirects the auction proceeds to a new FT account of the exhibitor
+    /// The new account must be a token account of the FT mint the auction is priced in
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[]` The exhibitor's new FT receiving account
+    /// 2. `[writable]` The escrow account holding the escrow info
+    ChangeExhibitorFTReceiver {
+        /// New FT receiving account
+        new_receiver: Pubkey,
+    },
+
+    /// Extends an expired auction so the highest bidder has more time to close it
+    /// Each highest bidder can extend the auction only once, bidding stays closed during the extension
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the highest bidder
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[]` The clock sysvar
+    ExtendBidDeadline {
+        /// Seconds to extend the auction by, capped at the auction's maximum
+        additional_seconds: u64,
+    },
+
+    /// Raises the minimum bid of a live auction
+    /// The floor can't be lower than the current highest bid
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[]` The clock sysvar
+    SetFloorPrice {
+        /// Minimum bid price
+        floor: u64,
+    },
+
+    /// Creates the global config of the program, the signer becomes the program authority
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the program authority
+    /// 1. `[writable]` The global config PDA account, seeded by `[b"global_config"]`
+    /// 2. `[]` The rent sysvar
+    /// 3. `[]` The system program
+    InitGlobalConfig {},
+
+    /// Returns the highest bidder's FT from an expired auction that was never closed
+    /// Only the program authority can recover a stuck bid, once the close grace period has elapsed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the program authority
+    /// 1. `[]` The global config PDA account
+    /// 2. `[writable]` The escrow account holding the escrow info
+    /// 3. `[writable]` The highest bidder's account to return the temporary account rent to
+    /// 4. `[writable]` The highest bidder's temporary FT account
+    /// 5. `[writable]` The highest bidder's FT account to get back to
+    /// 6. `[]` The clock sysvar
+    /// 7. `[]` The token program
+    /// 8. `[]` The PDA account
+    RecoverStuckBid {},
+
+    /// Starts several auctions at once, each with its own escrow account
+    /// All auctions are validated before any of them is started
+    /// Each auction is then settled on its own with `Close`, one transaction per lot
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// The accounts of `Exhibit`, repeated `count` times in the same order as `configs`
+    BatchExhibit {
+        /// Number of auctions to start, at most `MAX_BATCH_EXHIBIT`
+        count: u8,
+        /// Price, duration and category of each auction
+        configs: Vec<ExhibitConfig>,
+    },
+
+    /// Sets the share of each bid that goes to the insurance vault
+    /// Only the program authority can set the insurance fee
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the program authority
+    /// 1. `[writable]` The global config PDA account
+    SetInsuranceFee {
+        /// Insurance fee in basis points
+        fee_bps: u16,
+        /// Balance kept in the insurance vault on withdrawal
+        reserve: u64,
+    },
+
+    /// Pays the final price of an auction back to its winner from the insurance vault
+    /// The program authority co-signs once a dispute is resolved in the winner's favor
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the winner, pays for the insurance claim record
+    /// 1. `[signer]` The account of the program authority
+    /// 2. `[]` The global config PDA account
+    /// 3. `[]` The tombstone PDA account of the auction
+    /// 4. `[writable]` The insurance claim PDA account, seeded by `[b"insurance_claim", tombstone_pubkey]`
+    /// 5. `[writable]` The insurance vault PDA account
+    /// 6. `[writable]` The winner's FT account to receive the insurance
+    /// 7. `[]` The token program
+    /// 8. `[]` The system program
+    ClaimInsurance {},
+
+    /// Withdraws the insurance vault balance above the configured reserve
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the program authority
+    /// 1. `[]` The global config PDA account
+    /// 2. `[writable]` The insurance vault PDA account
+    /// 3. `[writable]` The FT account to receive the surplus
+    /// 4. `[]` The token program
+    WithdrawInsurance {
+        /// Amount to withdraw
+        amount: u64,
+    },
+
+    /// Adds more FT to the highest bid without placing a new bid
+    /// Only the current highest bidder can increase their escrow
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the highest bidder
+    /// 1. `[writable]` The highest bidder's FT account to take the additional FT from
+    /// 2. `[writable]` The highest bidder's temporary FT account
+    /// 3. `[writable]` The escrow account holding the escrow info
+    /// 4. `[]` The clock sysvar
+    /// 5. `[]` The token program
+    IncreaseBidEscrow {
+        /// Amount of FT to add to the bid
+        additional: u64,
+    },
+
+    /// Moves the auction to another category
+    /// The category can't be changed once a bid is placed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[writable]` The current category index PDA account
+    /// 3. `[writable]` The new category index PDA account
+    /// 4. `[]` The rent sysvar
+    /// 5. `[]` The system program
+    UpdateCategory {
+        /// New auction category
+        category: AuctionCategory,
+    },
+
+    /// Stores a permanent provenance note for the auction
+    /// The note can be set only once, by the exhibitor
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the person started the auction
+    /// 1. `[]` The escrow account holding the escrow info
+    /// 2. `[writable]` The note PDA account, seeded by `[b"note", escrow_pubkey]`
+    /// 3. `[]` The rent sysvar
+    /// 4. `[]` The clock sysvar
+    /// 5. `[]` The system program
+    SetExhibitorNote {
+        /// Provenance note
+        note: Box<[u8; EXHIBITOR_NOTE_LEN]>,
+        /// SHA256 of the note
+        note_hash: [u8; 32],
+    },
+
+    /// Caps the number of unique bidders of the auction, 0 removes the cap
+    /// Only the exhibitor can set it, before any bid is placed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[writable]` The bidder registry PDA account, seeded by `[b"bidder_registry", escrow_pubkey]`
+    /// 3. `[]` The rent sysvar
+    /// 4. `[]` The system program
+    SetMaxBidders {
+        /// Maximum number of unique bidders
+        max: u8,
+    },
+
+    /// Returns the NFT to the exhibitor and the FT to the winner when the winner never closed the auction
+    /// Only the exhibitor can call it, once the close grace period after the auction end has elapsed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the person started the auction
+    /// 1. `[writable]` The temporary NFT account holding the exhibited NFT
+    /// 2. `[writable]` The exhibitor's NFT account to get the NFT back to
+    /// 3. `[writable]` The escrow account holding the escrow info
+    /// 4. `[writable]` The winner's account to return the temporary account rent to
+    /// 5. `[writable]` The winner's temporary FT account
+    /// 6. `[writable]` The winner's FT account to get back to
+    /// 7. `[]` The clock sysvar
+    /// 8. `[]` The token program
+    /// 9. `[]` The PDA account
+    /// 10. `[writable]` The program stats PDA account, seeded by `[b"stats"]`
+    TransferNFTAfterExpiry {},
+
+    /// Records the exhibitor's attestation of the off-chain auction terms, bids are rejected until then
+    /// The instruction must be preceded by an Ed25519 program instruction verifying the exhibitor's signature over `message_hash`
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[]` The instructions sysvar
+    VerifySignedMessage {
+        /// Hash of the auction terms
+        message_hash: [u8; 32],
+    },
+
+    /// Registers the signer as a participant of the auction
+    /// Registration is open until the auction ends
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the participant
+    /// 1. `[]` The escrow account holding the escrow info
+    /// 2. `[writable]` The registration PDA account, seeded by `[b"registration", escrow_pubkey, participant_pubkey]`
+    /// 3. `[]` The rent sysvar
+    /// 4. `[]` The clock sysvar
+    /// 5. `[]` The system program
+    Register {},
+
+    /// Designates the registered participant who can claim the NFT
+    /// Only the exhibitor can select the winner, once the auction ended without any bid
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[]` The winner's registration PDA account
+    /// 3. `[]` The clock sysvar
+    SelectWinner {
+        /// Wallet pubkey of the registered participant
+        winner: Pubkey,
+    },
+
+    /// Transfers the NFT to the selected winner without any FT payment and closes the escrow
+    /// Only the selected winner can claim the NFT
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the selected winner
+    /// 1. `[writable]` The account of the person started the auction to close the escrow
+    /// 2. `[writable]` The temporary NFT account to send to the winner
+    /// 3. `[writable]` The NFT account that will receive NFT
+    /// 4. `[writable]` The escrow account holding the escrow info
+    /// 5. `[]` The token program
+    /// 6. `[]` The PDA account
+    /// 7. `[writable]` The program stats PDA account, seeded by `[b"stats"]`
+    ClaimAirdrop {},
+
+    /// Enables or disables the automatic settlement of the auction once it has expired
+    /// Only the exhibitor can change it
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetAutoClose {
+        /// Whether anyone can settle the auction
+        enabled: bool,
+    },
+
+    /// Settles an expired auction with auto close enabled, on behalf of the highest bidder
+    /// Any signer can trigger it and receives a small lamport tip from the escrow
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the caller, receives the tip
+    /// 1. `[writable]` The highest bidder's account to return the temporary account rent to
+    /// 2. `[writable]` The account ot the person started the auction to close the escrow
+    /// 3. `[writable]` The temporary NFT account to send to the highest bidder
+    /// 4. `[writable]` The FT account to sent FT to the exhibitor
+    /// 5. `[writable]` The temporary FT account that holds the highest bidder's FT
+    /// 6. `[writable]` The highest bidder's NFT account that will receive NFT
+    /// 7. `[writable]` The escrow account holding the escrow info
+    /// 8. `[]` The clock sysvar
+    /// 9. `[]` The token program
+    /// 10. `[]` The PDA account
+    /// 11. `[writable]` The program stats PDA account, seeded by `[b"stats"]`
+    ///
+    /// When the exhibitor set fractional owners, their FT receiving accounts are expected last, in the order they were set
+    TriggerAutoClose {},
+
+    /// Sets whether a forced cancel only refunds the highest bidder's escrowed FT
+    /// When disabled, the exhibitor must also pay the bid amount to the highest bidder on a forced cancel
+    /// Only the exhibitor can set it, before any bid is placed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetRefundPolicy {
+        /// Whether the escrowed FT refund is enough to cancel
+        refund_on_cancel: bool,
+    },
+
+    /// Cancels an active auction that already has a bid, returning the NFT to the exhibitor and the FT to the highest bidder
+    /// Only the exhibitor can force a cancel
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the person started the auction
+    /// 1. `[writable]` The temporary NFT account holding the exhibited NFT
+    /// 2. `[writable]` The exhibitor's NFT account to get the NFT back to
+    /// 3. `[writable]` The escrow account holding the escrow info
+    /// 4. `[writable]` The highest bidder's account to return the temporary account rent to
+    /// 5. `[writable]` The highest bidder's temporary FT account
+    /// 6. `[writable]` The highest bidder's FT account to get back to
+    /// 7. `[]` The clock sysvar
+    /// 8. `[]` The token program
+    /// 9. `[]` The PDA account
+    /// 10. `[writable]` The program stats PDA account, seeded by `[b"stats"]`
+    ///
+    /// When the refund policy is disabled, the following account is also expected:
+    ///
+    /// 11. `[writable]` The exhibitor's FT account paying the compensation to the highest bidder
+    ///
+    /// When the exhibitor locked collateral, the cancel penalty is paid from it and the following account is expected last:
+    ///
+    /// 12. `[writable]` The collateral vault PDA account, seeded by `[b"collateral", escrow_pubkey, exhibitor_pubkey]`
+    ForceCancel {},
+
+    /// Voids the highest bid when its temporary FT account holds less than the escrowed bid
+    /// Anyone can liquidate an undercollateralized bid, the remaining FT is returned to the highest bidder
+    /// The price falls back to the floor price, so the next bid is not priced against the voided one
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the caller
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[writable]` The highest bidder's account to return the temporary account rent to
+    /// 3. `[writable]` The highest bidder's temporary FT account
+    /// 4. `[writable]` The highest bidder's FT account to get back to
+    /// 5. `[]` The token program
+    /// 6. `[]` The PDA account
+    LiquidateBid {},
+
+    /// Logs the aggregate metrics of all auctions
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[]` The program stats PDA account, seeded by `[b"stats"]`
+    ReadProgramStats {},
+
+    /// Releases part of the highest bid to the exhibitor before the auction is closed
+    /// Once proceeds are advanced, the highest bid can no longer be outbid or cancelled
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[writable]` The temporary FT account that holds the highest bidder's FT
+    /// 3. `[writable]` The FT account to sent FT to the exhibitor
+    /// 4. `[]` The token program
+    /// 5. `[]` The PDA account
+    WithdrawPartialProceeds {
+        /// FT amount to withdraw
+        amount: u64,
+    },
+
+    /// Overrides the end time of any auction, as voted by governance
+    /// Only the program authority can set the expiry
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the program authority
+    /// 1. `[]` The global config PDA account
+    /// 2. `[writable]` The escrow account holding the escrow info
+    /// 3. `[]` The clock sysvar
+    SetAuctionExpiry {
+        /// New auction end time
+        new_end_at: i64,
+    },
+
+    /// Checks that a bid at `expected_price` would be accepted, without changing any state
+    /// Fails with the error the bid would fail with
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person about to bid
+    /// 1. `[]` The escrow account holding the escrow info
+    /// 2. `[]` The clock sysvar
+    ValidateAuction {
+        /// Price of the intended bid
+        expected_price: u64,
+    },
+
+    /// Shows or hides the current price in bid logs and auction state events
+    /// Only the exhibitor can change the visibility
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetBidVisible {
+        /// Whether the current price is exposed
+        visible: bool,
+    },
+
+    /// Logs the auction state with the real current price, even when bids are hidden
+    /// Only the exhibitor or the highest bidder can reveal the price
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the exhibitor or the highest bidder
+    /// 1. `[]` The escrow account holding the escrow info
+    RevealCurrentPrice {},
+
+    /// Records the NFT mint the exhibitor meant to list, failing if the escrowed NFT is of another mint
+    /// Only the exhibitor can set it, before any bid is placed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[]` The temporary NFT account holding the exhibited NFT
+    SetNFTMintValidation {
+        /// Expected NFT mint
+        expected_mint: Pubkey,
+    },
+
+    /// Assigns the mediator arbitrating disputes of the auction
+    /// The exhibitor and the mediator must both sign the assignment
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[signer]` The account of the mediator
+    /// 2. `[writable]` The escrow account holding the escrow info
+    AssignMediator {
+        /// Wallet pubkey of the mediator
+        mediator: Pubkey,
+    },
+
+    /// Removes the mediator assigned to the auction
+    /// Only the exhibitor can revoke the mediator
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    RevokeMediator {},
+
+    /// Logs the minimum valid next bid, the time remaining, the bid count and the floor price status
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[]` The escrow account holding the escrow info
+    /// 1. `[]` The clock sysvar
+    /// 2. `[]` The bid history PDA account, seeded by `[b"bid_history", escrow_pubkey]`
+    GetNextBidInfo {},
+
+    /// Creates the changelog of an auction, every later instruction changing the auction appends
+    /// an entry to it, including the instructions changing several auctions such as `BatchBid`
+    /// Those instructions then expect the changelog PDA account of each auction they change last,
+    /// after all their other accounts including the optional ones
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the person who created the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[writable]` The changelog PDA account, seeded by `[b"changelog", escrow_pubkey]`
+    /// 3. `[]` The rent sysvar
+    /// 4. `[]` The system program
+    InitChangelog {},
+
+    /// Logs every entry kept in the changelog of an auction
+    /// The bid prices of the entries are logged as 0 while the bids of the auction are hidden,
+    /// and once the auction is closed, and the end times set by `SetAuctionExpiry` are logged as 0
+    /// while the end time is sealed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[]` The escrow account holding the escrow info
+    /// 1. `[]` The changelog PDA account, seeded by `[b"changelog", escrow_pubkey]`
+    ReadChangelog {},
+
+    /// Delays the first accepted bid of an auction that has no bid yet
+    /// The auction must still be running once the delay elapses
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[]` The clock sysvar
+    SetStartDelay {
+        /// Seconds from now before the auction accepts bids
+        start_delay_sec: u64,
+    },
+
+    /// Grows an escrow account of the first release to the current `Auction` layout and records its version
+    /// Escrow accounts of any other layout than these two are rejected
+    /// The signer tops up the escrow rent for the added bytes
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the person started the auction or the global config authority
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[]` The global config PDA account, only read when the signer is not the exhibitor
+    /// 3. `[]` The rent sysvar
+    /// 4. `[]` The system program
+    ///
+    /// 5. `[]` The FT account of the exhibitor receiving the proceeds, whose mint the first release didn't record
+    MigrateState {
+        /// Layout version to migrate to
+        target_version: u8,
+    },
+
+    /// Creates or updates the price oracle of an FT, only callable by the global config authority
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The global config authority, pays for the oracle on the first update
+    /// 1. `[]` The global config PDA account
+    /// 2. `[writable]` The price oracle PDA account, seeded by `[b"price_oracle", ft_mint]`
+    /// 3. `[]` The FT mint
+    /// 4. `[]` The clock sysvar
+    /// 5. `[]` The rent sysvar
+    /// 6. `[]` The system program
+    SetOraclePrice {
+        /// Reference price in the smallest FT unit
+        price: u64,
+    },
+
+    /// Binds a price oracle to an auction without bids and requires the current price to reach
+    /// the given share of the oracle price
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[]` The price oracle PDA account of the auction FT mint
+    SetTrustlessOracle {
+        /// Share of the oracle price the auction price must reach, in basis points
+        floor_bps: u16,
+    },
+
+    /// Pays a share of the first bid to the creator as soon as it is escrowed, the auction price
+    /// is lowered by the paid royalty
+    /// The royalty is deposited in the creator's royalty vault, which the creator empties with `ClaimCreatorRoyalty`
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the person started the auction, pays for the royalty vault
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[]` The creator's account
+    /// 3. `[writable]` The creator's royalty vault PDA account, seeded by `[b"royalty", ft_mint, creator_pubkey]`
+    /// 4. `[]` The FT mint
+    /// 5. `[]` The token program
+    /// 6. `[]` The rent sysvar
+    /// 7. `[]` The system program
+    DeductRoyaltyBeforeEscrow {
+        /// Share of the first bid paid to the creator, in basis points
+        upfront_royalty_bps: u16,
+    },
+
+    /// Sets how long the winner alone can close the auction after it expires
+    /// The grace period can only change before the first bid
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetGracePeriod {
+        /// Seconds after the auction end reserved to the winner
+        close_grace_period_sec: u64,
+    },
+
+    /// Delegates closing the expired auction on behalf of the winner to an address, such as a
+    /// settlement crank, the default pubkey removes the delegation
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetCloseAuthority {
+        /// Address allowed to close the auction
+        close_authority: Pubkey,
+    },
+
+    /// Wraps the bidder's SOL into their WSOL account and bids on an auction priced in WSOL
+    /// Takes no whitelist proof, so it can't bid in a room with a whitelist
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// Same accounts as `Bid`, where the bidder's FT account at index 5 is a WSOL account owned by the bidder
+    BidWithSolBalance {
+        /// Bid price in lamports
+        price: u64,
+        /// WSOL already held by the bidder's WSOL account
+        existing_wsol: u64,
+    },
+
+    /// Registers the priority bidder of an auction, the default pubkey removes it
+    /// The bids of the priority bidder don't push back the end of a sliding expiry auction
+    /// Only the exhibitor or the program authority can register it
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction or the program authority
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[]` The global config PDA account, only read when the signer is not the exhibitor
+    RegisterPriorityBidder {
+        /// Wallet pubkey of the priority bidder
+        bidder: Pubkey,
+    },
+
+    /// Makes an auction without bids soulbound, the NFT is frozen in the winner's account on close
+    /// and burnt on cancel
+    /// The freeze authority of the NFT mint must be the PDA
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[]` The PDA's temporary NFT account
+    /// 3. `[]` The NFT mint
+    ExhibitSoulbound {},
+
+    /// Whitelists the Token-2022 transfer hook program allowed on exhibited NFTs,
+    /// the default pubkey removes it
+    /// Only the program authority can set it, a global config created before the whitelist is grown
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the program authority
+    /// 1. `[writable]` The global config PDA account
+    /// 2. `[]` The rent sysvar
+    /// 3. `[]` The system program
+    SetTokenExtensions {
+        /// Program id of the whitelisted transfer hook
+        transfer_hook_program: Pubkey,
+    },
+
+    /// Sets the minimum time every auction accepts bids for
+    /// Only the program authority can set it, a global config created before the minimum is grown
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the program authority
+    /// 1. `[writable]` The global config PDA account
+    /// 2. `[]` The rent sysvar
+    /// 3. `[]` The system program
+    SetMinimumDuration {
+        /// Minimum bidding window in seconds
+        min_sec: u64,
+    },
+
+    /// Sets the minimum time the auctions of an exhibitor accept bids for, on top of the global minimum
+    /// Only the program authority can set it
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the program authority
+    /// 1. `[]` The global config PDA account
+    /// 2. `[writable]` The exhibitor config PDA account, seeded by `[b"exhibitor_config", exhibitor_pubkey]`
+    /// 3. `[]` The account of the exhibitor
+    /// 4. `[]` The rent sysvar
+    /// 5. `[]` The system program
+    SetExhibitorMinDuration {
+        /// Minimum bidding window in seconds
+        min_sec: u64,
+    },
+
+    /// Sets the minimum increments of the next bids over the current price, before the first bid
+    /// The next bid must exceed the current price by the larger of both increments
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetBidMultiplier {
+        /// Flat minimum increment in the smallest FT unit
+        min_bid_increment: u64,
+        /// Proportional minimum increment in basis points of the current price
+        bid_multiplier_bps: u16,
+    },
+
+    /// Starts the auction like `Exhibit`, with absolute start and end times instead of a duration
+    /// `start_at` can't be in the past and must be before `end_at`
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// The accounts of `Exhibit`
+    ExhibitWithTimestamps {
+        /// Initial NFT price
+        initial_price: u64,
+        /// Time from which bids are accepted
+        start_at: i64,
+        /// Auction end time
+        end_at: i64,
+        /// Auction category
+        category: AuctionCategory,
+        /// Mint of the FT the auction is priced in
+        ft_mint: Pubkey,
+    },
+
+    /// Leaves feedback on the other party of a settled auction and updates their user profile
+    /// Only the winner or the exhibitor can leave it, once, within the feedback window after the tombstone was written
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the winner or the exhibitor, pays for the created accounts
+    /// 1. `[]` The tombstone PDA account of the auction, seeded by `[b"tombstone", for_auction]`
+    /// 2. `[writable]` The feedback PDA account, seeded by `[b"feedback", for_auction, reviewer_pubkey]`
+    /// 3. `[writable]` The user profile PDA account of the reviewed party, seeded by `[b"user_profile", reviewed_pubkey]`
+    /// 4. `[]` The clock sysvar
+    /// 5. `[]` The rent sysvar
+    /// 6. `[]` The system program
+    LeaveFeedback {
+        /// Escrow account pubkey of the auction
+        for_auction: Pubkey,
+        /// Score from 1 to 5
+        score: u8,
+        /// Hash of the comment stored off chain
+        comment_hash: [u8; 32],
+    },
+
+    /// Creates an auction room whose fee and whitelist apply to the auctions exhibited in it
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the room organizer, pays for the room
+    /// 1. `[writable]` The auction room PDA account, seeded by `[b"room", room_id]`
+    /// 2. `[]` The rent sysvar
+    /// 3. `[]` The system program
+    InitAuctionRoom {
+        /// Identifier of the room
+        room_id: u64,
+        /// Share of each bid paid to the organizer, in basis points
+        fee_bps: u16,
+        /// Merkle root of the allowed bidders, all zeros when anyone can bid
+        whitelist_root: [u8; 32],
+        /// Description of the room
+        room_description: [u8; ROOM_DESCRIPTION_LEN],
+    },
+
+    /// Closes an auction room and returns its rent to the organizer
+    /// Auctions already exhibited in the room keep its fee and whitelist
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the room organizer
+    /// 1. `[writable]` The auction room PDA account
+    CloseAuctionRoom {},
+
+    /// Disaster recovery moving the escrowed NFT and FT accounts of an auction to the PDA of a
+    /// redeployed program, this program's PDA signs the ownership change
+    /// Only the program authority can reassign them, with the consent of the upgrade authority of
+    /// the redeployed program. The auction is marked as migrated, this program rejects every later
+    /// instruction acting on it, and sponsors and the exhibitor reclaim the bonus and collateral
+    /// vaults it still holds
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the program authority
+    /// 1. `[]` The global config PDA account
+    /// 2. `[writable]` The escrow account holding the escrow info
+    /// 3. `[]` The PDA account
+    /// 4. `[]` The token program
+    /// 5. `[signer]` The upgrade authority of the redeployed program
+    /// 6. `[]` The program data account of the redeployed program
+    /// 7. `[writable]` The PDA's temporary NFT account
+    ///
+    /// When the auction has a highest bidder, the following account is also expected:
+    ///
+    /// 8. `[writable]` The highest bidder's temporary FT account
+    SetEscrowAuthority {
+        /// Program id the escrowed accounts are currently controlled by, this program as an
+        /// auction is migrated only once
+        old_program_id: Pubkey,
+        /// Program id of the redeployed program
+        new_program_id: Pubkey,
+        /// PDA of the redeployed program taking over the escrowed accounts
+        new_pda: Pubkey,
+        /// Bump seed of `new_pda`, derived from the `escrow` seed and `new_program_id`
+        new_pda_bump: u8,
+    },
+
+    /// Deposits FT in the bonus vault of a running auction, paid to the winner on close
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the sponsor, pays for the created accounts
+    /// 1. `[writable]` The sponsor's FT account
+    /// 2. `[writable]` The escrow account holding the escrow info
+    /// 3. `[writable]` The bonus vault PDA account, seeded by `[b"bonus", escrow_pubkey]`
+    /// 4. `[writable]` The bonus deposit PDA account, seeded by `[b"bonus_deposit", escrow_pubkey, sponsor_pubkey]`
+    /// 5. `[]` The FT mint of the auction
+    /// 6. `[]` The token program
+    /// 7. `[]` The clock sysvar
+    /// 8. `[]` The rent sysvar
+    /// 9. `[]` The system program
+    DepositBonus {
+        /// Deposited amount in the smallest FT unit
+        amount: u64,
+    },
+
+    /// Returns a sponsor's deposit from the bonus vault once the auction was cancelled or migrated
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the sponsor, receives the deposit record rent
+    /// 1. `[writable]` The sponsor's FT account
+    /// 2. `[]` The closed or migrated escrow account of the auction
+    /// 3. `[writable]` The bonus vault PDA account, seeded by `[b"bonus", escrow_pubkey]`
+    /// 4. `[writable]` The bonus deposit PDA account, seeded by `[b"bonus_deposit", escrow_pubkey, sponsor_pubkey]`
+    /// 5. `[]` The token program
+    ReclaimBonus {},
+
+    /// Lets other programs bid through CPI signed by the given authority instead of the bidder
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person exhibiting the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    EnableCrossProgram {
+        /// Address signing cross-program bids, default disables them
+        cpi_authority: Pubkey,
+    },
+
+    /// Sets the period after close during which the winner can dispute the settlement
+    /// The FT is released to the exhibitor by `FinalizeSettlement` once it has elapsed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetFinalityTimeout {
+        /// Seconds after close before the settlement is final, 0 settles on close
+        finality_timeout_sec: u64,
+    },
+
+    /// Disputes a closed auction before its settlement is final
+    /// The mediator must then co-sign the release of the FT to the exhibitor
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the winner
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[]` The clock sysvar
+    RaiseDispute {},
+
+    /// Releases the FT of a closed auction to the exhibitor once its finality timeout has elapsed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the caller
+    /// 1. `[writable]` The account of the person started the auction to close the escrow
+    /// 2. `[writable]` The emptied temporary NFT account of the exhibitor
+    /// 3. `[writable]` The FT account to send FT to the exhibitor
+    /// 4. `[writable]` The temporary FT account that holds the winner's FT
+    /// 5. `[writable]` The account of the winner to return the temporary account rent to
+    /// 6. `[writable]` The escrow account holding the escrow info
+    /// 7. `[]` The clock sysvar
+    /// 8. `[]` The token program
+    /// 9. `[]` The PDA account
+    ///
+    /// When the winner disputed the settlement, the following account is expected last:
+    ///
+    /// 10. `[signer]` The account of the mediator
+    ///
+    /// When the exhibitor set fractional owners, their FT receiving accounts are expected last, in the order they were set
+    FinalizeSettlement {},
+
+    /// Splits the proceeds of the auction among fractional owners of the NFT instead of paying the exhibitor
+    /// The shares must sum to 10000 basis points, an empty list pays the exhibitor again
+    /// Any FT account can receive a share, e.g. the members of a DAO, label or studio splitting the revenue
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetFractionalOwnership {
+        /// FT receiving account and share in basis points of each owner, at most `MAX_FRACTIONAL_OWNERS`
+        owners: Vec<(Pubkey, u16)>,
+    },
+
+    /// Withdraws the bid from the bidder's position in a yield vault, then bids on the auction
+    /// The FT is deposited back into the vault when the bid is outbid
+    /// Takes no whitelist proof, so it can't bid in a room with a whitelist
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[]` The vault program, implementing `VaultInterface`
+    /// 1. `[writable]` The bidder's position account in the vault
+    /// 2. `[writable]` The FT account of the vault
+    ///
+    /// Followed by the accounts of `Bid`, where the bidder's FT account receives the withdrawal
+    VaultBid {
+        /// Program id of the vault
+        vault_program: Pubkey,
+        /// Bidding price
+        price: u64,
+    },
+
+    /// Sets the penalty the winner forfeits when they don't close the auction in time
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetPenalty {
+        /// Share of the price forfeited to the exhibitor, in basis points
+        penalty_bps: u16,
+        /// Seconds after the auction end the winner has to close it
+        payment_grace_period_sec: u64,
+    },
+
+    /// Returns the NFT to the exhibitor when the winner didn't close the auction within the payment
+    /// grace period, pays the penalty from the winner's FT to the exhibitor and returns the rest
+    /// Anyone can call it
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the caller
+    /// 1. `[writable]` The account of the person started the auction to close the escrow
+    /// 2. `[writable]` The temporary NFT account holding the exhibited NFT
+    /// 3. `[writable]` The exhibitor's NFT account to get the NFT back to
+    /// 4. `[writable]` The FT account to send the penalty to the exhibitor
+    /// 5. `[writable]` The escrow account holding the escrow info
+    /// 6. `[writable]` The winner's account to return the temporary account rent to
+    /// 7. `[writable]` The winner's temporary FT account
+    /// 8. `[writable]` The winner's FT account to get back to
+    /// 9. `[]` The clock sysvar
+    /// 10. `[]` The token program
+    /// 11. `[]` The PDA account
+    /// 12. `[writable]` The program stats PDA account, seeded by `[b"stats"]`
+    SlashBidder {},
+
+    /// Restricts bidding to holders of a minimum balance of a token, the default mint removes the gate
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetBidTokenGate {
+        /// Mint of the token bidders must hold
+        gate_token_mint: Pubkey,
+        /// Minimum balance of the gate token
+        gate_token_min_amount: u64,
+    },
+
+    /// Sets the number of identical tokens sold in an auction without bids, to auction a batch of FT
+    /// The difference with the escrowed balance is moved between the exhibitor's token account and the temporary account
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The exhibitor's account of the exhibited token
+    /// 2. `[writable]` The temporary account holding the exhibited tokens
+    /// 3. `[writable]` The escrow account holding the escrow info
+    /// 4. `[]` The token program
+    /// 5. `[]` The PDA account
+    SetLotSize {
+        /// Number of tokens in the lot, in the smallest unit
+        lot_size: u64,
+    },
+
+    /// Accepts bids in other FT mints than the auction FT mint, bid through `BidInCurrency`
+    /// The exhibitor receives the winning bid in its own mint
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[]` The exhibitor's FT account for each accepted mint, in the same order
+    SetAcceptedCurrencies {
+        /// Accepted mints, the default pubkey marks an unused slot
+        accepted_mints: [Pubkey; MAX_ACCEPTED_MINTS],
+    },
+
+    /// Bids in one of the accepted mints of the auction
+    /// The price is compared with other bids in the auction FT mint, using the price oracles of both mints
+    /// Takes no whitelist proof, so it can't bid in a room with a whitelist
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[]` The price oracle PDA account of the auction FT mint
+    /// 1. `[]` The price oracle PDA account of the bid mint
+    ///
+    /// Followed by the accounts of `Bid`, with FT accounts of the bid mint
+    ///
+    /// The insurance fee, the room fee and the upfront royalty are charged in the bid mint, so the
+    /// insurance vault and the room organizer's FT account are those of the bid mint. When the
+    /// upfront royalty is set and not paid yet, the following accounts take the place of the
+    /// creator's royalty vault of `Bid`, before the accounts that follow it:
+    ///
+    /// 18. `[writable]` The creator's royalty vault PDA account, seeded by `[b"royalty", bid_mint, creator_pubkey]`
+    /// 19. `[]` The creator's account
+    /// 20. `[]` The bid mint
+    BidInCurrency {
+        /// Bidding price in the smallest unit of the bid mint
+        price: u64,
+        /// Mint of the bid, one of the accepted mints
+        bid_mint: Pubkey,
+    },
+
+    /// Makes the auction end once no bid arrived for the idle period, like a telephone auction
+    /// Each bid pushes the end back, up to the maximum duration after the start
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[]` The clock sysvar
+    SetSlidingExpiry {
+        /// Seconds without a bid after which the auction ends
+        idle_period_sec: u64,
+        /// Seconds after the start past which the end can't be pushed
+        max_duration_sec: u64,
+    },
+
+    /// Awards the expired auction to another account than the highest bidder, which pays the price on `Close`
+    /// The highest bidder gets the bid refunded, the default pubkey removes the override
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    /// 2. `[]` The clock sysvar
+    SetWinnerOverride {
+        /// Account awarded the NFT
+        winner: Pubkey,
+    },
+
+    /// Sets the account certifying the settlement price, the default pubkey removes it
+    /// The escrow of an audited auction is only closed by `CertifyPrice`
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetAuditOracle {
+        /// Account signing `CertifyPrice`
+        audit_oracle: Pubkey,
+    },
+
+    /// Certifies the price of a settled audited auction in an audit record and closes the escrow
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The audit oracle of the auction, paying the audit record
+    /// 1. `[writable]` The account of the person started the auction to close the escrow
+    /// 2. `[writable]` The temporary NFT account of the auction
+    /// 3. `[writable]` The escrow account holding the escrow info
+    /// 4. `[writable]` The audit record PDA account, seeded by `[b"audit", escrow_pubkey]`
+    /// 5. `[]` The token program
+    /// 6. `[]` The PDA account
+    /// 7. `[]` The rent sysvar
+    /// 8. `[]` The clock sysvar
+    /// 9. `[]` The system program
+    CertifyPrice {
+        /// Settlement price, must match the price of the auction
+        certified_price: u64,
+    },
+
+    /// Caps the sum of the bids every wallet places across all auctions, 0 removes the cap
+    /// Only the program authority can set it, a global config created before the cap is grown
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the program authority
+    /// 1. `[writable]` The global config PDA account
+    /// 2. `[]` The rent sysvar
+    /// 3. `[]` The system program
+    SetBidCap {
+        /// Maximum lifetime bid volume of a wallet in the smallest FT unit
+        max_lifetime_bid: u64,
+    },
+
+    /// Sets the time auction winners can't list the won NFT again, 0 removes the lock
+    /// Applies to auctions exhibited afterwards, only the program authority can set it
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the program authority
+    /// 1. `[writable]` The global config PDA account
+    /// 2. `[]` The rent sysvar
+    /// 3. `[]` The system program
+    SetNFTLockPeriod {
+        /// Lock period in seconds
+        lock_period_sec: u64,
+    },
+
+    /// Sets the price above which closing an auction needs the compliance signer, 0 removes the limit
+    /// Applies to auctions exhibited afterwards, only the program authority can set it
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the program authority
+    /// 1. `[writable]` The global config PDA account
+    /// 2. `[]` The rent sysvar
+    /// 3. `[]` The system program
+    SetTransactionLimit {
+        /// Limit in the smallest FT unit
+        transaction_limit: u64,
+        /// Account signing the close of auctions above the limit, e.g. a multisig or a regulator PDA
+        compliance_signer: Pubkey,
+    },
+
+    /// Refuses an FT mint for new auctions and bids
+    /// Only the program authority can blacklist a mint, a global config created before the blacklist is grown
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the program authority
+    /// 1. `[writable]` The global config PDA account
+    /// 2. `[writable]` The mint blacklist PDA account, seeded by `[b"blacklist", mint]`
+    /// 3. `[]` The rent sysvar
+    /// 4. `[]` The system program
+    BlacklistMint {
+        /// Mint to blacklist
+        mint: Pubkey,
+    },
+
+    /// Accepts a blacklisted FT mint again and closes its blacklist PDA
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the program authority, receiving the blacklist rent
+    /// 1. `[writable]` The global config PDA account
+    /// 2. `[writable]` The mint blacklist PDA account, seeded by `[b"blacklist", mint]`
+    UnblacklistMint {
+        /// Mint to accept again
+        mint: Pubkey,
+    },
+
+    /// Moves the NFT of an auction that can't be closed out of escrow and closes the escrow
+    /// Only the program authority can use it, 30 days after the auction end, and compensates any locked bidder separately
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the program authority
+    /// 1. `[]` The global config PDA account
+    /// 2. `[writable]` The escrow account holding the escrow info
+    /// 3. `[writable]` The account of the person started the auction to return the rent to
+    /// 4. `[writable]` The temporary NFT account holding the exhibited NFT
+    /// 5. `[writable]` The NFT account receiving the NFT
+    /// 6. `[]` The clock sysvar
+    /// 7. `[]` The token program
+    /// 8. `[]` The PDA account
+    /// 9. `[writable]` The program stats PDA account, seeded by `[b"stats"]`
+    EmergencyTransferNFT {
+        /// NFT account receiving the NFT
+        destination: Pubkey,
+    },
+
+    /// Allows a fee payer to submit bids on behalf of the given bidder through `BidWithFeePayer`
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the person started the auction, pays for the fee payer override
+    /// 1. `[]` The escrow account holding the escrow info
+    /// 2. `[writable]` The fee payer override PDA account, seeded by `[b"fee_payer_override", escrow_pubkey, bidder_pubkey]`
+    /// 3. `[]` The rent sysvar
+    /// 4. `[]` The system program
+    PreAuthorize {
+        /// Bidder whose bids a fee payer may submit
+        bidder: Pubkey,
+    },
+
+    /// Bids on behalf of a pre-authorized bidder, the fee payer signs the transaction and pays for the created accounts
+    /// The instruction must be preceded by an Ed25519 program instruction verifying the bidder's signature over the escrow pubkey followed by the little endian price and nonce
+    /// The nonce must match the auction's bid nonce, so the signed bid can't be submitted again once another bid is placed
+    /// The bidder approves the fee payer as delegate of their FT account, and the bidder's temporary FT account is owned by the fee payer
+    /// Takes no whitelist proof, so it can't bid in a room with a whitelist
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// Accounts of `Bid`, with the bidder's account not signing, followed last by:
+    ///
+    /// 1. `[]` The fee payer override PDA account, seeded by `[b"fee_payer_override", escrow_pubkey, bidder_pubkey]`
+    /// 2. `[]` The instructions sysvar
+    /// 3. `[signer, writable]` The fee payer
+    BidWithFeePayer {
+        /// Bidding price
+        price: u64,
+        /// Bid nonce of the auction
+        nonce: u64,
+    },
+
+    /// Sets whether cancelling the auction burns the NFT instead of returning it to the exhibitor
+    /// Only the exhibitor can set it, before any bid is placed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetBurnOnCancel {
+        /// Whether a cancel burns the NFT
+        burn_on_cancel: bool,
+    },
+
+    /// Logs the time weighted average of the bid prices, each weighted by the seconds it stayed the highest bid
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[]` The escrow account holding the escrow info
+    GetTWAP {},
+
+    /// Allows an account to update the URI of the exhibited NFT while in escrow, for NFTs that evolve over time
+    /// Only the exhibitor can set it, before any bid is placed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetNFTUpdateAuthority {
+        /// Account allowed to update the NFT, also the update authority of its metadata
+        nft_update_authority: Pubkey,
+        /// Whether updates are allowed at all
+        allow_updates_during_auction: bool,
+    },
+
+    /// Updates the URI of the exhibited NFT through the Token Metadata program, keeping the rest of its metadata
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The NFT update authority set by the exhibitor
+    /// 1. `[]` The escrow account holding the escrow info
+    /// 2. `[]` The PDA's temporary NFT account
+    /// 3. `[writable]` The metadata account of the NFT mint
+    /// 4. `[]` The Token Metadata program
+    UpdateNFTDuringAuction {
+        /// New URI, padded with zeros
+        new_uri: [u8; 64],
+    },
+
+    /// Commits to a bid without revealing its price, revealed later through `SubmitBid`
+    /// Committing again replaces the previous commitment
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the person bidding on the auction, pays for the pending bid
+    /// 1. `[]` The escrow account holding the escrow info
+    /// 2. `[writable]` The pending bid PDA account, seeded by `[b"pending_bid", escrow_pubkey, bidder_pubkey]`
+    /// 3. `[]` The rent sysvar
+    /// 4. `[]` The clock sysvar
+    /// 5. `[]` The system program
+    CommitBidHash {
+        /// SHA256 of the little endian price, the salt and the hash of `slot`
+        hash: [u8; 32],
+        /// Recent slot, before the commitment, whose hash is part of `hash`
+        slot: u64,
+    },
+
+    /// Reveals a committed bid and places it, closing the pending bid
+    /// Fails once the committed slot is no longer in the slot hashes sysvar, after 512 slots
+    /// Takes no whitelist proof, so it can't bid in a room with a whitelist
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` The pending bid PDA account, seeded by `[b"pending_bid", escrow_pubkey, bidder_pubkey]`
+    /// 1. `[]` The slot hashes sysvar
+    ///
+    /// Followed by the accounts of `Bid`
+    SubmitBid {
+        /// Bidding price
+        price: u64,
+        /// Salt of the committed hash
+        salt: [u8; 32],
+    },
+
+    /// Requires bidders to be verified by a trustline program, such as a KYC gateway, before bidding
+    /// Only the exhibitor can set it, before any bid is placed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetTrustlineRequirement {
+        /// Trustline program implementing `TrustlineVerifier`, the default pubkey removes the requirement
+        trustline_program: Pubkey,
+        /// Kind of trustline the bidders must hold
+        trustline_type: [u8; 8],
+    },
+
+    /// Switches the auction between an English and a sealed bid auction, a sealed bid auction also hides the bids
+    /// Either type removes the sliding expiry, only set through `SetSlidingExpiry`, keeping the current end
+    /// Only the exhibitor can set it, before any bid is placed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetAuctionType {
+        /// `English` or `SealedBid`
+        auction_type: AuctionType,
+    },
+
+    /// Locks FT of the auction FT mint as a good-faith deposit, part of which goes to the highest bidder on a forced cancel
+    /// Only the exhibitor can lock it, before any bid is placed, and locking again adds to the collateral
+    /// The rest is returned through `ReclaimCollateral` once the escrow is closed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the person started the auction, pays for the collateral vault
+    /// 1. `[writable]` The exhibitor's FT account to lock the collateral from
+    /// 2. `[writable]` The escrow account holding the escrow info
+    /// 3. `[writable]` The collateral vault PDA account, seeded by `[b"collateral", escrow_pubkey, exhibitor_pubkey]`
+    /// 4. `[]` The FT mint
+    /// 5. `[]` The token program
+    /// 6. `[]` The rent sysvar
+    /// 7. `[]` The system program
+    SetExhibitorCollateral {
+        /// FT to lock
+        amount: u64,
+        /// Share of the collateral paid to the highest bidder on a forced cancel, in basis points
+        cancel_penalty_bps: u16,
+    },
+
+    /// Returns the remaining collateral to the exhibitor and closes the collateral vault, once the escrow is closed or migrated
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the person started the auction, receiving the vault rent
+    /// 1. `[writable]` The exhibitor's FT account receiving the collateral
+    /// 2. `[]` The closed or migrated escrow account
+    /// 3. `[writable]` The collateral vault PDA account, seeded by `[b"collateral", escrow_pubkey, exhibitor_pubkey]`
+    /// 4. `[]` The token program
+    ReclaimCollateral {},
+
+    /// Transfers the royalties deposited in the creator's royalty vault to the creator
+    /// The vault stays open for the royalties of the auctions still running
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The creator's account
+    /// 1. `[writable]` The creator's FT account receiving the royalties
+    /// 2. `[writable]` The creator's royalty vault PDA account, seeded by `[b"royalty", ft_mint, creator_pubkey]`
+    /// 3. `[]` The token program
+    ClaimCreatorRoyalty {},
+
+    /// Bars a wallet from bidding on any auction
+    /// Only the program authority can ban a wallet, a global config created before the ban list is grown
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the program authority
+    /// 1. `[writable]` The global config PDA account
+    /// 2. `[writable]` The global ban PDA account, seeded by `[b"global_ban", bidder_pubkey]`
+    /// 3. `[]` The rent sysvar
+    /// 4. `[]` The system program
+    GlobalBanBidder {
+        /// Wallet to ban
+        bidder: Pubkey,
+    },
+
+    /// Lets a banned wallet bid again and closes its global ban PDA
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the program authority, receiving the ban rent
+    /// 1. `[writable]` The global config PDA account
+    /// 2. `[writable]` The global ban PDA account, seeded by `[b"global_ban", bidder_pubkey]`
+    GlobalUnbanBidder {
+        /// Wallet to allow again
+        bidder: Pubkey,
+    },
+
+    /// Places the same bid on up to `MAX_BATCH_BID` auctions at once, for a bidder who wants to win only one of them
+    /// The auctions are recorded in a batch bid group, which `ClaimBatchWinner` settles once one of them is won
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the person bidding, pays for the batch bid group
+    /// 1. `[writable]` The batch bid group PDA account, seeded by `[b"batch_bid", bidder_pubkey, first_escrow_pubkey]`
+    /// 2. `[]` The rent sysvar
+    /// 3. `[]` The system program
+    ///
+    /// Followed by the accounts of `Bid` for each auction, in the same order as `auctions`
+    BatchBid {
+        /// Escrows of the auctions to bid on, default for the unused entries
+        auctions: [Pubkey; MAX_BATCH_BID],
+        /// Bidding price on each auction
+        price: u64,
+    },
+
+    /// Returns the bids of a batch bid group on all auctions but the won one and closes the group
+    /// Only the bidder can claim, once an auction of the group expired with the bidder as the highest bidder
+    /// The won auction is then settled with `Close`
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the bidder, receiving the temporary accounts and group rent
+    /// 1. `[writable]` The batch bid group PDA account, seeded by `[b"batch_bid", bidder_pubkey, first_escrow_pubkey]`
+    /// 2. `[]` The escrow account of the won auction
+    /// 3. `[]` The clock sysvar
+    /// 4. `[]` The token program
+    /// 5. `[]` The PDA account
+    ///
+    /// Followed by these accounts for each other auction of the group, in the group order:
+    ///
+    /// 0. `[writable]` The escrow account
+    /// 1. `[writable]` The bidder's temporary FT account of the auction
+    /// 2. `[writable]` The bidder's FT account to get back to
+    ClaimBatchWinner {},
+
+    /// Sets the clock drift tolerance of the auction, bids stop that long before the auction end and
+    /// closing waits that long after it
+    /// Only the exhibitor can set it, before any bid is placed, up to `MAX_CLOCK_DRIFT_TOLERANCE_SEC`
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetAuctionTimer {
+        /// Tolerance in seconds, 0 to disable
+        clock_drift_tolerance_sec: u64,
+    },
+
+    /// Schedules a series of auctions exhibited one after the other with `TriggerNextOccurrence`
+    /// An exhibitor runs one series at a time, the first occurrence can be exhibited right away
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the exhibitor, pays for the recurring auction
+    /// 1. `[writable]` The recurring auction PDA account, seeded by `[b"recurring", exhibitor_pubkey]`
+    /// 2. `[]` The clock sysvar
+    /// 3. `[]` The rent sysvar
+    /// 4. `[]` The system program
+    SetupRecurring {
+        /// Seconds between the starts of two occurrences, at least `duration_sec`
+        interval_sec: u64,
+        /// Number of auctions in the series
+        occurrences: u8,
+        /// Initial NFT price of each occurrence
+        initial_price: u64,
+        /// Duration of each occurrence
+        duration_sec: u64,
+        /// Category of each occurrence
+        category: AuctionCategory,
+        /// Mint of the FT each occurrence is priced in
+        ft_mint: Pubkey,
+    },
+
+    /// Exhibits the next occurrence of a recurring auction, once its time has come and the previous
+    /// occurrence has ended
+    /// The exhibitor signs for the NFT of the occurrence, the series is closed after its last occurrence
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` The recurring auction PDA account, seeded by `[b"recurring", exhibitor_pubkey]`
+    /// 1. `[]` The escrow account of the previous occurrence, ignored for the first occurrence
+    ///
+    /// Followed by the accounts of `Exhibit` for the new occurrence
+    TriggerNextOccurrence {},
+
+    /// Sets whether the exhibitor approves each bidder before their bids are placed
+    /// Only the exhibitor can set it, before any bid is placed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetPreBidApproval {
+        /// Whether bids need the exhibitor's approval
+        requires_approval: bool,
+    },
+
+    /// Approves a bidder who submitted a bid, their next `Bid` is placed as usual
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[]` The escrow account holding the escrow info
+    /// 2. `[writable]` The bid approval PDA account, seeded by `[b"bid_approval", escrow_pubkey, bidder_pubkey]`
+    ApproveBid {
+        /// Bidder to approve
+        bidder: Pubkey,
+    },
+
+    /// Rejects a bidder who submitted a bid and closes their bid approval PDA
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[]` The escrow account holding the escrow info
+    /// 2. `[writable]` The bid approval PDA account, seeded by `[b"bid_approval", escrow_pubkey, bidder_pubkey]`
+    /// 3. `[writable]` The bidder's account, receiving the bid approval rent
+    RejectBid {
+        /// Bidder to reject
+        bidder: Pubkey,
+    },
+
+    /// Sets whether a bid placed in the same slot as another bidder's bid must beat it by 10%,
+    /// making it expensive to sandwich a bid within a block
+    /// Only the exhibitor can set it, before any bid is placed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetAntiSandwichProtection {
+        /// Whether same slot bids pay the premium
+        anti_sandwich: bool,
+    },
+
+    /// Sets a tiered price curve, the bid increment of the last tier whose minimum price the current price reached
+    /// applies when it's steeper than the bid multiplier, e.g. to make early bids climb fast
+    /// Tiers must be sorted by ascending minimum price, followed by the unused tiers
+    /// Only the exhibitor can set it, before any bid is placed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetAuctionPriceCurve {
+        /// Minimum price and bid increment in basis points of each tier, a 0 increment marks an unused tier
+        price_curve: [(u64, u16); MAX_PRICE_TIERS],
+    },
+
+    /// Lowers the proportional minimum increment of bids placed early in the auction, e.g. 1% during the first
+    /// 10% of the duration and 5% afterwards, replacing the bid multiplier of `SetBidMultiplier`
+    /// A 0 early bird period goes back to the bid multiplier
+    /// Only the exhibitor can set it, before any bid is placed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetEarlyBirdDiscount {
+        /// Share of the auction duration from its start in basis points during which bids are early
+        early_bird_period_bps: u16,
+        /// Proportional minimum increment in basis points of early bids
+        early_bird_increment_bps: u16,
+        /// Proportional minimum increment in basis points of later bids
+        late_increment_bps: u16,
+    },
+
+    /// Seals the end time of the auction, the events of `GetAuctionState`, `GetNextBidInfo`, `RevealCurrentPrice` and
+    /// `SetAuctionExpiry` log `i64::MAX` as the end time
+    /// until `reveal_end_at_time` or until the exhibitor reveals it through `RevealEndTime`, as in a candle auction
+    /// Only the exhibitor can set it, before any bid is placed, a 0 reveal time makes the end time public again
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetSealedEndTime {
+        /// Time from which the end time is logged, 0 to unseal it
+        reveal_end_at_time: i64,
+    },
+
+    /// Reveals the sealed end time of the auction before its reveal time
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    RevealEndTime {},
+
+    /// Weights the bids by a factor returned by an oracle program for each bidder, e.g. 1.5x for holders of a community token
+    /// A bid must beat the highest bid by its price times its weight, while the bidder still pays the price
+    /// Only the exhibitor can set it, before any bid is placed, the default pubkey compares bids by price again
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person started the auction
+    /// 1. `[writable]` The escrow account holding the escrow info
+    SetBidWeighting {
+        /// Oracle program implementing `BidWeightOracle`
+        bid_weight_oracle: Pubkey,
+    },
+}
+
+impl AuctionInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (instruction_type, rest) = input.split_first().ok_or(InvalidInstruction)?;
+        Ok(match instruction_type {
+            0 => Self::Exhibit {
+                initial_price: Self::unpack64(rest, 0)?,
+                seconds: Self::unpack64(rest, 8)?,
+                ft_mint: Self::unpack_pubkey(rest, 16)?,
+                category: if rest.len() > 48 {
+                    Self::unpack_category(rest, 48)?
+                } else {
+                    AuctionCategory::Other
+                },
+                start_delay_sec: if rest.len() > 49 {
+                    Self::unpack64(rest, 49)?
+                } else {
+                    0
+                },
+            },
+            1 => Self::Bid {
+                price: Self::unpack64(rest, 0)?,
+                nonce: Self::unpack64(rest, 8)?,
+                proof: rest
+                    .get(16..)
+                    .ok_or(InvalidInstruction)?
+                    .chunks(32)
+                    .map(|node| node.try_into().map_err(|_| InvalidInstruction.into()))
+                    .collect::<Result<Vec<_>, ProgramError>>()?,
+            },
+            2 => Self::Cancel {},
+            3 => Self::Close {},
+            4 => Self::GetAuctionState {},
+            6 => Self::ChangeExhibitorFTReceiver {
+                new_receiver: Self::unpack_pubkey(rest, 0)?,
+            },
+            7 => Self::ExtendBidDeadline {
+                additional_seconds: Self::unpack64(rest, 0)?,
+            },
+            8 => Self::SetFloorPrice {
+                floor: Self::unpack64(rest, 0)?,
+            },
+            9 => Self::InitGlobalConfig {},
+            10 => Self::RecoverStuckBid {},
+            11 => {
+                let (&count, rest) = rest.split_first().ok_or(InvalidInstruction)?;
+                let configs = (0..count as usize)
+                    .map(|i| {
+                        Ok(ExhibitConfig {
+                            initial_price: Self::unpack64(rest, i * 49)?,
+                            seconds: Self::unpack64(rest, i * 49 + 8)?,
+                            ft_mint: Self::unpack_pubkey(rest, i * 49 + 16)?,
+                            category: Self::unpack_category(rest, i * 49 + 48)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, ProgramError>>()?;
+                Self::BatchExhibit { count, configs }
+            }
+            12 => Self::SetInsuranceFee {
+                fee_bps: Self::unpack16(rest, 0)?,
+                reserve: Self::unpack64(rest, 2)?,
+            },
+            13 => Self::ClaimInsurance {},
+            14 => Self::WithdrawInsurance {
+                amount: Self::unpack64(rest, 0)?,
+            },
+            15 => Self::IncreaseBidEscrow {
+                additional: Self::unpack64(rest, 0)?,
+            },
+            16 => Self::UpdateCategory {
+                category: Self::unpack_category(rest, 0)?,
+            },
+            17 => Self::SetExhibitorNote {
+                note: Box::new(Self::unpack_bytes(rest, 0)?),
+                note_hash: Self::unpack_bytes(rest, EXHIBITOR_NOTE_LEN)?,
+            },
+            18 => Self::SetMaxBidders {
+                max: Self::unpack8(rest, 0)?,
+            },
+            19 => Self::TransferNFTAfterExpiry {},
+            20 => Self::VerifySignedMessage {
+                message_hash: Self::unpack_bytes(rest, 0)?,
+            },
+            21 => Self::Register {},
+            22 => Self::SelectWinner {
+                winner: Self::unpack_pubkey(rest, 0)?,
+            },
+            23 => Self::ClaimAirdrop {},
+            24 => Self::SetAutoClose {
+                enabled: Self::unpack_bool(rest, 0)?,
+            },
+            25 => Self::TriggerAutoClose {},
+            26 => Self::SetRefundPolicy {
+                refund_on_cancel: Self::unpack_bool(rest, 0)?,
+            },
+            27 => Self::ForceCancel {},
+            28 => Self::LiquidateBid {},
+            29 => Self::ReadProgramStats {},
+            30 => Self::WithdrawPartialProceeds {
+                amount: Self::unpack64(rest, 0)?,
+            },
+            31 => Self::SetAuctionExpiry {
+                new_end_at: Self::unpack_i64(rest, 0)?,
+            },
+            32 => Self::ValidateAuction {
+                expected_price: Self::unpack64(rest, 0)?,
+            },
+            33 => Self::SetBidVisible {
+                visible: Self::unpack_bool(rest, 0)?,
+            },
+            34 => Self::RevealCurrentPrice {},
+            35 => Self::SetNFTMintValidation {
+                expected_mint: Self::unpack_pubkey(rest, 0)?,
+            },
+            36 => Self::AssignMediator {
+                mediator: Self::unpack_pubkey(rest, 0)?,
+            },
+            37 => Self::RevokeMediator {},
+            38 => Self::GetNextBidInfo {},
+            39 => Self::InitChangelog {},
+            40 => Self::ReadChangelog {},
+            41 => Self::SetStartDelay {
+                start_delay_sec: Self::unpack64(rest, 0)?,
+            },
+            42 => Self::MigrateState {
+                target_version: Self::unpack8(rest, 0)?,
+            },
+            43 => Self::SetOraclePrice {
+                price: Self::unpack64(rest, 0)?,
+            },
+            44 => Self::SetTrustlessOracle {
+                floor_bps: Self::unpack16(rest, 0)?,
+            },
+            45 => Self::DeductRoyaltyBeforeEscrow {
+                upfront_royalty_bps: Self::unpack16(rest, 0)?,
+            },
+            46 => Self::SetGracePeriod {
+                close_grace_period_sec: Self::unpack64(rest, 0)?,
+            },
+            47 => Self::SetCloseAuthority {
+                close_authority: Self::unpack_pubkey(rest, 0)?,
+            },
+            48 => Self::BidWithSolBalance {
+                price: Self::unpack64(rest, 0)?,
+                existing_wsol: Self::unpack64(rest, 8)?,
+            },
+            49 => Self::RegisterPriorityBidder {
+                bidder: Self::unpack_pubkey(rest, 0)?,
+            },
+            50 => Self::ExhibitSoulbound {},
+            51 => Self::SetTokenExtensions {
+                transfer_hook_program: Self::unpack_pubkey(rest, 0)?,
+            },
+            52 => Self::SetMinimumDuration {
+                min_sec: Self::unpack64(rest, 0)?,
+            },
+            53 => Self::SetExhibitorMinDuration {
+                min_sec: Self::unpack64(rest, 0)?,
+            },
+            54 => Self::SetBidMultiplier {
+                min_bid_increment: Self::unpack64(rest, 0)?,
+                bid_multiplier_bps: Self::unpack16(rest, 8)?,
+            },
+            55 => Self::ExhibitWithTimestamps {
+                initial_price: Self::unpack64(rest, 0)?,
+                start_at: Self::unpack_i64(rest, 8)?,
+                end_at: Self::unpack_i64(rest, 16)?,
+                category: Self::unpack_category(rest, 24)?,
+                ft_mint: Self::unpack_pubkey(rest, 25)?,
+            },
+            56 => Self::LeaveFeedback {
+                for_auction: Self::unpack_pubkey(rest, 0)?,
+                score: Self::unpack8(rest, 32)?,
+                comment_hash: Self::unpack_bytes(rest, 33)?,
+            },
+            57 => Self::InitAuctionRoom {
+                room_id: Self::unpack64(rest, 0)?,
+                fee_bps: Self::unpack16(rest, 8)?,
+                whitelist_root: Self::unpack_bytes(rest, 10)?,
+                room_description: Self::unpack_bytes(rest, 42)?,
+            },
+            58 => Self::CloseAuctionRoom {},
+            59 => Self::SetEscrowAuthority {
+                old_program_id: Self::unpack_pubkey(rest, 0)?,
+                new_program_id: Self::unpack_pubkey(rest, 32)?,
+                new_pda: Self::unpack_pubkey(rest, 64)?,
+                new_pda_bump: Self::unpack8(rest, 96)?,
+            },
+            60 => Self::DepositBonus {
+                amount: Self::unpack64(rest, 0)?,
+            },
+            61 => Self::ReclaimBonus {},
+            62 => Self::EnableCrossProgram {
+                cpi_authority: Self::unpack_pubkey(rest, 0)?,
+            },
+            63 => Self::SetFinalityTimeout {
+                finality_timeout_sec: Self::unpack64(rest, 0)?,
+            },
+            64 => Self::RaiseDispute {},
+            65 => Self::FinalizeSettlement {},
+            66 => {
+                let (&count, rest) = rest.split_first().ok_or(InvalidInstruction)?;
+                let owners = (0..count as usize)
+                    .map(|i| {
+                        Ok((
+                            Self::unpack_pubkey(rest, i * 34)?,
+                            Self::unpack16(rest, i * 34 + 32)?,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, ProgramError>>()?;
+                Self::SetFractionalOwnership { owners }
+            }
+            67 => Self::VaultBid {
+                vault_program: Self::unpack_pubkey(rest, 0)?,
+                price: Self::unpack64(rest, 32)?,
+            },
+            68 => Self::SetPenalty {
+                penalty_bps: Self::unpack16(rest, 0)?,
+                payment_grace_period_sec: Self::unpack64(rest, 2)?,
+            },
+            69 => Self::SlashBidder {},
+            70 => Self::SetBidTokenGate {
+                gate_token_mint: Self::unpack_pubkey(rest, 0)?,
+                gate_token_min_amount: Self::unpack64(rest, 32)?,
+            },
+            71 => Self::SetLotSize {
+                lot_size: Self::unpack64(rest, 0)?,
+            },
+            72 => {
+                let mut accepted_mints = [Pubkey::default(); MAX_ACCEPTED_MINTS];
+                for (i, accepted_mint) in accepted_mints.iter_mut().enumerate() {
+                    *accepted_mint = Self::unpack_pubkey(rest, i * 32)?;
+                }
+                Self::SetAcceptedCurrencies { accepted_mints }
+            }
+            73 => Self::BidInCurrency {
+                price: Self::unpack64(rest, 0)?,
+                bid_mint: Self::unpack_pubkey(rest, 8)?,
+            },
+            74 => Self::SetSlidingExpiry {
+                idle_period_sec: Self::unpack64(rest, 0)?,
+                max_duration_sec: Self::unpack64(rest, 8)?,
+            },
+            75 => Self::SetWinnerOverride {
+                winner: Self::unpack_pubkey(rest, 0)?,
+            },
+            76 => Self::SetAuditOracle {
+                audit_oracle: Self::unpack_pubkey(rest, 0)?,
+            },
+            77 => Self::CertifyPrice {
+                certified_price: Self::unpack64(rest, 0)?,
+            },
+            78 => Self::SetBidCap {
+                max_lifetime_bid: Self::unpack64(rest, 0)?,
+            },
+            79 => Self::SetNFTLockPeriod {
+                lock_period_sec: Self::unpack64(rest, 0)?,
+            },
+            80 => Self::SetTransactionLimit {
+                transaction_limit: Self::unpack64(rest, 0)?,
+                compliance_signer: Self::unpack_pubkey(rest, 8)?,
+            },
+            81 => Self::BlacklistMint {
+                mint: Self::unpack_pubkey(rest, 0)?,
+            },
+            82 => Self::UnblacklistMint {
+                mint: Self::unpack_pubkey(rest, 0)?,
+            },
+            83 => Self::EmergencyTransferNFT {
+                destination: Self::unpack_pubkey(rest, 0)?,
+            },
+            84 => Self::PreAuthorize {
+                bidder: Self::unpack_pubkey(rest, 0)?,
+            },
+            85 => Self::BidWithFeePayer {
+                price: Self::unpack64(rest, 0)?,
+                nonce: Self::unpack64(rest, 8)?,
+            },
+            86 => Self::SetBurnOnCancel {
+                burn_on_cancel: Self::unpack_bool(rest, 0)?,
+            },
+            87 => Self::GetTWAP {},
+            88 => Self::SetNFTUpdateAuthority {
+                nft_update_authority: Self::unpack_pubkey(rest, 0)?,
+                allow_updates_during_auction: Self::unpack_bool(rest, 32)?,
+            },
+            89 => Self::UpdateNFTDuringAuction {
+                new_uri: Self::unpack_bytes(rest, 0)?,
+            },
+            90 => Self::CommitBidHash {
+                hash: Self::unpack_bytes(rest, 0)?,
+                slot: Self::unpack64(rest, 32)?,
+            },
+            91 => Self::SubmitBid {
+                price: Self::unpack64(rest, 0)?,
+                salt: Self::unpack_bytes(rest, 8)?,
+            },
+            92 => Self::SetTrustlineRequirement {
+                trustline_program: Self::unpack_pubkey(rest, 0)?,
+                trustline_type: Self::unpack_bytes(rest, 32)?,
+            },
+            93 => Self::SetAuctionType {
+                auction_type: Self::unpack_auction_type(rest, 0)?,
+            },
+            94 => Self::SetExhibitorCollateral {
+                amount: Self::unpack64(rest, 0)?,
+                cancel_penalty_bps: Self::unpack16(rest, 8)?,
+            },
+            95 => Self::ReclaimCollateral {},
+            96 => Self::ClaimCreatorRoyalty {},
+            97 => Self::GlobalBanBidder {
+                bidder: Self::unpack_pubkey(rest, 0)?,
+            },
+            98 => Self::GlobalUnbanBidder {
+                bidder: Self::unpack_pubkey(rest, 0)?,
+            },
+            99 => {
+                let mut auctions = [Pubkey::default(); MAX_BATCH_BID];
+                for (i, auction) in auctions.iter_mut().enumerate() {
+                    *auction = Self::unpack_pubkey(rest, i * 32)?;
+                }
+                Self::BatchBid {
+                    auctions,
+                    price: Self::unpack64(rest, 32 * MAX_BATCH_BID)?,
+                }
+            }
+            100 => Self::ClaimBatchWinner {},
+            101 => Self::SetAuctionTimer {
+                clock_drift_tolerance_sec: Self::unpack64(rest, 0)?,
+            },
+            102 => Self::SetupRecurring {
+                interval_sec: Self::unpack64(rest, 0)?,
+                occurrences: Self::unpack8(rest, 8)?,
+                initial_price: Self::unpack64(rest, 9)?,
+                duration_sec: Self::unpack64(rest, 17)?,
+                category: Self::unpack_category(rest, 25)?,
+                ft_mint: Self::unpack_pubkey(rest, 26)?,
+            },
+            103 => Self::TriggerNextOccurrence {},
+            104 => Self::SetPreBidApproval {
+                requires_approval: Self::unpack_bool(rest, 0)?,
+            },
+            105 => Self::ApproveBid {
+                bidder: Self::unpack_pubkey(rest, 0)?,
+            },
+            106 => Self::RejectBid {
+                bidder: Self::unpack_pubkey(rest, 0)?,
+            },
+            107 => Self::SetAntiSandwichProtection {
+                anti_sandwich: Self::unpack_bool(rest, 0)?,
+            },
+            108 => {
+                let mut price_curve = [(0, 0); MAX_PRICE_TIERS];
+                for (i, tier) in price_curve.iter_mut().enumerate() {
+                    *tier = (
+                        Self::unpack64(rest, i * 10)?,
+                        Self::unpack16(rest, i * 10 + 8)?,
+                    );
+                }
+                Self::SetAuctionPriceCurve { price_curve }
+            }
+            109 => Self::SetEarlyBirdDiscount {
+                early_bird_period_bps: Self::unpack16(rest, 0)?,
+                early_bird_increment_bps: Self::unpack16(rest, 2)?,
+                late_increment_bps: Self::unpack16(rest, 4)?,
+            },
+            110 => Self::SetSealedEndTime {
+                reveal_end_at_time: Self::unpack_i64(rest, 0)?,
+            },
+            111 => Self::RevealEndTime {},
+            112 => Self::SetBidWeighting {
+                bid_weight_oracle: Self::unpack_pubkey(rest, 0)?,
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    /// Instruction specific data recorded in the auction changelog
+    pub fn changelog_extra(&self) -> [u8; 8] {
+        match *self {
+            Self::Exhibit { initial_price, .. }
+            | Self::ExhibitWithTimestamps { initial_price, .. }
+            | Self::SetupRecurring { initial_price, .. } => initial_price.to_le_bytes(),
+            Self::Bid { price, .. } | Self::BidWithSolBalance { price, .. } => price.to_le_bytes(),
+            Self::ExtendBidDeadline { additional_seconds } => additional_seconds.to_le_bytes(),
+            Self::SetFloorPrice { floor } => floor.to_le_bytes(),
+            Self::SetInsuranceFee { fee_bps, .. } => u64::from(fee_bps).to_le_bytes(),
+            Self::WithdrawInsurance { amount }
+            | Self::WithdrawPartialProceeds { amount }
+            | Self::DepositBonus { amount }
+            | Self::SetExhibitorCollateral { amount, .. } => amount.to_le_bytes(),
+            Self::IncreaseBidEscrow { additional } => additional.to_le_bytes(),
+            Self::UpdateCategory { category } => u64::from(category as u8).to_le_bytes(),
+            Self::SetMaxBidders { max } => u64::from(max).to_le_bytes(),
+            Self::SetAuctionExpiry { new_end_at } => new_end_at.to_le_bytes(),
+            Self::ValidateAuction { expected_price } => expected_price.to_le_bytes(),
+            Self::SetStartDelay { start_delay_sec } => start_delay_sec.to_le_bytes(),
+            Self::MigrateState { target_version } => u64::from(target_version).to_le_bytes(),
+            Self::SetOraclePrice { price }
+            | Self::VaultBid { price, .. }
+            | Self::BidInCurrency { price, .. }
+            | Self::BidWithFeePayer { price, .. }
+            | Self::BatchBid { price, .. }
+            | Self::SubmitBid { price, .. } => price.to_le_bytes(),
+            Self::CertifyPrice { certified_price } => certified_price.to_le_bytes(),
+            Self::SetBidCap { max_lifetime_bid } => max_lifetime_bid.to_le_bytes(),
+            Self::SetNFTLockPeriod { lock_period_sec } => lock_period_sec.to_le_bytes(),
+            Self::SetTransactionLimit {
+                transaction_limit, ..
+            } => transaction_limit.to_le_bytes(),
+            Self::SetTrustlessOracle { floor_bps } => u64::from(floor_bps).to_le_bytes(),
+            Self::DeductRoyaltyBeforeEscrow {
+                upfront_royalty_bps,
+            } => u64::from(upfront_royalty_bps).to_le_bytes(),
+            Self::SetGracePeriod {
+                close_grace_period_sec,
+            } => close_grace_period_sec.to_le_bytes(),
+            Self::SetFinalityTimeout {
+                finality_timeout_sec,
+            } => finality_timeout_sec.to_le_bytes(),
+            Self::SetMinimumDuration { min_sec } | Self::SetExhibitorMinDuration { min_sec } => {
+                min_sec.to_le_bytes()
+            }
+            Self::SetBidMultiplier {
+                bid_multiplier_bps, ..
+            } => u64::from(bid_multiplier_bps).to_le_bytes(),
+            Self::SetPenalty { penalty_bps, .. } => u64::from(penalty_bps).to_le_bytes(),
+            Self::SetLotSize { lot_size } => lot_size.to_le_bytes(),
+            Self::SetSlidingExpiry {
+                idle_period_sec, ..
+            } => idle_period_sec.to_le_bytes(),
+            Self::LeaveFeedback { score, .. } => u64::from(score).to_le_bytes(),
+            _ => [0; 8],
+        }
+    }
+
+    /// Whether the changelog entries of the instruction tagged `tag` hold a bid price in their extra data
+    pub fn changelog_extra_is_bid_price(tag: u8) -> bool {
+        matches!(tag, 1 | 15 | 48 | 67 | 73 | 85 | 91 | 99)
+    }
+
+    /// Whether the changelog entries of the instruction tagged `tag` hold an auction end time in their extra data
+    pub fn changelog_extra_is_end_time(tag: u8) -> bool {
+        tag == 31
+    }
+
+    fn unpack8(input: &[u8], start: usize) -> Result<u8, ProgramError> {
+        let v = input.get(start).copied().ok_or(InvalidInstruction)?;
+        Ok(v)
+    }
+
+    fn unpack64(input: &[u8], start: usize) -> Result<u64, ProgramError> {
+        let v = input
+            .get(start..start + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(v)
+    }
+
+    fn unpack_i64(input: &[u8], start: usize) -> Result<i64, ProgramError> {
+        let v = input
+            .get(start..start + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(i64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(v)
+    }
+
+    fn unpack16(input: &[u8], start: usize) -> Result<u16, ProgramError> {
+        let v = input
+            .get(start..start + 2)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u16::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(v)
+    }
+
+    fn unpack_category(input: &[u8], start: usize) -> Result<AuctionCategory, ProgramError> {
+        let v = input
+            .get(start)
+            .and_then(|&category| AuctionCategory::try_from(category).ok())
+            .ok_or(InvalidInstruction)?;
+        Ok(v)
+    }
+
+    fn unpack_auction_type(input: &[u8], start: usize) -> Result<AuctionType, ProgramError> {
+        let v = input
+            .get(start)
+            .and_then(|&auction_type| AuctionType::try_from(auction_type).ok())
+            .ok_or(InvalidInstruction)?;
+        Ok(v)
+    }
+
+    fn unpack_bool(input: &[u8], start: usize) -> Result<bool, ProgramError> {
+        let v = match input.get(start) {
+            Some(0) => false,
+            Some(1) => true,
+            _ => return Err(InvalidInstruction.into()),
+        };
+        Ok(v)
+    }
+
+    fn unpack_bytes<const N: usize>(input: &[u8], start: usize) -> Result<[u8; N], ProgramError> {
+        let v = input
+            .get(start..start + N)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or(InvalidInstruction)?;
+        Ok(v)
+    }
+
+    fn unpack_pubkey(input: &[u8], start: usize) -> Result<Pubkey, ProgramError> {
+        let v = input
+            .get(start..start + 32)
+            .and_then(|slice| slice.try_into().ok())
+            .map(Pubkey::new_from_array)
+            .ok_or(InvalidInstruction)?;
+        Ok(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_invalid(result: Result<AuctionInstruction, ProgramError>) -> bool {
+        matches!(result, Err(error) if error == InvalidInstruction.into())
+    }
+
+    #[test]
+    fn unpack_empty_payload_is_invalid() {
+        assert!(is_invalid(AuctionInstruction::unpack(&[])));
+    }
+
+    #[test]
+    fn unpack_tag_only_payload_does_not_panic() {
+        for tag in 0..=u8::MAX {
+            let _ = AuctionInstruction::unpack(&[tag]);
+        }
+        assert!(is_invalid(AuctionInstruction::unpack(&[0])));
+        assert!(is_invalid(AuctionInstruction::unpack(&[1])));
+        assert!(is_invalid(AuctionInstruction::unpack(&[18])));
+        assert!(is_invalid(AuctionInstruction::unpack(&[42])));
+        assert!(matches!(
+            AuctionInstruction::unpack(&[2]),
+            Ok(AuctionInstruction::Cancel {})
+        ));
+    }
+
+    #[test]
+    fn unpack_one_byte_payload_does_not_panic() {
+        for tag in 0..=u8::MAX {
+            for byte in [0, 1, u8::MAX] {
+                let _ = AuctionInstruction::unpack(&[tag, byte]);
+            }
+        }
+        assert!(matches!(
+            AuctionInstruction::unpack(&[18, 7]),
+            Ok(AuctionInstruction::SetMaxBidders { max: 7 })
+        ));
+    }
+
+    #[test]
+    fn unpack_truncated_payloads_do_not_panic() {
+        for tag in 0..=u8::MAX {
+            for fill in [0, u8::MAX] {
+                for len in 0..512 {
+                    let mut input = vec![fill; len + 1];
+                    input[0] = tag;
+                    let _ = AuctionInstruction::unpack(&input);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unpack_exhibit_category_defaults_to_other() {
+        let mut input = vec![0; 49];
+        input[1..9].copy_from_slice(&100u64.to_le_bytes());
+        input[9..17].copy_from_slice(&3_600u64.to_le_bytes());
+        input[17..49].copy_from_slice(&[7; 32]);
+        assert!(matches!(
+            AuctionInstruction::unpack(&input),
+            Ok(AuctionInstruction::Exhibit {
+                initial_price: 100,
+                seconds: 3_600,
+                category: AuctionCategory::Other,
+                start_delay_sec: 0,
+                ..
+            })
+        ));
+
+        input.push(AuctionCategory::Gaming as u8);
+        assert!(matches!(
+            AuctionInstruction::unpack(&input),
+            Ok(AuctionInstruction::Exhibit {
+                category: AuctionCategory::Gaming,
+                ft_mint,
+                ..
+            }) if ft_mint == Pubkey::new_from_array([7; 32])
+        ));
+    }
+
+    #[test]
+    fn unpack_rejects_payloads_one_byte_short() {
+        assert!(is_invalid(AuctionInstruction::unpack(&[56; 33])));
+        assert!(is_invalid(AuctionInstruction::unpack(&[102; 9])));
+        assert!(is_invalid(AuctionInstruction::unpack(&[1; 16])));
     }
 }
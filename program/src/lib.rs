@@ -1,7 +1,13 @@
+pub mod accounts;
+pub mod bid_weight;
 pub mod error;
+pub mod event;
 pub mod instruction;
+pub mod metadata;
 pub mod processor;
 pub mod state;
+pub mod trustline;
+pub mod vault;
 
 #[cfg(not(feature = "no-entrypoint"))]
 pub mod entrypoint;
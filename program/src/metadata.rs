@@ -0,0 +1,116 @@
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey;
+use solana_program::pubkey::Pubkey;
+
+/// Program id of the Metaplex Token Metadata program
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Instruction tag of `UpdateMetadataAccountV2`
+pub const UPDATE_METADATA_ACCOUNT_V2_TAG: u8 = 15;
+
+/// Maximum length of a metadata URI
+pub const MAX_URI_LEN: usize = 200;
+
+/// Account key of a metadata account
+const METADATA_KEY: u8 = 4;
+
+/// Offset of the name, after the account key, the update authority and the mint
+const METADATA_DATA_OFFSET: usize = 65;
+
+/// Metadata PDA of the given mint
+pub fn find_metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &TOKEN_METADATA_PROGRAM_ID,
+    )
+    .0
+}
+
+/// `UpdateMetadataAccountV2` replacing the URI of the given metadata account and keeping the rest
+/// of its data, signed by the metadata update authority
+pub fn update_uri(
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    metadata_data: &[u8],
+    uri: &[u8],
+) -> Result<Instruction, ProgramError> {
+    if uri.len() > MAX_URI_LEN {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let data_v2 = data_v2_with_uri(metadata_data, uri).ok_or(ProgramError::InvalidAccountData)?;
+
+    let mut data = Vec::with_capacity(data_v2.len() + 5);
+    data.push(UPDATE_METADATA_ACCOUNT_V2_TAG);
+    data.push(1);
+    data.extend_from_slice(&data_v2);
+    // Update authority, primary sale and mutability are left unchanged
+    data.extend_from_slice(&[0, 0, 0]);
+    Ok(Instruction::new_with_bytes(
+        TOKEN_METADATA_PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(update_authority, true),
+        ],
+    ))
+}
+
+/// Borsh encoded `DataV2` of a metadata account with its URI replaced
+fn data_v2_with_uri(metadata_data: &[u8], uri: &[u8]) -> Option<Vec<u8>> {
+    if *metadata_data.first()? != METADATA_KEY {
+        return None;
+    }
+    let name_end = string_end(metadata_data, METADATA_DATA_OFFSET)?;
+    let symbol_end = string_end(metadata_data, name_end)?;
+    let uri_end = string_end(metadata_data, symbol_end)?;
+    // Seller fee basis points, followed by the optional creators of 34 bytes each
+    let creators_start = uri_end + 2;
+    let creators_end = creators_start
+        + match *metadata_data.get(creators_start)? {
+            0 => 1,
+            1 => 5 + 34 * read_u32(metadata_data, creators_start + 1)? as usize,
+            _ => return None,
+        };
+    // Primary sale and mutability flags, then the optional edition nonce and token standard
+    let mut offset = creators_end + 2;
+    offset += option_len(metadata_data, offset, 1)?;
+    offset += option_len(metadata_data, offset, 1)?;
+    let collection_start = offset;
+    offset += option_len(metadata_data, offset, 33)?;
+    offset += option_len(metadata_data, offset, 17)?;
+
+    let mut data_v2 = Vec::with_capacity(offset - METADATA_DATA_OFFSET + uri.len());
+    data_v2.extend_from_slice(metadata_data.get(METADATA_DATA_OFFSET..symbol_end)?);
+    data_v2.extend_from_slice(&(uri.len() as u32).to_le_bytes());
+    data_v2.extend_from_slice(uri);
+    data_v2.extend_from_slice(metadata_data.get(uri_end..creators_end)?);
+    data_v2.extend_from_slice(metadata_data.get(collection_start..offset)?);
+    Some(data_v2)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .and_then(|slice| slice.try_into().ok())
+        .map(u32::from_le_bytes)
+}
+
+/// End of the length prefixed string at `offset`
+fn string_end(data: &[u8], offset: usize) -> Option<usize> {
+    let end = offset + 4 + read_u32(data, offset)? as usize;
+    (end <= data.len()).then_some(end)
+}
+
+/// Length of the option at `offset` holding a value of `size` bytes when set
+fn option_len(data: &[u8], offset: usize, size: usize) -> Option<usize> {
+    match data.get(offset)? {
+        0 => Some(1),
+        1 => Some(1 + size),
+        _ => None,
+    }
+}
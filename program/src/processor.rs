@@ -1,18 +1,117 @@
+use crate::accounts::{
+    BidAccounts, CancelAccounts, CloseAccounts, ExhibitAccounts, EXHIBIT_ACCOUNTS_LEN,
+};
+use crate::bid_weight::{BidWeightOracle, StandardBidWeightOracle, UNIT_BID_WEIGHT_BPS};
 use crate::error::AuctionError;
-use crate::instruction::AuctionInstruction;
-use crate::state::Auction;
+use crate::event::{
+    AuctionStateEvent, BidEvent, ChangelogEntryEvent, ExpiryUpdatedEvent, ForfeitedAuctionEvent,
+    NewAuctionEvent, NextBidInfoEvent, ProgramStatsEvent, TWAPEvent, ValidationPassedEvent,
+};
+use crate::instruction::{AuctionInstruction, ExhibitConfig, MAX_BATCH_EXHIBIT};
+use crate::metadata::{self, TOKEN_METADATA_PROGRAM_ID};
+use crate::state::{
+    Auction, AuctionCategory, AuctionIndex, AuctionRoom, AuctionType, AuditRecord, BatchBidGroup, BidApproval, BidHistory, BonusDeposit, BidRecord, BidderRegistry, ChangelogAccount,
+    ChangelogEntry, ExhibitorConfig, ExhibitorNote, ExhibitorProfile, FeePayerOverride, FeedbackRecord, GlobalBanRecord, InsuranceClaimRecord, GlobalConfig, LifetimeBidVolume, MintBlacklist, NFTLockRecord, PendingBid, PriceOracle, ProgramStats,
+    RecurringAuction, RegistrationRecord, TombstoneRecord, UserProfile, AUCTION_VERSION, BIDDER_REGISTRY_CAPACITY,
+    CHANGELOG_CAPACITY, DEFAULT_CLOSE_GRACE_PERIOD_SEC, DEFAULT_MAX_BIDDER_EXTENSION_SEC, EXHIBITOR_NOTE_LEN,
+    GLOBAL_CONFIG_V1_LEN, MAX_ACCEPTED_MINTS, MAX_BATCH_BID, MAX_CLOCK_DRIFT_TOLERANCE_SEC, MAX_FRACTIONAL_OWNERS, MAX_PRICE_TIERS, ROOM_DESCRIPTION_LEN,
+};
+use crate::trustline::{StandardTrustline, TrustlineVerifier};
+use crate::vault::{StandardVault, VaultAccounts, VaultInterface};
 use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
 use solana_program::clock::Clock;
+use solana_program::ed25519_program;
 use solana_program::entrypoint::ProgramResult;
+use solana_program::hash::{hash, hashv};
+use solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
 use solana_program::msg;
-use solana_program::program::{invoke, invoke_signed};
+use solana_program::program::{get_return_data, invoke, invoke_signed};
 use solana_program::program_error::ProgramError;
+use solana_program::program_option::COption;
 use solana_program::program_pack::{IsInitialized, Pack};
+use solana_program::program_utils::limited_deserialize;
 use solana_program::pubkey::Pubkey;
 use solana_program::rent::Rent;
-use solana_program::sysvar::Sysvar;
-use spl_token::state::Account as TokenAccount;
-use std::ops::Add;
+use solana_program::system_instruction;
+use solana_program::sysvar::{instructions as sysvar_instructions, slot_hashes, Sysvar};
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+
+/// Basis points representing 100%
+const MAX_BASIS_POINTS: u16 = 10_000;
+
+/// Maximum combined insurance fee and royalty, leaving at least 5% of a bid to the exhibitor
+const MAX_DEDUCTION_BPS: u16 = 9_500;
+
+/// Premium over another bidder's bid in the same slot a bid must exceed when anti-sandwich protection is on
+const SAME_SLOT_PREMIUM_BPS: u16 = 1_000;
+
+/// Maximum seconds governance can push an auction end time by in a single update
+const MAX_GOVERNANCE_EXTENSION_SEC: i64 = 30 * 86_400;
+
+/// Lamports paid from the escrow to whoever triggers an auto close
+const AUTO_CLOSE_TIP_LAMPORTS: u64 = 10_000;
+
+/// Seconds after the tombstone is written during which both parties can leave feedback
+const FEEDBACK_WINDOW_SEC: i64 = 30 * 86_400;
+
+/// Seconds after the auction end before the program authority can move a stuck NFT out of escrow
+const EMERGENCY_THRESHOLD_SEC: i64 = 30 * 86_400;
+
+/// Size of the Ed25519 program instruction header and signature offsets
+const ED25519_OFFSETS_END: usize = 16;
+
+/// Mint of a bid made through `BidInCurrency` and the oracle prices converting it to the auction FT mint
+struct BidCurrency {
+    mint: Pubkey,
+    /// Oracle price of the auction FT mint
+    base_price: u64,
+    /// Oracle price of the bid mint, never 0
+    bid_price: u64,
+}
+
+impl BidCurrency {
+    /// Value in the auction FT mint of an amount of the bid mint
+    fn value_of(&self, amount: u64) -> Result<u64, ProgramError> {
+        u64::try_from(
+            u128::from(amount) * u128::from(self.base_price) / u128::from(self.bid_price),
+        )
+        .map_err(|_| AuctionError::AmountOverflow.into())
+    }
+}
+
+/// Instruction a bid reaches `process_bid` through
+enum BidOrigin<'a, 'b> {
+    /// Placed directly by the bidder or its CPI authority
+    Direct,
+    /// Submitted by the pre-authorized fee payer account, checked by `process_bid_with_fee_payer`
+    FeePayer(&'a AccountInfo<'b>),
+    /// Revealed from a commitment, checked by `process_submit_bid`
+    Commitment,
+}
+
+/// Auctions changed by the instruction being processed, each handler records the escrow it acts on
+/// and the instruction is appended to the changelog of every recorded auction that has one
+#[derive(Default)]
+struct ChangedAuctions {
+    /// Escrow account pubkey and changelog bump of each recorded auction
+    changelogs: Vec<(Pubkey, u8)>,
+}
+
+impl ChangedAuctions {
+    /// Records the auction, which this program no longer manages once its escrowed accounts are migrated
+    fn record(&mut self, escrow: &Pubkey, auction_info: &Auction) -> ProgramResult {
+        if auction_info.is_migrated() {
+            return Err(AuctionError::EscrowMigrated.into());
+        }
+        if auction_info.has_changelog
+            && !self.changelogs.iter().any(|(recorded, _)| recorded == escrow)
+        {
+            self.changelogs.push((*escrow, auction_info.changelog_bump));
+        }
+        Ok(())
+    }
+}
 
 pub struct Processor;
 
@@ -23,48 +122,719 @@ impl Processor {
         instruction_data: &[u8],
     ) -> ProgramResult {
         let instruction = AuctionInstruction::unpack(instruction_data)?;
+        let changelog_extra = instruction.changelog_extra();
+        let (accounts, changelog_accounts) =
+            Self::split_changelogs(accounts, &instruction, program_id);
+        let mut changed = ChangedAuctions::default();
         match instruction {
             AuctionInstruction::Exhibit {
                 initial_price,
                 seconds,
+                category,
+                ft_mint,
+                start_delay_sec,
             } => {
                 msg!("Initializing Auction...");
-                Self::process_exhibit(accounts, initial_price, seconds, program_id)
+                Self::process_exhibit(
+                    &ExhibitAccounts::from_accounts(accounts)?,
+                    initial_price,
+                    seconds,
+                    category,
+                    ft_mint,
+                    start_delay_sec,
+                    program_id,
+                )
             }
-            AuctionInstruction::Bid { price } => {
+            AuctionInstruction::Bid {
+                price,
+                nonce,
+                proof,
+            } => {
                 msg!("Placing a Bid in the Auction...");
-                Self::process_bid(accounts, price, program_id)
+                Self::process_bid(
+                    &BidAccounts::from_accounts(accounts)?,
+                    price,
+                    Some(nonce),
+                    &proof,
+                    None,
+                    BidOrigin::Direct,
+                    &mut changed,
+                    program_id,
+                )
             }
             AuctionInstruction::Cancel {} => {
                 msg!("Cancelling the Auction ...");
-                Self::process_cancel(accounts, program_id)
+                Self::process_cancel(
+                    &CancelAccounts::from_accounts(accounts)?,
+                    &mut changed,
+                    program_id,
+                )
             }
             AuctionInstruction::Close {} => {
                 msg!("Closing the Auction ...");
-                Self::closing_the_process(accounts, program_id)
+                Self::closing_the_process(
+                    &CloseAccounts::from_accounts(accounts)?,
+                    &mut changed,
+                    program_id,
+                )
             }
-        }
+            AuctionInstruction::GetAuctionState {} => {
+                msg!("Reading the Auction state...");
+                Self::process_get_auction_state(accounts)
+            }
+            AuctionInstruction::ChangeExhibitorFTReceiver { new_receiver } => {
+                msg!("Changing the Exhibitor's FT receiving account...");
+                Self::process_change_exhibitor_ft_receiver(accounts, new_receiver, &mut changed)
+            }
+            AuctionInstruction::ExtendBidDeadline { additional_seconds } => {
+                msg!("Extending the Auction deadline...");
+                Self::process_extend_bid_deadline(accounts, additional_seconds, &mut changed)
+            }
+            AuctionInstruction::SetFloorPrice { floor } => {
+                msg!("Setting the Auction floor price...");
+                Self::process_set_floor_price(accounts, floor, &mut changed)
+            }
+            AuctionInstruction::InitGlobalConfig {} => {
+                msg!("Initializing the Global Config...");
+                Self::process_init_global_config(accounts, program_id)
+            }
+            AuctionInstruction::RecoverStuckBid {} => {
+                msg!("Recovering a stuck Bid...");
+                Self::process_recover_stuck_bid(accounts, &mut changed, program_id)
+            }
+            AuctionInstruction::BatchExhibit { count, configs } => {
+                msg!("Initializing a batch of Auctions...");
+                Self::process_batch_exhibit(accounts, count, configs, program_id)
+            }
+            AuctionInstruction::SetInsuranceFee { fee_bps, reserve } => {
+                msg!("Setting the insurance fee...");
+                Self::process_set_insurance_fee(accounts, fee_bps, reserve, program_id)
+            }
+            AuctionInstruction::ClaimInsurance {} => {
+                msg!("Claiming insurance...");
+                Self::process_claim_insurance(accounts, program_id)
+            }
+            AuctionInstruction::WithdrawInsurance { amount } => {
+                msg!("Withdrawing insurance surplus...");
+                Self::process_withdraw_insurance(accounts, amount, program_id)
+            }
+            AuctionInstruction::IncreaseBidEscrow { additional } => {
+                msg!("Increasing the Bid escrow...");
+                Self::process_increase_bid_escrow(accounts, additional, &mut changed)
+            }
+            AuctionInstruction::UpdateCategory { category } => {
+                msg!("Updating the Auction category...");
+                Self::process_update_category(accounts, category, &mut changed, program_id)
+            }
+            AuctionInstruction::SetExhibitorNote { note, note_hash } => {
+                msg!("Setting the Exhibitor note...");
+                Self::process_set_exhibitor_note(
+                    accounts,
+                    note,
+                    note_hash,
+                    &mut changed,
+                    program_id,
+                )
+            }
+            AuctionInstruction::SetMaxBidders { max } => {
+                msg!("Setting the max bidders...");
+                Self::process_set_max_bidders(accounts, max, &mut changed, program_id)
+            }
+            AuctionInstruction::TransferNFTAfterExpiry {} => {
+                msg!("Returning the NFT of the forfeited Auction...");
+                Self::process_transfer_nft_after_expiry(accounts, &mut changed, program_id)
+            }
+            AuctionInstruction::VerifySignedMessage { message_hash } => {
+                msg!("Verifying the Auction terms signature...");
+                Self::process_verify_signed_message(accounts, message_hash, &mut changed)
+            }
+            AuctionInstruction::Register {} => {
+                msg!("Registering to the Auction...");
+                Self::process_register(accounts, &mut changed, program_id)
+            }
+            AuctionInstruction::SelectWinner { winner } => {
+                msg!("Selecting the Auction winner...");
+                Self::process_select_winner(accounts, winner, &mut changed, program_id)
+            }
+            AuctionInstruction::ClaimAirdrop {} => {
+                msg!("Claiming the airdropped NFT...");
+                Self::process_claim_airdrop(accounts, &mut changed, program_id)
+            }
+            AuctionInstruction::SetAutoClose { enabled } => {
+                msg!("Setting the Auction auto close...");
+                Self::process_set_auto_close(accounts, enabled, &mut changed)
+            }
+            AuctionInstruction::TriggerAutoClose {} => {
+                msg!("Auto closing the Auction...");
+                Self::process_trigger_auto_close(accounts, &mut changed, program_id)
+            }
+            AuctionInstruction::SetRefundPolicy { refund_on_cancel } => {
+                msg!("Setting the Auction refund policy...");
+                Self::process_set_refund_policy(accounts, refund_on_cancel, &mut changed)
+            }
+            AuctionInstruction::ForceCancel {} => {
+                msg!("Force cancelling the Auction...");
+                Self::process_force_cancel(accounts, &mut changed, program_id)
+            }
+            AuctionInstruction::LiquidateBid {} => {
+                msg!("Liquidating the highest Bid...");
+                Self::process_liquidate_bid(accounts, &mut changed, program_id)
+            }
+            AuctionInstruction::ReadProgramStats {} => {
+                msg!("Reading the Program stats...");
+                Self::process_read_program_stats(accounts, program_id)
+            }
+            AuctionInstruction::WithdrawPartialProceeds { amount } => {
+                msg!("Withdrawing part of the proceeds...");
+                Self::process_withdraw_partial_proceeds(accounts, amount, &mut changed, program_id)
+            }
+            AuctionInstruction::SetAuctionExpiry { new_end_at } => {
+                msg!("Setting the Auction expiry...");
+                Self::process_set_auction_expiry(accounts, new_end_at, &mut changed, program_id)
+            }
+            AuctionInstruction::ValidateAuction { expected_price } => {
+                msg!("Validating the Auction...");
+                Self::process_validate_auction(accounts, expected_price)
+            }
+            AuctionInstruction::SetBidVisible { visible } => {
+                msg!("Setting the Bid visibility...");
+                Self::process_set_bid_visible(accounts, visible, &mut changed)
+            }
+            AuctionInstruction::RevealCurrentPrice {} => {
+                msg!("Revealing the current price...");
+                Self::process_reveal_current_price(accounts)
+            }
+            AuctionInstruction::SetNFTMintValidation { expected_mint } => {
+                msg!("Validating the exhibited NFT mint...");
+                Self::process_set_nft_mint_validation(accounts, expected_mint, &mut changed)
+            }
+            AuctionInstruction::AssignMediator { mediator } => {
+                msg!("Assigning the Auction mediator...");
+                Self::process_assign_mediator(accounts, mediator, &mut changed)
+            }
+            AuctionInstruction::RevokeMediator {} => {
+                msg!("Revoking the Auction mediator...");
+                Self::process_revoke_mediator(accounts, &mut changed)
+            }
+            AuctionInstruction::GetNextBidInfo {} => {
+                msg!("Reading the next Bid info...");
+                Self::process_get_next_bid_info(accounts, program_id)
+            }
+            AuctionInstruction::InitChangelog {} => {
+                msg!("Creating the Auction changelog...");
+                Self::process_init_changelog(accounts, program_id)
+            }
+            AuctionInstruction::ReadChangelog {} => {
+                msg!("Reading the Auction changelog...");
+                Self::process_read_changelog(accounts, program_id)
+            }
+            AuctionInstruction::SetStartDelay { start_delay_sec } => {
+                msg!("Setting the Auction start delay...");
+                Self::process_set_start_delay(accounts, start_delay_sec, &mut changed)
+            }
+            AuctionInstruction::MigrateState { target_version } => {
+                msg!("Migrating the Auction state...");
+                Self::process_migrate_state(accounts, target_version, &mut changed, program_id)
+            }
+            AuctionInstruction::SetOraclePrice { price } => {
+                msg!("Setting the oracle price...");
+                Self::process_set_oracle_price(accounts, price, program_id)
+            }
+            AuctionInstruction::SetTrustlessOracle { floor_bps } => {
+                msg!("Validating the Auction price against the oracle...");
+                Self::process_set_trustless_oracle(accounts, floor_bps, &mut changed, program_id)
+            }
+            AuctionInstruction::DeductRoyaltyBeforeEscrow {
+                upfront_royalty_bps,
+            } => {
+                msg!("Setting the upfront royalty...");
+                Self::process_deduct_royalty_before_escrow(
+                    accounts,
+                    upfront_royalty_bps,
+                    &mut changed,
+                    program_id,
+                )
+            }
+            AuctionInstruction::SetGracePeriod {
+                close_grace_period_sec,
+            } => {
+                msg!("Setting the close grace period...");
+                Self::process_set_grace_period(accounts, close_grace_period_sec, &mut changed)
+            }
+            AuctionInstruction::SetCloseAuthority { close_authority } => {
+                msg!("Setting the Auction close authority...");
+                Self::process_set_close_authority(accounts, close_authority, &mut changed)
+            }
+            AuctionInstruction::BidWithSolBalance {
+                price,
+                existing_wsol,
+            } => {
+                msg!("Placing a Bid in the Auction with SOL...");
+                Self::process_bid_with_sol_balance(
+                    accounts,
+                    price,
+                    existing_wsol,
+                    &mut changed,
+                    program_id,
+                )
+            }
+            AuctionInstruction::RegisterPriorityBidder { bidder } => {
+                msg!("Registering the priority bidder...");
+                Self::process_register_priority_bidder(accounts, bidder, &mut changed, program_id)
+            }
+            AuctionInstruction::ExhibitSoulbound {} => {
+                msg!("Making the Auction soulbound...");
+                Self::process_exhibit_soulbound(accounts, &mut changed, program_id)
+            }
+            AuctionInstruction::SetTokenExtensions {
+                transfer_hook_program,
+            } => {
+                msg!("Setting the whitelisted transfer hook program...");
+                Self::process_set_token_extensions(accounts, transfer_hook_program, program_id)
+            }
+            AuctionInstruction::SetMinimumDuration { min_sec } => {
+                msg!("Setting the minimum Auction duration...");
+                Self::process_set_minimum_duration(accounts, min_sec, program_id)
+            }
+            AuctionInstruction::SetExhibitorMinDuration { min_sec } => {
+                msg!("Setting the exhibitor minimum Auction duration...");
+                Self::process_set_exhibitor_min_duration(accounts, min_sec, program_id)
+            }
+            AuctionInstruction::SetBidMultiplier {
+                min_bid_increment,
+                bid_multiplier_bps,
+            } => {
+                msg!("Setting the Auction bid increments...");
+                Self::process_set_bid_multiplier(
+                    accounts,
+                    min_bid_increment,
+                    bid_multiplier_bps,
+                    &mut changed,
+                )
+            }
+            AuctionInstruction::ExhibitWithTimestamps {
+                initial_price,
+                start_at,
+                end_at,
+                category,
+                ft_mint,
+            } => {
+                msg!("Initializing Auction with timestamps...");
+                Self::process_exhibit_with_timestamps(
+                    accounts,
+                    initial_price,
+                    start_at,
+                    end_at,
+                    category,
+                    ft_mint,
+                    program_id,
+                )
+            }
+            AuctionInstruction::LeaveFeedback {
+                for_auction,
+                score,
+                comment_hash,
+            } => {
+                msg!("Leaving feedback on the Auction...");
+                Self::process_leave_feedback(accounts, for_auction, score, comment_hash, program_id)
+            }
+            AuctionInstruction::InitAuctionRoom {
+                room_id,
+                fee_bps,
+                whitelist_root,
+                room_description,
+            } => {
+                msg!("Creating the Auction room...");
+                Self::process_init_auction_room(
+                    accounts,
+                    room_id,
+                    fee_bps,
+                    whitelist_root,
+                    room_description,
+                    program_id,
+                )
+            }
+            AuctionInstruction::CloseAuctionRoom {} => {
+                msg!("Closing the Auction room...");
+                Self::process_close_auction_room(accounts, program_id)
+            }
+            AuctionInstruction::SetEscrowAuthority {
+                old_program_id,
+                new_program_id,
+                new_pda,
+                new_pda_bump,
+            } => {
+                msg!("Reassigning the escrowed accounts to the new PDA...");
+                Self::process_set_escrow_authority(
+                    accounts,
+                    old_program_id,
+                    new_program_id,
+                    new_pda,
+                    new_pda_bump,
+                    &mut changed,
+                    program_id,
+                )
+            }
+            AuctionInstruction::DepositBonus { amount } => {
+                msg!("Depositing a bonus for the Auction winner...");
+                Self::process_deposit_bonus(accounts, amount, &mut changed, program_id)
+            }
+            AuctionInstruction::ReclaimBonus {} => {
+                msg!("Reclaiming the bonus deposit...");
+                Self::process_reclaim_bonus(accounts, program_id)
+            }
+            AuctionInstruction::EnableCrossProgram { cpi_authority } => {
+                msg!("Enabling cross-program bids...");
+                Self::process_enable_cross_program(accounts, cpi_authority, &mut changed)
+            }
+            AuctionInstruction::SetFinalityTimeout {
+                finality_timeout_sec,
+            } => {
+                msg!("Setting the Auction finality timeout...");
+                Self::process_set_finality_timeout(accounts, finality_timeout_sec, &mut changed)
+            }
+            AuctionInstruction::RaiseDispute {} => {
+                msg!("Raising a dispute on the Auction settlement...");
+                Self::process_raise_dispute(accounts, &mut changed)
+            }
+            AuctionInstruction::FinalizeSettlement {} => {
+                msg!("Finalizing the Auction settlement...");
+                Self::process_finalize_settlement(accounts, &mut changed, program_id)
+            }
+            AuctionInstruction::SetFractionalOwnership { owners } => {
+                msg!("Setting the fractional owners of the Auction...");
+                Self::process_set_fractional_ownership(accounts, &owners, &mut changed)
+            }
+            AuctionInstruction::VaultBid {
+                vault_program,
+                price,
+            } => {
+                msg!("Bidding on the Auction from a vault...");
+                Self::process_vault_bid(accounts, vault_program, price, &mut changed, program_id)
+            }
+            AuctionInstruction::SetPenalty {
+                penalty_bps,
+                payment_grace_period_sec,
+            } => {
+                msg!("Setting the Auction penalty...");
+                Self::process_set_penalty(
+                    accounts,
+                    penalty_bps,
+                    payment_grace_period_sec,
+                    &mut changed,
+                )
+            }
+            AuctionInstruction::SlashBidder {} => {
+                msg!("Slashing the Auction winner...");
+                Self::process_slash_bidder(accounts, &mut changed, program_id)
+            }
+            AuctionInstruction::SetBidTokenGate {
+                gate_token_mint,
+                gate_token_min_amount,
+            } => {
+                msg!("Setting the Auction gate token...");
+                Self::process_set_bid_token_gate(
+                    accounts,
+                    gate_token_mint,
+                    gate_token_min_amount,
+                    &mut changed,
+                )
+            }
+            AuctionInstruction::SetLotSize { lot_size } => {
+                msg!("Setting the Auction lot size...");
+                Self::process_set_lot_size(accounts, lot_size, &mut changed, program_id)
+            }
+            AuctionInstruction::SetAcceptedCurrencies { accepted_mints } => {
+                msg!("Setting the accepted currencies of the Auction...");
+                Self::process_set_accepted_currencies(accounts, accepted_mints, &mut changed)
+            }
+            AuctionInstruction::BidInCurrency { price, bid_mint } => {
+                msg!("Placing a Bid in the Auction in an accepted currency...");
+                Self::process_bid_in_currency(accounts, price, bid_mint, &mut changed, program_id)
+            }
+            AuctionInstruction::SetSlidingExpiry {
+                idle_period_sec,
+                max_duration_sec,
+            } => {
+                msg!("Setting the Auction sliding expiry...");
+                Self::process_set_sliding_expiry(
+                    accounts,
+                    idle_period_sec,
+                    max_duration_sec,
+                    &mut changed,
+                )
+            }
+            AuctionInstruction::SetWinnerOverride { winner } => {
+                msg!("Overriding the Auction winner...");
+                Self::process_set_winner_override(accounts, winner, &mut changed)
+            }
+            AuctionInstruction::SetAuditOracle { audit_oracle } => {
+                msg!("Setting the Auction audit oracle...");
+                Self::process_set_audit_oracle(accounts, audit_oracle, &mut changed)
+            }
+            AuctionInstruction::CertifyPrice { certified_price } => {
+                msg!("Certifying the Auction settlement price...");
+                Self::process_certify_price(accounts, certified_price, &mut changed, program_id)
+            }
+            AuctionInstruction::SetBidCap { max_lifetime_bid } => {
+                msg!("Setting the lifetime bid cap...");
+                Self::process_set_bid_cap(accounts, max_lifetime_bid, program_id)
+            }
+            AuctionInstruction::SetNFTLockPeriod { lock_period_sec } => {
+                msg!("Setting the NFT lock period...");
+                Self::process_set_nft_lock_period(accounts, lock_period_sec, program_id)
+            }
+            AuctionInstruction::SetTransactionLimit {
+                transaction_limit,
+                compliance_signer,
+            } => {
+                msg!("Setting the transaction limit...");
+                Self::process_set_transaction_limit(
+                    accounts,
+                    transaction_limit,
+                    compliance_signer,
+                    program_id,
+                )
+            }
+            AuctionInstruction::BlacklistMint { mint } => {
+                msg!("Blacklisting the FT mint...");
+                Self::process_blacklist_mint(accounts, mint, program_id)
+            }
+            AuctionInstruction::UnblacklistMint { mint } => {
+                msg!("Removing the FT mint from the blacklist...");
+                Self::process_unblacklist_mint(accounts, mint, program_id)
+            }
+            AuctionInstruction::EmergencyTransferNFT { destination } => {
+                msg!("Moving the NFT out of the stuck Auction...");
+                Self::process_emergency_transfer_nft(
+                    accounts,
+                    destination,
+                    &mut changed,
+                    program_id,
+                )
+            }
+            AuctionInstruction::PreAuthorize { bidder } => {
+                msg!("Pre-authorizing a fee payer for the bidder...");
+                Self::process_pre_authorize(accounts, bidder, &mut changed, program_id)
+            }
+            AuctionInstruction::BidWithFeePayer { price, nonce } => {
+                msg!("Placing a Bid paid by a fee payer...");
+                Self::process_bid_with_fee_payer(accounts, price, nonce, &mut changed, program_id)
+            }
+            AuctionInstruction::SetBurnOnCancel { burn_on_cancel } => {
+                msg!("Setting whether a cancel burns the NFT...");
+                Self::process_set_burn_on_cancel(accounts, burn_on_cancel, &mut changed)
+            }
+            AuctionInstruction::GetTWAP {} => {
+                msg!("Getting the time weighted average price...");
+                Self::process_get_twap(accounts)
+            }
+            AuctionInstruction::SetNFTUpdateAuthority {
+                nft_update_authority,
+                allow_updates_during_auction,
+            } => {
+                msg!("Setting the NFT update authority...");
+                Self::process_set_nft_update_authority(
+                    accounts,
+                    nft_update_authority,
+                    allow_updates_during_auction,
+                    &mut changed,
+                )
+            }
+            AuctionInstruction::UpdateNFTDuringAuction { new_uri } => {
+                msg!("Updating the NFT in escrow...");
+                Self::process_update_nft_during_auction(accounts, &new_uri, &mut changed)
+            }
+            AuctionInstruction::CommitBidHash { hash, slot } => {
+                msg!("Committing a Bid...");
+                Self::process_commit_bid_hash(accounts, hash, slot, &mut changed, program_id)
+            }
+            AuctionInstruction::SubmitBid { price, salt } => {
+                msg!("Revealing the committed Bid...");
+                Self::process_submit_bid(accounts, price, &salt, &mut changed, program_id)
+            }
+            AuctionInstruction::SetTrustlineRequirement {
+                trustline_program,
+                trustline_type,
+            } => {
+                msg!("Setting the trustline requirement...");
+                Self::process_set_trustline_requirement(
+                    accounts,
+                    trustline_program,
+                    trustline_type,
+                    &mut changed,
+                )
+            }
+            AuctionInstruction::SetAuctionType { auction_type } => {
+                msg!("Setting the auction type...");
+                Self::process_set_auction_type(accounts, auction_type, &mut changed)
+            }
+            AuctionInstruction::SetExhibitorCollateral {
+                amount,
+                cancel_penalty_bps,
+            } => {
+                msg!("Locking the exhibitor collateral...");
+                Self::process_set_exhibitor_collateral(
+                    accounts,
+                    amount,
+                    cancel_penalty_bps,
+                    &mut changed,
+                    program_id,
+                )
+            }
+            AuctionInstruction::ReclaimCollateral {} => {
+                msg!("Reclaiming the exhibitor collateral...");
+                Self::process_reclaim_collateral(accounts, program_id)
+            }
+            AuctionInstruction::ClaimCreatorRoyalty {} => {
+                msg!("Claiming the creator royalty...");
+                Self::process_claim_creator_royalty(accounts, program_id)
+            }
+            AuctionInstruction::GlobalBanBidder { bidder } => {
+                msg!("Banning the bidder from all auctions...");
+                Self::process_global_ban_bidder(accounts, bidder, program_id)
+            }
+            AuctionInstruction::GlobalUnbanBidder { bidder } => {
+                msg!("Lifting the global ban of the bidder...");
+                Self::process_global_unban_bidder(accounts, bidder, program_id)
+            }
+            AuctionInstruction::BatchBid { auctions, price } => {
+                msg!("Bidding on a batch of Auctions...");
+                Self::process_batch_bid(accounts, auctions, price, &mut changed, program_id)
+            }
+            AuctionInstruction::ClaimBatchWinner {} => {
+                msg!("Settling the batch bid group...");
+                Self::process_claim_batch_winner(accounts, &mut changed, program_id)
+            }
+            AuctionInstruction::SetAuctionTimer {
+                clock_drift_tolerance_sec,
+            } => {
+                msg!("Setting the clock drift tolerance...");
+                Self::process_set_auction_timer(accounts, clock_drift_tolerance_sec, &mut changed)
+            }
+            AuctionInstruction::SetupRecurring {
+                interval_sec,
+                occurrences,
+                initial_price,
+                duration_sec,
+                category,
+                ft_mint,
+            } => {
+                msg!("Scheduling the recurring Auction...");
+                Self::process_setup_recurring(
+                    accounts,
+                    interval_sec,
+                    occurrences,
+                    initial_price,
+                    duration_sec,
+                    category,
+                    ft_mint,
+                    program_id,
+                )
+            }
+            AuctionInstruction::TriggerNextOccurrence {} => {
+                msg!("Exhibiting the next occurrence of the recurring Auction...");
+                Self::process_trigger_next_occurrence(accounts, program_id)
+            }
+            AuctionInstruction::SetPreBidApproval { requires_approval } => {
+                msg!("Setting the bid approval requirement...");
+                Self::process_set_pre_bid_approval(accounts, requires_approval, &mut changed)
+            }
+            AuctionInstruction::ApproveBid { bidder } => {
+                msg!("Approving the bidder...");
+                Self::process_approve_bid(accounts, bidder, &mut changed, program_id)
+            }
+            AuctionInstruction::RejectBid { bidder } => {
+                msg!("Rejecting the bidder...");
+                Self::process_reject_bid(accounts, bidder, &mut changed, program_id)
+            }
+            AuctionInstruction::SetAntiSandwichProtection { anti_sandwich } => {
+                msg!("Setting the anti-sandwich protection...");
+                Self::process_set_anti_sandwich_protection(accounts, anti_sandwich, &mut changed)
+            }
+            AuctionInstruction::SetAuctionPriceCurve { price_curve } => {
+                msg!("Setting the price curve...");
+                Self::process_set_auction_price_curve(accounts, price_curve, &mut changed)
+            }
+            AuctionInstruction::SetEarlyBirdDiscount {
+                early_bird_period_bps,
+                early_bird_increment_bps,
+                late_increment_bps,
+            } => {
+                msg!("Setting the early bird discount...");
+                Self::process_set_early_bird_discount(
+                    accounts,
+                    early_bird_period_bps,
+                    early_bird_increment_bps,
+                    late_increment_bps,
+                    &mut changed,
+                )
+            }
+            AuctionInstruction::SetSealedEndTime { reveal_end_at_time } => {
+                msg!("Sealing the end time...");
+                Self::process_set_sealed_end_time(accounts, reveal_end_at_time, &mut changed)
+            }
+            AuctionInstruction::RevealEndTime {} => {
+                msg!("Revealing the end time...");
+                Self::process_reveal_end_time(accounts, &mut changed)
+            }
+            AuctionInstruction::SetBidWeighting { bid_weight_oracle } => {
+                msg!("Setting the bid weight oracle...");
+                Self::process_set_bid_weighting(accounts, bid_weight_oracle, &mut changed)
+            }
+        }?;
+
+        Self::append_changelogs(
+            &changed,
+            changelog_accounts,
+            accounts,
+            instruction_data[0],
+            changelog_extra,
+            program_id,
+        )
     }
 
     fn process_exhibit(
-        accounts: &[AccountInfo],
+        accounts: &ExhibitAccounts,
         initial_price: u64,
         auction_duration_sec: u64,
+        category: AuctionCategory,
+        ft_mint: Pubkey,
+        start_delay_sec: u64,
         program_id: &Pubkey,
     ) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+        let accouint_of_exhibitor = accounts.exhibitor;
 
         if !accouint_of_exhibitor.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
-        let exhibitor_nft_account = next_account_info(account_info_iter)?;
-        let exhibitor_nft_temp_account = next_account_info(account_info_iter)?;
-        let exhibitor_ft_receiving_account = next_account_info(account_info_iter)?;
+        let exhibitor_nft_account = accounts.exhibitor_nft_account;
+        let exhibitor_nft_temp_account = accounts.nft_temp_account;
+        let exhibitor_ft_receiving_account = accounts.ft_receiving_account;
 
-        let escrow_account = next_account_info(account_info_iter)?;
-        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let escrow_account = accounts.escrow_account;
+        let sys_var_rent_account = accounts.rent_sysvar;
+        Self::require_writable(exhibitor_nft_account)?;
+        Self::require_writable(exhibitor_nft_temp_account)?;
+        Self::require_writable(escrow_account)?;
+
+        if exhibitor_ft_receiving_account.key == exhibitor_nft_temp_account.key
+            || exhibitor_ft_receiving_account.key == exhibitor_nft_account.key
+        {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+
+        let exhibitor_nft_account_data =
+            TokenAccount::unpack_unchecked(&exhibitor_nft_account.try_borrow_data()?)?;
+        if exhibitor_nft_account_data.state != AccountState::Initialized {
+            return Err(AuctionError::TokenAccountFrozen.into());
+        }
+        let exhibitor_ft_receiving_account_data =
+            TokenAccount::unpack(&exhibitor_ft_receiving_account.try_borrow_data()?)?;
+        if exhibitor_ft_receiving_account_data.mint != ft_mint {
+            return Err(ProgramError::InvalidAccountData);
+        }
 
         let rent = &Rent::from_account_info(sys_var_rent_account)?;
         if !rent.is_exempt(escrow_account.lamports(), escrow_account.data_len()) {
@@ -76,19 +846,238 @@ impl Processor {
             return Err(ProgramError::AccountAlreadyInitialized);
         }
 
-        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        // Close zeroes the escrow but writes its tombstone, which a second auction could never write
+        let tombstone_account = accounts.tombstone;
+        let (tombstone_pda, _bump_seed) =
+            Pubkey::find_program_address(&[b"tombstone", escrow_account.key.as_ref()], program_id);
+        if tombstone_pda != *tombstone_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !tombstone_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let sys_var_clock_account = accounts.clock_sysvar;
         let clock = &Clock::from_account_info(sys_var_clock_account)?;
 
+        let (pda, bump_seed) = Pubkey::find_program_address(&[b"escrow"], program_id);
+
         auction_info.is_initialized = true;
         auction_info.exhibitor_pubkey = *accouint_of_exhibitor.key;
         auction_info.exhibiting_nft_temp_pubkey = *exhibitor_nft_temp_account.key;
         auction_info.exhibitor_ft_receiving_pubkey = *exhibitor_ft_receiving_account.key;
         auction_info.price = initial_price;
-        auction_info.end_at = clock.unix_timestamp.add(auction_duration_sec as i64);
+        auction_info.end_at = i64::try_from(auction_duration_sec)
+            .ok()
+            .and_then(|duration| clock.unix_timestamp.checked_add(duration))
+            .ok_or(AuctionError::AmountOverflow)?;
+        auction_info.starts_at = i64::try_from(start_delay_sec)
+            .ok()
+            .and_then(|delay| clock.unix_timestamp.checked_add(delay))
+            .ok_or(AuctionError::AmountOverflow)?;
+        if auction_info.starts_at > auction_info.end_at {
+            return Err(AuctionError::InvalidExpiry.into());
+        }
+        auction_info.pda_bump = bump_seed;
+        auction_info.max_bidder_extension_sec = DEFAULT_MAX_BIDDER_EXTENSION_SEC;
+        auction_info.close_grace_period_sec = DEFAULT_CLOSE_GRACE_PERIOD_SEC;
+        auction_info.category = category;
+        auction_info.ft_mint = ft_mint;
+        auction_info.insurance_vault_bump =
+            Pubkey::find_program_address(&[b"insurance_vault", ft_mint.as_ref()], program_id).1;
+        auction_info.bids_visible = true;
+        auction_info.version = AUCTION_VERSION;
+        auction_info.lot_size = 1;
+        auction_info.bid_nonce = 0;
+        let end_at = auction_info.end_at;
+
+        let program_of_token = accounts.token_program;
+        let exhibitor_profile_account = accounts.exhibitor_profile;
+        let system_program = accounts.system_program;
+        Self::require_writable(exhibitor_profile_account)?;
+
+        let (exhibitor_profile_pda, profile_bump_seed) = Pubkey::find_program_address(
+            &[b"exhibitor_profile", accouint_of_exhibitor.key.as_ref()],
+            program_id,
+        );
+        if exhibitor_profile_pda != *exhibitor_profile_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if exhibitor_profile_account.data_is_empty() {
+            Self::require_writable(accouint_of_exhibitor)?;
+            msg!("Creating the Exhibitor profile...");
+            Self::create_pda_account(
+                accouint_of_exhibitor,
+                exhibitor_profile_account,
+                system_program,
+                rent,
+                ExhibitorProfile::LEN,
+                &[
+                    b"exhibitor_profile",
+                    accouint_of_exhibitor.key.as_ref(),
+                    &[profile_bump_seed],
+                ],
+                program_id,
+            )?;
+        }
+        let mut exhibitor_profile =
+            ExhibitorProfile::unpack_unchecked(&exhibitor_profile_account.try_borrow_data()?)?;
+        exhibitor_profile.is_initialized = true;
+        exhibitor_profile.auction_count = exhibitor_profile
+            .auction_count
+            .checked_add(1)
+            .ok_or(AuctionError::AmountOverflow)?;
+        exhibitor_profile.latest_auction = *escrow_account.key;
+        ExhibitorProfile::pack(
+            exhibitor_profile,
+            &mut exhibitor_profile_account.try_borrow_mut_data()?,
+        )?;
+
+        let auction_index_account = accounts.auction_index;
+        let category_index_account = accounts.category_index;
+        Self::push_to_auction_index(
+            accouint_of_exhibitor,
+            auction_index_account,
+            system_program,
+            rent,
+            &[b"auction_index"],
+            escrow_account.key,
+            program_id,
+        )?;
+        Self::push_to_auction_index(
+            accouint_of_exhibitor,
+            category_index_account,
+            system_program,
+            rent,
+            &[b"category_index", &[category as u8]],
+            escrow_account.key,
+            program_id,
+        )?;
+
+        let ft_mint_account = accounts.ft_mint;
+        if *ft_mint_account.key != ft_mint {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Mint::unpack(&ft_mint_account.try_borrow_data()?)?;
+
+        let program_stats_account = accounts.program_stats;
+        Self::require_writable(program_stats_account)?;
+        let (program_stats_pda, stats_bump_seed) =
+            Pubkey::find_program_address(&[b"stats"], program_id);
+        if program_stats_pda != *program_stats_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if program_stats_account.data_is_empty() {
+            Self::require_writable(accouint_of_exhibitor)?;
+            msg!("Creating the Program stats...");
+            Self::create_pda_account(
+                accouint_of_exhibitor,
+                program_stats_account,
+                system_program,
+                rent,
+                ProgramStats::LEN,
+                &[b"stats", &[stats_bump_seed]],
+                program_id,
+            )?;
+        }
+        let mut program_stats =
+            ProgramStats::unpack_unchecked(&program_stats_account.try_borrow_data()?)?;
+        program_stats.is_initialized = true;
+        program_stats.total_auctions = program_stats
+            .total_auctions
+            .checked_add(1)
+            .ok_or(AuctionError::AmountOverflow)?;
+        program_stats.active_auctions = program_stats
+            .active_auctions
+            .checked_add(1)
+            .ok_or(AuctionError::AmountOverflow)?;
+        ProgramStats::pack(
+            program_stats,
+            &mut program_stats_account.try_borrow_mut_data()?,
+        )?;
+
+        let global_config_account = accounts.global_config;
+        let global_config = Self::load_global_config(global_config_account, program_id)?;
+        let exhibitor_config_account = accounts.exhibitor_config;
+        let (exhibitor_config_pda, _bump_seed) = Pubkey::find_program_address(
+            &[b"exhibitor_config", accouint_of_exhibitor.key.as_ref()],
+            program_id,
+        );
+        if exhibitor_config_pda != *exhibitor_config_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let exhibitor_min_duration_sec = if exhibitor_config_account.data_is_empty() {
+            0
+        } else {
+            if exhibitor_config_account.owner != program_id {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            ExhibitorConfig::unpack(&exhibitor_config_account.try_borrow_data()?)?.min_duration_sec
+        };
+        let min_duration_sec = global_config
+            .min_auction_duration_sec
+            .max(exhibitor_min_duration_sec);
+        if auction_duration_sec.saturating_sub(start_delay_sec) < min_duration_sec {
+            return Err(AuctionError::AuctionTooShort.into());
+        }
+
+        let trailing_accounts_len = usize::from(global_config.mint_blacklist_count > 0)
+            + usize::from(global_config.nft_lock_period_sec > 0);
+        let optional_accounts = accounts.remaining;
+        let room_accounts_len = optional_accounts
+            .len()
+            .checked_sub(trailing_accounts_len)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let (room_accounts, trailing_accounts) = optional_accounts.split_at(room_accounts_len);
+        let trailing_account_iter = &mut trailing_accounts.iter();
+        if let Some(room_account) = room_accounts.first() {
+            let room = Self::load_auction_room(room_account, program_id)?;
+            auction_info.room = *room_account.key;
+            auction_info.room_organizer = room.organizer;
+            auction_info.room_fee_bps = room.fee_bps;
+            auction_info.whitelist_root = room.whitelist_root;
+        }
+        if global_config.mint_blacklist_count > 0 {
+            let mint_blacklist_account = next_account_info(trailing_account_iter)?;
+            if Self::is_mint_blacklisted(mint_blacklist_account, &ft_mint, program_id)? {
+                return Err(AuctionError::MintBlacklisted.into());
+            }
+        }
+        if global_config.nft_lock_period_sec > 0 {
+            let nft_lock_account = next_account_info(trailing_account_iter)?;
+            let (nft_lock_pda, _nft_lock_bump_seed) = Pubkey::find_program_address(
+                &[
+                    b"nft_lock",
+                    exhibitor_nft_account_data.mint.as_ref(),
+                    accouint_of_exhibitor.key.as_ref(),
+                ],
+                program_id,
+            );
+            if nft_lock_pda != *nft_lock_account.key {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            if !nft_lock_account.data_is_empty() {
+                if nft_lock_account.owner != program_id {
+                    return Err(ProgramError::IncorrectProgramId);
+                }
+                let nft_lock = NFTLockRecord::unpack(&nft_lock_account.try_borrow_data()?)?;
+                if clock.unix_timestamp < nft_lock.unlocks_at {
+                    return Err(AuctionError::NFTLockedForRelisting.into());
+                }
+            }
+        }
+        auction_info.nft_lock_period_sec = global_config.nft_lock_period_sec;
+        auction_info.transaction_limit = global_config.transaction_limit;
         Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
 
-        let (pda, _bump_seed) = Pubkey::find_program_address(&[b"escrow"], program_id);
-        let program_of_token = next_account_info(account_info_iter)?;
+        NewAuctionEvent {
+            exhibitor: *accouint_of_exhibitor.key,
+            escrow: *escrow_account.key,
+            nft_mint: exhibitor_nft_account_data.mint,
+            initial_price,
+            end_at,
+        }
+        .emit();
 
         let exhibit_ix = spl_token::instruction::transfer(
             program_of_token.key,
@@ -129,33 +1118,134 @@ impl Processor {
         Ok(())
     }
 
-    fn process_bid(accounts: &[AccountInfo], price: u64, program_id: &Pubkey) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-        let bidder_account = next_account_info(account_info_iter)?;
+    /// `nonce` is checked against the auction's bid nonce when given, the nonce is incremented by
+    /// every bid whatever the instruction
+    #[allow(clippy::too_many_arguments)]
+    fn process_bid<'a, 'b>(
+        accounts: &BidAccounts<'a, 'b>,
+        price: u64,
+        nonce: Option<u64>,
+        proof: &[[u8; 32]],
+        currency: Option<BidCurrency>,
+        origin: BidOrigin<'a, 'b>,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.remaining.iter();
+        let bidder_account = accounts.bidder;
+        let highest_bidder_account = accounts.highest_bidder;
+        let highest_bidder_ft_temp_account = accounts.highest_bidder_ft_temp_account;
+        let highest_bidder_ft_returning_account = accounts.highest_bidder_ft_returning_account;
 
-        if !bidder_account.is_signer {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-        let highest_bidder_account = next_account_info(account_info_iter)?;
-        let highest_bidder_ft_temp_account = next_account_info(account_info_iter)?;
-        let highest_bidder_ft_returning_account = next_account_info(account_info_iter)?;
+        let bidder_ft_temp_account = accounts.bidder_ft_temp_account;
+        let bidder_ft_account = accounts.bidder_ft_account;
 
-        let bidder_ft_temp_account = next_account_info(account_info_iter)?;
-        let bidder_ft_account = next_account_info(account_info_iter)?;
+        let escrow_account = accounts.escrow_account;
+        Self::require_writable(highest_bidder_ft_temp_account)?;
+        Self::require_writable(highest_bidder_ft_returning_account)?;
+        Self::require_writable(bidder_ft_temp_account)?;
+        Self::require_writable(bidder_ft_account)?;
+        Self::require_writable(escrow_account)?;
 
-        let escrow_account = next_account_info(account_info_iter)?;
+        if bidder_ft_account.key == bidder_ft_temp_account.key
+            || bidder_ft_temp_account.key == highest_bidder_ft_temp_account.key
+        {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
         let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let bid_authority = if bidder_account.is_signer {
+            bidder_account
+        } else if let BidOrigin::FeePayer(fee_payer_account) = origin {
+            fee_payer_account
+        } else {
+            Self::cross_program_authority(&auction_info, accounts.remaining)?
+        };
 
-        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let sys_var_clock_account = accounts.clock_sysvar;
         let clock = &Clock::from_account_info(sys_var_clock_account)?;
 
-        if auction_info.end_at <= clock.unix_timestamp {
+        // The extension of the highest bidder only delays the close, it doesn't reopen bidding
+        if auction_info.bidder_extended
+            || auction_info
+                .bidding_end_at()
+                .ok_or(AuctionError::AmountOverflow)?
+                <= clock.unix_timestamp
+        {
             return Err(AuctionError::InactiveAuction.into());
         }
+        if clock.unix_timestamp < auction_info.starts_at {
+            return Err(AuctionError::AuctionNotStarted.into());
+        }
+        if !auction_info.terms_accepted {
+            return Err(AuctionError::TermsNotAccepted.into());
+        }
+        if auction_info.advanced_amount > 0 {
+            return Err(AuctionError::ProceedsAdvanced.into());
+        }
+        if auction_info.auction_type == AuctionType::SealedBid
+            && !matches!(origin, BidOrigin::Commitment)
+        {
+            return Err(AuctionError::BidNotCommitted.into());
+        }
+        if nonce.is_some() && nonce != Some(auction_info.bid_nonce) {
+            return Err(AuctionError::InvalidBidNonce.into());
+        }
 
-        if auction_info.price >= price {
+        // Bids in an accepted currency pay their fees in the bid mint and are compared by their
+        // value in the auction FT mint
+        let value_of = |amount| {
+            currency
+                .as_ref()
+                .map_or(Ok(amount), |currency| currency.value_of(amount))
+        };
+        let value = value_of(price)?;
+        let bid_mint = currency.as_ref().map_or(auction_info.ft_mint, |currency| currency.mint);
+        if auction_info.floor_price > 0 && value < auction_info.floor_price {
+            return Err(AuctionError::BelowFloorPrice.into());
+        }
+        // Weighted bids are compared by their price times the weight of the bidder
+        let bid_weight_bps = if auction_info.bid_weight_oracle != Pubkey::default() {
+            Self::query_bid_weight(&auction_info, bidder_account, accounts.remaining)?
+        } else {
+            0
+        };
+        let (weighted_value, min_next_bid) = if bid_weight_bps > 0 {
+            (
+                Self::weighted_amount(value, bid_weight_bps)?,
+                auction_info.min_next_weighted_bid(clock.unix_timestamp),
+            )
+        } else {
+            (value, auction_info.min_next_bid(clock.unix_timestamp))
+        };
+        if weighted_value < min_next_bid.ok_or(AuctionError::AmountOverflow)? {
             return Err(AuctionError::InsufficientBidPrice.into());
         }
+        if auction_info.anti_sandwich
+            && auction_info.last_block_bid_slot == clock.slot
+            && auction_info.last_block_bidder != Pubkey::default()
+            && auction_info.last_block_bidder != *bidder_account.key
+        {
+            let same_slot_min = auction_info
+                .last_block_bid_price
+                .checked_add(Self::basis_points_of(
+                    auction_info.last_block_bid_price,
+                    SAME_SLOT_PREMIUM_BPS,
+                )?)
+                .ok_or(AuctionError::AmountOverflow)?;
+            if value <= same_slot_min {
+                return Err(AuctionError::SameSlotBidTooLow.into());
+            }
+        }
+        if auction_info.whitelist_root != [0; 32]
+            && !Self::verify_merkle_proof(
+                &auction_info.whitelist_root,
+                hash(bidder_account.key.as_ref()).to_bytes(),
+                proof,
+            )
+        {
+            return Err(AuctionError::NotWhitelisted.into());
+        }
 
         if auction_info.highest_bidder_ft_temp_pubkey != *highest_bidder_ft_temp_account.key {
             return Err(AuctionError::InvalidInstruction.into());
@@ -171,17 +1261,62 @@ impl Processor {
         if auction_info.highest_bidder_pubkey == *bidder_account.key {
             return Err(AuctionError::AlreadyBid.into());
         }
-        let program_of_token = next_account_info(account_info_iter)?;
-        let pda_account = next_account_info(account_info_iter)?;
-        let (pda, bump_seed) = Pubkey::find_program_address(&[b"escrow"], program_id);
-
-        let transfer_to_escrow_ix = spl_token::instruction::transfer(
-            program_of_token.key,
+        if auction_info.requires_approval
+            && !Self::submit_for_approval(accounts, bid_authority, price, clock, program_id)?
+        {
+            return Ok(());
+        }
+        let program_of_token = accounts.token_program;
+        let pda_account = accounts.pda_account;
+        let bump_seed = auction_info.pda_bump;
+        let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+        let global_config_account = accounts.global_config;
+        let global_config = Self::load_global_config(global_config_account, program_id)?;
+        let due_royalty_bps = if auction_info.upfront_royalty_paid {
+            0
+        } else {
+            auction_info.upfront_royalty_bps
+        };
+        if u32::from(global_config.insurance_fee_bps)
+            + u32::from(due_royalty_bps)
+            + u32::from(auction_info.room_fee_bps)
+            > u32::from(MAX_DEDUCTION_BPS)
+        {
+            return Err(AuctionError::InvalidFeeBasisPoints.into());
+        }
+        let insurance_fee = Self::basis_points_of(price, global_config.insurance_fee_bps)?;
+        let room_fee = Self::basis_points_of(price, auction_info.room_fee_bps)?;
+        let escrowed_amount = price
+            .checked_sub(insurance_fee)
+            .and_then(|amount| amount.checked_sub(room_fee))
+            .ok_or(AuctionError::AmountOverflow)?;
+        let bid_history_account = accounts.bid_history;
+        let sys_var_rent_account = accounts.rent_sysvar;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = accounts.system_program;
+        let program_stats_account = accounts.program_stats;
+        Self::require_writable(program_stats_account)?;
+        let mut program_stats = Self::load_program_stats(program_stats_account, program_id)?;
+        program_stats.total_bids = program_stats
+            .total_bids
+            .checked_add(1)
+            .ok_or(AuctionError::AmountOverflow)?;
+        program_stats.total_volume = program_stats
+            .total_volume
+            .checked_add(value)
+            .ok_or(AuctionError::AmountOverflow)?;
+        ProgramStats::pack(
+            program_stats,
+            &mut program_stats_account.try_borrow_mut_data()?,
+        )?;
+
+        let transfer_to_escrow_ix = spl_token::instruction::transfer(
+            program_of_token.key,
             bidder_ft_account.key,
             bidder_ft_temp_account.key,
-            bidder_account.key,
+            bid_authority.key,
             &[], 
-            price,
+            escrowed_amount,
         )?;
         msg!("Transferring FT to the Escrow Account from the bidder...");
         invoke(
@@ -189,7 +1324,7 @@ impl Processor {
             &[
                 bidder_ft_account.clone(),
                 bidder_ft_temp_account.clone(),
-                bidder_account.clone(),
+                bid_authority.clone(),
                 program_of_token.clone(),
             ],
         )?;
@@ -199,7 +1334,7 @@ impl Processor {
             bidder_ft_temp_account.key,
             Some(&pda),
             spl_token::instruction::AuthorityType::AccountOwner,
-            bidder_account.key,
+            bid_authority.key,
             &[], // owner_pubkey is default signer when the signer_pubkeys is empty.
         )?;
         msg!("Changing ownership of the token account...");
@@ -207,32 +1342,371 @@ impl Processor {
             &owner_change_ix,
             &[
                 bidder_ft_temp_account.clone(),
-                bidder_account.clone(),
+                bid_authority.clone(),
                 program_of_token.clone(),
             ],
         )?;
 
-        if auction_info.highest_bidder_pubkey != Pubkey::default(){
-            let transfer_to_previous_bidder_ix = spl_token::instruction::transfer(
+        let bidder_ft_temp_account_data =
+            TokenAccount::unpack(&bidder_ft_temp_account.try_borrow_data()?)?;
+        if bidder_ft_temp_account_data.owner != pda {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if bidder_ft_temp_account_data.mint != bid_mint {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if insurance_fee > 0 {
+            let insurance_vault_account = next_account_info(account_info_iter)?;
+            let ft_mint_account = next_account_info(account_info_iter)?;
+            Self::require_writable(insurance_vault_account)?;
+
+            if bidder_ft_temp_account_data.mint != *ft_mint_account.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            // Only the vault of the auction FT mint has its bump cached
+            let (insurance_vault_pda, vault_bump_seed) = if bid_mint == auction_info.ft_mint {
+                let vault_bump_seed = auction_info.insurance_vault_bump;
+                let insurance_vault_pda = Pubkey::create_program_address(
+                    &[
+                        b"insurance_vault",
+                        ft_mint_account.key.as_ref(),
+                        &[vault_bump_seed],
+                    ],
+                    program_id,
+                )?;
+                (insurance_vault_pda, vault_bump_seed)
+            } else {
+                Pubkey::find_program_address(
+                    &[b"insurance_vault", ft_mint_account.key.as_ref()],
+                    program_id,
+                )
+            };
+            if insurance_vault_pda != *insurance_vault_account.key {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            if insurance_vault_account.data_is_empty() {
+                Self::require_writable(bid_authority)?;
+                msg!("Creating the Insurance Vault...");
+                Self::create_pda_account(
+                    bid_authority,
+                    insurance_vault_account,
+                    system_program,
+                    rent,
+                    TokenAccount::LEN,
+                    &[
+                        b"insurance_vault",
+                        ft_mint_account.key.as_ref(),
+                        &[vault_bump_seed],
+                    ],
+                    program_of_token.key,
+                )?;
+                let init_vault_ix = spl_token::instruction::initialize_account3(
+                    program_of_token.key,
+                    insurance_vault_account.key,
+                    ft_mint_account.key,
+                    &insurance_vault_pda,
+                )?;
+                invoke(
+                    &init_vault_ix,
+                    &[
+                        insurance_vault_account.clone(),
+                        ft_mint_account.clone(),
+                        program_of_token.clone(),
+                    ],
+                )?;
+            }
+
+            let transfer_to_vault_ix = spl_token::instruction::transfer(
                 program_of_token.key,
-                highest_bidder_ft_temp_account.key,
-                highest_bidder_ft_returning_account.key,
+                bidder_ft_account.key,
+                insurance_vault_account.key,
+                bid_authority.key,
+                &[],
+                insurance_fee,
+            )?;
+            msg!("Transferring the insurance fee to the Insurance Vault...");
+            invoke(
+                &transfer_to_vault_ix,
+                &[
+                    bidder_ft_account.clone(),
+                    insurance_vault_account.clone(),
+                    bid_authority.clone(),
+                    program_of_token.clone(),
+                ],
+            )?;
+        }
+
+        if auction_info.max_bidders > 0 {
+            let bidder_registry_account = next_account_info(account_info_iter)?;
+            Self::require_writable(bidder_registry_account)?;
+            let bidder_registry_pda = Pubkey::create_program_address(
+                &[
+                    b"bidder_registry",
+                    escrow_account.key.as_ref(),
+                    &[auction_info.bidder_registry_bump],
+                ],
+                program_id,
+            )?;
+            if bidder_registry_pda != *bidder_registry_account.key
+                || bidder_registry_account.owner != program_id
+            {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            let mut bidder_registry =
+                BidderRegistry::unpack(&bidder_registry_account.try_borrow_data()?)?;
+            let bidder_count = auction_info.unique_bidder_count as usize;
+            if !bidder_registry.contains(bidder_count, bidder_account.key) {
+                if auction_info.unique_bidder_count >= auction_info.max_bidders {
+                    return Err(AuctionError::MaxBiddersReached.into());
+                }
+                bidder_registry.insert(bidder_count, *bidder_account.key);
+                auction_info.unique_bidder_count += 1;
+                BidderRegistry::pack(
+                    bidder_registry,
+                    &mut bidder_registry_account.try_borrow_mut_data()?,
+                )?;
+            }
+        }
+
+        let mut upfront_royalty = 0;
+        if auction_info.upfront_royalty_bps > 0 && !auction_info.upfront_royalty_paid {
+            let royalty_creator_ft_account = next_account_info(account_info_iter)?;
+            Self::require_writable(royalty_creator_ft_account)?;
+            if bid_mint == auction_info.ft_mint {
+                if auction_info.royalty_creator_ft_pubkey != *royalty_creator_ft_account.key {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+            } else {
+                // The royalty goes to the creator's vault of the bid mint, the creator is checked
+                // against the vault of the auction FT mint
+                let creator_account = next_account_info(account_info_iter)?;
+                let bid_mint_account = next_account_info(account_info_iter)?;
+                let (royalty_vault_pda, _) = Pubkey::find_program_address(
+                    &[
+                        b"royalty",
+                        auction_info.ft_mint.as_ref(),
+                        creator_account.key.as_ref(),
+                    ],
+                    program_id,
+                );
+                if royalty_vault_pda != auction_info.royalty_creator_ft_pubkey
+                    || *bid_mint_account.key != bid_mint
+                {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                let (royalty_vault_pda, royalty_bump_seed) = Pubkey::find_program_address(
+                    &[
+                        b"royalty",
+                        bid_mint.as_ref(),
+                        creator_account.key.as_ref(),
+                    ],
+                    program_id,
+                );
+                if royalty_vault_pda != *royalty_creator_ft_account.key {
+                    return Err(ProgramError::InvalidSeeds);
+                }
+                if royalty_creator_ft_account.data_is_empty() {
+                    Self::require_writable(bid_authority)?;
+                    msg!("Creating the Royalty Vault of the bid mint...");
+                    Self::create_pda_account(
+                        bid_authority,
+                        royalty_creator_ft_account,
+                        system_program,
+                        rent,
+                        TokenAccount::LEN,
+                        &[
+                            b"royalty",
+                            bid_mint.as_ref(),
+                            creator_account.key.as_ref(),
+                            &[royalty_bump_seed],
+                        ],
+                        program_of_token.key,
+                    )?;
+                    let init_vault_ix = spl_token::instruction::initialize_account3(
+                        program_of_token.key,
+                        royalty_creator_ft_account.key,
+                        bid_mint_account.key,
+                        &royalty_vault_pda,
+                    )?;
+                    invoke(
+                        &init_vault_ix,
+                        &[
+                            royalty_creator_ft_account.clone(),
+                            bid_mint_account.clone(),
+                            program_of_token.clone(),
+                        ],
+                    )?;
+                }
+            }
+
+            upfront_royalty = Self::basis_points_of(price, auction_info.upfront_royalty_bps)?;
+            if upfront_royalty > escrowed_amount {
+                return Err(AuctionError::InsufficientEscrow.into());
+            }
+            let transfer_royalty_ix = spl_token::instruction::transfer(
+                program_of_token.key,
+                bidder_ft_temp_account.key,
+                royalty_creator_ft_account.key,
                 &pda,
-                &[], // authority_pubkey is default signer when the signer_pubkeys is empty.
-                auction_info.price,
+                &[],
+                upfront_royalty,
             )?;
-            msg!("Transferring FT to the previous highest bidder from the escrow account...");
+            msg!("Transferring the upfront royalty to the creator's Royalty Vault...");
             let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
             invoke_signed(
-                &transfer_to_previous_bidder_ix,
+                &transfer_royalty_ix,
                 &[
-                    highest_bidder_ft_temp_account.clone(),
-                    highest_bidder_ft_returning_account.clone(),
+                    bidder_ft_temp_account.clone(),
+                    royalty_creator_ft_account.clone(),
                     pda_account.clone(),
                     program_of_token.clone(),
                 ],
                 signers_seeds,
+            )?;
+            auction_info.upfront_royalty_paid = true;
+        }
+
+        if room_fee > 0 {
+            let organizer_ft_account = next_account_info(account_info_iter)?;
+            Self::require_writable(organizer_ft_account)?;
+            let organizer_ft_account_data =
+                TokenAccount::unpack(&organizer_ft_account.try_borrow_data()?)?;
+            if organizer_ft_account_data.owner != auction_info.room_organizer
+                || organizer_ft_account_data.mint != bid_mint
+            {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let transfer_room_fee_ix = spl_token::instruction::transfer(
+                program_of_token.key,
+                bidder_ft_account.key,
+                organizer_ft_account.key,
+                bid_authority.key,
+                &[],
+                room_fee,
+            )?;
+            msg!("Transferring the room fee to the organizer...");
+            invoke(
+                &transfer_room_fee_ix,
+                &[
+                    bidder_ft_account.clone(),
+                    organizer_ft_account.clone(),
+                    bid_authority.clone(),
+                    program_of_token.clone(),
+                ],
+            )?;
+        }
+
+        Self::require_writable(bid_history_account)?;
+        if bid_history_account.data_is_empty() {
+            let (bid_history_pda, history_bump_seed) = Pubkey::find_program_address(
+                &[b"bid_history", escrow_account.key.as_ref()],
+                program_id,
             );
+            if bid_history_pda != *bid_history_account.key {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            Self::require_writable(bid_authority)?;
+            msg!("Creating the Bid history...");
+            Self::create_pda_account(
+                bid_authority,
+                bid_history_account,
+                system_program,
+                rent,
+                BidHistory::LEN,
+                &[
+                    b"bid_history",
+                    escrow_account.key.as_ref(),
+                    &[history_bump_seed],
+                ],
+                program_id,
+            )?;
+            auction_info.bid_history_bump = history_bump_seed;
+        } else {
+            let bid_history_pda = Pubkey::create_program_address(
+                &[
+                    b"bid_history",
+                    escrow_account.key.as_ref(),
+                    &[auction_info.bid_history_bump],
+                ],
+                program_id,
+            )?;
+            if bid_history_pda != *bid_history_account.key {
+                return Err(ProgramError::InvalidSeeds);
+            }
+        }
+        let mut bid_history = BidHistory::unpack_unchecked(&bid_history_account.try_borrow_data()?)?;
+        bid_history.is_initialized = true;
+        bid_history.push(BidRecord {
+            bidder: *bidder_account.key,
+            price: value,
+            bid_at: clock.unix_timestamp,
+        });
+        BidHistory::pack(bid_history, &mut bid_history_account.try_borrow_mut_data()?)?;
+
+        if auction_info.highest_bidder_pubkey != Pubkey::default(){
+            Self::require_writable(highest_bidder_account)?;
+            let highest_bidder_ft_temp_account_data =
+                TokenAccount::unpack(&highest_bidder_ft_temp_account.try_borrow_data()?)?;
+            let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+            if auction_info.highest_bidder_vault_program != Pubkey::default() {
+                let vault_program = next_account_info(account_info_iter)?;
+                let vault_account = next_account_info(account_info_iter)?;
+                let vault_ft_account = next_account_info(account_info_iter)?;
+                Self::require_writable(vault_account)?;
+                Self::require_writable(vault_ft_account)?;
+                if auction_info.highest_bidder_vault_program != *vault_program.key
+                    || auction_info.highest_bidder_vault != *vault_account.key
+                    || auction_info.highest_bidder_vault_ft != *vault_ft_account.key
+                {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+
+                let deposit_to_vault_ix = StandardVault(*vault_program.key).deposit(
+                    &VaultAccounts {
+                        vault: *vault_account.key,
+                        vault_ft: *vault_ft_account.key,
+                        ft_account: *highest_bidder_ft_temp_account.key,
+                        authority: pda,
+                        token_program: *program_of_token.key,
+                    },
+                    highest_bidder_ft_temp_account_data.amount,
+                );
+                msg!("Depositing FT back into the previous highest bidder's vault...");
+                invoke_signed(
+                    &deposit_to_vault_ix,
+                    &[
+                        vault_account.clone(),
+                        vault_ft_account.clone(),
+                        highest_bidder_ft_temp_account.clone(),
+                        pda_account.clone(),
+                        program_of_token.clone(),
+                        vault_program.clone(),
+                    ],
+                    signers_seeds,
+                )?;
+            } else {
+                let transfer_to_previous_bidder_ix = spl_token::instruction::transfer(
+                    program_of_token.key,
+                    highest_bidder_ft_temp_account.key,
+                    highest_bidder_ft_returning_account.key,
+                    &pda,
+                    &[], // authority_pubkey is default signer when the signer_pubkeys is empty.
+                    highest_bidder_ft_temp_account_data.amount,
+                )?;
+                msg!("Transferring FT to the previous highest bidder from the escrow account...");
+                invoke_signed(
+                    &transfer_to_previous_bidder_ix,
+                    &[
+                        highest_bidder_ft_temp_account.clone(),
+                        highest_bidder_ft_returning_account.clone(),
+                        pda_account.clone(),
+                        program_of_token.clone(),
+                    ],
+                    signers_seeds,
+                );
+            }
 
             Self::close_temporary_ft(
                 program_of_token,
@@ -244,26 +1718,327 @@ impl Processor {
             )?;
         }
 
-        auction_info.price = price;
+        if auction_info.gate_token_mint != Pubkey::default() {
+            let gate_token_account = next_account_info(account_info_iter)?;
+            let gate_token_account_data =
+                TokenAccount::unpack(&gate_token_account.try_borrow_data()?)?;
+            if gate_token_account_data.owner != *bidder_account.key
+                || gate_token_account_data.mint != auction_info.gate_token_mint
+                || gate_token_account_data.amount < auction_info.gate_token_min_amount
+            {
+                return Err(AuctionError::GateTokenRequirementNotMet.into());
+            }
+        }
+
+        if global_config.max_lifetime_bid > 0 {
+            let lifetime_bid_volume_account = next_account_info(account_info_iter)?;
+            Self::require_writable(lifetime_bid_volume_account)?;
+            let mut lifetime_bid_volume = if lifetime_bid_volume_account.data_is_empty() {
+                let (lifetime_bid_volume_pda, ltv_bump_seed) = Pubkey::find_program_address(
+                    &[b"ltv", bidder_account.key.as_ref()],
+                    program_id,
+                );
+                if lifetime_bid_volume_pda != *lifetime_bid_volume_account.key {
+                    return Err(ProgramError::InvalidSeeds);
+                }
+                Self::require_writable(bid_authority)?;
+                Self::create_pda_account(
+                    bid_authority,
+                    lifetime_bid_volume_account,
+                    system_program,
+                    rent,
+                    LifetimeBidVolume::LEN,
+                    &[b"ltv", bidder_account.key.as_ref(), &[ltv_bump_seed]],
+                    program_id,
+                )?;
+                LifetimeBidVolume {
+                    is_initialized: true,
+                    total_bid_volume: 0,
+                    bump: ltv_bump_seed,
+                }
+            } else {
+                let lifetime_bid_volume =
+                    LifetimeBidVolume::unpack(&lifetime_bid_volume_account.try_borrow_data()?)?;
+                let lifetime_bid_volume_pda = Pubkey::create_program_address(
+                    &[
+                        b"ltv",
+                        bidder_account.key.as_ref(),
+                        &[lifetime_bid_volume.bump],
+                    ],
+                    program_id,
+                )?;
+                if lifetime_bid_volume_pda != *lifetime_bid_volume_account.key {
+                    return Err(ProgramError::InvalidSeeds);
+                }
+                lifetime_bid_volume
+            };
+            // Outbid refunds don't decrease the lifetime volume
+            lifetime_bid_volume.total_bid_volume = lifetime_bid_volume
+                .total_bid_volume
+                .checked_add(value)
+                .ok_or(AuctionError::AmountOverflow)?;
+            if lifetime_bid_volume.total_bid_volume > global_config.max_lifetime_bid {
+                return Err(AuctionError::LifetimeBidCapExceeded.into());
+            }
+            LifetimeBidVolume::pack(
+                lifetime_bid_volume,
+                &mut lifetime_bid_volume_account.try_borrow_mut_data()?,
+            )?;
+        }
+
+        if global_config.mint_blacklist_count > 0 {
+            let mint_blacklist_account = next_account_info(account_info_iter)?;
+            if Self::is_mint_blacklisted(mint_blacklist_account, &bid_mint, program_id)? {
+                return Err(AuctionError::MintBlacklisted.into());
+            }
+        }
+
+        if global_config.global_ban_count > 0 {
+            let global_ban_account = next_account_info(account_info_iter)?;
+            if Self::is_globally_banned(global_ban_account, bidder_account.key, program_id)? {
+                return Err(AuctionError::GloballyBanned.into());
+            }
+        }
+
+        if auction_info.trustline_program != Pubkey::default() {
+            let trustline_program_account = next_account_info(account_info_iter)?;
+            if *trustline_program_account.key != auction_info.trustline_program {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            let verify_ix = StandardTrustline(auction_info.trustline_program)
+                .verify(bidder_account.key, &auction_info.trustline_type);
+            msg!("Verifying the bidder's trustline...");
+            invoke(
+                &verify_ix,
+                &[bidder_account.clone(), trustline_program_account.clone()],
+            )
+            .map_err(|_| AuctionError::TrustlineNotVerified)?;
+        }
+
+        if auction_info.last_bid_at > 0 {
+            // The outbid price is weighted by the time it stayed the highest bid
+            let elapsed_sec = clock
+                .unix_timestamp
+                .checked_sub(auction_info.last_bid_at)
+                .and_then(|elapsed| u64::try_from(elapsed).ok())
+                .ok_or(AuctionError::AmountOverflow)?;
+            auction_info.twap_numerator = auction_info
+                .twap_numerator
+                .checked_add(u128::from(auction_info.price) * u128::from(elapsed_sec))
+                .ok_or(AuctionError::AmountOverflow)?;
+            auction_info.twap_denominator = auction_info
+                .twap_denominator
+                .checked_add(elapsed_sec)
+                .ok_or(AuctionError::AmountOverflow)?;
+        }
+        auction_info.last_bid_at = clock.unix_timestamp;
+        auction_info.last_block_bidder = *bidder_account.key;
+        auction_info.last_block_bid_price = value;
+        auction_info.last_block_bid_slot = clock.slot;
+        auction_info.highest_bid_weight_bps = bid_weight_bps;
+        auction_info.bid_nonce = auction_info
+            .bid_nonce
+            .checked_add(1)
+            .ok_or(AuctionError::AmountOverflow)?;
+        auction_info.price = value_of(
+            price
+                .checked_sub(upfront_royalty)
+                .ok_or(AuctionError::AmountOverflow)?,
+        )?;
         auction_info.highest_bidder_pubkey = *bidder_account.key;
         auction_info.highest_bidder_ft_temp_pubkey = *bidder_ft_temp_account.key;
         auction_info.highest_bidder_ft_returning_pubkey = *bidder_ft_account.key;
+        auction_info.highest_bidder_vault_program = Pubkey::default();
+        auction_info.highest_bidder_vault = Pubkey::default();
+        auction_info.highest_bidder_vault_ft = Pubkey::default();
+        auction_info.bid_locked = true;
+        // The priority bidder bids without pushing the end back
+        if auction_info.auction_type == AuctionType::SlidingExpiry
+            && auction_info.priority_bidder != *bidder_account.key
+        {
+            auction_info.end_at = auction_info
+                .sliding_end_at(clock.unix_timestamp)
+                .ok_or(AuctionError::AmountOverflow)?;
+        }
+        auction_info.bid_escrow_amount = escrowed_amount
+            .checked_sub(upfront_royalty)
+            .ok_or(AuctionError::AmountOverflow)?;
+        let visible_price = auction_info.visible_price();
         Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+
+        BidEvent {
+            escrow: *escrow_account.key,
+            bidder: *bidder_account.key,
+            price: visible_price,
+        }
+        .emit();
         Ok(())
     }
 
-    fn process_cancel(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+    fn process_bid_with_sol_balance(
+        accounts: &[AccountInfo],
+        price: u64,
+        existing_wsol: u64,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let bid_accounts = BidAccounts::from_accounts(accounts)?;
+        let bidder_account = bid_accounts.bidder;
+
+        if !bidder_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let bidder_ft_account = bid_accounts.bidder_ft_account;
+        let escrow_account = bid_accounts.escrow_account;
+        let program_of_token = bid_accounts.token_program;
+        let system_program = bid_accounts.system_program;
+        Self::require_writable(bidder_account)?;
+        Self::require_writable(bidder_ft_account)?;
+
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        if auction_info.ft_mint != spl_token::native_mint::id() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let bidder_ft_account_data = TokenAccount::unpack(&bidder_ft_account.try_borrow_data()?)?;
+        if !bidder_ft_account_data.is_native() || bidder_ft_account_data.owner != *bidder_account.key
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if bidder_ft_account_data.amount < existing_wsol {
+            return Err(AuctionError::ExpectedAmountMismatch.into());
+        }
+
+        let wrap_amount = price
+            .checked_sub(existing_wsol)
+            .ok_or(AuctionError::AmountOverflow)?;
+        if wrap_amount > 0 {
+            let wrap_ix =
+                system_instruction::transfer(bidder_account.key, bidder_ft_account.key, wrap_amount);
+            msg!("Wrapping SOL into the bidder's WSOL account...");
+            invoke(
+                &wrap_ix,
+                &[
+                    bidder_account.clone(),
+                    bidder_ft_account.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        }
+        let sync_native_ix =
+            spl_token::instruction::sync_native(program_of_token.key, bidder_ft_account.key)?;
+        invoke(
+            &sync_native_ix,
+            &[bidder_ft_account.clone(), program_of_token.clone()],
+        )?;
+
+        Self::process_bid(
+            &bid_accounts,
+            price,
+            None,
+            &[],
+            None,
+            BidOrigin::Direct,
+            changed,
+            program_id,
+        )
+    }
+
+    fn process_vault_bid(
+        accounts: &[AccountInfo],
+        vault_program: Pubkey,
+        price: u64,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
-        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+        let vault_program_account = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let vault_ft_account = next_account_info(account_info_iter)?;
+        let bid_accounts = BidAccounts::from_accounts(account_info_iter.as_slice())?;
+        let bidder_account = bid_accounts.bidder;
+
+        if !bidder_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let bidder_ft_account = bid_accounts.bidder_ft_account;
+        let escrow_account = bid_accounts.escrow_account;
+        let program_of_token = bid_accounts.token_program;
+        Self::require_writable(vault_account)?;
+        Self::require_writable(vault_ft_account)?;
+        Self::require_writable(bidder_ft_account)?;
+
+        if vault_program != *vault_program_account.key {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        if vault_program == *program_id || vault_program == *program_of_token.key {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let withdraw_from_vault_ix = StandardVault(vault_program).withdraw(
+            &VaultAccounts {
+                vault: *vault_account.key,
+                vault_ft: *vault_ft_account.key,
+                ft_account: *bidder_ft_account.key,
+                authority: *bidder_account.key,
+                token_program: *program_of_token.key,
+            },
+            price,
+        );
+        msg!("Withdrawing the bid from the bidder's vault...");
+        invoke(
+            &withdraw_from_vault_ix,
+            &[
+                vault_account.clone(),
+                vault_ft_account.clone(),
+                bidder_ft_account.clone(),
+                bidder_account.clone(),
+                program_of_token.clone(),
+                vault_program_account.clone(),
+            ],
+        )?;
+
+        Self::process_bid(
+            &bid_accounts,
+            price,
+            None,
+            &[],
+            None,
+            BidOrigin::Direct,
+            changed,
+            program_id,
+        )?;
+
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        auction_info.highest_bidder_vault_program = vault_program;
+        auction_info.highest_bidder_vault = *vault_account.key;
+        auction_info.highest_bidder_vault_ft = *vault_ft_account.key;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_cancel(
+        accounts: &CancelAccounts,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.remaining.iter();
+        let accouint_of_exhibitor = accounts.exhibitor;
 
         if !accouint_of_exhibitor.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
-        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
-        let exhibiting_nft_returning_account = next_account_info(account_info_iter)?;
-        let escrow_account = next_account_info(account_info_iter)?;
+        let exhibiting_nft_temp_account = accounts.nft_temp_account;
+        let exhibiting_nft_returning_account = accounts.nft_returning_account;
+        let escrow_account = accounts.escrow_account;
+        Self::require_writable(accouint_of_exhibitor)?;
+        Self::require_writable(exhibiting_nft_temp_account)?;
+        Self::require_writable(exhibiting_nft_returning_account)?;
+        Self::require_writable(escrow_account)?;
         let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
 
         if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
             return Err(ProgramError::InvalidAccountData);
@@ -276,13 +2051,51 @@ impl Processor {
             return Err(AuctionError::AlreadyBid.into());
         }
 
-        let (pda, bump_seed) = Pubkey::find_program_address(&[b"escrow"], program_id);
-        let program_of_token = next_account_info(account_info_iter)?;
-        let pda_account = next_account_info(account_info_iter)?;
+        let bump_seed = auction_info.pda_bump;
+        let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+        let program_of_token = accounts.token_program;
+        let pda_account = accounts.pda_account;
+        let program_stats_account = accounts.program_stats;
         let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
 
         let exhibiting_nft_temp_account_data =
             TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
+        if auction_info.is_soulbound || auction_info.burn_on_cancel {
+            let nft_mint_account = next_account_info(account_info_iter)?;
+            Self::require_writable(nft_mint_account)?;
+            let burn_nft_ix = spl_token::instruction::burn(
+                program_of_token.key,
+                exhibiting_nft_temp_account.key,
+                nft_mint_account.key,
+                &pda,
+                &[],
+                exhibiting_nft_temp_account_data.amount,
+            )?;
+            msg!("Burning the NFT...");
+            invoke_signed(
+                &burn_nft_ix,
+                &[
+                    exhibiting_nft_temp_account.clone(),
+                    nft_mint_account.clone(),
+                    pda_account.clone(),
+                    program_of_token.clone(),
+                ],
+                signers_seeds,
+            )
+            .map_err(|_| AuctionError::BurnFailed)?;
+
+            Self::record_auction_closed(program_stats_account, program_id)?;
+
+            return Self::escrow_is_closing(
+                program_of_token,
+                exhibiting_nft_temp_account,
+                accouint_of_exhibitor,
+                pda,
+                pda_account,
+                escrow_account,
+                signers_seeds,
+            );
+        }
         let transfer_nft_to_exhibitor_ix = spl_token::instruction::transfer(
             program_of_token.key,
             exhibiting_nft_temp_account.key,
@@ -303,6 +2116,8 @@ impl Processor {
             signers_seeds,
         )?;
 
+        Self::record_auction_closed(program_stats_account, program_id)?;
+
         Self::escrow_is_closing(
             program_of_token,
             exhibiting_nft_temp_account,
@@ -314,42 +2129,124 @@ impl Processor {
         )
     }
 
-    fn closing_the_process(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {let account_info_iter = &mut accounts.iter();let highest_bidder_account = next_account_info(account_info_iter)?;
+    fn closing_the_process(accounts: &CloseAccounts, changed: &mut ChangedAuctions, program_id: &Pubkey) -> ProgramResult {let account_info_iter = &mut accounts.remaining.iter();let closer_account = accounts.closer;
 
-        if !highest_bidder_account.is_signer {
+        if !closer_account.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
-        let accouint_of_exhibitor = next_account_info(account_info_iter)?;let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
-        let exhibitor_ft_receiving_account = next_account_info(account_info_iter)?;let highest_bidder_ft_temp_account = next_account_info(account_info_iter)?;
-        let highest_bidder_nft_receiving_account = next_account_info(account_info_iter)?;let escrow_account = next_account_info(account_info_iter)?;let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        let accouint_of_exhibitor = accounts.exhibitor;let exhibiting_nft_temp_account = accounts.nft_temp_account;
+        let exhibitor_ft_receiving_account = accounts.ft_receiving_account;let highest_bidder_ft_temp_account = accounts.highest_bidder_ft_temp_account;
+        let highest_bidder_nft_receiving_account = accounts.highest_bidder_nft_receiving_account;let escrow_account = accounts.escrow_account;let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        Self::require_writable(closer_account)?;
+        Self::require_writable(accouint_of_exhibitor)?;
+        Self::require_writable(exhibiting_nft_temp_account)?;
+        Self::require_writable(exhibitor_ft_receiving_account)?;
+        Self::require_writable(highest_bidder_ft_temp_account)?;
+        Self::require_writable(highest_bidder_nft_receiving_account)?;
+        Self::require_writable(escrow_account)?;
 
-        let sys_var_clock_account = next_account_info(account_info_iter)?;let clock = &Clock::from_account_info(sys_var_clock_account)?;if auction_info.end_at > clock.unix_timestamp {
+        let sys_var_clock_account = accounts.clock_sysvar;let clock = &Clock::from_account_info(sys_var_clock_account)?;let closable_at = auction_info.closable_at().ok_or(AuctionError::AmountOverflow)?;if closable_at > clock.unix_timestamp {
             msg!(
                 "Auction will end in {} seconds",
-                (auction_info.end_at - clock.unix_timestamp)
+                closable_at
+                    .checked_sub(clock.unix_timestamp)
+                    .ok_or(AuctionError::AmountOverflow)?
             );
             return Err(AuctionError::ActiveAuction.into());
+        }
+        if auction_info.finalized_at != 0 || auction_info.audit_pending {
+            return Err(AuctionError::SettlementPending.into());
         }if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
             return Err(ProgramError::InvalidAccountData);
         }if auction_info.exhibiting_nft_temp_pubkey != *exhibiting_nft_temp_account.key {
             return Err(ProgramError::InvalidAccountData);
-        }if auction_info.exhibitor_ft_receiving_pubkey != *exhibitor_ft_receiving_account.key {
+        }if !Self::is_exhibitor_ft_receiving(
+            &auction_info,
+            highest_bidder_ft_temp_account,
+            exhibitor_ft_receiving_account,
+        )? {
             return Err(ProgramError::InvalidAccountData);
         }if auction_info.highest_bidder_ft_temp_pubkey != *highest_bidder_ft_temp_account.key {
             return Err(ProgramError::InvalidAccountData);
-        }if auction_info.highest_bidder_pubkey != *highest_bidder_account.key {
-            return Err(ProgramError::InvalidAccountData);
         }
 
-        let (pda, bump_seed) = Pubkey::find_program_address(&[b"escrow"], program_id);
-        let program_of_token = next_account_info(account_info_iter)?;
-        let pda_account = next_account_info(account_info_iter)?;
+        let bump_seed = auction_info.pda_bump;
+        let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+        let program_of_token = accounts.token_program;
+        let pda_account = accounts.pda_account;
+        let program_stats_account = accounts.program_stats;
+        let tombstone_account = accounts.tombstone;
+        let system_program = accounts.system_program;
+        Self::require_writable(tombstone_account)?;
+        let winner_account = if auction_info.winner_override != Pubkey::default() {
+            if auction_info.winner_override != *closer_account.key {
+                return Err(AuctionError::WinnerOverrideRequired.into());
+            }
+            let highest_bidder_nft_receiving_account_data =
+                TokenAccount::unpack(&highest_bidder_nft_receiving_account.try_borrow_data()?)?;
+            if highest_bidder_nft_receiving_account_data.owner != *closer_account.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            closer_account
+        } else if auction_info.highest_bidder_pubkey == *closer_account.key {
+            closer_account
+        } else {
+            let close_allowed_at = auction_info
+                .close_allowed_at()
+                .ok_or(AuctionError::AmountOverflow)?;
+            let is_close_authority = auction_info.close_authority != Pubkey::default()
+                && auction_info.close_authority == *closer_account.key;
+            if !is_close_authority && close_allowed_at > clock.unix_timestamp {
+                return Err(AuctionError::GracePeriodActive.into());
+            }
+            let winner_account = next_account_info(account_info_iter)?;
+            Self::require_writable(winner_account)?;
+            if auction_info.highest_bidder_pubkey != *winner_account.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            let highest_bidder_nft_receiving_account_data =
+                TokenAccount::unpack(&highest_bidder_nft_receiving_account.try_borrow_data()?)?;
+            if highest_bidder_nft_receiving_account_data.owner != *winner_account.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            winner_account
+        };
         let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
 
         let exhibiting_nft_temp_account_data =
             TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
 
+        let (tombstone_pda, tombstone_bump_seed) = Pubkey::find_program_address(
+            &[b"tombstone", escrow_account.key.as_ref()],
+            program_id,
+        );
+        if tombstone_pda != *tombstone_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !tombstone_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        Self::create_pda_account(
+            closer_account,
+            tombstone_account,
+            system_program,
+            &Rent::get()?,
+            TombstoneRecord::LEN,
+            &[b"tombstone", escrow_account.key.as_ref(), &[tombstone_bump_seed]],
+            program_id,
+        )?;
+        let tombstone = TombstoneRecord {
+            is_initialized: true,
+            winner: *winner_account.key,
+            final_price: auction_info.price,
+            closed_at: clock.unix_timestamp,
+            nft_mint: exhibiting_nft_temp_account_data.mint,
+            exhibitor: auction_info.exhibitor_pubkey,
+        };
+        TombstoneRecord::pack(tombstone, &mut tombstone_account.try_borrow_mut_data()?)?;
+
         let highest_bidder_nft_transfer = spl_token::instruction::transfer(
             program_of_token.key,
             exhibiting_nft_temp_account.key,
@@ -370,45 +2267,7045 @@ impl Processor {
             signers_seeds,
         )?;
 
-        let temp_account_data_of_highest_Bidder =
-            TokenAccount::unpack(&highest_bidder_ft_temp_account.try_borrow_data()?)?;
-        let transfer_ft_to_exhibitor_ix = spl_token::instruction::transfer(
-            program_of_token.key,
-            highest_bidder_ft_temp_account.key,
-            &exhibitor_ft_receiving_account.key,
-            &pda,
-            &[], 
-            temp_account_data_of_highest_Bidder.amount,
-        )?;
-        msg!("Transferring FT to the Exhibitor...");
-        invoke_signed(
-            &transfer_ft_to_exhibitor_ix,
-            &[
-                highest_bidder_ft_temp_account.clone(),
-                exhibitor_ft_receiving_account.clone(),
-                pda_account.clone(),
-                program_of_token.clone(),
-            ],
-            signers_seeds,
-        )?;
+        if auction_info.is_soulbound {
+            let nft_mint_account = next_account_info(account_info_iter)?;
+            if exhibiting_nft_temp_account_data.mint != *nft_mint_account.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            let freeze_nft_ix = spl_token::instruction::freeze_account(
+                program_of_token.key,
+                highest_bidder_nft_receiving_account.key,
+                nft_mint_account.key,
+                &pda,
+                &[],
+            )?;
+            msg!("Freezing the soulbound NFT in the winner's account...");
+            invoke_signed(
+                &freeze_nft_ix,
+                &[
+                    highest_bidder_nft_receiving_account.clone(),
+                    nft_mint_account.clone(),
+                    pda_account.clone(),
+                    program_of_token.clone(),
+                ],
+                signers_seeds,
+            )?;
+        }
 
-        Self::close_temporary_ft(
-            program_of_token,
-            highest_bidder_ft_temp_account,
-            highest_bidder_account,
-            pda,
-            pda_account,
-            signers_seeds,
-        )?;
+        if auction_info.bonus_amount > 0 {
+            let bonus_vault_account = next_account_info(account_info_iter)?;
+            let winner_ft_account = next_account_info(account_info_iter)?;
+            Self::require_writable(bonus_vault_account)?;
+            Self::require_writable(winner_ft_account)?;
 
-        Self::escrow_is_closing(
-            program_of_token,
-            exhibiting_nft_temp_account,
-            accouint_of_exhibitor,
-            pda,
-            pda_account,
-            escrow_account,
-            signers_seeds,
+            let (bonus_vault_pda, bonus_bump_seed) = Pubkey::find_program_address(
+                &[b"bonus", escrow_account.key.as_ref()],
+                program_id,
+            );
+            if bonus_vault_pda != *bonus_vault_account.key {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            let winner_ft_account_data = TokenAccount::unpack(&winner_ft_account.try_borrow_data()?)?;
+            if winner_ft_account_data.owner != *winner_account.key
+                || winner_ft_account_data.mint != auction_info.ft_mint
+            {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            let bonus_vault_data = TokenAccount::unpack(&bonus_vault_account.try_borrow_data()?)?;
+
+            let transfer_bonus_ix = spl_token::instruction::transfer(
+                program_of_token.key,
+                bonus_vault_account.key,
+                winner_ft_account.key,
+                &bonus_vault_pda,
+                &[],
+                bonus_vault_data.amount,
+            )?;
+            msg!("Transferring the bonus to the Highest Bidder...");
+            invoke_signed(
+                &transfer_bonus_ix,
+                &[
+                    bonus_vault_account.clone(),
+                    winner_ft_account.clone(),
+                    bonus_vault_account.clone(),
+                    program_of_token.clone(),
+                ],
+                &[&[b"bonus", escrow_account.key.as_ref(), &[bonus_bump_seed]]],
+            )?;
+        }
+
+        if auction_info.nft_lock_period_sec > 0 {
+            let nft_lock_account = next_account_info(account_info_iter)?;
+            Self::require_writable(nft_lock_account)?;
+            let (nft_lock_pda, nft_lock_bump_seed) = Pubkey::find_program_address(
+                &[
+                    b"nft_lock",
+                    exhibiting_nft_temp_account_data.mint.as_ref(),
+                    winner_account.key.as_ref(),
+                ],
+                program_id,
+            );
+            if nft_lock_pda != *nft_lock_account.key {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            if nft_lock_account.data_is_empty() {
+                Self::create_pda_account(
+                    closer_account,
+                    nft_lock_account,
+                    system_program,
+                    &Rent::get()?,
+                    NFTLockRecord::LEN,
+                    &[
+                        b"nft_lock",
+                        exhibiting_nft_temp_account_data.mint.as_ref(),
+                        winner_account.key.as_ref(),
+                        &[nft_lock_bump_seed],
+                    ],
+                    program_id,
+                )?;
+            }
+            let mut nft_lock =
+                NFTLockRecord::unpack_unchecked(&nft_lock_account.try_borrow_data()?)?;
+            nft_lock.is_initialized = true;
+            nft_lock.unlocks_at = i64::try_from(auction_info.nft_lock_period_sec)
+                .ok()
+                .and_then(|lock_period| clock.unix_timestamp.checked_add(lock_period))
+                .ok_or(AuctionError::AmountOverflow)?;
+            NFTLockRecord::pack(nft_lock, &mut nft_lock_account.try_borrow_mut_data()?)?;
+        }
+
+        if auction_info.exceeds_transaction_limit() {
+            let global_config_account = next_account_info(account_info_iter)?;
+            let compliance_signer_account = next_account_info(account_info_iter)?;
+            let global_config = Self::load_global_config(global_config_account, program_id)?;
+            if !compliance_signer_account.is_signer
+                || global_config.compliance_signer != *compliance_signer_account.key
+            {
+                return Err(AuctionError::TransactionLimitExceeded.into());
+            }
+        }
+
+        if auction_info.finality_timeout_sec > 0 {
+            Self::record_auction_closed(program_stats_account, program_id)?;
+
+            // The FT stays in escrow until `FinalizeSettlement` releases it to the exhibitor
+            auction_info.finalized_at = clock
+                .unix_timestamp
+                .checked_add(auction_info.finality_timeout_sec as i64)
+                .ok_or(AuctionError::AmountOverflow)?;
+            Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+            return Ok(());
+        }
+
+        if auction_info.winner_override != Pubkey::default() {
+            let highest_bidder_account = next_account_info(account_info_iter)?;
+            let highest_bidder_ft_returning_account = next_account_info(account_info_iter)?;
+            let winner_ft_account = next_account_info(account_info_iter)?;
+            Self::require_writable(highest_bidder_account)?;
+            Self::require_writable(highest_bidder_ft_returning_account)?;
+            Self::require_writable(winner_ft_account)?;
+            if auction_info.highest_bidder_pubkey != *highest_bidder_account.key
+                || auction_info.highest_bidder_ft_returning_pubkey
+                    != *highest_bidder_ft_returning_account.key
+            {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            let winner_ft_account_data = TokenAccount::unpack(&winner_ft_account.try_borrow_data()?)?;
+            if winner_ft_account_data.mint != auction_info.ft_mint {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if winner_ft_account_data.amount < auction_info.price {
+                return Err(ProgramError::InsufficientFunds);
+            }
+
+            let transfer_price_ix = spl_token::instruction::transfer(
+                program_of_token.key,
+                winner_ft_account.key,
+                exhibitor_ft_receiving_account.key,
+                winner_account.key,
+                &[],
+                auction_info.price,
+            )?;
+            msg!("Transferring FT to the exhibitor from the selected winner...");
+            invoke(
+                &transfer_price_ix,
+                &[
+                    winner_ft_account.clone(),
+                    exhibitor_ft_receiving_account.clone(),
+                    winner_account.clone(),
+                    program_of_token.clone(),
+                ],
+            )?;
+
+            let highest_bidder_ft_temp_account_data =
+                TokenAccount::unpack(&highest_bidder_ft_temp_account.try_borrow_data()?)?;
+            let refund_highest_bidder_ix = spl_token::instruction::transfer(
+                program_of_token.key,
+                highest_bidder_ft_temp_account.key,
+                highest_bidder_ft_returning_account.key,
+                &pda,
+                &[],
+                highest_bidder_ft_temp_account_data.amount,
+            )?;
+            msg!("Refunding FT to the highest bidder from the escrow account...");
+            invoke_signed(
+                &refund_highest_bidder_ix,
+                &[
+                    highest_bidder_ft_temp_account.clone(),
+                    highest_bidder_ft_returning_account.clone(),
+                    pda_account.clone(),
+                    program_of_token.clone(),
+                ],
+                signers_seeds,
+            )?;
+
+            Self::close_temporary_ft(
+                program_of_token,
+                highest_bidder_ft_temp_account,
+                highest_bidder_account,
+                pda,
+                pda_account,
+                signers_seeds,
+            )?;
+        } else {
+            Self::transfer_proceeds(
+                program_of_token,
+                highest_bidder_ft_temp_account,
+                exhibitor_ft_receiving_account,
+                account_info_iter.as_slice(),
+                &auction_info,
+                pda_account,
+                signers_seeds,
+            )?;
+
+            Self::close_temporary_ft(
+                program_of_token,
+                highest_bidder_ft_temp_account,
+                winner_account,
+                pda,
+                pda_account,
+                signers_seeds,
+            )?;
+        }
+
+        Self::record_auction_closed(program_stats_account, program_id)?;
+
+        if auction_info.audit_oracle != Pubkey::default() {
+            auction_info.audit_pending = true;
+            Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+            return Ok(());
+        }
+
+        Self::escrow_is_closing(
+            program_of_token,
+            exhibiting_nft_temp_account,
+            accouint_of_exhibitor,
+            pda,
+            pda_account,
+            escrow_account,
+            signers_seeds,
+        )
+    }
+
+    fn process_get_auction_state(accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let escrow_account = next_account_info(account_info_iter)?;
+
+        let auction_info = Auction::unpack_unchecked(&escrow_account.try_borrow_data()?)
+            .map_err(|_| AuctionError::AuctionNotFound)?;
+        if !auction_info.is_initialized() {
+            return Err(AuctionError::AuctionNotFound.into());
+        }
+
+        let mut auction_state_event = AuctionStateEvent::new(
+            escrow_account.key,
+            &auction_info,
+            Clock::get()?.unix_timestamp,
+        );
+        auction_state_event.price = auction_info.visible_price();
+        auction_state_event.emit();
+        Ok(())
+    }
+
+    fn process_change_exhibitor_ft_receiver(
+        accounts: &[AccountInfo],
+        new_receiver: Pubkey,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let new_ft_receiving_account = next_account_info(account_info_iter)?;
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if new_receiver != *new_ft_receiving_account.key {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        if *new_ft_receiving_account.owner != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let new_ft_receiving_account_data =
+            TokenAccount::unpack(&new_ft_receiving_account.try_borrow_data()?)?;
+        if new_ft_receiving_account_data.mint != auction_info.ft_mint {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        auction_info.exhibitor_ft_receiving_pubkey = new_receiver;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_extend_bid_deadline(
+        accounts: &[AccountInfo],
+        additional_seconds: u64,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let highest_bidder_account = next_account_info(account_info_iter)?;
+
+        if !highest_bidder_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+
+        if auction_info.highest_bidder_pubkey != *highest_bidder_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.end_at > clock.unix_timestamp {
+            return Err(AuctionError::ActiveAuction.into());
+        }
+        if auction_info.bidder_extended {
+            return Err(AuctionError::AlreadyExtended.into());
+        }
+
+        let extension = additional_seconds.min(auction_info.max_bidder_extension_sec);
+        auction_info.end_at = i64::try_from(extension)
+            .ok()
+            .and_then(|extension| auction_info.end_at.checked_add(extension))
+            .ok_or(AuctionError::AmountOverflow)?;
+        auction_info.bidder_extended = true;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_floor_price(
+        accounts: &[AccountInfo],
+        floor: u64,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.end_at <= clock.unix_timestamp {
+            return Err(AuctionError::InactiveAuction.into());
+        }
+        if floor <= auction_info.price {
+            return Err(AuctionError::InsufficientBidPrice.into());
+        }
+
+        auction_info.floor_price = floor;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_init_global_config(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(authority_account)?;
+        Self::require_writable(global_config_account)?;
+
+        let (global_config_pda, bump_seed) =
+            Pubkey::find_program_address(&[b"global_config"], program_id);
+        if global_config_pda != *global_config_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !global_config_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        Self::create_pda_account(
+            authority_account,
+            global_config_account,
+            system_program,
+            rent,
+            GlobalConfig::LEN,
+            &[b"global_config", &[bump_seed]],
+            program_id,
+        )?;
+
+        let global_config = GlobalConfig {
+            is_initialized: true,
+            authority: *authority_account.key,
+            insurance_fee_bps: 0,
+            insurance_reserve: 0,
+            transfer_hook_program: Pubkey::default(),
+            min_auction_duration_sec: 0,
+            max_lifetime_bid: 0,
+            nft_lock_period_sec: 0,
+            transaction_limit: 0,
+            compliance_signer: Pubkey::default(),
+            mint_blacklist_count: 0,
+            global_ban_count: 0,
+        };
+        GlobalConfig::pack(global_config, &mut global_config_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_emergency_transfer_nft(
+        accounts: &[AccountInfo],
+        destination: Pubkey,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        let destination_nft_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        Self::require_writable(accouint_of_exhibitor)?;
+        Self::require_writable(exhibiting_nft_temp_account)?;
+        Self::require_writable(destination_nft_account)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+
+        let emergency_allowed_at = auction_info
+            .end_at
+            .checked_add(EMERGENCY_THRESHOLD_SEC)
+            .ok_or(AuctionError::AmountOverflow)?;
+        if emergency_allowed_at > clock.unix_timestamp {
+            return Err(AuctionError::GracePeriodActive.into());
+        }
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.exhibiting_nft_temp_pubkey != *exhibiting_nft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if destination != *destination_nft_account.key {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+
+        let program_of_token = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        let program_stats_account = next_account_info(account_info_iter)?;
+        let bump_seed = auction_info.pda_bump;
+        let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+        let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+
+        let exhibiting_nft_temp_account_data =
+            TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
+        let emergency_transfer_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            exhibiting_nft_temp_account.key,
+            destination_nft_account.key,
+            &pda,
+            &[],
+            exhibiting_nft_temp_account_data.amount,
+        )?;
+        msg!("Transferring the NFT to the emergency destination...");
+        invoke_signed(
+            &emergency_transfer_ix,
+            &[
+                exhibiting_nft_temp_account.clone(),
+                destination_nft_account.clone(),
+                pda_account.clone(),
+                program_of_token.clone(),
+            ],
+            signers_seeds,
+        )?;
+
+        Self::record_auction_closed(program_stats_account, program_id)?;
+
+        Self::escrow_is_closing(
+            program_of_token,
+            exhibiting_nft_temp_account,
+            accouint_of_exhibitor,
+            pda,
+            pda_account,
+            escrow_account,
+            signers_seeds,
+        )
+    }
+
+    fn process_recover_stuck_bid(
+        accounts: &[AccountInfo],
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        let highest_bidder_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_temp_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_returning_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        Self::require_writable(highest_bidder_account)?;
+        Self::require_writable(highest_bidder_ft_temp_account)?;
+        Self::require_writable(highest_bidder_ft_returning_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+
+        if auction_info.end_at > clock.unix_timestamp {
+            return Err(AuctionError::ActiveAuction.into());
+        }
+        if auction_info.highest_bidder_pubkey == Pubkey::default() {
+            return Err(AuctionError::NoBidderFound.into());
+        }
+        let close_allowed_at = auction_info
+            .close_allowed_at()
+            .ok_or(AuctionError::AmountOverflow)?;
+        if close_allowed_at > clock.unix_timestamp {
+            return Err(AuctionError::GracePeriodActive.into());
+        }
+        if auction_info.highest_bidder_pubkey != *highest_bidder_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_ft_temp_pubkey != *highest_bidder_ft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_ft_returning_pubkey
+            != *highest_bidder_ft_returning_account.key
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let program_of_token = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        let bump_seed = auction_info.pda_bump;
+        let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+        let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+
+        let highest_bidder_ft_temp_account_data =
+            TokenAccount::unpack(&highest_bidder_ft_temp_account.try_borrow_data()?)?;
+        let transfer_to_highest_bidder_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            highest_bidder_ft_temp_account.key,
+            highest_bidder_ft_returning_account.key,
+            &pda,
+            &[],
+            highest_bidder_ft_temp_account_data.amount,
+        )?;
+        msg!("Transferring FT back to the Highest Bidder...");
+        invoke_signed(
+            &transfer_to_highest_bidder_ix,
+            &[
+                highest_bidder_ft_temp_account.clone(),
+                highest_bidder_ft_returning_account.clone(),
+                pda_account.clone(),
+                program_of_token.clone(),
+            ],
+            signers_seeds,
+        )?;
+
+        Self::close_temporary_ft(
+            program_of_token,
+            highest_bidder_ft_temp_account,
+            highest_bidder_account,
+            pda,
+            pda_account,
+            signers_seeds,
+        )?;
+
+        auction_info.highest_bidder_pubkey = Pubkey::default();
+        auction_info.highest_bidder_ft_temp_pubkey = Pubkey::default();
+        auction_info.highest_bidder_ft_returning_pubkey = Pubkey::default();
+        auction_info.bid_locked = false;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_batch_exhibit(
+        accounts: &[AccountInfo],
+        count: u8,
+        configs: Vec<ExhibitConfig>,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if count == 0 || count > MAX_BATCH_EXHIBIT || configs.len() != count as usize {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        if accounts.len() < count as usize * EXHIBIT_ACCOUNTS_LEN {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        msg!("Validating the batch before starting any Auction...");
+        for (exhibit_accounts, config) in accounts.chunks(EXHIBIT_ACCOUNTS_LEN).zip(&configs) {
+            let exhibit_accounts = ExhibitAccounts::from_accounts(exhibit_accounts)?;
+            if !exhibit_accounts.exhibitor.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            let auction_info =
+                Auction::unpack_unchecked(&exhibit_accounts.escrow_account.try_borrow_data()?)?;
+            if auction_info.is_initialized() {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            if i64::try_from(config.seconds).is_err() {
+                return Err(AuctionError::AmountOverflow.into());
+            }
+        }
+
+        for (exhibit_accounts, config) in accounts.chunks(EXHIBIT_ACCOUNTS_LEN).zip(configs) {
+            Self::process_exhibit(
+                &ExhibitAccounts::from_accounts(exhibit_accounts)?,
+                config.initial_price,
+                config.seconds,
+                config.category,
+                config.ft_mint,
+                0,
+                program_id,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn process_batch_bid(
+        accounts: &[AccountInfo],
+        auctions: [Pubkey; MAX_BATCH_BID],
+        price: u64,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let bidder_account = next_account_info(account_info_iter)?;
+
+        if !bidder_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let batch_bid_group_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(bidder_account)?;
+        Self::require_writable(batch_bid_group_account)?;
+
+        let batch_auctions: Vec<Pubkey> = auctions
+            .into_iter()
+            .filter(|auction| *auction != Pubkey::default())
+            .collect();
+        if batch_auctions.is_empty() {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        for (i, auction) in batch_auctions.iter().enumerate() {
+            if batch_auctions[i + 1..].contains(auction) {
+                return Err(AuctionError::InvalidInstruction.into());
+            }
+        }
+
+        // The accounts of each bid are located by its escrow, the seventh account of `Bid`
+        let bid_accounts = account_info_iter.as_slice();
+        let mut bid_starts = Vec::with_capacity(batch_auctions.len());
+        let mut search_from = 6;
+        for auction in &batch_auctions {
+            let escrow_index = bid_accounts
+                .iter()
+                .skip(search_from)
+                .position(|account| account.key == auction)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?
+                + search_from;
+            bid_starts.push(escrow_index - 6);
+            search_from = escrow_index + 7;
+        }
+
+        let (batch_bid_group_pda, group_bump_seed) = Pubkey::find_program_address(
+            &[
+                b"batch_bid",
+                bidder_account.key.as_ref(),
+                batch_auctions[0].as_ref(),
+            ],
+            program_id,
+        );
+        if batch_bid_group_pda != *batch_bid_group_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !batch_bid_group_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        msg!("Creating the batch bid group...");
+        Self::create_pda_account(
+            bidder_account,
+            batch_bid_group_account,
+            system_program,
+            rent,
+            BatchBidGroup::LEN,
+            &[
+                b"batch_bid",
+                bidder_account.key.as_ref(),
+                batch_auctions[0].as_ref(),
+                &[group_bump_seed],
+            ],
+            program_id,
+        )?;
+        let mut group_auctions = [Pubkey::default(); MAX_BATCH_BID];
+        group_auctions[..batch_auctions.len()].copy_from_slice(&batch_auctions);
+        let batch_bid_group = BatchBidGroup {
+            is_initialized: true,
+            bidder: *bidder_account.key,
+            auctions: group_auctions,
+            price,
+        };
+        BatchBidGroup::pack(
+            batch_bid_group,
+            &mut batch_bid_group_account.try_borrow_mut_data()?,
+        )?;
+
+        for (i, bid_start) in bid_starts.iter().enumerate() {
+            let bid_end = bid_starts.get(i + 1).copied().unwrap_or(bid_accounts.len());
+            let auction_accounts = &bid_accounts[*bid_start..bid_end];
+            if auction_accounts[0].key != bidder_account.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            Self::process_bid(
+                &BidAccounts::from_accounts(auction_accounts)?,
+                price,
+                None,
+                &[],
+                None,
+                BidOrigin::Direct,
+                changed,
+                program_id,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn process_claim_batch_winner(
+        accounts: &[AccountInfo],
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let bidder_account = next_account_info(account_info_iter)?;
+
+        if !bidder_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let batch_bid_group_account = next_account_info(account_info_iter)?;
+        let won_escrow_account = next_account_info(account_info_iter)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+        let program_of_token = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        Self::require_writable(bidder_account)?;
+        Self::require_writable(batch_bid_group_account)?;
+
+        if batch_bid_group_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let batch_bid_group = BatchBidGroup::unpack(&batch_bid_group_account.try_borrow_data()?)?;
+        if batch_bid_group.bidder != *bidder_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let (batch_bid_group_pda, _bump_seed) = Pubkey::find_program_address(
+            &[
+                b"batch_bid",
+                bidder_account.key.as_ref(),
+                batch_bid_group.auctions[0].as_ref(),
+            ],
+            program_id,
+        );
+        if batch_bid_group_pda != *batch_bid_group_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        if !batch_bid_group.auctions.contains(won_escrow_account.key) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if won_escrow_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let won_auction_info = Auction::unpack(&won_escrow_account.try_borrow_data()?)?;
+        if won_auction_info.end_at > clock.unix_timestamp {
+            return Err(AuctionError::ActiveAuction.into());
+        }
+        if won_auction_info.highest_bidder_pubkey != *bidder_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        for auction in batch_bid_group
+            .auctions
+            .iter()
+            .filter(|auction| **auction != Pubkey::default() && *auction != won_escrow_account.key)
+        {
+            let escrow_account = next_account_info(account_info_iter)?;
+            let bidder_ft_temp_account = next_account_info(account_info_iter)?;
+            let bidder_ft_account = next_account_info(account_info_iter)?;
+            if escrow_account.key != auction {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            // A closed auction or an outbid bidder has nothing left to return
+            if escrow_account.lamports() == 0 {
+                continue;
+            }
+            if escrow_account.owner != program_id {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+            if auction_info.highest_bidder_pubkey != *bidder_account.key {
+                continue;
+            }
+            changed.record(escrow_account.key, &auction_info)?;
+            Self::require_writable(escrow_account)?;
+            Self::require_writable(bidder_ft_temp_account)?;
+            Self::require_writable(bidder_ft_account)?;
+            if auction_info.highest_bidder_ft_temp_pubkey != *bidder_ft_temp_account.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if auction_info.highest_bidder_ft_returning_pubkey != *bidder_ft_account.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if auction_info.advanced_amount > 0 {
+                return Err(AuctionError::ProceedsAdvanced.into());
+            }
+
+            let bump_seed = auction_info.pda_bump;
+            let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+            let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+            let bidder_ft_temp_account_data =
+                TokenAccount::unpack(&bidder_ft_temp_account.try_borrow_data()?)?;
+            let transfer_to_bidder_ix = spl_token::instruction::transfer(
+                program_of_token.key,
+                bidder_ft_temp_account.key,
+                bidder_ft_account.key,
+                &pda,
+                &[],
+                bidder_ft_temp_account_data.amount,
+            )?;
+            msg!("Transferring FT back to the batch bidder...");
+            invoke_signed(
+                &transfer_to_bidder_ix,
+                &[
+                    bidder_ft_temp_account.clone(),
+                    bidder_ft_account.clone(),
+                    pda_account.clone(),
+                    program_of_token.clone(),
+                ],
+                signers_seeds,
+            )?;
+
+            Self::close_temporary_ft(
+                program_of_token,
+                bidder_ft_temp_account,
+                bidder_account,
+                pda,
+                pda_account,
+                signers_seeds,
+            )?;
+
+            auction_info.highest_bidder_pubkey = Pubkey::default();
+            auction_info.highest_bidder_ft_temp_pubkey = Pubkey::default();
+            auction_info.highest_bidder_ft_returning_pubkey = Pubkey::default();
+            auction_info.bid_locked = false;
+            auction_info.bidder_extended = false;
+            auction_info.bid_escrow_amount = 0;
+            Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        }
+
+        msg!("Closing the batch bid group...");
+        **bidder_account.try_borrow_mut_lamports()? = bidder_account
+            .lamports()
+            .checked_add(batch_bid_group_account.lamports())
+            .ok_or(AuctionError::AmountOverflow)?;
+        **batch_bid_group_account.try_borrow_mut_lamports()? = 0;
+        batch_bid_group_account.try_borrow_mut_data()?.fill(0);
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn process_setup_recurring(
+        accounts: &[AccountInfo],
+        interval_sec: u64,
+        occurrences: u8,
+        initial_price: u64,
+        duration_sec: u64,
+        category: AuctionCategory,
+        ft_mint: Pubkey,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let recurring_auction_account = next_account_info(account_info_iter)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(accouint_of_exhibitor)?;
+        Self::require_writable(recurring_auction_account)?;
+
+        // Occurrences of a series never overlap
+        if occurrences == 0 || duration_sec == 0 || interval_sec < duration_sec {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        if i64::try_from(interval_sec).is_err() {
+            return Err(AuctionError::AmountOverflow.into());
+        }
+
+        let (recurring_auction_pda, recurring_bump_seed) = Pubkey::find_program_address(
+            &[b"recurring", accouint_of_exhibitor.key.as_ref()],
+            program_id,
+        );
+        if recurring_auction_pda != *recurring_auction_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !recurring_auction_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        msg!("Creating the recurring auction...");
+        Self::create_pda_account(
+            accouint_of_exhibitor,
+            recurring_auction_account,
+            system_program,
+            rent,
+            RecurringAuction::LEN,
+            &[
+                b"recurring",
+                accouint_of_exhibitor.key.as_ref(),
+                &[recurring_bump_seed],
+            ],
+            program_id,
+        )?;
+        let recurring_auction = RecurringAuction {
+            is_initialized: true,
+            exhibitor: *accouint_of_exhibitor.key,
+            interval_sec,
+            occurrences,
+            triggered: 0,
+            initial_price,
+            duration_sec,
+            category,
+            ft_mint,
+            next_occurrence_at: clock.unix_timestamp,
+            last_escrow: Pubkey::default(),
+        };
+        RecurringAuction::pack(
+            recurring_auction,
+            &mut recurring_auction_account.try_borrow_mut_data()?,
+        )?;
+        Ok(())
+    }
+
+    fn process_trigger_next_occurrence(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let recurring_auction_account = next_account_info(account_info_iter)?;
+        let previous_escrow_account = next_account_info(account_info_iter)?;
+        let exhibit_accounts = ExhibitAccounts::from_accounts(account_info_iter.as_slice())?;
+        Self::require_writable(recurring_auction_account)?;
+
+        if recurring_auction_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let mut recurring_auction =
+            RecurringAuction::unpack(&recurring_auction_account.try_borrow_data()?)?;
+        if recurring_auction.exhibitor != *exhibit_accounts.exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let (recurring_auction_pda, _bump_seed) = Pubkey::find_program_address(
+            &[b"recurring", recurring_auction.exhibitor.as_ref()],
+            program_id,
+        );
+        if recurring_auction_pda != *recurring_auction_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let clock = &Clock::from_account_info(exhibit_accounts.clock_sysvar)?;
+        if clock.unix_timestamp < recurring_auction.next_occurrence_at {
+            return Err(AuctionError::AuctionNotStarted.into());
+        }
+        if recurring_auction.last_escrow != Pubkey::default() {
+            if recurring_auction.last_escrow != *previous_escrow_account.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            // A closed previous occurrence has ended
+            if previous_escrow_account.lamports() > 0 {
+                let previous_auction_info =
+                    Auction::unpack(&previous_escrow_account.try_borrow_data()?)?;
+                if previous_auction_info.end_at > clock.unix_timestamp {
+                    return Err(AuctionError::ActiveAuction.into());
+                }
+            }
+        }
+
+        Self::process_exhibit(
+            &exhibit_accounts,
+            recurring_auction.initial_price,
+            recurring_auction.duration_sec,
+            recurring_auction.category,
+            recurring_auction.ft_mint,
+            0,
+            program_id,
+        )?;
+
+        recurring_auction.triggered += 1;
+        if recurring_auction.triggered >= recurring_auction.occurrences {
+            msg!("Closing the finished recurring auction...");
+            let accouint_of_exhibitor = exhibit_accounts.exhibitor;
+            **accouint_of_exhibitor.try_borrow_mut_lamports()? = accouint_of_exhibitor
+                .lamports()
+                .checked_add(recurring_auction_account.lamports())
+                .ok_or(AuctionError::AmountOverflow)?;
+            **recurring_auction_account.try_borrow_mut_lamports()? = 0;
+            recurring_auction_account.try_borrow_mut_data()?.fill(0);
+            return Ok(());
+        }
+        recurring_auction.last_escrow = *exhibit_accounts.escrow_account.key;
+        recurring_auction.next_occurrence_at = i64::try_from(recurring_auction.interval_sec)
+            .ok()
+            .and_then(|interval| recurring_auction.next_occurrence_at.checked_add(interval))
+            .ok_or(AuctionError::AmountOverflow)?;
+        RecurringAuction::pack(
+            recurring_auction,
+            &mut recurring_auction_account.try_borrow_mut_data()?,
+        )?;
+        Ok(())
+    }
+
+    fn process_set_insurance_fee(
+        accounts: &[AccountInfo],
+        fee_bps: u16,
+        reserve: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        Self::require_writable(global_config_account)?;
+        let mut global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+        if fee_bps > MAX_BASIS_POINTS {
+            return Err(AuctionError::InvalidFeeBasisPoints.into());
+        }
+
+        global_config.insurance_fee_bps = fee_bps;
+        global_config.insurance_reserve = reserve;
+        Self::store_global_config(global_config, global_config_account)
+    }
+
+    fn process_claim_insurance(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let winner_account = next_account_info(account_info_iter)?;
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !winner_account.is_signer || !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+
+        let tombstone_account = next_account_info(account_info_iter)?;
+        let insurance_claim_account = next_account_info(account_info_iter)?;
+        let insurance_vault_account = next_account_info(account_info_iter)?;
+        let winner_ft_account = next_account_info(account_info_iter)?;
+        let program_of_token = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(winner_account)?;
+        Self::require_writable(insurance_claim_account)?;
+        Self::require_writable(insurance_vault_account)?;
+        Self::require_writable(winner_ft_account)?;
+
+        if tombstone_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let tombstone = TombstoneRecord::unpack(&tombstone_account.try_borrow_data()?)?;
+        if tombstone.winner != *winner_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+
+        let (insurance_claim_pda, claim_bump_seed) = Pubkey::find_program_address(
+            &[b"insurance_claim", tombstone_account.key.as_ref()],
+            program_id,
+        );
+        if insurance_claim_pda != *insurance_claim_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !insurance_claim_account.data_is_empty() {
+            return Err(AuctionError::InsuranceAlreadyClaimed.into());
+        }
+
+        let insurance_vault_data =
+            TokenAccount::unpack(&insurance_vault_account.try_borrow_data()?)?;
+        let (insurance_vault_pda, vault_bump_seed) = Pubkey::find_program_address(
+            &[b"insurance_vault", insurance_vault_data.mint.as_ref()],
+            program_id,
+        );
+        if insurance_vault_pda != *insurance_vault_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if insurance_vault_data.amount < tombstone.final_price {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        let transfer_to_winner_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            insurance_vault_account.key,
+            winner_ft_account.key,
+            &insurance_vault_pda,
+            &[],
+            tombstone.final_price,
+        )?;
+        msg!("Transferring FT to the Winner from the Insurance Vault...");
+        invoke_signed(
+            &transfer_to_winner_ix,
+            &[
+                insurance_vault_account.clone(),
+                winner_ft_account.clone(),
+                insurance_vault_account.clone(),
+                program_of_token.clone(),
+            ],
+            &[&[
+                b"insurance_vault",
+                insurance_vault_data.mint.as_ref(),
+                &[vault_bump_seed],
+            ]],
+        )?;
+
+        Self::create_pda_account(
+            winner_account,
+            insurance_claim_account,
+            system_program,
+            &Rent::get()?,
+            InsuranceClaimRecord::LEN,
+            &[
+                b"insurance_claim",
+                tombstone_account.key.as_ref(),
+                &[claim_bump_seed],
+            ],
+            program_id,
+        )?;
+        InsuranceClaimRecord::pack(
+            InsuranceClaimRecord {
+                is_initialized: true,
+                amount: tombstone.final_price,
+            },
+            &mut insurance_claim_account.try_borrow_mut_data()?,
+        )?;
+        Ok(())
+    }
+
+    fn process_withdraw_insurance(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+
+        let insurance_vault_account = next_account_info(account_info_iter)?;
+        let destination_ft_account = next_account_info(account_info_iter)?;
+        let program_of_token = next_account_info(account_info_iter)?;
+        Self::require_writable(insurance_vault_account)?;
+        Self::require_writable(destination_ft_account)?;
+
+        let insurance_vault_data =
+            TokenAccount::unpack(&insurance_vault_account.try_borrow_data()?)?;
+        let (insurance_vault_pda, vault_bump_seed) = Pubkey::find_program_address(
+            &[b"insurance_vault", insurance_vault_data.mint.as_ref()],
+            program_id,
+        );
+        if insurance_vault_pda != *insurance_vault_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let remaining = insurance_vault_data
+            .amount
+            .checked_sub(amount)
+            .ok_or(ProgramError::InsufficientFunds)?;
+        if remaining < global_config.insurance_reserve {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        let withdraw_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            insurance_vault_account.key,
+            destination_ft_account.key,
+            &insurance_vault_pda,
+            &[],
+            amount,
+        )?;
+        msg!("Transferring surplus FT from the Insurance Vault...");
+        invoke_signed(
+            &withdraw_ix,
+            &[
+                insurance_vault_account.clone(),
+                destination_ft_account.clone(),
+                insurance_vault_account.clone(),
+                program_of_token.clone(),
+            ],
+            &[&[
+                b"insurance_vault",
+                insurance_vault_data.mint.as_ref(),
+                &[vault_bump_seed],
+            ]],
+        )
+    }
+
+    fn process_increase_bid_escrow(
+        accounts: &[AccountInfo],
+        additional: u64,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let highest_bidder_account = next_account_info(account_info_iter)?;
+
+        if !highest_bidder_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let highest_bidder_ft_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_temp_account = next_account_info(account_info_iter)?;
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(highest_bidder_ft_account)?;
+        Self::require_writable(highest_bidder_ft_temp_account)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+
+        if auction_info.end_at <= clock.unix_timestamp {
+            return Err(AuctionError::InactiveAuction.into());
+        }
+        if auction_info.highest_bidder_pubkey != *highest_bidder_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_ft_temp_pubkey != *highest_bidder_ft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        auction_info.price = auction_info
+            .price
+            .checked_add(additional)
+            .ok_or(AuctionError::AmountOverflow)?;
+        auction_info.bid_escrow_amount = auction_info
+            .bid_escrow_amount
+            .checked_add(additional)
+            .ok_or(AuctionError::AmountOverflow)?;
+
+        let program_of_token = next_account_info(account_info_iter)?;
+        let transfer_to_escrow_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            highest_bidder_ft_account.key,
+            highest_bidder_ft_temp_account.key,
+            highest_bidder_account.key,
+            &[],
+            additional,
+        )?;
+        msg!("Transferring additional FT to the Escrow Account...");
+        invoke(
+            &transfer_to_escrow_ix,
+            &[
+                highest_bidder_ft_account.clone(),
+                highest_bidder_ft_temp_account.clone(),
+                highest_bidder_account.clone(),
+                program_of_token.clone(),
+            ],
+        )?;
+
+        let price = auction_info.visible_price();
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+
+        BidEvent {
+            escrow: *escrow_account.key,
+            bidder: *highest_bidder_account.key,
+            price,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_update_category(
+        accounts: &[AccountInfo],
+        category: AuctionCategory,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        let current_category_index_account = next_account_info(account_info_iter)?;
+        let new_category_index_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        Self::require_writable(current_category_index_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        let (current_category_index_pda, _bump_seed) = Pubkey::find_program_address(
+            &[b"category_index", &[auction_info.category as u8]],
+            program_id,
+        );
+        if current_category_index_pda != *current_category_index_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let mut current_category_index =
+            AuctionIndex::unpack(&current_category_index_account.try_borrow_data()?)?;
+        current_category_index.remove(escrow_account.key);
+        AuctionIndex::pack(
+            current_category_index,
+            &mut current_category_index_account.try_borrow_mut_data()?,
+        )?;
+
+        Self::push_to_auction_index(
+            accouint_of_exhibitor,
+            new_category_index_account,
+            system_program,
+            rent,
+            &[b"category_index", &[category as u8]],
+            escrow_account.key,
+            program_id,
+        )?;
+
+        auction_info.category = category;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_exhibitor_note(
+        accounts: &[AccountInfo],
+        note: Box<[u8; EXHIBITOR_NOTE_LEN]>,
+        note_hash: [u8; 32],
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let note_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(accouint_of_exhibitor)?;
+        Self::require_writable(note_account)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if hash(note.as_ref()).to_bytes() != note_hash {
+            return Err(AuctionError::NoteHashMismatch.into());
+        }
+
+        let (note_pda, bump_seed) =
+            Pubkey::find_program_address(&[b"note", escrow_account.key.as_ref()], program_id);
+        if note_pda != *note_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !note_account.data_is_empty() {
+            return Err(AuctionError::NoteAlreadySet.into());
+        }
+
+        Self::create_pda_account(
+            accouint_of_exhibitor,
+            note_account,
+            system_program,
+            rent,
+            ExhibitorNote::LEN,
+            &[b"note", escrow_account.key.as_ref(), &[bump_seed]],
+            program_id,
+        )?;
+
+        let exhibitor_note = ExhibitorNote {
+            is_initialized: true,
+            note: *note,
+            signed_at: clock.unix_timestamp,
+        };
+        ExhibitorNote::pack(exhibitor_note, &mut note_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_max_bidders(
+        accounts: &[AccountInfo],
+        max: u8,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let bidder_registry_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+        if max as usize > BIDDER_REGISTRY_CAPACITY {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let (bidder_registry_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"bidder_registry", escrow_account.key.as_ref()],
+            program_id,
+        );
+        if bidder_registry_pda != *bidder_registry_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if max > 0 && bidder_registry_account.data_is_empty() {
+            Self::require_writable(accouint_of_exhibitor)?;
+            Self::require_writable(bidder_registry_account)?;
+            msg!("Creating the Bidder registry...");
+            Self::create_pda_account(
+                accouint_of_exhibitor,
+                bidder_registry_account,
+                system_program,
+                rent,
+                BidderRegistry::LEN,
+                &[
+                    b"bidder_registry",
+                    escrow_account.key.as_ref(),
+                    &[bump_seed],
+                ],
+                program_id,
+            )?;
+            auction_info.bidder_registry_bump = bump_seed;
+            let bidder_registry = BidderRegistry {
+                is_initialized: true,
+                bidders: [Pubkey::default(); BIDDER_REGISTRY_CAPACITY],
+            };
+            BidderRegistry::pack(
+                bidder_registry,
+                &mut bidder_registry_account.try_borrow_mut_data()?,
+            )?;
+        }
+
+        auction_info.max_bidders = max;
+        auction_info.unique_bidder_count = 0;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_transfer_nft_after_expiry(
+        accounts: &[AccountInfo],
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        let exhibiting_nft_returning_account = next_account_info(account_info_iter)?;
+        let escrow_account = next_account_info(account_info_iter)?;
+        let highest_bidder_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_temp_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_returning_account = next_account_info(account_info_iter)?;
+        Self::require_writable(accouint_of_exhibitor)?;
+        Self::require_writable(exhibiting_nft_temp_account)?;
+        Self::require_writable(exhibiting_nft_returning_account)?;
+        Self::require_writable(escrow_account)?;
+        Self::require_writable(highest_bidder_account)?;
+        Self::require_writable(highest_bidder_ft_temp_account)?;
+        Self::require_writable(highest_bidder_ft_returning_account)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.exhibiting_nft_temp_pubkey != *exhibiting_nft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey == Pubkey::default() {
+            return Err(AuctionError::NoBidderFound.into());
+        }
+        if auction_info.advanced_amount > 0 {
+            return Err(AuctionError::ProceedsAdvanced.into());
+        }
+        if auction_info.highest_bidder_pubkey != *highest_bidder_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_ft_temp_pubkey != *highest_bidder_ft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_ft_returning_pubkey
+            != *highest_bidder_ft_returning_account.key
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let close_allowed_at = auction_info
+            .close_allowed_at()
+            .ok_or(AuctionError::AmountOverflow)?;
+        if close_allowed_at > clock.unix_timestamp {
+            return Err(AuctionError::GracePeriodActive.into());
+        }
+
+        let program_of_token = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        let program_stats_account = next_account_info(account_info_iter)?;
+        let bump_seed = auction_info.pda_bump;
+        let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+        let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+
+        let exhibiting_nft_temp_account_data =
+            TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
+        let transfer_nft_to_exhibitor_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            exhibiting_nft_temp_account.key,
+            exhibiting_nft_returning_account.key,
+            &pda,
+            &[],
+            exhibiting_nft_temp_account_data.amount,
+        )?;
+        msg!("Transferring NFT back to the Exhibitor...");
+        invoke_signed(
+            &transfer_nft_to_exhibitor_ix,
+            &[
+                exhibiting_nft_temp_account.clone(),
+                exhibiting_nft_returning_account.clone(),
+                pda_account.clone(),
+                program_of_token.clone(),
+            ],
+            signers_seeds,
+        )?;
+
+        let highest_bidder_ft_temp_account_data =
+            TokenAccount::unpack(&highest_bidder_ft_temp_account.try_borrow_data()?)?;
+        let transfer_ft_to_highest_bidder_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            highest_bidder_ft_temp_account.key,
+            highest_bidder_ft_returning_account.key,
+            &pda,
+            &[],
+            highest_bidder_ft_temp_account_data.amount,
+        )?;
+        msg!("Transferring FT back to the Highest Bidder...");
+        invoke_signed(
+            &transfer_ft_to_highest_bidder_ix,
+            &[
+                highest_bidder_ft_temp_account.clone(),
+                highest_bidder_ft_returning_account.clone(),
+                pda_account.clone(),
+                program_of_token.clone(),
+            ],
+            signers_seeds,
+        )?;
+
+        Self::close_temporary_ft(
+            program_of_token,
+            highest_bidder_ft_temp_account,
+            highest_bidder_account,
+            pda,
+            pda_account,
+            signers_seeds,
+        )?;
+
+        ForfeitedAuctionEvent {
+            escrow: *escrow_account.key,
+            exhibitor: *accouint_of_exhibitor.key,
+            winner: auction_info.highest_bidder_pubkey,
+            price: auction_info.price,
+        }
+        .emit();
+
+        Self::record_auction_closed(program_stats_account, program_id)?;
+
+        Self::escrow_is_closing(
+            program_of_token,
+            exhibiting_nft_temp_account,
+            accouint_of_exhibitor,
+            pda,
+            pda_account,
+            escrow_account,
+            signers_seeds,
+        )
+    }
+
+    fn process_verify_signed_message(
+        accounts: &[AccountInfo],
+        message_hash: [u8; 32],
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let instructions_sysvar_account = next_account_info(account_info_iter)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Self::verify_ed25519_signature(
+            instructions_sysvar_account,
+            accouint_of_exhibitor.key,
+            &message_hash,
+        )?;
+
+        auction_info.terms_accepted = true;
+        auction_info.terms_hash = message_hash;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    /// Checks that the previous instruction verified `signer`'s Ed25519 signature over `message`
+    fn verify_ed25519_signature(
+        instructions_sysvar_account: &AccountInfo,
+        signer: &Pubkey,
+        message: &[u8],
+    ) -> ProgramResult {
+        if !sysvar_instructions::check_id(instructions_sysvar_account.key) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let current_index =
+            sysvar_instructions::load_current_index_checked(instructions_sysvar_account)?;
+        let ed25519_index = current_index
+            .checked_sub(1)
+            .ok_or(AuctionError::InvalidTermsSignature)?;
+        let ed25519_ix = sysvar_instructions::load_instruction_at_checked(
+            ed25519_index as usize,
+            instructions_sysvar_account,
+        )?;
+        if !ed25519_program::check_id(&ed25519_ix.program_id) {
+            return Err(AuctionError::InvalidTermsSignature.into());
+        }
+
+        let data = &ed25519_ix.data;
+        if data.len() < ED25519_OFFSETS_END || data[0] != 1 {
+            return Err(AuctionError::InvalidTermsSignature.into());
+        }
+        // Header is the signature count and a padding byte, followed by the signature offsets
+        let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+        // Offsets must point inside the Ed25519 instruction itself
+        if [4, 8, 14].iter().any(|&offset| read_u16(offset) != u16::MAX) {
+            return Err(AuctionError::InvalidTermsSignature.into());
+        }
+
+        let public_key_offset = read_u16(6) as usize;
+        let message_offset = read_u16(10) as usize;
+        let message_size = read_u16(12) as usize;
+        let public_key = data.get(public_key_offset..public_key_offset + 32);
+        let signed_message = data.get(message_offset..message_offset + message_size);
+        if public_key != Some(signer.as_ref()) || signed_message != Some(message) {
+            return Err(AuctionError::InvalidTermsSignature.into());
+        }
+        Ok(())
+    }
+
+    fn process_register(
+        accounts: &[AccountInfo],
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let participant_account = next_account_info(account_info_iter)?;
+
+        if !participant_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let registration_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(participant_account)?;
+        Self::require_writable(registration_account)?;
+
+        if auction_info.end_at <= clock.unix_timestamp {
+            return Err(AuctionError::InactiveAuction.into());
+        }
+
+        let (registration_pda, bump_seed) = Pubkey::find_program_address(
+            &[
+                b"registration",
+                escrow_account.key.as_ref(),
+                participant_account.key.as_ref(),
+            ],
+            program_id,
+        );
+        if registration_pda != *registration_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !registration_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        Self::create_pda_account(
+            participant_account,
+            registration_account,
+            system_program,
+            rent,
+            RegistrationRecord::LEN,
+            &[
+                b"registration",
+                escrow_account.key.as_ref(),
+                participant_account.key.as_ref(),
+                &[bump_seed],
+            ],
+            program_id,
+        )?;
+
+        let registration_record = RegistrationRecord {
+            is_initialized: true,
+            registered_at: clock.unix_timestamp,
+        };
+        RegistrationRecord::pack(
+            registration_record,
+            &mut registration_account.try_borrow_mut_data()?,
+        )?;
+        Ok(())
+    }
+
+    fn process_select_winner(
+        accounts: &[AccountInfo],
+        winner: Pubkey,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let registration_account = next_account_info(account_info_iter)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.end_at > clock.unix_timestamp {
+            return Err(AuctionError::ActiveAuction.into());
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        let (registration_pda, _) = Pubkey::find_program_address(
+            &[b"registration", escrow_account.key.as_ref(), winner.as_ref()],
+            program_id,
+        );
+        if registration_pda != *registration_account.key
+            || registration_account.owner != program_id
+        {
+            return Err(AuctionError::NotRegistered.into());
+        }
+        RegistrationRecord::unpack(&registration_account.try_borrow_data()?)
+            .map_err(|_| AuctionError::NotRegistered)?;
+
+        auction_info.airdrop_winner = winner;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_claim_airdrop(
+        accounts: &[AccountInfo],
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let winner_account = next_account_info(account_info_iter)?;
+
+        if !winner_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        let winner_nft_receiving_account = next_account_info(account_info_iter)?;
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(accouint_of_exhibitor)?;
+        Self::require_writable(exhibiting_nft_temp_account)?;
+        Self::require_writable(winner_nft_receiving_account)?;
+        Self::require_writable(escrow_account)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.airdrop_winner == Pubkey::default()
+            || auction_info.airdrop_winner != *winner_account.key
+        {
+            return Err(AuctionError::Unauthorized.into());
+        }
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.exhibiting_nft_temp_pubkey != *exhibiting_nft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let program_of_token = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        let program_stats_account = next_account_info(account_info_iter)?;
+        let bump_seed = auction_info.pda_bump;
+        let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+        let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+
+        let exhibiting_nft_temp_account_data =
+            TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
+        let transfer_nft_to_winner_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            exhibiting_nft_temp_account.key,
+            winner_nft_receiving_account.key,
+            &pda,
+            &[],
+            exhibiting_nft_temp_account_data.amount,
+        )?;
+        msg!("Transferring NFT to the Winner...");
+        invoke_signed(
+            &transfer_nft_to_winner_ix,
+            &[
+                exhibiting_nft_temp_account.clone(),
+                winner_nft_receiving_account.clone(),
+                pda_account.clone(),
+                program_of_token.clone(),
+            ],
+            signers_seeds,
+        )?;
+
+        Self::record_auction_closed(program_stats_account, program_id)?;
+
+        Self::escrow_is_closing(
+            program_of_token,
+            exhibiting_nft_temp_account,
+            accouint_of_exhibitor,
+            pda,
+            pda_account,
+            escrow_account,
+            signers_seeds,
+        )
+    }
+
+    fn process_set_auto_close(
+        accounts: &[AccountInfo],
+        enabled: bool,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        auction_info.auto_close = enabled;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_trigger_auto_close(
+        accounts: &[AccountInfo],
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let caller_account = next_account_info(account_info_iter)?;
+
+        if !caller_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let highest_bidder_account = next_account_info(account_info_iter)?;
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        let exhibitor_ft_receiving_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_temp_account = next_account_info(account_info_iter)?;
+        let highest_bidder_nft_receiving_account = next_account_info(account_info_iter)?;
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(caller_account)?;
+        Self::require_writable(highest_bidder_account)?;
+        Self::require_writable(accouint_of_exhibitor)?;
+        Self::require_writable(exhibiting_nft_temp_account)?;
+        Self::require_writable(exhibitor_ft_receiving_account)?;
+        Self::require_writable(highest_bidder_ft_temp_account)?;
+        Self::require_writable(highest_bidder_nft_receiving_account)?;
+        Self::require_writable(escrow_account)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+
+        if !auction_info.auto_close {
+            return Err(AuctionError::AutoCloseDisabled.into());
+        }
+        if auction_info.finality_timeout_sec > 0 {
+            return Err(AuctionError::SettlementPending.into());
+        }
+        if auction_info.winner_override != Pubkey::default() {
+            return Err(AuctionError::WinnerOverrideRequired.into());
+        }
+        if auction_info.audit_oracle != Pubkey::default() {
+            return Err(AuctionError::SettlementPending.into());
+        }
+        if auction_info.exceeds_transaction_limit() {
+            return Err(AuctionError::TransactionLimitExceeded.into());
+        }
+        if auction_info.end_at > clock.unix_timestamp {
+            return Err(AuctionError::ActiveAuction.into());
+        }
+        if auction_info.highest_bidder_pubkey == Pubkey::default() {
+            return Err(AuctionError::NoBidderFound.into());
+        }
+        if auction_info.highest_bidder_pubkey != *highest_bidder_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.exhibiting_nft_temp_pubkey != *exhibiting_nft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !Self::is_exhibitor_ft_receiving(
+            &auction_info,
+            highest_bidder_ft_temp_account,
+            exhibitor_ft_receiving_account,
+        )? {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_ft_temp_pubkey != *highest_bidder_ft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let exhibiting_nft_temp_account_data =
+            TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
+        // The highest bidder didn't sign, so the receiving account must be theirs
+        let highest_bidder_nft_receiving_account_data =
+            TokenAccount::unpack(&highest_bidder_nft_receiving_account.try_borrow_data()?)?;
+        if highest_bidder_nft_receiving_account_data.owner != auction_info.highest_bidder_pubkey
+            || highest_bidder_nft_receiving_account_data.mint
+                != exhibiting_nft_temp_account_data.mint
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let program_of_token = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        let program_stats_account = next_account_info(account_info_iter)?;
+        let bump_seed = auction_info.pda_bump;
+        let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+        let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+
+        let transfer_nft_to_highest_bidder_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            exhibiting_nft_temp_account.key,
+            highest_bidder_nft_receiving_account.key,
+            &pda,
+            &[],
+            exhibiting_nft_temp_account_data.amount,
+        )?;
+        msg!("Transferring NFT to the Highest Bidder...");
+        invoke_signed(
+            &transfer_nft_to_highest_bidder_ix,
+            &[
+                exhibiting_nft_temp_account.clone(),
+                highest_bidder_nft_receiving_account.clone(),
+                pda_account.clone(),
+                program_of_token.clone(),
+            ],
+            signers_seeds,
+        )?;
+
+        let highest_bidder_ft_temp_account_data =
+            TokenAccount::unpack(&highest_bidder_ft_temp_account.try_borrow_data()?)?;
+        if highest_bidder_ft_temp_account_data.amount < auction_info.bid_escrow_amount {
+            return Err(AuctionError::InsufficientEscrow.into());
+        }
+        Self::transfer_proceeds(
+            program_of_token,
+            highest_bidder_ft_temp_account,
+            exhibitor_ft_receiving_account,
+            account_info_iter.as_slice(),
+            &auction_info,
+            pda_account,
+            signers_seeds,
+        )?;
+
+        Self::close_temporary_ft(
+            program_of_token,
+            highest_bidder_ft_temp_account,
+            highest_bidder_account,
+            pda,
+            pda_account,
+            signers_seeds,
+        )?;
+
+        msg!("Paying the auto close tip...");
+        let tip = AUTO_CLOSE_TIP_LAMPORTS.min(escrow_account.lamports());
+        **escrow_account.try_borrow_mut_lamports()? -= tip;
+        **caller_account.try_borrow_mut_lamports()? = caller_account
+            .lamports()
+            .checked_add(tip)
+            .ok_or(AuctionError::AmountOverflow)?;
+
+        Self::record_auction_closed(program_stats_account, program_id)?;
+
+        Self::escrow_is_closing(
+            program_of_token,
+            exhibiting_nft_temp_account,
+            accouint_of_exhibitor,
+            pda,
+            pda_account,
+            escrow_account,
+            signers_seeds,
+        )
+    }
+
+    fn process_set_burn_on_cancel(
+        accounts: &[AccountInfo],
+        burn_on_cancel: bool,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        auction_info.burn_on_cancel = burn_on_cancel;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_auction_timer(
+        accounts: &[AccountInfo],
+        clock_drift_tolerance_sec: u64,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+        if clock_drift_tolerance_sec > MAX_CLOCK_DRIFT_TOLERANCE_SEC {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+
+        auction_info.clock_drift_tolerance_sec = clock_drift_tolerance_sec;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_pre_bid_approval(
+        accounts: &[AccountInfo],
+        requires_approval: bool,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        auction_info.requires_approval = requires_approval;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_approve_bid(
+        accounts: &[AccountInfo],
+        bidder: Pubkey,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        let bid_approval_account = next_account_info(account_info_iter)?;
+        Self::require_writable(bid_approval_account)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut bid_approval =
+            Self::load_bid_approval(bid_approval_account, escrow_account, &bidder, program_id)?;
+
+        bid_approval.approved = true;
+        BidApproval::pack(
+            bid_approval,
+            &mut bid_approval_account.try_borrow_mut_data()?,
+        )?;
+        Ok(())
+    }
+
+    fn process_reject_bid(
+        accounts: &[AccountInfo],
+        bidder: Pubkey,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        let bid_approval_account = next_account_info(account_info_iter)?;
+        let bidder_account = next_account_info(account_info_iter)?;
+        Self::require_writable(bid_approval_account)?;
+        Self::require_writable(bidder_account)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if bidder != *bidder_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::load_bid_approval(bid_approval_account, escrow_account, &bidder, program_id)?;
+
+        msg!("Closing the bid approval account...");
+        **bidder_account.try_borrow_mut_lamports()? = bidder_account
+            .lamports()
+            .checked_add(bid_approval_account.lamports())
+            .ok_or(AuctionError::AmountOverflow)?;
+        **bid_approval_account.try_borrow_mut_lamports()? = 0;
+        bid_approval_account.try_borrow_mut_data()?.fill(0);
+        Ok(())
+    }
+
+    fn process_set_anti_sandwich_protection(
+        accounts: &[AccountInfo],
+        anti_sandwich: bool,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        auction_info.anti_sandwich = anti_sandwich;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_auction_price_curve(
+        accounts: &[AccountInfo],
+        price_curve: [(u64, u16); MAX_PRICE_TIERS],
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+        let tiers = price_curve
+            .iter()
+            .take_while(|(_, increment_bps)| *increment_bps > 0)
+            .count();
+        if price_curve[tiers..].iter().any(|tier| *tier != (0, 0))
+            || price_curve[..tiers]
+                .windows(2)
+                .any(|pair| pair[0].0 >= pair[1].0)
+        {
+            return Err(AuctionError::InvalidPriceCurve.into());
+        }
+
+        auction_info.price_curve = price_curve;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_early_bird_discount(
+        accounts: &[AccountInfo],
+        early_bird_period_bps: u16,
+        early_bird_increment_bps: u16,
+        late_increment_bps: u16,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+        if early_bird_period_bps > MAX_BASIS_POINTS {
+            return Err(AuctionError::InvalidFeeBasisPoints.into());
+        }
+
+        auction_info.early_bird_period_bps = early_bird_period_bps;
+        auction_info.early_bird_increment_bps = early_bird_increment_bps;
+        auction_info.late_increment_bps = late_increment_bps;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_sealed_end_time(
+        accounts: &[AccountInfo],
+        reveal_end_at_time: i64,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+        if reveal_end_at_time < 0 {
+            return Err(AuctionError::InvalidExpiry.into());
+        }
+
+        auction_info.reveal_end_at_time = reveal_end_at_time;
+        auction_info.hidden_end_at_revealed = false;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_reveal_end_time(
+        accounts: &[AccountInfo],
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        auction_info.hidden_end_at_revealed = true;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_bid_weighting(
+        accounts: &[AccountInfo],
+        bid_weight_oracle: Pubkey,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        auction_info.bid_weight_oracle = bid_weight_oracle;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_nft_update_authority(
+        accounts: &[AccountInfo],
+        nft_update_authority: Pubkey,
+        allow_updates_during_auction: bool,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        // Bidders must know the NFT may change before they bid
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        auction_info.nft_update_authority = nft_update_authority;
+        auction_info.allow_updates_during_auction = allow_updates_during_auction;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_update_nft_during_auction(
+        accounts: &[AccountInfo],
+        new_uri: &[u8],
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let nft_update_authority_account = next_account_info(account_info_iter)?;
+
+        if !nft_update_authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        let metadata_account = next_account_info(account_info_iter)?;
+        let token_metadata_program = next_account_info(account_info_iter)?;
+        Self::require_writable(metadata_account)?;
+
+        if !auction_info.allow_updates_during_auction {
+            return Err(AuctionError::NFTUpdatesDisabled.into());
+        }
+        if auction_info.nft_update_authority == Pubkey::default()
+            || auction_info.nft_update_authority != *nft_update_authority_account.key
+        {
+            return Err(AuctionError::Unauthorized.into());
+        }
+        if auction_info.exhibiting_nft_temp_pubkey != *exhibiting_nft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if *token_metadata_program.key != TOKEN_METADATA_PROGRAM_ID
+            || *metadata_account.owner != TOKEN_METADATA_PROGRAM_ID
+        {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let exhibiting_nft_temp_account_data =
+            TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
+        if metadata::find_metadata_address(&exhibiting_nft_temp_account_data.mint)
+            != *metadata_account.key
+        {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let uri_len = new_uri
+            .iter()
+            .rposition(|&byte| byte != 0)
+            .map_or(0, |last| last + 1);
+        if uri_len == 0 {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        let update_uri_ix = metadata::update_uri(
+            *metadata_account.key,
+            *nft_update_authority_account.key,
+            &metadata_account.try_borrow_data()?,
+            &new_uri[..uri_len],
+        )?;
+        msg!("Updating the NFT metadata URI...");
+        invoke(
+            &update_uri_ix,
+            &[
+                metadata_account.clone(),
+                nft_update_authority_account.clone(),
+                token_metadata_program.clone(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn process_set_trustline_requirement(
+        accounts: &[AccountInfo],
+        trustline_program: Pubkey,
+        trustline_type: [u8; 8],
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        auction_info.trustline_program = trustline_program;
+        auction_info.trustline_type = trustline_type;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_auction_type(
+        accounts: &[AccountInfo],
+        auction_type: AuctionType,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        match auction_type {
+            AuctionType::English => {}
+            AuctionType::SealedBid => auction_info.bids_visible = false,
+            // Needs the idle period and maximum duration of `SetSlidingExpiry`
+            AuctionType::SlidingExpiry => return Err(AuctionError::InvalidInstruction.into()),
+        }
+        auction_info.auction_type = auction_type;
+        auction_info.idle_period_sec = 0;
+        auction_info.max_duration_sec = 0;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_refund_policy(
+        accounts: &[AccountInfo],
+        refund_on_cancel: bool,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        auction_info.refund_on_cancel = refund_on_cancel;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_force_cancel(
+        accounts: &[AccountInfo],
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        let exhibiting_nft_returning_account = next_account_info(account_info_iter)?;
+        let escrow_account = next_account_info(account_info_iter)?;
+        let highest_bidder_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_temp_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_returning_account = next_account_info(account_info_iter)?;
+        Self::require_writable(accouint_of_exhibitor)?;
+        Self::require_writable(exhibiting_nft_temp_account)?;
+        Self::require_writable(exhibiting_nft_returning_account)?;
+        Self::require_writable(escrow_account)?;
+        Self::require_writable(highest_bidder_account)?;
+        Self::require_writable(highest_bidder_ft_temp_account)?;
+        Self::require_writable(highest_bidder_ft_returning_account)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.exhibiting_nft_temp_pubkey != *exhibiting_nft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.end_at <= clock.unix_timestamp {
+            return Err(AuctionError::InactiveAuction.into());
+        }
+        if auction_info.highest_bidder_pubkey == Pubkey::default() {
+            return Err(AuctionError::NoBidderFound.into());
+        }
+        if auction_info.advanced_amount > 0 {
+            return Err(AuctionError::ProceedsAdvanced.into());
+        }
+        if auction_info.highest_bidder_pubkey != *highest_bidder_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_ft_temp_pubkey != *highest_bidder_ft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_ft_returning_pubkey
+            != *highest_bidder_ft_returning_account.key
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let program_of_token = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        let program_stats_account = next_account_info(account_info_iter)?;
+        let bump_seed = auction_info.pda_bump;
+        let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+        let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+
+        if !auction_info.refund_on_cancel {
+            let exhibitor_ft_account = next_account_info(account_info_iter)?;
+            Self::require_writable(exhibitor_ft_account)?;
+            let compensation_ix = spl_token::instruction::transfer(
+                program_of_token.key,
+                exhibitor_ft_account.key,
+                highest_bidder_ft_returning_account.key,
+                accouint_of_exhibitor.key,
+                &[],
+                auction_info.price,
+            )?;
+            msg!("Transferring the compensation to the Highest Bidder...");
+            invoke(
+                &compensation_ix,
+                &[
+                    exhibitor_ft_account.clone(),
+                    highest_bidder_ft_returning_account.clone(),
+                    accouint_of_exhibitor.clone(),
+                    program_of_token.clone(),
+                ],
+            )?;
+        }
+
+        if auction_info.exhibitor_collateral > 0 {
+            let collateral_vault_account = next_account_info(account_info_iter)?;
+            Self::require_writable(collateral_vault_account)?;
+            let (collateral_vault_pda, collateral_bump_seed) = Pubkey::find_program_address(
+                &[
+                    b"collateral",
+                    escrow_account.key.as_ref(),
+                    accouint_of_exhibitor.key.as_ref(),
+                ],
+                program_id,
+            );
+            if collateral_vault_pda != *collateral_vault_account.key {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            let cancel_penalty = Self::basis_points_of(
+                auction_info.exhibitor_collateral,
+                auction_info.cancel_penalty_bps,
+            )?;
+            if cancel_penalty > 0 {
+                let slash_collateral_ix = spl_token::instruction::transfer(
+                    program_of_token.key,
+                    collateral_vault_account.key,
+                    highest_bidder_ft_returning_account.key,
+                    &collateral_vault_pda,
+                    &[],
+                    cancel_penalty,
+                )?;
+                msg!("Paying the cancel penalty from the collateral to the Highest Bidder...");
+                invoke_signed(
+                    &slash_collateral_ix,
+                    &[
+                        collateral_vault_account.clone(),
+                        highest_bidder_ft_returning_account.clone(),
+                        collateral_vault_account.clone(),
+                        program_of_token.clone(),
+                    ],
+                    &[&[
+                        b"collateral",
+                        escrow_account.key.as_ref(),
+                        accouint_of_exhibitor.key.as_ref(),
+                        &[collateral_bump_seed],
+                    ]],
+                )?;
+            }
+        }
+
+        let highest_bidder_ft_temp_account_data =
+            TokenAccount::unpack(&highest_bidder_ft_temp_account.try_borrow_data()?)?;
+        let transfer_ft_to_highest_bidder_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            highest_bidder_ft_temp_account.key,
+            highest_bidder_ft_returning_account.key,
+            &pda,
+            &[],
+            highest_bidder_ft_temp_account_data.amount,
+        )?;
+        msg!("Transferring FT back to the Highest Bidder...");
+        invoke_signed(
+            &transfer_ft_to_highest_bidder_ix,
+            &[
+                highest_bidder_ft_temp_account.clone(),
+                highest_bidder_ft_returning_account.clone(),
+                pda_account.clone(),
+                program_of_token.clone(),
+            ],
+            signers_seeds,
+        )?;
+
+        Self::close_temporary_ft(
+            program_of_token,
+            highest_bidder_ft_temp_account,
+            highest_bidder_account,
+            pda,
+            pda_account,
+            signers_seeds,
+        )?;
+
+        let exhibiting_nft_temp_account_data =
+            TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
+        let transfer_nft_to_exhibitor_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            exhibiting_nft_temp_account.key,
+            exhibiting_nft_returning_account.key,
+            &pda,
+            &[],
+            exhibiting_nft_temp_account_data.amount,
+        )?;
+        msg!("Transferring NFT back to the Exhibitor...");
+        invoke_signed(
+            &transfer_nft_to_exhibitor_ix,
+            &[
+                exhibiting_nft_temp_account.clone(),
+                exhibiting_nft_returning_account.clone(),
+                pda_account.clone(),
+                program_of_token.clone(),
+            ],
+            signers_seeds,
+        )?;
+
+        Self::record_auction_closed(program_stats_account, program_id)?;
+
+        Self::escrow_is_closing(
+            program_of_token,
+            exhibiting_nft_temp_account,
+            accouint_of_exhibitor,
+            pda,
+            pda_account,
+            escrow_account,
+            signers_seeds,
+        )
+    }
+
+    fn process_liquidate_bid(
+        accounts: &[AccountInfo],
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let caller_account = next_account_info(account_info_iter)?;
+
+        if !caller_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        let highest_bidder_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_temp_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_returning_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        Self::require_writable(highest_bidder_account)?;
+        Self::require_writable(highest_bidder_ft_temp_account)?;
+        Self::require_writable(highest_bidder_ft_returning_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.highest_bidder_pubkey == Pubkey::default() {
+            return Err(AuctionError::NoBidderFound.into());
+        }
+        if auction_info.highest_bidder_pubkey != *highest_bidder_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_ft_temp_pubkey != *highest_bidder_ft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_ft_returning_pubkey
+            != *highest_bidder_ft_returning_account.key
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let highest_bidder_ft_temp_account_data =
+            TokenAccount::unpack(&highest_bidder_ft_temp_account.try_borrow_data()?)?;
+        if highest_bidder_ft_temp_account_data.amount >= auction_info.bid_escrow_amount {
+            return Err(AuctionError::ExpectedAmountMismatch.into());
+        }
+        msg!(
+            "Escrowed {} of {}, the bid is liquidatable",
+            highest_bidder_ft_temp_account_data.amount,
+            auction_info.bid_escrow_amount
+        );
+
+        let program_of_token = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        let bump_seed = auction_info.pda_bump;
+        let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+        let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+
+        let transfer_to_highest_bidder_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            highest_bidder_ft_temp_account.key,
+            highest_bidder_ft_returning_account.key,
+            &pda,
+            &[],
+            highest_bidder_ft_temp_account_data.amount,
+        )?;
+        msg!("Transferring the remaining FT back to the Highest Bidder...");
+        invoke_signed(
+            &transfer_to_highest_bidder_ix,
+            &[
+                highest_bidder_ft_temp_account.clone(),
+                highest_bidder_ft_returning_account.clone(),
+                pda_account.clone(),
+                program_of_token.clone(),
+            ],
+            signers_seeds,
+        )?;
+
+        Self::close_temporary_ft(
+            program_of_token,
+            highest_bidder_ft_temp_account,
+            highest_bidder_account,
+            pda,
+            pda_account,
+            signers_seeds,
+        )?;
+
+        auction_info.highest_bidder_pubkey = Pubkey::default();
+        auction_info.highest_bidder_ft_temp_pubkey = Pubkey::default();
+        auction_info.highest_bidder_ft_returning_pubkey = Pubkey::default();
+        auction_info.bid_locked = false;
+        auction_info.bidder_extended = false;
+        auction_info.bid_escrow_amount = 0;
+        auction_info.advanced_amount = 0;
+        // The voided bid no longer sets the price, bidding resumes from the floor price
+        auction_info.price = auction_info.floor_price;
+        auction_info.highest_bid_weight_bps = 0;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_read_program_stats(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let program_stats_account = next_account_info(account_info_iter)?;
+        let program_stats = Self::load_program_stats(program_stats_account, program_id)?;
+
+        ProgramStatsEvent::new(&program_stats).emit();
+        Ok(())
+    }
+
+    fn process_withdraw_partial_proceeds(
+        accounts: &[AccountInfo],
+        amount: u64,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_temp_account = next_account_info(account_info_iter)?;
+        let exhibitor_ft_receiving_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        Self::require_writable(highest_bidder_ft_temp_account)?;
+        Self::require_writable(exhibitor_ft_receiving_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey == Pubkey::default() {
+            return Err(AuctionError::NoBidderFound.into());
+        }
+        if auction_info.finality_timeout_sec > 0 {
+            return Err(AuctionError::SettlementPending.into());
+        }
+        // The proceeds of a fractionally owned NFT are only split on settlement
+        if auction_info.fractional_owners[0].1 > 0 {
+            return Err(AuctionError::Unauthorized.into());
+        }
+        if auction_info.winner_override != Pubkey::default() {
+            return Err(AuctionError::WinnerOverrideRequired.into());
+        }
+        if auction_info.highest_bidder_ft_temp_pubkey != *highest_bidder_ft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !Self::is_exhibitor_ft_receiving(
+            &auction_info,
+            highest_bidder_ft_temp_account,
+            exhibitor_ft_receiving_account,
+        )? {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let advanced_amount = auction_info
+            .advanced_amount
+            .checked_add(amount)
+            .ok_or(AuctionError::AmountOverflow)?;
+        if advanced_amount > auction_info.price {
+            return Err(AuctionError::ExpectedAmountMismatch.into());
+        }
+        auction_info.advanced_amount = advanced_amount;
+        auction_info.bid_escrow_amount = auction_info
+            .bid_escrow_amount
+            .checked_sub(amount)
+            .ok_or(AuctionError::InsufficientEscrow)?;
+
+        let program_of_token = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        let bump_seed = auction_info.pda_bump;
+        let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+        let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+
+        let transfer_ft_to_exhibitor_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            highest_bidder_ft_temp_account.key,
+            exhibitor_ft_receiving_account.key,
+            &pda,
+            &[],
+            amount,
+        )?;
+        msg!("Transferring part of the proceeds to the Exhibitor...");
+        invoke_signed(
+            &transfer_ft_to_exhibitor_ix,
+            &[
+                highest_bidder_ft_temp_account.clone(),
+                exhibitor_ft_receiving_account.clone(),
+                pda_account.clone(),
+                program_of_token.clone(),
+            ],
+            signers_seeds,
+        )?;
+
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_auction_expiry(
+        accounts: &[AccountInfo],
+        new_end_at: i64,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+
+        let max_end_at = auction_info
+            .end_at
+            .checked_add(MAX_GOVERNANCE_EXTENSION_SEC)
+            .ok_or(AuctionError::AmountOverflow)?;
+        if new_end_at <= clock.unix_timestamp || new_end_at > max_end_at {
+            return Err(AuctionError::InvalidExpiry.into());
+        }
+
+        let previous_end_at = auction_info.visible_end_at(clock.unix_timestamp);
+        auction_info.end_at = new_end_at;
+        let expiry_updated_event = ExpiryUpdatedEvent {
+            escrow: *escrow_account.key,
+            previous_end_at,
+            new_end_at: auction_info.visible_end_at(clock.unix_timestamp),
+        };
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+
+        expiry_updated_event.emit();
+        Ok(())
+    }
+
+    fn process_validate_auction(accounts: &[AccountInfo], expected_price: u64) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let bidder_account = next_account_info(account_info_iter)?;
+
+        if !bidder_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        let auction_info = Auction::unpack_unchecked(&escrow_account.try_borrow_data()?)
+            .map_err(|_| AuctionError::AuctionNotFound)?;
+        if !auction_info.is_initialized() {
+            return Err(AuctionError::AuctionNotFound.into());
+        }
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+
+        if auction_info.end_at <= clock.unix_timestamp {
+            return Err(AuctionError::InactiveAuction.into());
+        }
+        if clock.unix_timestamp < auction_info.starts_at {
+            return Err(AuctionError::AuctionNotStarted.into());
+        }
+        if !auction_info.terms_accepted {
+            return Err(AuctionError::TermsNotAccepted.into());
+        }
+        if auction_info.advanced_amount > 0 {
+            return Err(AuctionError::ProceedsAdvanced.into());
+        }
+        if auction_info.floor_price > 0 && expected_price < auction_info.floor_price {
+            return Err(AuctionError::BelowFloorPrice.into());
+        }
+        if expected_price < auction_info.min_next_bid(clock.unix_timestamp).ok_or(AuctionError::AmountOverflow)? {
+            return Err(AuctionError::InsufficientBidPrice.into());
+        }
+        if auction_info.highest_bidder_pubkey == *bidder_account.key {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        ValidationPassedEvent {
+            escrow: *escrow_account.key,
+            bidder: *bidder_account.key,
+            expected_price,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_set_bid_visible(
+        accounts: &[AccountInfo],
+        visible: bool,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        auction_info.bids_visible = visible;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_reveal_current_price(accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let requester_account = next_account_info(account_info_iter)?;
+
+        if !requester_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+
+        if auction_info.exhibitor_pubkey != *requester_account.key
+            && auction_info.highest_bidder_pubkey != *requester_account.key
+        {
+            return Err(AuctionError::Unauthorized.into());
+        }
+
+        AuctionStateEvent::new(
+            escrow_account.key,
+            &auction_info,
+            Clock::get()?.unix_timestamp,
+        )
+        .emit();
+        Ok(())
+    }
+
+    fn process_set_nft_mint_validation(
+        accounts: &[AccountInfo],
+        expected_mint: Pubkey,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.exhibiting_nft_temp_pubkey != *exhibiting_nft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        let exhibiting_nft_temp_account_data =
+            TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
+        if exhibiting_nft_temp_account_data.mint != expected_mint {
+            return Err(AuctionError::UnexpectedNFTMint.into());
+        }
+
+        auction_info.expected_nft_mint = expected_mint;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_assign_mediator(
+        accounts: &[AccountInfo],
+        mediator: Pubkey,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+        let mediator_account = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer || !mediator_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if *mediator_account.key != mediator || mediator == auction_info.exhibitor_pubkey {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        auction_info.mediator_pubkey = mediator;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_revoke_mediator(
+        accounts: &[AccountInfo],
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        auction_info.mediator_pubkey = Pubkey::default();
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_get_next_bid_info(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let escrow_account = next_account_info(account_info_iter)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+        let bid_history_account = next_account_info(account_info_iter)?;
+
+        let (bid_history_pda, _) = Pubkey::find_program_address(
+            &[b"bid_history", escrow_account.key.as_ref()],
+            program_id,
+        );
+        if bid_history_pda != *bid_history_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let bid_count = if bid_history_account.data_is_empty() {
+            0
+        } else {
+            BidHistory::unpack(&bid_history_account.try_borrow_data()?)?.total_count
+        };
+
+        let price = auction_info.visible_price();
+        let min_next_bid = if auction_info.bids_visible {
+            auction_info.min_next_bid(clock.unix_timestamp).ok_or(AuctionError::AmountOverflow)?
+        } else {
+            1
+        }
+        .max(auction_info.floor_price);
+        NextBidInfoEvent {
+            escrow: *escrow_account.key,
+            min_next_bid,
+            time_remaining: auction_info
+                .visible_end_at(clock.unix_timestamp)
+                .saturating_sub(clock.unix_timestamp),
+            bid_count,
+            has_reserve: auction_info.floor_price > 0,
+            reserve_met: price >= auction_info.floor_price,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_get_twap(accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let escrow_account = next_account_info(account_info_iter)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+
+        let twap = if auction_info.bids_visible {
+            auction_info.twap().unwrap_or(0)
+        } else {
+            0
+        };
+        TWAPEvent {
+            escrow: *escrow_account.key,
+            twap,
+            duration_sec: auction_info.twap_denominator,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_init_changelog(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let exhibitor_account = next_account_info(account_info_iter)?;
+
+        if !exhibitor_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        let changelog_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(exhibitor_account)?;
+        Self::require_writable(escrow_account)?;
+        Self::require_writable(changelog_account)?;
+
+        if auction_info.exhibitor_pubkey != *exhibitor_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (changelog_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"changelog", escrow_account.key.as_ref()],
+            program_id,
+        );
+        if changelog_pda != *changelog_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !changelog_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        Self::create_pda_account(
+            exhibitor_account,
+            changelog_account,
+            system_program,
+            rent,
+            ChangelogAccount::LEN,
+            &[b"changelog", escrow_account.key.as_ref(), &[bump_seed]],
+            program_id,
+        )?;
+
+        let changelog = ChangelogAccount {
+            is_initialized: true,
+            escrow: *escrow_account.key,
+            total_count: 0,
+            entries: [ChangelogEntry::default(); CHANGELOG_CAPACITY],
+        };
+        ChangelogAccount::pack(changelog, &mut changelog_account.try_borrow_mut_data()?)?;
+
+        auction_info.has_changelog = true;
+        auction_info.changelog_bump = bump_seed;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_read_changelog(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let escrow_account = next_account_info(account_info_iter)?;
+        let changelog_account = next_account_info(account_info_iter)?;
+
+        let (changelog_pda, _) = Pubkey::find_program_address(
+            &[b"changelog", escrow_account.key.as_ref()],
+            program_id,
+        );
+        if changelog_pda != *changelog_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        // The changelog outlives a closed auction, whose bid visibility is no longer known
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?);
+        let bid_prices_hidden = auction_info
+            .as_ref()
+            .map_or(true, |auction_info| auction_info.bid_prices_hidden());
+        let now = Clock::get()?.unix_timestamp;
+        let end_at_sealed = auction_info
+            .as_ref()
+            .is_ok_and(|auction_info| auction_info.visible_end_at(now) == i64::MAX);
+        let changelog = ChangelogAccount::unpack(&changelog_account.try_borrow_data()?)?;
+        let first_index = changelog.total_count - changelog.ordered_entries().count() as u64;
+        for (index, entry) in (first_index..).zip(changelog.ordered_entries()) {
+            let mut entry_event = ChangelogEntryEvent::new(*escrow_account.key, index, entry);
+            if (bid_prices_hidden
+                && AuctionInstruction::changelog_extra_is_bid_price(entry.instruction))
+                || (end_at_sealed
+                    && AuctionInstruction::changelog_extra_is_end_time(entry.instruction))
+            {
+                entry_event.extra = [0; 8];
+            }
+            entry_event.emit();
+        }
+
+        Ok(())
+    }
+
+    fn process_set_start_delay(
+        accounts: &[AccountInfo],
+        start_delay_sec: u64,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        let starts_at = i64::try_from(start_delay_sec)
+            .ok()
+            .and_then(|delay| clock.unix_timestamp.checked_add(delay))
+            .ok_or(AuctionError::AmountOverflow)?;
+        if starts_at >= auction_info.end_at {
+            return Err(AuctionError::InvalidExpiry.into());
+        }
+
+        auction_info.starts_at = starts_at;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_migrate_state(
+        accounts: &[AccountInfo],
+        target_version: u8,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let signer_account = next_account_info(account_info_iter)?;
+
+        if !signer_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        let global_config_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(signer_account)?;
+        Self::require_writable(escrow_account)?;
+
+        if escrow_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if target_version != AUCTION_VERSION {
+            return Err(AuctionError::UnsupportedVersion.into());
+        }
+        let (version, exhibitor_pubkey) = {
+            let escrow_data = escrow_account.try_borrow_data()?;
+            let version = Auction::layout_version(&escrow_data)
+                .ok_or(ProgramError::InvalidAccountData)?;
+            // Every layout starts with the initialized flag and the exhibitor pubkey
+            if escrow_data[0] != 1 {
+                return Err(ProgramError::UninitializedAccount);
+            }
+            let exhibitor_bytes: [u8; 32] = escrow_data[1..33]
+                .try_into()
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+            (version, Pubkey::new_from_array(exhibitor_bytes))
+        };
+        if version == AUCTION_VERSION {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        if exhibitor_pubkey != *signer_account.key {
+            let global_config = Self::load_global_config(global_config_account, program_id)?;
+            if global_config.authority != *signer_account.key {
+                return Err(AuctionError::Unauthorized.into());
+            }
+        }
+
+        let rent_top_up = rent
+            .minimum_balance(Auction::LEN)
+            .saturating_sub(escrow_account.lamports());
+        if rent_top_up > 0 {
+            let transfer_ix =
+                system_instruction::transfer(signer_account.key, escrow_account.key, rent_top_up);
+            invoke(
+                &transfer_ix,
+                &[
+                    signer_account.clone(),
+                    escrow_account.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        }
+        // The current layout extends the first release one, the added fields start zeroed
+        escrow_account.realloc(Auction::LEN, true)?;
+
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        // Only accounts of the first release are migrated, they had no terms, category, FT mint
+        // or PDA bumps to carry over
+        let exhibitor_ft_receiving_account = next_account_info(account_info_iter)?;
+        if auction_info.exhibitor_ft_receiving_pubkey != *exhibitor_ft_receiving_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let exhibitor_ft_receiving_account_data =
+            TokenAccount::unpack(&exhibitor_ft_receiving_account.try_borrow_data()?)?;
+        auction_info.pda_bump = Pubkey::find_program_address(&[b"escrow"], program_id).1;
+        auction_info.max_bidder_extension_sec = DEFAULT_MAX_BIDDER_EXTENSION_SEC;
+        auction_info.close_grace_period_sec = DEFAULT_CLOSE_GRACE_PERIOD_SEC;
+        auction_info.category = AuctionCategory::Other;
+        auction_info.terms_accepted = true;
+        auction_info.ft_mint = exhibitor_ft_receiving_account_data.mint;
+        auction_info.bids_visible = true;
+        auction_info.lot_size = 1;
+        auction_info.bid_history_bump = Pubkey::find_program_address(
+            &[b"bid_history", escrow_account.key.as_ref()],
+            program_id,
+        )
+        .1;
+        auction_info.bidder_registry_bump = Pubkey::find_program_address(
+            &[b"bidder_registry", escrow_account.key.as_ref()],
+            program_id,
+        )
+        .1;
+        auction_info.insurance_vault_bump = Pubkey::find_program_address(
+            &[b"insurance_vault", auction_info.ft_mint.as_ref()],
+            program_id,
+        )
+        .1;
+        auction_info.version = target_version;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_oracle_price(
+        accounts: &[AccountInfo],
+        price: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+
+        let price_oracle_account = next_account_info(account_info_iter)?;
+        let ft_mint_account = next_account_info(account_info_iter)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(price_oracle_account)?;
+
+        Mint::unpack(&ft_mint_account.try_borrow_data()?)?;
+        let (price_oracle_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"price_oracle", ft_mint_account.key.as_ref()],
+            program_id,
+        );
+        if price_oracle_pda != *price_oracle_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if price_oracle_account.data_is_empty() {
+            Self::require_writable(authority_account)?;
+            msg!("Creating the price oracle...");
+            Self::create_pda_account(
+                authority_account,
+                price_oracle_account,
+                system_program,
+                rent,
+                PriceOracle::LEN,
+                &[b"price_oracle", ft_mint_account.key.as_ref(), &[bump_seed]],
+                program_id,
+            )?;
+        }
+
+        let price_oracle = PriceOracle {
+            is_initialized: true,
+            price,
+            updated_at: clock.unix_timestamp,
+        };
+        PriceOracle::pack(price_oracle, &mut price_oracle_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_trustless_oracle(
+        accounts: &[AccountInfo],
+        floor_bps: u16,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let price_oracle_account = next_account_info(account_info_iter)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+        if floor_bps > MAX_BASIS_POINTS {
+            return Err(AuctionError::InvalidFeeBasisPoints.into());
+        }
+
+        if price_oracle_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let (price_oracle_pda, _) = Pubkey::find_program_address(
+            &[b"price_oracle", auction_info.ft_mint.as_ref()],
+            program_id,
+        );
+        if price_oracle_pda != *price_oracle_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let price_oracle = PriceOracle::unpack(&price_oracle_account.try_borrow_data()?)?;
+
+        let oracle_min_price = Self::basis_points_of(price_oracle.price, floor_bps)?;
+        if auction_info.price < oracle_min_price {
+            return Err(AuctionError::BelowFloorPrice.into());
+        }
+
+        auction_info.oracle_pubkey = *price_oracle_account.key;
+        auction_info.oracle_min_price = oracle_min_price;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_deduct_royalty_before_escrow(
+        accounts: &[AccountInfo],
+        upfront_royalty_bps: u16,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(accouint_of_exhibitor)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let creator_account = next_account_info(account_info_iter)?;
+        let royalty_vault_account = next_account_info(account_info_iter)?;
+        let ft_mint_account = next_account_info(account_info_iter)?;
+        let program_of_token = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(royalty_vault_account)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default()
+            || auction_info.upfront_royalty_paid
+        {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+        if upfront_royalty_bps > MAX_DEDUCTION_BPS {
+            return Err(AuctionError::InvalidFeeBasisPoints.into());
+        }
+
+        if auction_info.ft_mint != *ft_mint_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (royalty_vault_pda, royalty_bump_seed) = Pubkey::find_program_address(
+            &[
+                b"royalty",
+                ft_mint_account.key.as_ref(),
+                creator_account.key.as_ref(),
+            ],
+            program_id,
+        );
+        if royalty_vault_pda != *royalty_vault_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if royalty_vault_account.data_is_empty() {
+            msg!("Creating the Royalty Vault...");
+            Self::create_pda_account(
+                accouint_of_exhibitor,
+                royalty_vault_account,
+                system_program,
+                rent,
+                TokenAccount::LEN,
+                &[
+                    b"royalty",
+                    ft_mint_account.key.as_ref(),
+                    creator_account.key.as_ref(),
+                    &[royalty_bump_seed],
+                ],
+                program_of_token.key,
+            )?;
+            let init_vault_ix = spl_token::instruction::initialize_account3(
+                program_of_token.key,
+                royalty_vault_account.key,
+                ft_mint_account.key,
+                &royalty_vault_pda,
+            )?;
+            invoke(
+                &init_vault_ix,
+                &[
+                    royalty_vault_account.clone(),
+                    ft_mint_account.clone(),
+                    program_of_token.clone(),
+                ],
+            )?;
+        }
+
+        auction_info.upfront_royalty_bps = upfront_royalty_bps;
+        auction_info.royalty_creator_ft_pubkey = *royalty_vault_account.key;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_claim_creator_royalty(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let creator_account = next_account_info(account_info_iter)?;
+
+        if !creator_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let creator_ft_account = next_account_info(account_info_iter)?;
+        let royalty_vault_account = next_account_info(account_info_iter)?;
+        let program_of_token = next_account_info(account_info_iter)?;
+        Self::require_writable(creator_ft_account)?;
+        Self::require_writable(royalty_vault_account)?;
+
+        if royalty_vault_account.data_is_empty() {
+            return Err(AuctionError::NoRoyaltyToClaim.into());
+        }
+        let royalty_vault_data = TokenAccount::unpack(&royalty_vault_account.try_borrow_data()?)?;
+        let (royalty_vault_pda, royalty_bump_seed) = Pubkey::find_program_address(
+            &[
+                b"royalty",
+                royalty_vault_data.mint.as_ref(),
+                creator_account.key.as_ref(),
+            ],
+            program_id,
+        );
+        if royalty_vault_pda != *royalty_vault_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if royalty_vault_data.amount == 0 {
+            return Err(AuctionError::NoRoyaltyToClaim.into());
+        }
+
+        let claim_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            royalty_vault_account.key,
+            creator_ft_account.key,
+            &royalty_vault_pda,
+            &[],
+            royalty_vault_data.amount,
+        )?;
+        msg!("Transferring the royalties to the creator...");
+        invoke_signed(
+            &claim_ix,
+            &[
+                royalty_vault_account.clone(),
+                creator_ft_account.clone(),
+                royalty_vault_account.clone(),
+                program_of_token.clone(),
+            ],
+            &[&[
+                b"royalty",
+                royalty_vault_data.mint.as_ref(),
+                creator_account.key.as_ref(),
+                &[royalty_bump_seed],
+            ]],
+        )?;
+        Ok(())
+    }
+
+    fn process_set_grace_period(
+        accounts: &[AccountInfo],
+        close_grace_period_sec: u64,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        auction_info.close_grace_period_sec = close_grace_period_sec;
+        auction_info
+            .close_allowed_at()
+            .ok_or(AuctionError::AmountOverflow)?;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_close_authority(
+        accounts: &[AccountInfo],
+        close_authority: Pubkey,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        auction_info.close_authority = close_authority;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_register_priority_bidder(
+        accounts: &[AccountInfo],
+        bidder: Pubkey,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let signer_account = next_account_info(account_info_iter)?;
+
+        if !signer_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let global_config_account = next_account_info(account_info_iter)?;
+
+        if auction_info.exhibitor_pubkey != *signer_account.key {
+            let global_config = Self::load_global_config(global_config_account, program_id)?;
+            if global_config.authority != *signer_account.key {
+                return Err(AuctionError::Unauthorized.into());
+            }
+        }
+
+        auction_info.priority_bidder = bidder;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_bid_multiplier(
+        accounts: &[AccountInfo],
+        min_bid_increment: u64,
+        bid_multiplier_bps: u16,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        auction_info.min_bid_increment = min_bid_increment;
+        auction_info.bid_multiplier_bps = bid_multiplier_bps;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_exhibit_with_timestamps(
+        accounts: &[AccountInfo],
+        initial_price: u64,
+        start_at: i64,
+        end_at: i64,
+        category: AuctionCategory,
+        ft_mint: Pubkey,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let now = Clock::get()?.unix_timestamp;
+        if start_at < now || end_at <= start_at {
+            return Err(AuctionError::InvalidExpiry.into());
+        }
+
+        // `Exhibit` derives the same times from the clock of this transaction
+        let start_delay_sec = start_at
+            .checked_sub(now)
+            .and_then(|delay| u64::try_from(delay).ok())
+            .ok_or(AuctionError::AmountOverflow)?;
+        let auction_duration_sec = end_at
+            .checked_sub(now)
+            .and_then(|duration| u64::try_from(duration).ok())
+            .ok_or(AuctionError::AmountOverflow)?;
+        Self::process_exhibit(
+            &ExhibitAccounts::from_accounts(accounts)?,
+            initial_price,
+            auction_duration_sec,
+            category,
+            ft_mint,
+            start_delay_sec,
+            program_id,
+        )
+    }
+
+    fn process_exhibit_soulbound(
+        accounts: &[AccountInfo],
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        let nft_mint_account = next_account_info(account_info_iter)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.exhibiting_nft_temp_pubkey != *exhibiting_nft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        let exhibiting_nft_temp_account_data =
+            TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
+        if exhibiting_nft_temp_account_data.mint != *nft_mint_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let bump_seed = auction_info.pda_bump;
+        let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+        let nft_mint = Mint::unpack(&nft_mint_account.try_borrow_data()?)?;
+        if nft_mint.freeze_authority != COption::Some(pda) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        auction_info.is_soulbound = true;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_token_extensions(
+        accounts: &[AccountInfo],
+        transfer_hook_program: Pubkey,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(authority_account)?;
+        Self::require_writable(global_config_account)?;
+
+        let mut global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+
+        Self::grow_global_config(
+            authority_account,
+            global_config_account,
+            system_program,
+            rent,
+        )?;
+        global_config.transfer_hook_program = transfer_hook_program;
+        Self::store_global_config(global_config, global_config_account)
+    }
+
+    fn process_set_minimum_duration(
+        accounts: &[AccountInfo],
+        min_sec: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(authority_account)?;
+        Self::require_writable(global_config_account)?;
+
+        let mut global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+        if i64::try_from(min_sec).is_err() {
+            return Err(AuctionError::AmountOverflow.into());
+        }
+
+        Self::grow_global_config(
+            authority_account,
+            global_config_account,
+            system_program,
+            rent,
+        )?;
+        global_config.min_auction_duration_sec = min_sec;
+        Self::store_global_config(global_config, global_config_account)
+    }
+
+    fn process_set_bid_cap(
+        accounts: &[AccountInfo],
+        max_lifetime_bid: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(authority_account)?;
+        Self::require_writable(global_config_account)?;
+
+        let mut global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+
+        Self::grow_global_config(
+            authority_account,
+            global_config_account,
+            system_program,
+            rent,
+        )?;
+        global_config.max_lifetime_bid = max_lifetime_bid;
+        Self::store_global_config(global_config, global_config_account)
+    }
+
+    fn process_set_nft_lock_period(
+        accounts: &[AccountInfo],
+        lock_period_sec: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(authority_account)?;
+        Self::require_writable(global_config_account)?;
+
+        let mut global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+        if i64::try_from(lock_period_sec).is_err() {
+            return Err(AuctionError::AmountOverflow.into());
+        }
+
+        Self::grow_global_config(
+            authority_account,
+            global_config_account,
+            system_program,
+            rent,
+        )?;
+        global_config.nft_lock_period_sec = lock_period_sec;
+        Self::store_global_config(global_config, global_config_account)
+    }
+
+    fn process_set_transaction_limit(
+        accounts: &[AccountInfo],
+        transaction_limit: u64,
+        compliance_signer: Pubkey,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(authority_account)?;
+        Self::require_writable(global_config_account)?;
+
+        let mut global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+        if transaction_limit > 0 && compliance_signer == Pubkey::default() {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+
+        Self::grow_global_config(
+            authority_account,
+            global_config_account,
+            system_program,
+            rent,
+        )?;
+        global_config.transaction_limit = transaction_limit;
+        global_config.compliance_signer = compliance_signer;
+        Self::store_global_config(global_config, global_config_account)
+    }
+
+    fn process_blacklist_mint(
+        accounts: &[AccountInfo],
+        mint: Pubkey,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let mint_blacklist_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(authority_account)?;
+        Self::require_writable(global_config_account)?;
+        Self::require_writable(mint_blacklist_account)?;
+
+        let mut global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+        let (mint_blacklist_pda, blacklist_bump_seed) =
+            Pubkey::find_program_address(&[b"blacklist", mint.as_ref()], program_id);
+        if mint_blacklist_pda != *mint_blacklist_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !mint_blacklist_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        Self::create_pda_account(
+            authority_account,
+            mint_blacklist_account,
+            system_program,
+            rent,
+            MintBlacklist::LEN,
+            &[b"blacklist", mint.as_ref(), &[blacklist_bump_seed]],
+            program_id,
+        )?;
+        let mint_blacklist = MintBlacklist {
+            is_initialized: true,
+            mint,
+        };
+        MintBlacklist::pack(mint_blacklist, &mut mint_blacklist_account.try_borrow_mut_data()?)?;
+
+        Self::grow_global_config(
+            authority_account,
+            global_config_account,
+            system_program,
+            rent,
+        )?;
+        global_config.mint_blacklist_count = global_config
+            .mint_blacklist_count
+            .checked_add(1)
+            .ok_or(AuctionError::AmountOverflow)?;
+        Self::store_global_config(global_config, global_config_account)
+    }
+
+    fn process_unblacklist_mint(
+        accounts: &[AccountInfo],
+        mint: Pubkey,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let mint_blacklist_account = next_account_info(account_info_iter)?;
+        Self::require_writable(authority_account)?;
+        Self::require_writable(global_config_account)?;
+        Self::require_writable(mint_blacklist_account)?;
+
+        let mut global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+        if !Self::is_mint_blacklisted(mint_blacklist_account, &mint, program_id)? {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+
+        msg!("Closing the mint blacklist account...");
+        **authority_account.try_borrow_mut_lamports()? = authority_account
+            .lamports()
+            .checked_add(mint_blacklist_account.lamports())
+            .ok_or(AuctionError::AmountOverflow)?;
+        **mint_blacklist_account.try_borrow_mut_lamports()? = 0;
+        mint_blacklist_account.try_borrow_mut_data()?.fill(0);
+
+        global_config.mint_blacklist_count = global_config
+            .mint_blacklist_count
+            .checked_sub(1)
+            .ok_or(AuctionError::AmountOverflow)?;
+        Self::store_global_config(global_config, global_config_account)
+    }
+
+    fn process_global_ban_bidder(
+        accounts: &[AccountInfo],
+        bidder: Pubkey,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let global_ban_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(authority_account)?;
+        Self::require_writable(global_config_account)?;
+        Self::require_writable(global_ban_account)?;
+
+        let mut global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+        let (global_ban_pda, ban_bump_seed) =
+            Pubkey::find_program_address(&[b"global_ban", bidder.as_ref()], program_id);
+        if global_ban_pda != *global_ban_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !global_ban_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        Self::create_pda_account(
+            authority_account,
+            global_ban_account,
+            system_program,
+            rent,
+            GlobalBanRecord::LEN,
+            &[b"global_ban", bidder.as_ref(), &[ban_bump_seed]],
+            program_id,
+        )?;
+        let global_ban = GlobalBanRecord {
+            is_initialized: true,
+            bidder,
+        };
+        GlobalBanRecord::pack(global_ban, &mut global_ban_account.try_borrow_mut_data()?)?;
+
+        Self::grow_global_config(
+            authority_account,
+            global_config_account,
+            system_program,
+            rent,
+        )?;
+        global_config.global_ban_count = global_config
+            .global_ban_count
+            .checked_add(1)
+            .ok_or(AuctionError::AmountOverflow)?;
+        Self::store_global_config(global_config, global_config_account)
+    }
+
+    fn process_global_unban_bidder(
+        accounts: &[AccountInfo],
+        bidder: Pubkey,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let global_ban_account = next_account_info(account_info_iter)?;
+        Self::require_writable(authority_account)?;
+        Self::require_writable(global_config_account)?;
+        Self::require_writable(global_ban_account)?;
+
+        let mut global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+        if !Self::is_globally_banned(global_ban_account, &bidder, program_id)? {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+
+        msg!("Closing the global ban account...");
+        **authority_account.try_borrow_mut_lamports()? = authority_account
+            .lamports()
+            .checked_add(global_ban_account.lamports())
+            .ok_or(AuctionError::AmountOverflow)?;
+        **global_ban_account.try_borrow_mut_lamports()? = 0;
+        global_ban_account.try_borrow_mut_data()?.fill(0);
+
+        global_config.global_ban_count = global_config
+            .global_ban_count
+            .checked_sub(1)
+            .ok_or(AuctionError::AmountOverflow)?;
+        Self::store_global_config(global_config, global_config_account)
+    }
+
+    fn process_set_exhibitor_min_duration(
+        accounts: &[AccountInfo],
+        min_sec: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+        if i64::try_from(min_sec).is_err() {
+            return Err(AuctionError::AmountOverflow.into());
+        }
+
+        let exhibitor_config_account = next_account_info(account_info_iter)?;
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(exhibitor_config_account)?;
+
+        let (exhibitor_config_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"exhibitor_config", accouint_of_exhibitor.key.as_ref()],
+            program_id,
+        );
+        if exhibitor_config_pda != *exhibitor_config_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if exhibitor_config_account.data_is_empty() {
+            Self::require_writable(authority_account)?;
+            msg!("Creating the Exhibitor config...");
+            Self::create_pda_account(
+                authority_account,
+                exhibitor_config_account,
+                system_program,
+                rent,
+                ExhibitorConfig::LEN,
+                &[
+                    b"exhibitor_config",
+                    accouint_of_exhibitor.key.as_ref(),
+                    &[bump_seed],
+                ],
+                program_id,
+            )?;
+        }
+
+        let exhibitor_config = ExhibitorConfig {
+            is_initialized: true,
+            min_duration_sec: min_sec,
+        };
+        ExhibitorConfig::pack(
+            exhibitor_config,
+            &mut exhibitor_config_account.try_borrow_mut_data()?,
+        )?;
+        Ok(())
+    }
+
+    fn process_leave_feedback(
+        accounts: &[AccountInfo],
+        for_auction: Pubkey,
+        score: u8,
+        comment_hash: [u8; 32],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let reviewer_account = next_account_info(account_info_iter)?;
+
+        if !reviewer_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let tombstone_account = next_account_info(account_info_iter)?;
+        let feedback_account = next_account_info(account_info_iter)?;
+        let user_profile_account = next_account_info(account_info_iter)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(reviewer_account)?;
+        Self::require_writable(feedback_account)?;
+        Self::require_writable(user_profile_account)?;
+
+        if !(1..=5).contains(&score) {
+            return Err(AuctionError::InvalidScore.into());
+        }
+
+        if tombstone_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let (tombstone_pda, _bump_seed) =
+            Pubkey::find_program_address(&[b"tombstone", for_auction.as_ref()], program_id);
+        if tombstone_pda != *tombstone_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let tombstone = TombstoneRecord::unpack(&tombstone_account.try_borrow_data()?)?;
+        let reviewed = if tombstone.winner == *reviewer_account.key {
+            tombstone.exhibitor
+        } else if tombstone.exhibitor == *reviewer_account.key {
+            tombstone.winner
+        } else {
+            return Err(AuctionError::Unauthorized.into());
+        };
+        let feedback_deadline = tombstone
+            .closed_at
+            .checked_add(FEEDBACK_WINDOW_SEC)
+            .ok_or(AuctionError::AmountOverflow)?;
+        if clock.unix_timestamp > feedback_deadline {
+            return Err(AuctionError::FeedbackWindowClosed.into());
+        }
+
+        let (feedback_pda, feedback_bump_seed) = Pubkey::find_program_address(
+            &[b"feedback", for_auction.as_ref(), reviewer_account.key.as_ref()],
+            program_id,
+        );
+        if feedback_pda != *feedback_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !feedback_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        Self::create_pda_account(
+            reviewer_account,
+            feedback_account,
+            system_program,
+            rent,
+            FeedbackRecord::LEN,
+            &[
+                b"feedback",
+                for_auction.as_ref(),
+                reviewer_account.key.as_ref(),
+                &[feedback_bump_seed],
+            ],
+            program_id,
+        )?;
+        let feedback = FeedbackRecord {
+            is_initialized: true,
+            reviewer: *reviewer_account.key,
+            score,
+            comment_hash,
+        };
+        FeedbackRecord::pack(feedback, &mut feedback_account.try_borrow_mut_data()?)?;
+
+        let (user_profile_pda, profile_bump_seed) =
+            Pubkey::find_program_address(&[b"user_profile", reviewed.as_ref()], program_id);
+        if user_profile_pda != *user_profile_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if user_profile_account.data_is_empty() {
+            msg!("Creating the User profile...");
+            Self::create_pda_account(
+                reviewer_account,
+                user_profile_account,
+                system_program,
+                rent,
+                UserProfile::LEN,
+                &[b"user_profile", reviewed.as_ref(), &[profile_bump_seed]],
+                program_id,
+            )?;
+        }
+        let mut user_profile =
+            UserProfile::unpack_unchecked(&user_profile_account.try_borrow_data()?)?;
+        user_profile.is_initialized = true;
+        user_profile
+            .add_score(score)
+            .ok_or(AuctionError::AmountOverflow)?;
+        UserProfile::pack(user_profile, &mut user_profile_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_init_auction_room(
+        accounts: &[AccountInfo],
+        room_id: u64,
+        fee_bps: u16,
+        whitelist_root: [u8; 32],
+        room_description: [u8; ROOM_DESCRIPTION_LEN],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let organizer_account = next_account_info(account_info_iter)?;
+
+        if !organizer_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let room_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(organizer_account)?;
+        Self::require_writable(room_account)?;
+
+        if fee_bps > MAX_DEDUCTION_BPS {
+            return Err(AuctionError::InvalidFeeBasisPoints.into());
+        }
+        let (room_pda, bump_seed) =
+            Pubkey::find_program_address(&[b"room", &room_id.to_le_bytes()], program_id);
+        if room_pda != *room_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !room_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        Self::create_pda_account(
+            organizer_account,
+            room_account,
+            system_program,
+            rent,
+            AuctionRoom::LEN,
+            &[b"room", &room_id.to_le_bytes(), &[bump_seed]],
+            program_id,
+        )?;
+        let room = AuctionRoom {
+            is_initialized: true,
+            room_id,
+            organizer: *organizer_account.key,
+            fee_bps,
+            whitelist_root,
+            room_description,
+        };
+        AuctionRoom::pack(room, &mut room_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_close_auction_room(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let organizer_account = next_account_info(account_info_iter)?;
+
+        if !organizer_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let room_account = next_account_info(account_info_iter)?;
+        Self::require_writable(organizer_account)?;
+        Self::require_writable(room_account)?;
+
+        let room = Self::load_auction_room(room_account, program_id)?;
+        if room.organizer != *organizer_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+
+        **organizer_account.try_borrow_mut_lamports()? = organizer_account
+            .lamports()
+            .checked_add(room_account.lamports())
+            .ok_or(AuctionError::AmountOverflow)?;
+        **room_account.try_borrow_mut_lamports()? = 0;
+        room_account.try_borrow_mut_data()?.fill(0);
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn process_set_escrow_authority(
+        accounts: &[AccountInfo],
+        old_program_id: Pubkey,
+        new_program_id: Pubkey,
+        new_pda: Pubkey,
+        new_pda_bump: u8,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account = next_account_info(account_info_iter)?;
+
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let global_config_account = next_account_info(account_info_iter)?;
+        let global_config = Self::load_global_config(global_config_account, program_id)?;
+        if global_config.authority != *authority_account.key {
+            return Err(AuctionError::Unauthorized.into());
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        if escrow_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        // The escrowed accounts of a migrated auction are no longer owned by the PDA of this program
+        if auction_info.is_migrated() {
+            return Err(AuctionError::EscrowMigrated.into());
+        }
+        if old_program_id != *program_id || new_program_id == *program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        changed.record(escrow_account.key, &auction_info)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        let program_of_token = next_account_info(account_info_iter)?;
+
+        let new_authority_account = next_account_info(account_info_iter)?;
+        if !new_authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        let new_program_data_account = next_account_info(account_info_iter)?;
+        let (new_program_data, _) = Pubkey::find_program_address(
+            &[new_program_id.as_ref()],
+            &bpf_loader_upgradeable::id(),
+        );
+        if new_program_data != *new_program_data_account.key
+            || *new_program_data_account.owner != bpf_loader_upgradeable::id()
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let upgrade_authority = match limited_deserialize(
+            &new_program_data_account.try_borrow_data()?,
+            UpgradeableLoaderState::size_of_programdata_metadata() as u64,
+        )
+        .map_err(|_| ProgramError::InvalidAccountData)?
+        {
+            UpgradeableLoaderState::ProgramData {
+                upgrade_authority_address,
+                ..
+            } => upgrade_authority_address,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        if upgrade_authority != Some(*new_authority_account.key) {
+            return Err(AuctionError::Unauthorized.into());
+        }
+        if Pubkey::create_program_address(&[b"escrow", &[new_pda_bump]], &new_program_id)?
+            != new_pda
+        {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        Self::require_writable(exhibiting_nft_temp_account)?;
+
+        if auction_info.exhibiting_nft_temp_pubkey != *exhibiting_nft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut escrowed_accounts = vec![exhibiting_nft_temp_account];
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            let highest_bidder_ft_temp_account = next_account_info(account_info_iter)?;
+            Self::require_writable(highest_bidder_ft_temp_account)?;
+            if auction_info.highest_bidder_ft_temp_pubkey != *highest_bidder_ft_temp_account.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            escrowed_accounts.push(highest_bidder_ft_temp_account);
+        }
+
+        let bump_seed = auction_info.pda_bump;
+        let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+        if pda != *pda_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+        for escrowed_account in escrowed_accounts {
+            let owner_change_ix = spl_token::instruction::set_authority(
+                program_of_token.key,
+                escrowed_account.key,
+                Some(&new_pda),
+                spl_token::instruction::AuthorityType::AccountOwner,
+                &pda,
+                &[],
+            )?;
+            msg!("Changing ownership of the escrowed token account...");
+            invoke_signed(
+                &owner_change_ix,
+                &[
+                    escrowed_account.clone(),
+                    pda_account.clone(),
+                    program_of_token.clone(),
+                ],
+                signers_seeds,
+            )?;
+        }
+
+        auction_info.migrated_to = new_program_id;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_deposit_bonus(
+        accounts: &[AccountInfo],
+        amount: u64,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let sponsor_account = next_account_info(account_info_iter)?;
+
+        if !sponsor_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let sponsor_ft_account = next_account_info(account_info_iter)?;
+        let escrow_account = next_account_info(account_info_iter)?;
+        let bonus_vault_account = next_account_info(account_info_iter)?;
+        let bonus_deposit_account = next_account_info(account_info_iter)?;
+        let ft_mint_account = next_account_info(account_info_iter)?;
+        let program_of_token = next_account_info(account_info_iter)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(sponsor_account)?;
+        Self::require_writable(sponsor_ft_account)?;
+        Self::require_writable(escrow_account)?;
+        Self::require_writable(bonus_vault_account)?;
+        Self::require_writable(bonus_deposit_account)?;
+
+        if escrow_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        if auction_info.end_at <= clock.unix_timestamp {
+            return Err(AuctionError::InactiveAuction.into());
+        }
+        if auction_info.ft_mint != *ft_mint_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if amount == 0 {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+
+        let (bonus_vault_pda, bonus_bump_seed) =
+            Pubkey::find_program_address(&[b"bonus", escrow_account.key.as_ref()], program_id);
+        if bonus_vault_pda != *bonus_vault_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if bonus_vault_account.data_is_empty() {
+            msg!("Creating the Bonus Vault...");
+            Self::create_pda_account(
+                sponsor_account,
+                bonus_vault_account,
+                system_program,
+                rent,
+                TokenAccount::LEN,
+                &[b"bonus", escrow_account.key.as_ref(), &[bonus_bump_seed]],
+                program_of_token.key,
+            )?;
+            let init_vault_ix = spl_token::instruction::initialize_account3(
+                program_of_token.key,
+                bonus_vault_account.key,
+                ft_mint_account.key,
+                &bonus_vault_pda,
+            )?;
+            invoke(
+                &init_vault_ix,
+                &[
+                    bonus_vault_account.clone(),
+                    ft_mint_account.clone(),
+                    program_of_token.clone(),
+                ],
+            )?;
+        }
+
+        let (bonus_deposit_pda, deposit_bump_seed) = Pubkey::find_program_address(
+            &[
+                b"bonus_deposit",
+                escrow_account.key.as_ref(),
+                sponsor_account.key.as_ref(),
+            ],
+            program_id,
+        );
+        if bonus_deposit_pda != *bonus_deposit_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if bonus_deposit_account.data_is_empty() {
+            msg!("Creating the Bonus deposit...");
+            Self::create_pda_account(
+                sponsor_account,
+                bonus_deposit_account,
+                system_program,
+                rent,
+                BonusDeposit::LEN,
+                &[
+                    b"bonus_deposit",
+                    escrow_account.key.as_ref(),
+                    sponsor_account.key.as_ref(),
+                    &[deposit_bump_seed],
+                ],
+                program_id,
+            )?;
+        }
+        let mut bonus_deposit =
+            BonusDeposit::unpack_unchecked(&bonus_deposit_account.try_borrow_data()?)?;
+        bonus_deposit.is_initialized = true;
+        bonus_deposit.amount = bonus_deposit
+            .amount
+            .checked_add(amount)
+            .ok_or(AuctionError::AmountOverflow)?;
+        BonusDeposit::pack(bonus_deposit, &mut bonus_deposit_account.try_borrow_mut_data()?)?;
+
+        auction_info.bonus_amount = auction_info
+            .bonus_amount
+            .checked_add(amount)
+            .ok_or(AuctionError::AmountOverflow)?;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+
+        let deposit_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            sponsor_ft_account.key,
+            bonus_vault_account.key,
+            sponsor_account.key,
+            &[],
+            amount,
+        )?;
+        msg!("Transferring the bonus to the Bonus Vault...");
+        invoke(
+            &deposit_ix,
+            &[
+                sponsor_ft_account.clone(),
+                bonus_vault_account.clone(),
+                sponsor_account.clone(),
+                program_of_token.clone(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn process_reclaim_bonus(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let sponsor_account = next_account_info(account_info_iter)?;
+
+        if !sponsor_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let sponsor_ft_account = next_account_info(account_info_iter)?;
+        let escrow_account = next_account_info(account_info_iter)?;
+        let bonus_vault_account = next_account_info(account_info_iter)?;
+        let bonus_deposit_account = next_account_info(account_info_iter)?;
+        let program_of_token = next_account_info(account_info_iter)?;
+        Self::require_writable(sponsor_account)?;
+        Self::require_writable(sponsor_ft_account)?;
+        Self::require_writable(bonus_vault_account)?;
+        Self::require_writable(bonus_deposit_account)?;
+
+        // A closed auction pays the whole vault to the winner, so a remaining deposit means it was
+        // cancelled, or migrated to a program that can't sign for the vault
+        Self::require_settled(escrow_account, program_id)?;
+
+        let (bonus_vault_pda, bonus_bump_seed) =
+            Pubkey::find_program_address(&[b"bonus", escrow_account.key.as_ref()], program_id);
+        if bonus_vault_pda != *bonus_vault_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let (bonus_deposit_pda, _bump_seed) = Pubkey::find_program_address(
+            &[
+                b"bonus_deposit",
+                escrow_account.key.as_ref(),
+                sponsor_account.key.as_ref(),
+            ],
+            program_id,
+        );
+        if bonus_deposit_pda != *bonus_deposit_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if bonus_deposit_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let bonus_deposit = BonusDeposit::unpack(&bonus_deposit_account.try_borrow_data()?)?;
+
+        let reclaim_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            bonus_vault_account.key,
+            sponsor_ft_account.key,
+            &bonus_vault_pda,
+            &[],
+            bonus_deposit.amount,
+        )?;
+        msg!("Returning the bonus deposit to the sponsor...");
+        invoke_signed(
+            &reclaim_ix,
+            &[
+                bonus_vault_account.clone(),
+                sponsor_ft_account.clone(),
+                bonus_vault_account.clone(),
+                program_of_token.clone(),
+            ],
+            &[&[b"bonus", escrow_account.key.as_ref(), &[bonus_bump_seed]]],
+        )?;
+
+        **sponsor_account.try_borrow_mut_lamports()? = sponsor_account
+            .lamports()
+            .checked_add(bonus_deposit_account.lamports())
+            .ok_or(AuctionError::AmountOverflow)?;
+        **bonus_deposit_account.try_borrow_mut_lamports()? = 0;
+        bonus_deposit_account.try_borrow_mut_data()?.fill(0);
+        Ok(())
+    }
+
+    fn process_set_exhibitor_collateral(
+        accounts: &[AccountInfo],
+        amount: u64,
+        cancel_penalty_bps: u16,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let exhibitor_ft_account = next_account_info(account_info_iter)?;
+        let escrow_account = next_account_info(account_info_iter)?;
+        let collateral_vault_account = next_account_info(account_info_iter)?;
+        let ft_mint_account = next_account_info(account_info_iter)?;
+        let program_of_token = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(accouint_of_exhibitor)?;
+        Self::require_writable(exhibitor_ft_account)?;
+        Self::require_writable(escrow_account)?;
+        Self::require_writable(collateral_vault_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+        if auction_info.ft_mint != *ft_mint_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if amount == 0 {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        if cancel_penalty_bps > MAX_BASIS_POINTS {
+            return Err(AuctionError::InvalidFeeBasisPoints.into());
+        }
+
+        let (collateral_vault_pda, collateral_bump_seed) = Pubkey::find_program_address(
+            &[
+                b"collateral",
+                escrow_account.key.as_ref(),
+                accouint_of_exhibitor.key.as_ref(),
+            ],
+            program_id,
+        );
+        if collateral_vault_pda != *collateral_vault_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if collateral_vault_account.data_is_empty() {
+            msg!("Creating the Collateral Vault...");
+            Self::create_pda_account(
+                accouint_of_exhibitor,
+                collateral_vault_account,
+                system_program,
+                rent,
+                TokenAccount::LEN,
+                &[
+                    b"collateral",
+                    escrow_account.key.as_ref(),
+                    accouint_of_exhibitor.key.as_ref(),
+                    &[collateral_bump_seed],
+                ],
+                program_of_token.key,
+            )?;
+            let init_vault_ix = spl_token::instruction::initialize_account3(
+                program_of_token.key,
+                collateral_vault_account.key,
+                ft_mint_account.key,
+                &collateral_vault_pda,
+            )?;
+            invoke(
+                &init_vault_ix,
+                &[
+                    collateral_vault_account.clone(),
+                    ft_mint_account.clone(),
+                    program_of_token.clone(),
+                ],
+            )?;
+        }
+
+        auction_info.exhibitor_collateral = auction_info
+            .exhibitor_collateral
+            .checked_add(amount)
+            .ok_or(AuctionError::AmountOverflow)?;
+        auction_info.cancel_penalty_bps = cancel_penalty_bps;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+
+        let deposit_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            exhibitor_ft_account.key,
+            collateral_vault_account.key,
+            accouint_of_exhibitor.key,
+            &[],
+            amount,
+        )?;
+        msg!("Transferring the collateral to the Collateral Vault...");
+        invoke(
+            &deposit_ix,
+            &[
+                exhibitor_ft_account.clone(),
+                collateral_vault_account.clone(),
+                accouint_of_exhibitor.clone(),
+                program_of_token.clone(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn process_reclaim_collateral(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let exhibitor_ft_account = next_account_info(account_info_iter)?;
+        let escrow_account = next_account_info(account_info_iter)?;
+        let collateral_vault_account = next_account_info(account_info_iter)?;
+        let program_of_token = next_account_info(account_info_iter)?;
+        Self::require_writable(accouint_of_exhibitor)?;
+        Self::require_writable(exhibitor_ft_account)?;
+        Self::require_writable(collateral_vault_account)?;
+
+        // The collateral stays locked until the auction is closed, cancelled or migrated
+        Self::require_settled(escrow_account, program_id)?;
+
+        let (collateral_vault_pda, collateral_bump_seed) = Pubkey::find_program_address(
+            &[
+                b"collateral",
+                escrow_account.key.as_ref(),
+                accouint_of_exhibitor.key.as_ref(),
+            ],
+            program_id,
+        );
+        if collateral_vault_pda != *collateral_vault_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let signers_seeds: &[&[&[u8]]] = &[&[
+            b"collateral",
+            escrow_account.key.as_ref(),
+            accouint_of_exhibitor.key.as_ref(),
+            &[collateral_bump_seed],
+        ]];
+
+        let collateral_vault_data =
+            TokenAccount::unpack(&collateral_vault_account.try_borrow_data()?)?;
+        let reclaim_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            collateral_vault_account.key,
+            exhibitor_ft_account.key,
+            &collateral_vault_pda,
+            &[],
+            collateral_vault_data.amount,
+        )?;
+        msg!("Returning the collateral to the exhibitor...");
+        invoke_signed(
+            &reclaim_ix,
+            &[
+                collateral_vault_account.clone(),
+                exhibitor_ft_account.clone(),
+                collateral_vault_account.clone(),
+                program_of_token.clone(),
+            ],
+            signers_seeds,
+        )?;
+
+        let close_vault_ix = spl_token::instruction::close_account(
+            program_of_token.key,
+            collateral_vault_account.key,
+            accouint_of_exhibitor.key,
+            &collateral_vault_pda,
+            &[],
+        )?;
+        msg!("Closing the Collateral Vault...");
+        invoke_signed(
+            &close_vault_ix,
+            &[
+                collateral_vault_account.clone(),
+                accouint_of_exhibitor.clone(),
+                collateral_vault_account.clone(),
+                program_of_token.clone(),
+            ],
+            signers_seeds,
+        )?;
+        Ok(())
+    }
+
+    fn process_enable_cross_program(
+        accounts: &[AccountInfo],
+        cpi_authority: Pubkey,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        auction_info.cross_program_bid = cpi_authority != Pubkey::default();
+        auction_info.cpi_authority = cpi_authority;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_finality_timeout(
+        accounts: &[AccountInfo],
+        finality_timeout_sec: u64,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+        if i64::try_from(finality_timeout_sec).is_err() {
+            return Err(AuctionError::AmountOverflow.into());
+        }
+
+        auction_info.finality_timeout_sec = finality_timeout_sec;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_raise_dispute(
+        accounts: &[AccountInfo],
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let winner_account = next_account_info(account_info_iter)?;
+
+        if !winner_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+
+        if auction_info.highest_bidder_pubkey != *winner_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.finalized_at == 0 {
+            return Err(AuctionError::ActiveAuction.into());
+        }
+        if clock.unix_timestamp >= auction_info.finalized_at {
+            return Err(AuctionError::DisputeWindowClosed.into());
+        }
+        if auction_info.mediator_pubkey == Pubkey::default() {
+            return Err(AuctionError::MediatorRequired.into());
+        }
+
+        auction_info.is_disputed = true;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_finalize_settlement(
+        accounts: &[AccountInfo],
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let caller_account = next_account_info(account_info_iter)?;
+
+        if !caller_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        let exhibitor_ft_receiving_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_temp_account = next_account_info(account_info_iter)?;
+        let winner_account = next_account_info(account_info_iter)?;
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(accouint_of_exhibitor)?;
+        Self::require_writable(exhibiting_nft_temp_account)?;
+        Self::require_writable(exhibitor_ft_receiving_account)?;
+        Self::require_writable(highest_bidder_ft_temp_account)?;
+        Self::require_writable(winner_account)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+        let program_of_token = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+
+        if auction_info.finalized_at == 0
+            || clock.unix_timestamp < auction_info.finalized_at
+            || auction_info.audit_pending
+        {
+            return Err(AuctionError::SettlementPending.into());
+        }
+        if auction_info.is_disputed {
+            let mediator_account = next_account_info(account_info_iter)?;
+            if !mediator_account.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if auction_info.mediator_pubkey != *mediator_account.key {
+                return Err(AuctionError::Unauthorized.into());
+            }
+        }
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.exhibiting_nft_temp_pubkey != *exhibiting_nft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !Self::is_exhibitor_ft_receiving(
+            &auction_info,
+            highest_bidder_ft_temp_account,
+            exhibitor_ft_receiving_account,
+        )? {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_ft_temp_pubkey != *highest_bidder_ft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != *winner_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let bump_seed = auction_info.pda_bump;
+        let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+        let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+
+        Self::transfer_proceeds(
+            program_of_token,
+            highest_bidder_ft_temp_account,
+            exhibitor_ft_receiving_account,
+            account_info_iter.as_slice(),
+            &auction_info,
+            pda_account,
+            signers_seeds,
+        )?;
+
+        auction_info.is_finalized = true;
+        let is_audited = auction_info.audit_oracle != Pubkey::default();
+        auction_info.audit_pending = is_audited;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+
+        Self::close_temporary_ft(
+            program_of_token,
+            highest_bidder_ft_temp_account,
+            winner_account,
+            pda,
+            pda_account,
+            signers_seeds,
+        )?;
+        if is_audited {
+            return Ok(());
+        }
+
+        Self::escrow_is_closing(
+            program_of_token,
+            exhibiting_nft_temp_account,
+            accouint_of_exhibitor,
+            pda,
+            pda_account,
+            escrow_account,
+            signers_seeds,
+        )
+    }
+
+    fn process_set_fractional_ownership(
+        accounts: &[AccountInfo],
+        owners: &[(Pubkey, u16)],
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+        if owners.len() > MAX_FRACTIONAL_OWNERS {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        // Owners' FT accounts are in the auction FT mint
+        if !owners.is_empty() && auction_info.accepted_mints != [Pubkey::default(); MAX_ACCEPTED_MINTS] {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        let total_bps: u32 = owners.iter().map(|(_, share_bps)| u32::from(*share_bps)).sum();
+        if !owners.is_empty()
+            && (total_bps != u32::from(MAX_BASIS_POINTS)
+                || owners.iter().any(|(_, share_bps)| *share_bps == 0))
+        {
+            return Err(AuctionError::InvalidOwnerShares.into());
+        }
+
+        auction_info.fractional_owners = [(Pubkey::default(), 0); MAX_FRACTIONAL_OWNERS];
+        auction_info.fractional_owners[..owners.len()].copy_from_slice(owners);
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    /// Pays the escrowed FT to the exhibitor, or splits it across the FT accounts of the fractional owners
+    /// The last owner receives the rounding remainder
+    fn transfer_proceeds<'a, 'b>(
+        program_of_token: &'a AccountInfo<'b>,
+        highest_bidder_ft_temp_account: &'a AccountInfo<'b>,
+        exhibitor_ft_receiving_account: &'a AccountInfo<'b>,
+        owner_ft_accounts: &'a [AccountInfo<'b>],
+        auction_info: &Auction,
+        pda_account: &'a AccountInfo<'b>,
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let proceeds = TokenAccount::unpack(&highest_bidder_ft_temp_account.try_borrow_data()?)?.amount;
+        let owners: Vec<_> = auction_info
+            .fractional_owners
+            .iter()
+            .filter(|(_, share_bps)| *share_bps > 0)
+            .collect();
+
+        let mut payouts = Vec::with_capacity(owners.len().max(1));
+        if owners.is_empty() {
+            payouts.push((exhibitor_ft_receiving_account, proceeds));
+        } else {
+            if owner_ft_accounts.len() < owners.len() {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            }
+            let mut remaining = proceeds;
+            for (i, ((owner_ft_pubkey, share_bps), owner_ft_account)) in
+                owners.iter().zip(owner_ft_accounts).enumerate()
+            {
+                if *owner_ft_pubkey != *owner_ft_account.key {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                Self::require_writable(owner_ft_account)?;
+                let share = if i + 1 == owners.len() {
+                    remaining
+                } else {
+                    Self::basis_points_of(proceeds, *share_bps)?
+                };
+                remaining = remaining
+                    .checked_sub(share)
+                    .ok_or(AuctionError::AmountOverflow)?;
+                payouts.push((owner_ft_account, share));
+            }
+        }
+
+        for (receiving_account, amount) in payouts {
+            let transfer_ft_to_exhibitor_ix = spl_token::instruction::transfer(
+                program_of_token.key,
+                highest_bidder_ft_temp_account.key,
+                receiving_account.key,
+                pda_account.key,
+                &[],
+                amount,
+            )?;
+            msg!("Transferring FT to the Exhibitor...");
+            invoke_signed(
+                &transfer_ft_to_exhibitor_ix,
+                &[
+                    highest_bidder_ft_temp_account.clone(),
+                    receiving_account.clone(),
+                    pda_account.clone(),
+                    program_of_token.clone(),
+                ],
+                signers_seeds,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn process_set_bid_token_gate(
+        accounts: &[AccountInfo],
+        gate_token_mint: Pubkey,
+        gate_token_min_amount: u64,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        auction_info.gate_token_mint = gate_token_mint;
+        auction_info.gate_token_min_amount = gate_token_min_amount;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_accepted_currencies(
+        accounts: &[AccountInfo],
+        accepted_mints: [Pubkey; MAX_ACCEPTED_MINTS],
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+        // The proceeds of a fractionally owned NFT are split in the auction FT mint only
+        if auction_info.fractional_owners[0].1 > 0 {
+            return Err(AuctionError::Unauthorized.into());
+        }
+
+        let mut accepted_ft_receiving_pubkeys = [Pubkey::default(); MAX_ACCEPTED_MINTS];
+        for (i, accepted_mint) in accepted_mints.iter().enumerate() {
+            if *accepted_mint == Pubkey::default() {
+                continue;
+            }
+            if *accepted_mint == auction_info.ft_mint || accepted_mints[..i].contains(accepted_mint) {
+                return Err(AuctionError::InvalidInstruction.into());
+            }
+            let exhibitor_ft_receiving_account = next_account_info(account_info_iter)?;
+            let exhibitor_ft_receiving_account_data =
+                TokenAccount::unpack(&exhibitor_ft_receiving_account.try_borrow_data()?)?;
+            if exhibitor_ft_receiving_account_data.mint != *accepted_mint {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            accepted_ft_receiving_pubkeys[i] = *exhibitor_ft_receiving_account.key;
+        }
+
+        auction_info.accepted_mints = accepted_mints;
+        auction_info.accepted_ft_receiving_pubkeys = accepted_ft_receiving_pubkeys;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_bid_in_currency(
+        accounts: &[AccountInfo],
+        price: u64,
+        bid_mint: Pubkey,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let base_price_oracle_account = next_account_info(account_info_iter)?;
+        let bid_price_oracle_account = next_account_info(account_info_iter)?;
+        let bid_accounts = BidAccounts::from_accounts(account_info_iter.as_slice())?;
+        let escrow_account = bid_accounts.escrow_account;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+
+        if bid_mint == auction_info.ft_mint || auction_info.ft_receiving_pubkey(&bid_mint).is_none() {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        let base_price_oracle =
+            Self::load_price_oracle(base_price_oracle_account, &auction_info.ft_mint, program_id)?;
+        let bid_price_oracle =
+            Self::load_price_oracle(bid_price_oracle_account, &bid_mint, program_id)?;
+        if bid_price_oracle.price == 0 {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+
+        Self::process_bid(
+            &bid_accounts,
+            price,
+            None,
+            &[],
+            Some(BidCurrency {
+                mint: bid_mint,
+                base_price: base_price_oracle.price,
+                bid_price: bid_price_oracle.price,
+            }),
+            BidOrigin::Direct,
+            changed,
+            program_id,
+        )
+    }
+
+    fn process_commit_bid_hash(
+        accounts: &[AccountInfo],
+        hash: [u8; 32],
+        slot: u64,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let bidder_account = next_account_info(account_info_iter)?;
+
+        if !bidder_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let pending_bid_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(bidder_account)?;
+        Self::require_writable(pending_bid_account)?;
+
+        if auction_info.end_at <= clock.unix_timestamp {
+            return Err(AuctionError::InactiveAuction.into());
+        }
+        // The slot hash must already be known when committing
+        if slot >= clock.slot {
+            return Err(AuctionError::StaleSlotHash.into());
+        }
+
+        let (pending_bid_pda, bump_seed) = Pubkey::find_program_address(
+            &[
+                b"pending_bid",
+                escrow_account.key.as_ref(),
+                bidder_account.key.as_ref(),
+            ],
+            program_id,
+        );
+        if pending_bid_pda != *pending_bid_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if pending_bid_account.data_is_empty() {
+            Self::create_pda_account(
+                bidder_account,
+                pending_bid_account,
+                system_program,
+                rent,
+                PendingBid::LEN,
+                &[
+                    b"pending_bid",
+                    escrow_account.key.as_ref(),
+                    bidder_account.key.as_ref(),
+                    &[bump_seed],
+                ],
+                program_id,
+            )?;
+        } else if pending_bid_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let pending_bid = PendingBid {
+            is_initialized: true,
+            hash,
+            slot,
+        };
+        PendingBid::pack(pending_bid, &mut pending_bid_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_submit_bid(
+        accounts: &[AccountInfo],
+        price: u64,
+        salt: &[u8; 32],
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let pending_bid_account = next_account_info(account_info_iter)?;
+        let slot_hashes_account = next_account_info(account_info_iter)?;
+        let bid_accounts = BidAccounts::from_accounts(account_info_iter.as_slice())?;
+        let bidder_account = bid_accounts.bidder;
+        let escrow_account = bid_accounts.escrow_account;
+        Self::require_writable(pending_bid_account)?;
+
+        let (pending_bid_pda, _bump_seed) = Pubkey::find_program_address(
+            &[
+                b"pending_bid",
+                escrow_account.key.as_ref(),
+                bidder_account.key.as_ref(),
+            ],
+            program_id,
+        );
+        if pending_bid_pda != *pending_bid_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if pending_bid_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let pending_bid = PendingBid::unpack(&pending_bid_account.try_borrow_data()?)?;
+
+        let slot_hash = Self::find_slot_hash(slot_hashes_account, pending_bid.slot)?
+            .ok_or(AuctionError::StaleSlotHash)?;
+        if hashv(&[&price.to_le_bytes(), salt, &slot_hash]).to_bytes() != pending_bid.hash {
+            return Err(AuctionError::BidCommitmentMismatch.into());
+        }
+
+        Self::process_bid(
+            &bid_accounts,
+            price,
+            None,
+            &[],
+            None,
+            BidOrigin::Commitment,
+            changed,
+            program_id,
+        )?;
+
+        msg!("Closing the pending bid...");
+        **bidder_account.try_borrow_mut_lamports()? = bidder_account
+            .lamports()
+            .checked_add(pending_bid_account.lamports())
+            .ok_or(AuctionError::AmountOverflow)?;
+        **pending_bid_account.try_borrow_mut_lamports()? = 0;
+        pending_bid_account.try_borrow_mut_data()?.fill(0);
+        Ok(())
+    }
+
+    fn process_bid_with_fee_payer(
+        accounts: &[AccountInfo],
+        price: u64,
+        nonce: u64,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let bid_accounts = BidAccounts::from_accounts(accounts)?;
+        let bidder_account = bid_accounts.bidder;
+        let escrow_account = bid_accounts.escrow_account;
+        let (fee_payer_override_account, instructions_sysvar_account, fee_payer_account) =
+            match bid_accounts.remaining {
+                [.., fee_payer_override, instructions_sysvar, fee_payer] => {
+                    (fee_payer_override, instructions_sysvar, fee_payer)
+                }
+                _ => return Err(ProgramError::NotEnoughAccountKeys),
+            };
+
+        if !fee_payer_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let (fee_payer_override_pda, _) = Pubkey::find_program_address(
+            &[
+                b"fee_payer_override",
+                escrow_account.key.as_ref(),
+                bidder_account.key.as_ref(),
+            ],
+            program_id,
+        );
+        if fee_payer_override_pda != *fee_payer_override_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if fee_payer_override_account.owner != program_id
+            || fee_payer_override_account.data_len() != FeePayerOverride::LEN
+        {
+            return Err(AuctionError::NotPreAuthorized.into());
+        }
+        let fee_payer_override =
+            FeePayerOverride::unpack(&fee_payer_override_account.try_borrow_data()?)
+                .map_err(|_| AuctionError::NotPreAuthorized)?;
+        if fee_payer_override.bidder != *bidder_account.key {
+            return Err(AuctionError::NotPreAuthorized.into());
+        }
+
+        // The bidder signs the escrow, the price and the nonce, so the fee payer can't bid for them
+        // elsewhere nor submit the signed bid again
+        let bid_message = [
+            escrow_account.key.as_ref(),
+            &price.to_le_bytes(),
+            &nonce.to_le_bytes(),
+        ]
+        .concat();
+        Self::verify_ed25519_signature(
+            instructions_sysvar_account,
+            bidder_account.key,
+            &bid_message,
+        )
+        .map_err(|_| AuctionError::InvalidBidderSignature)?;
+
+        Self::process_bid(
+            &bid_accounts,
+            price,
+            Some(nonce),
+            &[],
+            None,
+            BidOrigin::FeePayer(fee_payer_account),
+            changed,
+            program_id,
+        )
+    }
+
+    fn process_pre_authorize(
+        accounts: &[AccountInfo],
+        bidder: Pubkey,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let fee_payer_override_account = next_account_info(account_info_iter)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+        Self::require_writable(accouint_of_exhibitor)?;
+        Self::require_writable(fee_payer_override_account)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (fee_payer_override_pda, bump_seed) = Pubkey::find_program_address(
+            &[
+                b"fee_payer_override",
+                escrow_account.key.as_ref(),
+                bidder.as_ref(),
+            ],
+            program_id,
+        );
+        if fee_payer_override_pda != *fee_payer_override_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !fee_payer_override_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        Self::create_pda_account(
+            accouint_of_exhibitor,
+            fee_payer_override_account,
+            system_program,
+            rent,
+            FeePayerOverride::LEN,
+            &[
+                b"fee_payer_override",
+                escrow_account.key.as_ref(),
+                bidder.as_ref(),
+                &[bump_seed],
+            ],
+            program_id,
+        )?;
+
+        let fee_payer_override = FeePayerOverride {
+            is_initialized: true,
+            bidder,
+        };
+        FeePayerOverride::pack(
+            fee_payer_override,
+            &mut fee_payer_override_account.try_borrow_mut_data()?,
+        )?;
+        Ok(())
+    }
+
+    fn process_set_sliding_expiry(
+        accounts: &[AccountInfo],
+        idle_period_sec: u64,
+        max_duration_sec: u64,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+        if idle_period_sec == 0 || max_duration_sec < idle_period_sec {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+
+        auction_info.auction_type = AuctionType::SlidingExpiry;
+        auction_info.idle_period_sec = idle_period_sec;
+        auction_info.max_duration_sec = max_duration_sec;
+        // The idle period of the first bid runs from the start
+        auction_info.end_at = auction_info
+            .sliding_end_at(auction_info.starts_at.max(clock.unix_timestamp))
+            .ok_or(AuctionError::AmountOverflow)?;
+        if auction_info.end_at <= clock.unix_timestamp {
+            return Err(AuctionError::InactiveAuction.into());
+        }
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_winner_override(
+        accounts: &[AccountInfo],
+        winner: Pubkey,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.end_at > clock.unix_timestamp {
+            return Err(AuctionError::ActiveAuction.into());
+        }
+        if auction_info.highest_bidder_pubkey == Pubkey::default() {
+            return Err(AuctionError::NoBidderFound.into());
+        }
+        if auction_info.finalized_at != 0 || auction_info.finality_timeout_sec > 0 {
+            return Err(AuctionError::SettlementPending.into());
+        }
+        if auction_info.advanced_amount > 0 {
+            return Err(AuctionError::ProceedsAdvanced.into());
+        }
+        // The selected winner pays the exhibitor alone, in the auction FT mint
+        if auction_info.fractional_owners[0].1 > 0
+            || auction_info.accepted_mints != [Pubkey::default(); MAX_ACCEPTED_MINTS]
+        {
+            return Err(AuctionError::Unauthorized.into());
+        }
+
+        auction_info.winner_override = winner;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_audit_oracle(
+        accounts: &[AccountInfo],
+        audit_oracle: Pubkey,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+
+        auction_info.audit_oracle = audit_oracle;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_certify_price(
+        accounts: &[AccountInfo],
+        certified_price: u64,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let audit_oracle_account = next_account_info(account_info_iter)?;
+
+        if !audit_oracle_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        let escrow_account = next_account_info(account_info_iter)?;
+        let audit_record_account = next_account_info(account_info_iter)?;
+        let program_of_token = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        Self::require_writable(audit_oracle_account)?;
+        Self::require_writable(accouint_of_exhibitor)?;
+        Self::require_writable(exhibiting_nft_temp_account)?;
+        Self::require_writable(escrow_account)?;
+        Self::require_writable(audit_record_account)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(sys_var_rent_account)?;
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        if auction_info.audit_oracle == Pubkey::default()
+            || auction_info.audit_oracle != *audit_oracle_account.key
+        {
+            return Err(AuctionError::Unauthorized.into());
+        }
+        if !auction_info.audit_pending {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        if certified_price != auction_info.price {
+            return Err(AuctionError::ExpectedAmountMismatch.into());
+        }
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.exhibiting_nft_temp_pubkey != *exhibiting_nft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (audit_record_pda, audit_bump_seed) = Pubkey::find_program_address(
+            &[b"audit", escrow_account.key.as_ref()],
+            program_id,
+        );
+        if audit_record_pda != *audit_record_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !audit_record_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        Self::create_pda_account(
+            audit_oracle_account,
+            audit_record_account,
+            system_program,
+            rent,
+            AuditRecord::LEN,
+            &[b"audit", escrow_account.key.as_ref(), &[audit_bump_seed]],
+            program_id,
+        )?;
+        let audit_record = AuditRecord {
+            is_initialized: true,
+            escrow: *escrow_account.key,
+            certified_price,
+            certified_at: clock.unix_timestamp,
+        };
+        AuditRecord::pack(audit_record, &mut audit_record_account.try_borrow_mut_data()?)?;
+
+        let bump_seed = auction_info.pda_bump;
+        let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+        let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+        Self::escrow_is_closing(
+            program_of_token,
+            exhibiting_nft_temp_account,
+            accouint_of_exhibitor,
+            pda,
+            pda_account,
+            escrow_account,
+            signers_seeds,
+        )
+    }
+
+    fn process_set_lot_size(
+        accounts: &[AccountInfo],
+        lot_size: u64,
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let exhibitor_token_account = next_account_info(account_info_iter)?;
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        let escrow_account = next_account_info(account_info_iter)?;
+        let program_of_token = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        Self::require_writable(exhibitor_token_account)?;
+        Self::require_writable(exhibiting_nft_temp_account)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.exhibiting_nft_temp_pubkey != *exhibiting_nft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+        if lot_size == 0 {
+            return Err(AuctionError::InvalidLotSize.into());
+        }
+
+        let exhibitor_token_account_data =
+            TokenAccount::unpack(&exhibitor_token_account.try_borrow_data()?)?;
+        let exhibiting_nft_temp_account_data =
+            TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
+        if exhibitor_token_account_data.owner != *accouint_of_exhibitor.key
+            || exhibitor_token_account_data.mint != exhibiting_nft_temp_account_data.mint
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let escrowed = exhibiting_nft_temp_account_data.amount;
+        if lot_size > escrowed {
+            // An NFT has a supply of 1, so its lot can't grow
+            let top_up = lot_size - escrowed;
+            if exhibitor_token_account_data.amount < top_up {
+                return Err(AuctionError::InvalidLotSize.into());
+            }
+            let top_up_ix = spl_token::instruction::transfer(
+                program_of_token.key,
+                exhibitor_token_account.key,
+                exhibiting_nft_temp_account.key,
+                accouint_of_exhibitor.key,
+                &[],
+                top_up,
+            )?;
+            msg!("Transferring the lot top up to the temporary account...");
+            invoke(
+                &top_up_ix,
+                &[
+                    exhibitor_token_account.clone(),
+                    exhibiting_nft_temp_account.clone(),
+                    accouint_of_exhibitor.clone(),
+                    program_of_token.clone(),
+                ],
+            )?;
+        } else if lot_size < escrowed {
+            let bump_seed = auction_info.pda_bump;
+            let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+            let return_ix = spl_token::instruction::transfer(
+                program_of_token.key,
+                exhibiting_nft_temp_account.key,
+                exhibitor_token_account.key,
+                &pda,
+                &[],
+                escrowed - lot_size,
+            )?;
+            msg!("Returning the lot excess to the Exhibitor...");
+            let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+            invoke_signed(
+                &return_ix,
+                &[
+                    exhibiting_nft_temp_account.clone(),
+                    exhibitor_token_account.clone(),
+                    pda_account.clone(),
+                    program_of_token.clone(),
+                ],
+                signers_seeds,
+            )?;
+        }
+
+        auction_info.lot_size = lot_size;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_set_penalty(
+        accounts: &[AccountInfo],
+        penalty_bps: u16,
+        payment_grace_period_sec: u64,
+        changed: &mut ChangedAuctions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+
+        if !accouint_of_exhibitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let escrow_account = next_account_info(account_info_iter)?;
+        Self::require_writable(escrow_account)?;
+        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+            return Err(AuctionError::AlreadyBid.into());
+        }
+        if penalty_bps > MAX_BASIS_POINTS {
+            return Err(AuctionError::InvalidFeeBasisPoints.into());
+        }
+
+        auction_info.penalty_bps = penalty_bps;
+        auction_info.payment_grace_period_sec = payment_grace_period_sec;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_slash_bidder(
+        accounts: &[AccountInfo],
+        changed: &mut ChangedAuctions,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let caller_account = next_account_info(account_info_iter)?;
+
+        if !caller_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let accouint_of_exhibitor = next_account_info(account_info_iter)?;
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        let exhibiting_nft_returning_account = next_account_info(account_info_iter)?;
+        let exhibitor_ft_receiving_account = next_account_info(account_info_iter)?;
+        let escrow_account = next_account_info(account_info_iter)?;
+        let highest_bidder_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_temp_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_returning_account = next_account_info(account_info_iter)?;
+        Self::require_writable(accouint_of_exhibitor)?;
+        Self::require_writable(exhibiting_nft_temp_account)?;
+        Self::require_writable(exhibiting_nft_returning_account)?;
+        Self::require_writable(exhibitor_ft_receiving_account)?;
+        Self::require_writable(escrow_account)?;
+        Self::require_writable(highest_bidder_account)?;
+        Self::require_writable(highest_bidder_ft_temp_account)?;
+        Self::require_writable(highest_bidder_ft_returning_account)?;
+        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+        changed.record(escrow_account.key, &auction_info)?;
+
+        let sys_var_clock_account = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+
+        if auction_info.penalty_bps == 0 {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        // The highest bidder isn't the one failing to close an overridden auction
+        if auction_info.winner_override != Pubkey::default() {
+            return Err(AuctionError::WinnerOverrideRequired.into());
+        }
+        if auction_info.exhibitor_pubkey != *accouint_of_exhibitor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.exhibiting_nft_temp_pubkey != *exhibiting_nft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !Self::is_exhibitor_ft_receiving(
+            &auction_info,
+            highest_bidder_ft_temp_account,
+            exhibitor_ft_receiving_account,
+        )? {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_pubkey == Pubkey::default() {
+            return Err(AuctionError::NoBidderFound.into());
+        }
+        if auction_info.advanced_amount > 0 {
+            return Err(AuctionError::ProceedsAdvanced.into());
+        }
+        if auction_info.finalized_at != 0 {
+            return Err(AuctionError::SettlementPending.into());
+        }
+        if auction_info.highest_bidder_pubkey != *highest_bidder_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_ft_temp_pubkey != *highest_bidder_ft_temp_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if auction_info.highest_bidder_ft_returning_pubkey
+            != *highest_bidder_ft_returning_account.key
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let slash_allowed_at = auction_info
+            .slash_allowed_at()
+            .ok_or(AuctionError::AmountOverflow)?;
+        if slash_allowed_at > clock.unix_timestamp {
+            return Err(AuctionError::GracePeriodActive.into());
+        }
+        // Anyone can slash, so the NFT must go back to the exhibitor's own account
+        let exhibiting_nft_returning_account_data =
+            TokenAccount::unpack(&exhibiting_nft_returning_account.try_borrow_data()?)?;
+        if exhibiting_nft_returning_account_data.owner != auction_info.exhibitor_pubkey {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let program_of_token = next_account_info(account_info_iter)?;
+        let pda_account = next_account_info(account_info_iter)?;
+        let program_stats_account = next_account_info(account_info_iter)?;
+        let bump_seed = auction_info.pda_bump;
+        let pda = Pubkey::create_program_address(&[b"escrow", &[bump_seed]], program_id)?;
+        let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+
+        let exhibiting_nft_temp_account_data =
+            TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
+        let transfer_nft_to_exhibitor_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            exhibiting_nft_temp_account.key,
+            exhibiting_nft_returning_account.key,
+            &pda,
+            &[],
+            exhibiting_nft_temp_account_data.amount,
+        )?;
+        msg!("Transferring NFT back to the Exhibitor...");
+        invoke_signed(
+            &transfer_nft_to_exhibitor_ix,
+            &[
+                exhibiting_nft_temp_account.clone(),
+                exhibiting_nft_returning_account.clone(),
+                pda_account.clone(),
+                program_of_token.clone(),
+            ],
+            signers_seeds,
+        )?;
+
+        let highest_bidder_ft_temp_account_data =
+            TokenAccount::unpack(&highest_bidder_ft_temp_account.try_borrow_data()?)?;
+        let penalty = Self::basis_points_of(auction_info.price, auction_info.penalty_bps)?
+            .min(highest_bidder_ft_temp_account_data.amount);
+        let refund = highest_bidder_ft_temp_account_data.amount - penalty;
+
+        let transfer_penalty_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            highest_bidder_ft_temp_account.key,
+            exhibitor_ft_receiving_account.key,
+            &pda,
+            &[],
+            penalty,
+        )?;
+        msg!("Transferring the penalty to the Exhibitor...");
+        invoke_signed(
+            &transfer_penalty_ix,
+            &[
+                highest_bidder_ft_temp_account.clone(),
+                exhibitor_ft_receiving_account.clone(),
+                pda_account.clone(),
+                program_of_token.clone(),
+            ],
+            signers_seeds,
+        )?;
+
+        let transfer_ft_to_highest_bidder_ix = spl_token::instruction::transfer(
+            program_of_token.key,
+            highest_bidder_ft_temp_account.key,
+            highest_bidder_ft_returning_account.key,
+            &pda,
+            &[],
+            refund,
+        )?;
+        msg!("Transferring the remaining FT back to the Highest Bidder...");
+        invoke_signed(
+            &transfer_ft_to_highest_bidder_ix,
+            &[
+                highest_bidder_ft_temp_account.clone(),
+                highest_bidder_ft_returning_account.clone(),
+                pda_account.clone(),
+                program_of_token.clone(),
+            ],
+            signers_seeds,
+        )?;
+
+        Self::close_temporary_ft(
+            program_of_token,
+            highest_bidder_ft_temp_account,
+            highest_bidder_account,
+            pda,
+            pda_account,
+            signers_seeds,
+        )?;
+
+        ForfeitedAuctionEvent {
+            escrow: *escrow_account.key,
+            exhibitor: *accouint_of_exhibitor.key,
+            winner: auction_info.highest_bidder_pubkey,
+            price: auction_info.price,
+        }
+        .emit();
+
+        Self::record_auction_closed(program_stats_account, program_id)?;
+
+        Self::escrow_is_closing(
+            program_of_token,
+            exhibiting_nft_temp_account,
+            accouint_of_exhibitor,
+            pda,
+            pda_account,
+            escrow_account,
+            signers_seeds,
+        )
+    }
+
+    /// Returns the CPI authority signing a bid in place of the bidder, passed as the last account
+    fn cross_program_authority<'a, 'b>(
+        auction_info: &Auction,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<&'a AccountInfo<'b>, ProgramError> {
+        if !auction_info.cross_program_bid || get_stack_height() <= TRANSACTION_LEVEL_STACK_HEIGHT {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        let cpi_authority_account = accounts.last().ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if !cpi_authority_account.is_signer || *cpi_authority_account.key != auction_info.cpi_authority {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        Ok(cpi_authority_account)
+    }
+
+    /// Splits the changelog PDA accounts passed after the accounts of an instruction off the end
+    /// of `accounts`, the changelog instructions take the changelog as one of their own accounts
+    fn split_changelogs<'a, 'b>(
+        accounts: &'a [AccountInfo<'b>],
+        instruction: &AuctionInstruction,
+        program_id: &Pubkey,
+    ) -> (&'a [AccountInfo<'b>], &'a [AccountInfo<'b>]) {
+        if matches!(
+            instruction,
+            AuctionInstruction::InitChangelog {} | AuctionInstruction::ReadChangelog {}
+        ) {
+            return (accounts, &[]);
+        }
+        let is_changelog = |account: &AccountInfo| {
+            account.owner == program_id
+                && account.data_len() == ChangelogAccount::LEN
+                && account.try_borrow_data().is_ok_and(|data| data[0] == 1)
+        };
+        let own_len = accounts
+            .iter()
+            .rposition(|account| !is_changelog(account))
+            .map_or(0, |index| index + 1);
+        accounts.split_at(own_len)
+    }
+
+    /// Appends the executed instruction to the changelog of every auction recorded in `changed`,
+    /// each of them must be among the changelog accounts split off by `split_changelogs`
+    fn append_changelogs(
+        changed: &ChangedAuctions,
+        changelog_accounts: &[AccountInfo],
+        accounts: &[AccountInfo],
+        instruction: u8,
+        extra: [u8; 8],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        for (escrow, changelog_bump) in &changed.changelogs {
+            let changelog_pda = Pubkey::create_program_address(
+                &[b"changelog", escrow.as_ref(), &[*changelog_bump]],
+                program_id,
+            )?;
+            let changelog_account = changelog_accounts
+                .iter()
+                .find(|account| *account.key == changelog_pda)
+                .ok_or(ProgramError::InvalidSeeds)?;
+            Self::require_writable(changelog_account)?;
+            Self::append_changelog(changelog_account, accounts, instruction, extra)?;
+        }
+
+        Ok(())
+    }
+
+    /// Appends the executed instruction to a changelog checked by `append_changelogs`
+    fn append_changelog(
+        changelog_account: &AccountInfo,
+        accounts: &[AccountInfo],
+        instruction: u8,
+        extra: [u8; 8],
+    ) -> ProgramResult {
+        let mut changelog = ChangelogAccount::unpack(&changelog_account.try_borrow_data()?)?;
+        let actor = accounts
+            .iter()
+            .find(|account| account.is_signer)
+            .map(|account| *account.key)
+            .unwrap_or_default();
+        changelog.push(ChangelogEntry {
+            instruction,
+            actor,
+            timestamp: Clock::get()?.unix_timestamp,
+            extra,
+        });
+        ChangelogAccount::pack(changelog, &mut changelog_account.try_borrow_mut_data()?)?;
+
+        Ok(())
+    }
+
+    fn record_auction_closed(
+        program_stats_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        Self::require_writable(program_stats_account)?;
+        let mut program_stats = Self::load_program_stats(program_stats_account, program_id)?;
+        program_stats.active_auctions = program_stats.active_auctions.saturating_sub(1);
+        ProgramStats::pack(
+            program_stats,
+            &mut program_stats_account.try_borrow_mut_data()?,
+        )
+    }
+
+    fn push_to_auction_index<'a>(
+        payer_account: &AccountInfo<'a>,
+        index_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        rent: &Rent,
+        seeds: &[&[u8]],
+        escrow: &Pubkey,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        Self::require_writable(index_account)?;
+        let (index_pda, bump_seed) = Pubkey::find_program_address(seeds, program_id);
+        if index_pda != *index_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if index_account.data_is_empty() {
+            Self::require_writable(payer_account)?;
+            let bump = [bump_seed];
+            let mut signer_seeds = seeds.to_vec();
+            signer_seeds.push(&bump);
+            Self::create_pda_account(
+                payer_account,
+                index_account,
+                system_program,
+                rent,
+                AuctionIndex::LEN,
+                &signer_seeds,
+                program_id,
+            )?;
+        }
+
+        let mut auction_index = AuctionIndex::unpack_unchecked(&index_account.try_borrow_data()?)?;
+        auction_index.is_initialized = true;
+        auction_index.push(*escrow);
+        AuctionIndex::pack(auction_index, &mut index_account.try_borrow_mut_data()?)
+    }
+
+    /// Whether `leaf` belongs to the merkle tree of `root`, each pair being hashed in sorted order
+    fn verify_merkle_proof(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+        let computed_root = proof.iter().fold(leaf, |node, sibling| {
+            if node <= *sibling {
+                hashv(&[&node, sibling]).to_bytes()
+            } else {
+                hashv(&[sibling, &node]).to_bytes()
+            }
+        });
+        computed_root == *root
+    }
+
+    fn weighted_amount(amount: u64, weight_bps: u64) -> Result<u64, ProgramError> {
+        let weighted =
+            u128::from(amount) * u128::from(weight_bps) / u128::from(UNIT_BID_WEIGHT_BPS);
+        u64::try_from(weighted).map_err(|_| AuctionError::AmountOverflow.into())
+    }
+
+    /// Weight in basis points of the bids of the bidder, returned by the bid weight oracle of the auction
+    /// The oracle program is found among the accounts by its key
+    fn query_bid_weight<'a>(
+        auction_info: &Auction,
+        bidder_account: &AccountInfo<'a>,
+        accounts: &[AccountInfo<'a>],
+    ) -> Result<u64, ProgramError> {
+        let oracle_account = accounts
+            .iter()
+            .find(|account| *account.key == auction_info.bid_weight_oracle)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let weight_ix =
+            StandardBidWeightOracle(auction_info.bid_weight_oracle).weight(bidder_account.key);
+        msg!("Querying the bidder's weight...");
+        invoke(
+            &weight_ix,
+            &[bidder_account.clone(), oracle_account.clone()],
+        )?;
+
+        let weight_bps = get_return_data()
+            .filter(|(program_id, _)| *program_id == auction_info.bid_weight_oracle)
+            .and_then(|(_, data)| <[u8; 8]>::try_from(data.as_slice()).ok())
+            .map(u64::from_le_bytes)
+            .ok_or(AuctionError::InvalidBidWeight)?;
+        if weight_bps == 0 {
+            return Err(AuctionError::InvalidBidWeight.into());
+        }
+        Ok(weight_bps)
+    }
+
+    fn basis_points_of(amount: u64, bps: u16) -> Result<u64, ProgramError> {
+        let share = amount
+            .checked_mul(bps as u64)
+            .ok_or(AuctionError::AmountOverflow)?
+            / MAX_BASIS_POINTS as u64;
+        Ok(share)
+    }
+
+    fn load_price_oracle(
+        price_oracle_account: &AccountInfo,
+        mint: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<PriceOracle, ProgramError> {
+        if price_oracle_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let (price_oracle_pda, _bump_seed) =
+            Pubkey::find_program_address(&[b"price_oracle", mint.as_ref()], program_id);
+        if price_oracle_pda != *price_oracle_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        PriceOracle::unpack(&price_oracle_account.try_borrow_data()?)
+    }
+
+    /// Whether the mint blacklist PDA of the mint is initialized
+    fn is_mint_blacklisted(
+        mint_blacklist_account: &AccountInfo,
+        mint: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<bool, ProgramError> {
+        let (mint_blacklist_pda, _bump_seed) =
+            Pubkey::find_program_address(&[b"blacklist", mint.as_ref()], program_id);
+        if mint_blacklist_pda != *mint_blacklist_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if mint_blacklist_account.owner != program_id || mint_blacklist_account.data_is_empty() {
+            return Ok(false);
+        }
+        let mint_blacklist =
+            MintBlacklist::unpack_unchecked(&mint_blacklist_account.try_borrow_data()?)?;
+        Ok(mint_blacklist.is_initialized)
+    }
+
+    /// Whether the global ban PDA of the bidder is initialized
+    fn is_globally_banned(
+        global_ban_account: &AccountInfo,
+        bidder: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<bool, ProgramError> {
+        let (global_ban_pda, _bump_seed) =
+            Pubkey::find_program_address(&[b"global_ban", bidder.as_ref()], program_id);
+        if global_ban_pda != *global_ban_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if global_ban_account.owner != program_id || global_ban_account.data_is_empty() {
+            return Ok(false);
+        }
+        let global_ban = GlobalBanRecord::unpack_unchecked(&global_ban_account.try_borrow_data()?)?;
+        Ok(global_ban.is_initialized)
+    }
+
+    /// Loads the bid approval PDA of the bidder on the auction
+    fn load_bid_approval(
+        bid_approval_account: &AccountInfo,
+        escrow_account: &AccountInfo,
+        bidder: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<BidApproval, ProgramError> {
+        if bid_approval_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let (bid_approval_pda, _bump_seed) = Pubkey::find_program_address(
+            &[
+                b"bid_approval",
+                escrow_account.key.as_ref(),
+                bidder.as_ref(),
+            ],
+            program_id,
+        );
+        if bid_approval_pda != *bid_approval_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        BidApproval::unpack(&bid_approval_account.try_borrow_data()?)
+    }
+
+    /// Whether the exhibitor approved the bidder, otherwise records the bid in the bidder's approval
+    /// PDA, found among the accounts, for the exhibitor to review
+    fn submit_for_approval<'a>(
+        accounts: &BidAccounts<'_, 'a>,
+        payer_account: &AccountInfo<'a>,
+        price: u64,
+        clock: &Clock,
+        program_id: &Pubkey,
+    ) -> Result<bool, ProgramError> {
+        let bidder_account = accounts.bidder;
+        let escrow_account = accounts.escrow_account;
+        let (bid_approval_pda, approval_bump_seed) = Pubkey::find_program_address(
+            &[
+                b"bid_approval",
+                escrow_account.key.as_ref(),
+                bidder_account.key.as_ref(),
+            ],
+            program_id,
+        );
+        let bid_approval_account = accounts
+            .remaining
+            .iter()
+            .find(|account| *account.key == bid_approval_pda)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        Self::require_writable(bid_approval_account)?;
+
+        if bid_approval_account.data_is_empty() {
+            let rent = &Rent::from_account_info(accounts.rent_sysvar)?;
+            let system_program = accounts.system_program;
+            Self::require_writable(payer_account)?;
+            Self::create_pda_account(
+                payer_account,
+                bid_approval_account,
+                system_program,
+                rent,
+                BidApproval::LEN,
+                &[
+                    b"bid_approval",
+                    escrow_account.key.as_ref(),
+                    bidder_account.key.as_ref(),
+                    &[approval_bump_seed],
+                ],
+                program_id,
+            )?;
+        } else if bid_approval_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let mut bid_approval =
+            BidApproval::unpack_unchecked(&bid_approval_account.try_borrow_data()?)?;
+        if bid_approval.approved {
+            return Ok(true);
+        }
+
+        msg!("Submitting the bid for the exhibitor's approval...");
+        bid_approval.is_initialized = true;
+        bid_approval.bidder = *bidder_account.key;
+        bid_approval.price = price;
+        bid_approval.submitted_at = clock.unix_timestamp;
+        BidApproval::pack(
+            bid_approval,
+            &mut bid_approval_account.try_borrow_mut_data()?,
+        )?;
+        Ok(false)
+    }
+
+    /// Hash of the slot in the slot hashes sysvar, `None` once the slot is too old to be listed
+    fn find_slot_hash(
+        slot_hashes_account: &AccountInfo,
+        slot: u64,
+    ) -> Result<Option<[u8; 32]>, ProgramError> {
+        if !slot_hashes::check_id(slot_hashes_account.key) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let data = slot_hashes_account.try_borrow_data()?;
+        let len = data
+            .get(..8)
+            .and_then(|len| len.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        // Each entry is the slot followed by its hash
+        Ok(data[8..]
+            .chunks_exact(40)
+            .take(len as usize)
+            .find(|entry| entry[..8] == slot.to_le_bytes())
+            .and_then(|entry| entry[8..].try_into().ok()))
+    }
+
+    /// Whether the account is the exhibitor's FT account in the mint of the highest bid
+    fn is_exhibitor_ft_receiving(
+        auction_info: &Auction,
+        highest_bidder_ft_temp_account: &AccountInfo,
+        exhibitor_ft_receiving_account: &AccountInfo,
+    ) -> Result<bool, ProgramError> {
+        let bid_mint = TokenAccount::unpack(&highest_bidder_ft_temp_account.try_borrow_data()?)
+            .map_or(auction_info.ft_mint, |account_data| account_data.mint);
+        Ok(auction_info.ft_receiving_pubkey(&bid_mint) == Some(*exhibitor_ft_receiving_account.key))
+    }
+
+    fn load_auction_room(
+        room_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<AuctionRoom, ProgramError> {
+        if room_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let room = AuctionRoom::unpack(&room_account.try_borrow_data()?)?;
+        let (room_pda, _bump_seed) =
+            Pubkey::find_program_address(&[b"room", &room.room_id.to_le_bytes()], program_id);
+        if room_pda != *room_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        Ok(room)
+    }
+
+    fn load_global_config(
+        global_config_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<GlobalConfig, ProgramError> {
+        if global_config_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let (global_config_pda, _bump_seed) =
+            Pubkey::find_program_address(&[b"global_config"], program_id);
+        if global_config_pda != *global_config_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let global_config_data = global_config_account.try_borrow_data()?;
+        let data_len = global_config_data.len();
+        if (GLOBAL_CONFIG_V1_LEN..GlobalConfig::LEN).contains(&data_len) {
+            // Fields added after an older layout read as unset
+            let mut padded = [0u8; GlobalConfig::LEN];
+            padded[..data_len].copy_from_slice(&global_config_data);
+            return GlobalConfig::unpack(&padded);
+        }
+        GlobalConfig::unpack(&global_config_data)
+    }
+
+    fn store_global_config(
+        global_config: GlobalConfig,
+        global_config_account: &AccountInfo,
+    ) -> ProgramResult {
+        let mut global_config_data = global_config_account.try_borrow_mut_data()?;
+        let data_len = global_config_data.len();
+        if data_len < GlobalConfig::LEN {
+            // An older layout config only holds unset newer fields, which are dropped
+            let mut packed = [0u8; GlobalConfig::LEN];
+            GlobalConfig::pack(global_config, &mut packed)?;
+            global_config_data.copy_from_slice(&packed[..data_len]);
+            return Ok(());
+        }
+        GlobalConfig::pack(global_config, &mut global_config_data)
+    }
+
+    /// Grows a global config created with an older layout to the current `GlobalConfig` size
+    /// The authority tops up the config rent for the added bytes
+    fn grow_global_config<'a>(
+        authority_account: &AccountInfo<'a>,
+        global_config_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        rent: &Rent,
+    ) -> ProgramResult {
+        if global_config_account.data_len() >= GlobalConfig::LEN {
+            return Ok(());
+        }
+        let rent_top_up = rent
+            .minimum_balance(GlobalConfig::LEN)
+            .saturating_sub(global_config_account.lamports());
+        if rent_top_up > 0 {
+            let transfer_ix = system_instruction::transfer(
+                authority_account.key,
+                global_config_account.key,
+                rent_top_up,
+            );
+            invoke(
+                &transfer_ix,
+                &[
+                    authority_account.clone(),
+                    global_config_account.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        }
+        global_config_account.realloc(GlobalConfig::LEN, true)
+    }
+
+    fn load_program_stats(
+        program_stats_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<ProgramStats, ProgramError> {
+        if program_stats_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let (program_stats_pda, _bump_seed) = Pubkey::find_program_address(&[b"stats"], program_id);
+        if program_stats_pda != *program_stats_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        ProgramStats::unpack(&program_stats_account.try_borrow_data()?)
+    }
+
+    fn require_writable(account: &AccountInfo) -> ProgramResult {
+        if !account.is_writable {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    /// Requires the escrow to be closed, or handed to another program by `SetEscrowAuthority`
+    fn require_settled(escrow_account: &AccountInfo, program_id: &Pubkey) -> ProgramResult {
+        if escrow_account.lamports() == 0 {
+            return Ok(());
+        }
+        if escrow_account.owner != program_id
+            || !Auction::unpack(&escrow_account.try_borrow_data()?)?.is_migrated()
+        {
+            return Err(AuctionError::ActiveAuction.into());
+        }
+        Ok(())
+    }
+
+    fn create_pda_account<'a>(
+        payer_account: &AccountInfo<'a>,
+        pda_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        rent: &Rent,
+        space: usize,
+        signer_seeds: &[&[u8]],
+        owner: &Pubkey,
+    ) -> ProgramResult {
+        let create_account_ix = system_instruction::create_account(
+            payer_account.key,
+            pda_account.key,
+            rent.minimum_balance(space),
+            space as u64,
+            owner,
+        );
+        invoke_signed(
+            &create_account_ix,
+            &[
+                payer_account.clone(),
+                pda_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
         )
     }
 
@@ -446,7 +9343,7 @@ impl Processor {
             .checked_add(escrow_account.lamports())
             .ok_or(AuctionError::AmountOverflow)?;
         **escrow_account.try_borrow_mut_lamports()? = 0;
-        *escrow_account.try_borrow_mut_data()? = &mut [];
+        escrow_account.try_borrow_mut_data()?.fill(0);
 
         Ok(())
     }
@@ -7,6 +7,132 @@ use solana_program::{
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use solana_program::program_pack::IsInitialized;
 
+/// Declares a fixed size byte layout with `write` and `read` helpers, so a field's offset follows
+/// from its position in the declaration instead of hand counted `array_refs!` lengths
+macro_rules! packed_layout {
+    (
+        $(#[$meta:meta])*
+        struct $name:ident {
+            $($field:ident: $len:expr,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[repr(C)]
+        struct $name {
+            $($field: [u8; $len],)*
+        }
+
+        impl $name {
+            const LEN: usize = 0 $(+ $len)*;
+
+            fn write(&self, dst: &mut [u8; $name::LEN]) {
+                let ($($field,)*) = mut_array_refs![dst, $($len),*];
+                $($field.copy_from_slice(&self.$field);)*
+            }
+
+            fn read(src: &[u8; $name::LEN]) -> Self {
+                let ($($field,)*) = array_refs![src, $($len),*];
+                $name {
+                    $($field: *$field,)*
+                }
+            }
+        }
+    };
+}
+
+/// Default cap on how long the highest bidder can extend an expired auction
+pub const DEFAULT_MAX_BIDDER_EXTENSION_SEC: u64 = 86_400;
+
+/// Default seconds the winner has to close an expired auction before anyone else can settle it
+pub const DEFAULT_CLOSE_GRACE_PERIOD_SEC: u64 = 7 * 86_400;
+
+/// Cap on the clock drift tolerance an exhibitor can set on an auction
+pub const MAX_CLOCK_DRIFT_TOLERANCE_SEC: u64 = 3_600;
+
+/// Maximum number of fractional owners splitting the proceeds of an auction
+pub const MAX_FRACTIONAL_OWNERS: usize = 8;
+
+/// Maximum number of FT mints accepted in addition to the auction FT mint
+pub const MAX_ACCEPTED_MINTS: usize = 3;
+
+/// Maximum number of tiers in the price curve of an auction
+pub const MAX_PRICE_TIERS: usize = 4;
+
+/// Maximum number of auctions a single `BatchBid` bids on
+pub const MAX_BATCH_BID: usize = 4;
+
+/// Layout version written by `Exhibit` and `MigrateState`, bumped whenever the `Auction` layout changes
+/// Only the layout of the first release and the current one are supported, escrow accounts of the
+/// versions in between are rejected
+pub const AUCTION_VERSION: u8 = 5;
+
+/// Size of an `Auction` account of the first release, whose layout carried no version byte
+pub const AUCTION_V1_LEN: usize = 209;
+
+/// Offset of the version byte in the current `Auction` layout
+const AUCTION_VERSION_OFFSET: usize = 419;
+/// Size of a `GlobalConfig` account created before the transfer hook whitelist
+pub const GLOBAL_CONFIG_V1_LEN: usize = 43;
+
+/// Number of escrow pubkeys kept in an `AuctionIndex`
+pub const AUCTION_INDEX_CAPACITY: usize = 32;
+
+/// Number of bidder pubkeys kept in a `BidderRegistry`
+pub const BIDDER_REGISTRY_CAPACITY: usize = 32;
+
+/// Number of bids kept in a `BidHistory`
+pub const BID_HISTORY_CAPACITY: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuctionCategory {
+    Art,
+    Gaming,
+    Music,
+    Collectible,
+    Utility,
+    Other,
+}
+
+impl TryFrom<u8> for AuctionCategory {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(AuctionCategory::Art),
+            1 => Ok(AuctionCategory::Gaming),
+            2 => Ok(AuctionCategory::Music),
+            3 => Ok(AuctionCategory::Collectible),
+            4 => Ok(AuctionCategory::Utility),
+            5 => Ok(AuctionCategory::Other),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+/// How bids are placed and how the auction ends
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuctionType {
+    /// Open ascending bids until a fixed end
+    English,
+    /// Hidden bids, each committed through `CommitBidHash` and revealed through `SubmitBid`
+    SealedBid,
+    /// Open ascending bids until no bid came for the idle period, set through `SetSlidingExpiry`
+    SlidingExpiry,
+}
+
+impl TryFrom<u8> for AuctionType {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(AuctionType::English),
+            1 => Ok(AuctionType::SealedBid),
+            2 => Ok(AuctionType::SlidingExpiry),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
 pub struct Auction {
     pub is_initialized: bool,
     /// Wallet pubkey of exhibitor
@@ -25,6 +151,368 @@ pub struct Auction {
     pub highest_bidder_ft_temp_pubkey: Pubkey,
     /// FT pubkey of highest bidder to return to when the auction cancel or other bidder become the highest bidder.
     pub highest_bidder_ft_returning_pubkey: Pubkey,
+    /// Whether the highest bidder's temporary FT account is controlled by the PDA
+    pub bid_locked: bool,
+    /// Bump seed of the escrow PDA
+    pub pda_bump: u8,
+    /// Maximum seconds the highest bidder can extend the auction by
+    pub max_bidder_extension_sec: u64,
+    /// Whether the highest bidder has already extended the auction
+    pub bidder_extended: bool,
+    /// Minimum bid set by the exhibitor, 0 when unset
+    pub floor_price: u64,
+    /// Category of the auction
+    pub category: AuctionCategory,
+    /// Maximum number of unique bidders, 0 when unlimited
+    pub max_bidders: u8,
+    /// Number of unique bidders, tracked when `max_bidders` is set
+    pub unique_bidder_count: u8,
+    /// Whether the exhibitor attested the auction terms
+    pub terms_accepted: bool,
+    /// Hash of the auction terms signed by the exhibitor
+    pub terms_hash: [u8; 32],
+    /// Mint of the FT the auction is priced in
+    pub ft_mint: Pubkey,
+    /// Wallet pubkey of the registered participant selected by the exhibitor, default when unset
+    pub airdrop_winner: Pubkey,
+    /// Whether anyone can settle the auction once it has expired
+    pub auto_close: bool,
+    /// Whether a forced cancel only refunds the escrowed bid, otherwise the exhibitor also compensates the highest bidder
+    pub refund_on_cancel: bool,
+    /// FT amount the highest bidder's temporary account is expected to hold
+    pub bid_escrow_amount: u64,
+    /// FT already withdrawn by the exhibitor from the highest bid
+    pub advanced_amount: u64,
+    /// Whether the current price is exposed in logs and events
+    pub bids_visible: bool,
+    /// NFT mint the exhibitor expects to be listed, default when unset
+    pub expected_nft_mint: Pubkey,
+    /// Wallet pubkey of the mediator assigned by the exhibitor, default when unset
+    pub mediator_pubkey: Pubkey,
+    /// Time from which bids are accepted
+    pub starts_at: i64,
+    /// Layout version of the account
+    pub version: u8,
+    /// Price oracle the auction was validated against, default when unset
+    pub oracle_pubkey: Pubkey,
+    /// Minimum initial price derived from the oracle price
+    pub oracle_min_price: u64,
+    /// Share of the first bid paid to the creator when the bid is placed, in basis points
+    pub upfront_royalty_bps: u16,
+    /// Royalty vault of the creator receiving the upfront royalty
+    pub royalty_creator_ft_pubkey: Pubkey,
+    /// Whether the upfront royalty has already been paid
+    pub upfront_royalty_paid: bool,
+    /// Seconds after `end_at` during which only the winner can close the auction
+    pub close_grace_period_sec: u64,
+    /// Address allowed to close the expired auction for the winner, default when unset
+    pub close_authority: Pubkey,
+    /// Wallet pubkey of the bidder exempt from bid time extensions, default when unset
+    pub priority_bidder: Pubkey,
+    /// Whether the NFT is frozen in the winner's account once the auction closes
+    pub is_soulbound: bool,
+    /// Minimum amount a bid must exceed the current price by
+    pub min_bid_increment: u64,
+    /// Minimum share of the current price a bid must exceed it by, in basis points
+    pub bid_multiplier_bps: u16,
+    /// Auction room the auction belongs to, default when unset
+    pub room: Pubkey,
+    /// Wallet pubkey of the room organizer receiving the room fee
+    pub room_organizer: Pubkey,
+    /// Share of each bid paid to the room organizer, in basis points
+    pub room_fee_bps: u16,
+    /// Merkle root of the bidders allowed by the room, all zeros when anyone can bid
+    pub whitelist_root: [u8; 32],
+    /// FT deposited by sponsors in the bonus vault, paid to the winner on close
+    pub bonus_amount: u64,
+    /// Whether other programs may bid through CPI signed by `cpi_authority`
+    pub cross_program_bid: bool,
+    /// Address signing cross-program bids in place of the bidder, default when unset
+    pub cpi_authority: Pubkey,
+    /// Seconds after close during which the winner can dispute the settlement
+    pub finality_timeout_sec: u64,
+    /// Time from which the FT can be released to the exhibitor, 0 until closed
+    pub finalized_at: i64,
+    /// Whether the FT was released to the exhibitor
+    pub is_finalized: bool,
+    /// Whether the winner disputed the settlement before `finalized_at`
+    pub is_disputed: bool,
+    /// FT receiving account and share in basis points of each fractional owner, unused slots have a 0 share
+    pub fractional_owners: [(Pubkey, u16); MAX_FRACTIONAL_OWNERS],
+    /// Vault program the highest bid was withdrawn from, default when bid directly
+    pub highest_bidder_vault_program: Pubkey,
+    /// Position account of the highest bidder in the vault, credited back when outbid
+    pub highest_bidder_vault: Pubkey,
+    /// FT account of the vault the highest bid was withdrawn from
+    pub highest_bidder_vault_ft: Pubkey,
+    /// Share of the price the winner forfeits to the exhibitor when slashed, in basis points
+    pub penalty_bps: u16,
+    /// Seconds after `end_at` the winner has to close before being slashable
+    pub payment_grace_period_sec: u64,
+    /// Mint of the token bidders must hold, default when anyone can bid
+    pub gate_token_mint: Pubkey,
+    /// Minimum gate token balance of a bidder
+    pub gate_token_min_amount: u64,
+    /// Number of identical tokens sold in the auction, 1 for an NFT
+    pub lot_size: u64,
+    /// FT mints accepted for bids in addition to `ft_mint`, unused slots are default
+    pub accepted_mints: [Pubkey; MAX_ACCEPTED_MINTS],
+    /// Exhibitor's FT account receiving bids in each of `accepted_mints`
+    pub accepted_ft_receiving_pubkeys: [Pubkey; MAX_ACCEPTED_MINTS],
+    /// Seconds without a bid after which a sliding expiry auction ends, 0 for a fixed end
+    pub idle_period_sec: u64,
+    /// Seconds after the start past which a sliding expiry auction can't be pushed
+    pub max_duration_sec: u64,
+    /// Time of the last bid, 0 before the first bid
+    pub last_bid_at: i64,
+    /// Winner selected by the exhibitor in place of the highest bidder, default when unset
+    pub winner_override: Pubkey,
+    /// Account certifying the settlement price before the escrow closes, default when unset
+    pub audit_oracle: Pubkey,
+    /// Whether the auction is settled and waits for `CertifyPrice` to close the escrow
+    pub audit_pending: bool,
+    /// Seconds the winner can't list the NFT again after closing, copied from the global config
+    pub nft_lock_period_sec: u64,
+    /// Price above which closing needs the compliance signer, copied from the global config, 0 for no limit
+    pub transaction_limit: u64,
+    /// Whether a cancel burns the NFT instead of returning it to the exhibitor
+    pub burn_on_cancel: bool,
+    /// Sum of each bid price weighted by the seconds it stayed the highest bid until the next one
+    pub twap_numerator: u128,
+    /// Seconds covered by `twap_numerator`
+    pub twap_denominator: u64,
+    /// Account allowed to update the NFT metadata while in escrow, default when unset
+    pub nft_update_authority: Pubkey,
+    /// Whether the exhibitor allowed the NFT metadata to change during the auction
+    pub allow_updates_during_auction: bool,
+    /// Program verifying the identity of bidders, default when bids aren't gated
+    pub trustline_program: Pubkey,
+    /// Kind of trustline the bidders must hold, passed to `trustline_program`
+    pub trustline_type: [u8; 8],
+    /// How bids are placed and how the auction ends
+    pub auction_type: AuctionType,
+    /// FT the exhibitor locked in the collateral vault as a good-faith deposit
+    pub exhibitor_collateral: u64,
+    /// Share of the collateral paid to the highest bidder on a forced cancel, in basis points
+    pub cancel_penalty_bps: u16,
+    /// Nonce the next bid must carry, incremented by every bid so a bid can't be replayed
+    pub bid_nonce: u64,
+    /// Margin around `end_at` covering clock drift, bids stop that long before it and closing
+    /// waits that long after it
+    pub clock_drift_tolerance_sec: u64,
+    /// Whether the exhibitor approves each bidder before their bids are placed
+    pub requires_approval: bool,
+    /// Whether a bid by another bidder in the slot of the last bid must beat it by a premium
+    pub anti_sandwich: bool,
+    /// Bidder of the last bid, default before the first bid
+    pub last_block_bidder: Pubkey,
+    /// Price of the last bid
+    pub last_block_bid_price: u64,
+    /// Slot of the last bid
+    pub last_block_bid_slot: u64,
+    /// Minimum price from which each tier of the price curve applies and its bid increment in basis points,
+    /// unused tiers have a 0 increment
+    pub price_curve: [(u64, u16); MAX_PRICE_TIERS],
+    /// Share of the auction duration in basis points during which bids use the early bird increment,
+    /// 0 to use the bid multiplier instead
+    pub early_bird_period_bps: u16,
+    /// Proportional minimum increment in basis points of bids during the early bird period
+    pub early_bird_increment_bps: u16,
+    /// Proportional minimum increment in basis points of bids after the early bird period
+    pub late_increment_bps: u16,
+    /// Time until which the logged end time is masked, 0 when the end time is public
+    pub reveal_end_at_time: i64,
+    /// Whether the exhibitor revealed the end time before `reveal_end_at_time`
+    pub hidden_end_at_revealed: bool,
+    /// Oracle program weighting the bids, default when bids are compared by price alone
+    pub bid_weight_oracle: Pubkey,
+    /// Weight in basis points of the highest bid, 0 when unweighted
+    pub highest_bid_weight_bps: u64,
+    /// Whether `InitChangelog` created the changelog PDA account of the auction
+    pub has_changelog: bool,
+    /// Bump seed of the changelog PDA account
+    pub changelog_bump: u8,
+    /// Bump seed of the bid history PDA account, cached by the bid that created it
+    pub bid_history_bump: u8,
+    /// Bump seed of the bidder registry PDA account, cached by `SetMaxBidders`
+    pub bidder_registry_bump: u8,
+    /// Bump seed of the insurance vault PDA account of the auction FT mint
+    pub insurance_vault_bump: u8,
+    /// Program `SetEscrowAuthority` handed the escrowed accounts to, default while this program
+    /// holds them
+    pub migrated_to: Pubkey,
+}
+
+impl Auction {
+    /// Layout version of the data of an escrow account, `None` when it matches no supported layout
+    pub fn layout_version(data: &[u8]) -> Option<u8> {
+        if data.len() == AUCTION_V1_LEN {
+            return Some(1);
+        }
+        let version = *data.get(AUCTION_VERSION_OFFSET)?;
+        (Self::layout_len(version)? == data.len()).then_some(version)
+    }
+
+    /// Size of the `Auction` layout of `version`, `None` for the unsupported versions
+    pub fn layout_len(version: u8) -> Option<usize> {
+        match version {
+            1 => Some(AUCTION_V1_LEN),
+            AUCTION_VERSION => Some(Auction::LEN),
+            _ => None,
+        }
+    }
+
+    /// Whether `SetEscrowAuthority` handed the escrowed accounts to another program
+    pub fn is_migrated(&self) -> bool {
+        self.migrated_to != Pubkey::default()
+    }
+
+    /// Whether bid prices are kept out of logs and events, as they are in sealed-bid auctions
+    pub fn bid_prices_hidden(&self) -> bool {
+        !self.bids_visible || self.auction_type == AuctionType::SealedBid
+    }
+
+    /// Current price as exposed in logs and events, 0 when bids are hidden
+    pub fn visible_price(&self) -> u64 {
+        if !self.bid_prices_hidden() {
+            self.price
+        } else {
+            0
+        }
+    }
+
+    /// End time shown at `now`, `i64::MAX` while a sealed end time is neither revealed nor past its reveal time
+    pub fn visible_end_at(&self, now: i64) -> i64 {
+        if self.reveal_end_at_time != 0
+            && !self.hidden_end_at_revealed
+            && now < self.reveal_end_at_time
+        {
+            i64::MAX
+        } else {
+            self.end_at
+        }
+    }
+
+    /// Time from which anyone can settle the expired auction, `None` on overflow
+    pub fn close_allowed_at(&self) -> Option<i64> {
+        i64::try_from(self.close_grace_period_sec)
+            .ok()
+            .and_then(|grace_period| self.end_at.checked_add(grace_period))
+    }
+
+    /// Time from which bids are refused, the clock drift tolerance before `end_at`, `None` on overflow
+    pub fn bidding_end_at(&self) -> Option<i64> {
+        i64::try_from(self.clock_drift_tolerance_sec)
+            .ok()
+            .and_then(|tolerance| self.end_at.checked_sub(tolerance))
+    }
+
+    /// Time from which the auction can be closed, the clock drift tolerance after `end_at`, `None` on overflow
+    pub fn closable_at(&self) -> Option<i64> {
+        i64::try_from(self.clock_drift_tolerance_sec)
+            .ok()
+            .and_then(|tolerance| self.end_at.checked_add(tolerance))
+    }
+
+    /// Time from which the winner can be slashed for not closing the auction, `None` on overflow
+    pub fn slash_allowed_at(&self) -> Option<i64> {
+        i64::try_from(self.payment_grace_period_sec)
+            .ok()
+            .and_then(|grace_period| self.end_at.checked_add(grace_period))
+    }
+
+    /// End of a sliding expiry auction whose last bid was at `last_bid_at`, `None` on overflow
+    pub fn sliding_end_at(&self, last_bid_at: i64) -> Option<i64> {
+        let idle_end_at = i64::try_from(self.idle_period_sec)
+            .ok()
+            .and_then(|idle_period| last_bid_at.checked_add(idle_period))?;
+        let max_end_at = i64::try_from(self.max_duration_sec)
+            .ok()
+            .and_then(|max_duration| self.starts_at.checked_add(max_duration))?;
+        Some(idle_end_at.min(max_end_at))
+    }
+
+    /// Time weighted average of the bid prices outbid so far, `None` before the second bid
+    pub fn twap(&self) -> Option<u64> {
+        if self.twap_denominator == 0 {
+            return None;
+        }
+        u64::try_from(self.twap_numerator / u128::from(self.twap_denominator)).ok()
+    }
+
+    /// Whether closing at the current price needs the compliance signer
+    pub fn exceeds_transaction_limit(&self) -> bool {
+        self.transaction_limit > 0 && self.price > self.transaction_limit
+    }
+
+    /// Exhibitor's FT account receiving bids in the given mint, `None` when the mint isn't accepted
+    pub fn ft_receiving_pubkey(&self, mint: &Pubkey) -> Option<Pubkey> {
+        if *mint == self.ft_mint {
+            return Some(self.exhibitor_ft_receiving_pubkey);
+        }
+        self.accepted_mints
+            .iter()
+            .position(|accepted_mint| accepted_mint == mint && *mint != Pubkey::default())
+            .map(|i| self.accepted_ft_receiving_pubkeys[i])
+    }
+
+    /// Bid increment of the last price curve tier reached by `price`, 0 without a curve
+    pub fn price_tier_increment_bps(&self, price: u64) -> u16 {
+        self.price_curve
+            .iter()
+            .take_while(|(_, increment_bps)| *increment_bps > 0)
+            .filter(|(min_bid, _)| *min_bid <= price)
+            .last()
+            .map_or(0, |(_, increment_bps)| *increment_bps)
+    }
+
+    /// Proportional increment in basis points of a bid at `now`, the early bird or the late increment
+    /// when an early bird period is set, the bid multiplier otherwise
+    pub fn bid_increment_bps_at(&self, now: i64) -> u16 {
+        if self.early_bird_period_bps == 0 {
+            return self.bid_multiplier_bps;
+        }
+        let duration = i128::from(self.end_at) - i128::from(self.starts_at);
+        let elapsed = i128::from(now) - i128::from(self.starts_at);
+        if duration > 0 && elapsed * 10_000 < duration * i128::from(self.early_bird_period_bps) {
+            self.early_bird_increment_bps
+        } else {
+            self.late_increment_bps
+        }
+    }
+
+    /// Lowest price a bid at `now` can have, `None` on overflow
+    /// The current price must be exceeded by the flat or the proportional increment, whichever is larger,
+    /// the proportional increment being the steeper of the bid increment at `now` and the price curve tier
+    pub fn min_next_bid(&self, now: i64) -> Option<u64> {
+        self.min_bid_over(self.price, now)
+    }
+
+    /// Current price weighted by the weight of the highest bid, `None` on overflow
+    pub fn weighted_price(&self) -> Option<u64> {
+        if self.highest_bid_weight_bps == 0 {
+            return Some(self.price);
+        }
+        u64::try_from(u128::from(self.price) * u128::from(self.highest_bid_weight_bps) / 10_000)
+            .ok()
+    }
+
+    /// Lowest weighted price a weighted bid at `now` can have, `None` on overflow
+    pub fn min_next_weighted_bid(&self, now: i64) -> Option<u64> {
+        self.min_bid_over(self.weighted_price()?, now)
+    }
+
+    fn min_bid_over(&self, price: u64, now: i64) -> Option<u64> {
+        let increment_bps = self
+            .bid_increment_bps_at(now)
+            .max(self.price_tier_increment_bps(price));
+        let multiplier_increment = u128::from(price) * u128::from(increment_bps) / 10_000;
+        let increment = u64::try_from(multiplier_increment)
+            .ok()?
+            .max(self.min_bid_increment)
+            .max(1);
+        price.checked_add(increment)
+    }
 }
 
 impl Sealed for Auction {}
@@ -35,78 +523,2249 @@ impl IsInitialized for Auction {
     }
 }
 
+packed_layout! {
+    /// Byte layout of an `Auction` account, in field order
+    struct AuctionLayout {
+        is_initialized: 1,
+        exhibitor_pubkey: 32,
+        exhibiting_nft_temp_pubkey: 32,
+        exhibitor_ft_receiving_pubkey: 32,
+        price: 8,
+        end_at: 8,
+        highest_bidder_pubkey: 32,
+        highest_bidder_ft_temp_pubkey: 32,
+        highest_bidder_ft_returning_pubkey: 32,
+        bid_locked: 1,
+        pda_bump: 1,
+        max_bidder_extension_sec: 8,
+        bidder_extended: 1,
+        floor_price: 8,
+        category: 1,
+        max_bidders: 1,
+        unique_bidder_count: 1,
+        terms_accepted: 1,
+        terms_hash: 32,
+        ft_mint: 32,
+        airdrop_winner: 32,
+        auto_close: 1,
+        refund_on_cancel: 1,
+        bid_escrow_amount: 8,
+        advanced_amount: 8,
+        bids_visible: 1,
+        expected_nft_mint: 32,
+        mediator_pubkey: 32,
+        starts_at: 8,
+        version: 1,
+        oracle_pubkey: 32,
+        oracle_min_price: 8,
+        upfront_royalty_bps: 2,
+        royalty_creator_ft_pubkey: 32,
+        upfront_royalty_paid: 1,
+        close_grace_period_sec: 8,
+        close_authority: 32,
+        priority_bidder: 32,
+        is_soulbound: 1,
+        min_bid_increment: 8,
+        bid_multiplier_bps: 2,
+        room: 32,
+        room_organizer: 32,
+        room_fee_bps: 2,
+        whitelist_root: 32,
+        bonus_amount: 8,
+        cross_program_bid: 1,
+        cpi_authority: 32,
+        finality_timeout_sec: 8,
+        finalized_at: 8,
+        is_finalized: 1,
+        is_disputed: 1,
+        fractional_owners: 34 * MAX_FRACTIONAL_OWNERS,
+        highest_bidder_vault_program: 32,
+        highest_bidder_vault: 32,
+        highest_bidder_vault_ft: 32,
+        penalty_bps: 2,
+        payment_grace_period_sec: 8,
+        gate_token_mint: 32,
+        gate_token_min_amount: 8,
+        lot_size: 8,
+        accepted_mints: 32 * MAX_ACCEPTED_MINTS,
+        accepted_ft_receiving_pubkeys: 32 * MAX_ACCEPTED_MINTS,
+        idle_period_sec: 8,
+        max_duration_sec: 8,
+        last_bid_at: 8,
+        winner_override: 32,
+        audit_oracle: 32,
+        audit_pending: 1,
+        nft_lock_period_sec: 8,
+        transaction_limit: 8,
+        burn_on_cancel: 1,
+        twap_numerator: 16,
+        twap_denominator: 8,
+        nft_update_authority: 32,
+        allow_updates_during_auction: 1,
+        trustline_program: 32,
+        trustline_type: 8,
+        auction_type: 1,
+        exhibitor_collateral: 8,
+        cancel_penalty_bps: 2,
+        bid_nonce: 8,
+        clock_drift_tolerance_sec: 8,
+        requires_approval: 1,
+        anti_sandwich: 1,
+        last_block_bidder: 32,
+        last_block_bid_price: 8,
+        last_block_bid_slot: 8,
+        price_curve: 10 * MAX_PRICE_TIERS,
+        early_bird_period_bps: 2,
+        early_bird_increment_bps: 2,
+        late_increment_bps: 2,
+        reveal_end_at_time: 8,
+        hidden_end_at_revealed: 1,
+        bid_weight_oracle: 32,
+        highest_bid_weight_bps: 8,
+        has_changelog: 1,
+        changelog_bump: 1,
+        bid_history_bump: 1,
+        bidder_registry_bump: 1,
+        insurance_vault_bump: 1,
+        migrated_to: 32,
+    }
+}
+
+const _: () = assert!(std::mem::size_of::<AuctionLayout>() == Auction::LEN);
+
 impl Pack for Auction {
-    const LEN: usize = 209;
+    const LEN: usize = 1765;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        AuctionLayout {
+            is_initialized: [self.is_initialized as u8],
+            exhibitor_pubkey: self.exhibitor_pubkey.to_bytes(),
+            exhibiting_nft_temp_pubkey: self.exhibiting_nft_temp_pubkey.to_bytes(),
+            exhibitor_ft_receiving_pubkey: self.exhibitor_ft_receiving_pubkey.to_bytes(),
+            price: self.price.to_le_bytes(),
+            end_at: self.end_at.to_le_bytes(),
+            highest_bidder_pubkey: self.highest_bidder_pubkey.to_bytes(),
+            highest_bidder_ft_temp_pubkey: self.highest_bidder_ft_temp_pubkey.to_bytes(),
+            highest_bidder_ft_returning_pubkey: self.highest_bidder_ft_returning_pubkey.to_bytes(),
+            bid_locked: [self.bid_locked as u8],
+            pda_bump: [self.pda_bump],
+            max_bidder_extension_sec: self.max_bidder_extension_sec.to_le_bytes(),
+            bidder_extended: [self.bidder_extended as u8],
+            floor_price: self.floor_price.to_le_bytes(),
+            category: [self.category as u8],
+            max_bidders: [self.max_bidders],
+            unique_bidder_count: [self.unique_bidder_count],
+            terms_accepted: [self.terms_accepted as u8],
+            terms_hash: self.terms_hash,
+            ft_mint: self.ft_mint.to_bytes(),
+            airdrop_winner: self.airdrop_winner.to_bytes(),
+            auto_close: [self.auto_close as u8],
+            refund_on_cancel: [self.refund_on_cancel as u8],
+            bid_escrow_amount: self.bid_escrow_amount.to_le_bytes(),
+            advanced_amount: self.advanced_amount.to_le_bytes(),
+            bids_visible: [self.bids_visible as u8],
+            expected_nft_mint: self.expected_nft_mint.to_bytes(),
+            mediator_pubkey: self.mediator_pubkey.to_bytes(),
+            starts_at: self.starts_at.to_le_bytes(),
+            version: [self.version],
+            oracle_pubkey: self.oracle_pubkey.to_bytes(),
+            oracle_min_price: self.oracle_min_price.to_le_bytes(),
+            upfront_royalty_bps: self.upfront_royalty_bps.to_le_bytes(),
+            royalty_creator_ft_pubkey: self.royalty_creator_ft_pubkey.to_bytes(),
+            upfront_royalty_paid: [self.upfront_royalty_paid as u8],
+            close_grace_period_sec: self.close_grace_period_sec.to_le_bytes(),
+            close_authority: self.close_authority.to_bytes(),
+            priority_bidder: self.priority_bidder.to_bytes(),
+            is_soulbound: [self.is_soulbound as u8],
+            min_bid_increment: self.min_bid_increment.to_le_bytes(),
+            bid_multiplier_bps: self.bid_multiplier_bps.to_le_bytes(),
+            room: self.room.to_bytes(),
+            room_organizer: self.room_organizer.to_bytes(),
+            room_fee_bps: self.room_fee_bps.to_le_bytes(),
+            whitelist_root: self.whitelist_root,
+            bonus_amount: self.bonus_amount.to_le_bytes(),
+            cross_program_bid: [self.cross_program_bid as u8],
+            cpi_authority: self.cpi_authority.to_bytes(),
+            finality_timeout_sec: self.finality_timeout_sec.to_le_bytes(),
+            finalized_at: self.finalized_at.to_le_bytes(),
+            is_finalized: [self.is_finalized as u8],
+            is_disputed: [self.is_disputed as u8],
+            fractional_owners: pack_fractional_owners(&self.fractional_owners),
+            highest_bidder_vault_program: self.highest_bidder_vault_program.to_bytes(),
+            highest_bidder_vault: self.highest_bidder_vault.to_bytes(),
+            highest_bidder_vault_ft: self.highest_bidder_vault_ft.to_bytes(),
+            penalty_bps: self.penalty_bps.to_le_bytes(),
+            payment_grace_period_sec: self.payment_grace_period_sec.to_le_bytes(),
+            gate_token_mint: self.gate_token_mint.to_bytes(),
+            gate_token_min_amount: self.gate_token_min_amount.to_le_bytes(),
+            lot_size: self.lot_size.to_le_bytes(),
+            accepted_mints: pack_pubkeys(&self.accepted_mints),
+            accepted_ft_receiving_pubkeys: pack_pubkeys(&self.accepted_ft_receiving_pubkeys),
+            idle_period_sec: self.idle_period_sec.to_le_bytes(),
+            max_duration_sec: self.max_duration_sec.to_le_bytes(),
+            last_bid_at: self.last_bid_at.to_le_bytes(),
+            winner_override: self.winner_override.to_bytes(),
+            audit_oracle: self.audit_oracle.to_bytes(),
+            audit_pending: [self.audit_pending as u8],
+            nft_lock_period_sec: self.nft_lock_period_sec.to_le_bytes(),
+            transaction_limit: self.transaction_limit.to_le_bytes(),
+            burn_on_cancel: [self.burn_on_cancel as u8],
+            twap_numerator: self.twap_numerator.to_le_bytes(),
+            twap_denominator: self.twap_denominator.to_le_bytes(),
+            nft_update_authority: self.nft_update_authority.to_bytes(),
+            allow_updates_during_auction: [self.allow_updates_during_auction as u8],
+            trustline_program: self.trustline_program.to_bytes(),
+            trustline_type: self.trustline_type,
+            auction_type: [self.auction_type as u8],
+            exhibitor_collateral: self.exhibitor_collateral.to_le_bytes(),
+            cancel_penalty_bps: self.cancel_penalty_bps.to_le_bytes(),
+            bid_nonce: self.bid_nonce.to_le_bytes(),
+            clock_drift_tolerance_sec: self.clock_drift_tolerance_sec.to_le_bytes(),
+            requires_approval: [self.requires_approval as u8],
+            anti_sandwich: [self.anti_sandwich as u8],
+            last_block_bidder: self.last_block_bidder.to_bytes(),
+            last_block_bid_price: self.last_block_bid_price.to_le_bytes(),
+            last_block_bid_slot: self.last_block_bid_slot.to_le_bytes(),
+            price_curve: pack_price_curve(&self.price_curve),
+            early_bird_period_bps: self.early_bird_period_bps.to_le_bytes(),
+            early_bird_increment_bps: self.early_bird_increment_bps.to_le_bytes(),
+            late_increment_bps: self.late_increment_bps.to_le_bytes(),
+            reveal_end_at_time: self.reveal_end_at_time.to_le_bytes(),
+            hidden_end_at_revealed: [self.hidden_end_at_revealed as u8],
+            bid_weight_oracle: self.bid_weight_oracle.to_bytes(),
+            highest_bid_weight_bps: self.highest_bid_weight_bps.to_le_bytes(),
+            has_changelog: [self.has_changelog as u8],
+            changelog_bump: [self.changelog_bump],
+            bid_history_bump: [self.bid_history_bump],
+            bidder_registry_bump: [self.bidder_registry_bump],
+            insurance_vault_bump: [self.insurance_vault_bump],
+            migrated_to: self.migrated_to.to_bytes(),
+        }
+        .write(array_mut_ref![dst, 0, Auction::LEN]);
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let layout = AuctionLayout::read(array_ref![src, 0, Auction::LEN]);
+
+        Ok(Auction {
+            is_initialized: unpack_bool(&layout.is_initialized)?,
+            exhibitor_pubkey: Pubkey::new_from_array(layout.exhibitor_pubkey),
+            exhibiting_nft_temp_pubkey: Pubkey::new_from_array(layout.exhibiting_nft_temp_pubkey),
+            exhibitor_ft_receiving_pubkey: Pubkey::new_from_array(
+                layout.exhibitor_ft_receiving_pubkey,
+            ),
+            price: u64::from_le_bytes(layout.price),
+            end_at: i64::from_le_bytes(layout.end_at),
+            highest_bidder_pubkey: Pubkey::new_from_array(layout.highest_bidder_pubkey),
+            highest_bidder_ft_temp_pubkey: Pubkey::new_from_array(
+                layout.highest_bidder_ft_temp_pubkey,
+            ),
+            highest_bidder_ft_returning_pubkey: Pubkey::new_from_array(
+                layout.highest_bidder_ft_returning_pubkey,
+            ),
+            bid_locked: unpack_bool(&layout.bid_locked)?,
+            pda_bump: layout.pda_bump[0],
+            max_bidder_extension_sec: u64::from_le_bytes(layout.max_bidder_extension_sec),
+            bidder_extended: unpack_bool(&layout.bidder_extended)?,
+            floor_price: u64::from_le_bytes(layout.floor_price),
+            category: AuctionCategory::try_from(layout.category[0])?,
+            max_bidders: layout.max_bidders[0],
+            unique_bidder_count: layout.unique_bidder_count[0],
+            terms_accepted: unpack_bool(&layout.terms_accepted)?,
+            terms_hash: layout.terms_hash,
+            ft_mint: Pubkey::new_from_array(layout.ft_mint),
+            airdrop_winner: Pubkey::new_from_array(layout.airdrop_winner),
+            auto_close: unpack_bool(&layout.auto_close)?,
+            refund_on_cancel: unpack_bool(&layout.refund_on_cancel)?,
+            bid_escrow_amount: u64::from_le_bytes(layout.bid_escrow_amount),
+            advanced_amount: u64::from_le_bytes(layout.advanced_amount),
+            bids_visible: unpack_bool(&layout.bids_visible)?,
+            expected_nft_mint: Pubkey::new_from_array(layout.expected_nft_mint),
+            mediator_pubkey: Pubkey::new_from_array(layout.mediator_pubkey),
+            starts_at: i64::from_le_bytes(layout.starts_at),
+            version: layout.version[0],
+            oracle_pubkey: Pubkey::new_from_array(layout.oracle_pubkey),
+            oracle_min_price: u64::from_le_bytes(layout.oracle_min_price),
+            upfront_royalty_bps: u16::from_le_bytes(layout.upfront_royalty_bps),
+            royalty_creator_ft_pubkey: Pubkey::new_from_array(layout.royalty_creator_ft_pubkey),
+            upfront_royalty_paid: unpack_bool(&layout.upfront_royalty_paid)?,
+            close_grace_period_sec: u64::from_le_bytes(layout.close_grace_period_sec),
+            close_authority: Pubkey::new_from_array(layout.close_authority),
+            priority_bidder: Pubkey::new_from_array(layout.priority_bidder),
+            is_soulbound: unpack_bool(&layout.is_soulbound)?,
+            min_bid_increment: u64::from_le_bytes(layout.min_bid_increment),
+            bid_multiplier_bps: u16::from_le_bytes(layout.bid_multiplier_bps),
+            room: Pubkey::new_from_array(layout.room),
+            room_organizer: Pubkey::new_from_array(layout.room_organizer),
+            room_fee_bps: u16::from_le_bytes(layout.room_fee_bps),
+            whitelist_root: layout.whitelist_root,
+            bonus_amount: u64::from_le_bytes(layout.bonus_amount),
+            cross_program_bid: unpack_bool(&layout.cross_program_bid)?,
+            cpi_authority: Pubkey::new_from_array(layout.cpi_authority),
+            finality_timeout_sec: u64::from_le_bytes(layout.finality_timeout_sec),
+            finalized_at: i64::from_le_bytes(layout.finalized_at),
+            is_finalized: unpack_bool(&layout.is_finalized)?,
+            is_disputed: unpack_bool(&layout.is_disputed)?,
+            fractional_owners: unpack_fractional_owners(&layout.fractional_owners),
+            highest_bidder_vault_program: Pubkey::new_from_array(
+                layout.highest_bidder_vault_program,
+            ),
+            highest_bidder_vault: Pubkey::new_from_array(layout.highest_bidder_vault),
+            highest_bidder_vault_ft: Pubkey::new_from_array(layout.highest_bidder_vault_ft),
+            penalty_bps: u16::from_le_bytes(layout.penalty_bps),
+            payment_grace_period_sec: u64::from_le_bytes(layout.payment_grace_period_sec),
+            gate_token_mint: Pubkey::new_from_array(layout.gate_token_mint),
+            gate_token_min_amount: u64::from_le_bytes(layout.gate_token_min_amount),
+            lot_size: u64::from_le_bytes(layout.lot_size),
+            accepted_mints: unpack_pubkeys(&layout.accepted_mints),
+            accepted_ft_receiving_pubkeys: unpack_pubkeys(&layout.accepted_ft_receiving_pubkeys),
+            idle_period_sec: u64::from_le_bytes(layout.idle_period_sec),
+            max_duration_sec: u64::from_le_bytes(layout.max_duration_sec),
+            last_bid_at: i64::from_le_bytes(layout.last_bid_at),
+            winner_override: Pubkey::new_from_array(layout.winner_override),
+            audit_oracle: Pubkey::new_from_array(layout.audit_oracle),
+            audit_pending: unpack_bool(&layout.audit_pending)?,
+            nft_lock_period_sec: u64::from_le_bytes(layout.nft_lock_period_sec),
+            transaction_limit: u64::from_le_bytes(layout.transaction_limit),
+            burn_on_cancel: unpack_bool(&layout.burn_on_cancel)?,
+            twap_numerator: u128::from_le_bytes(layout.twap_numerator),
+            twap_denominator: u64::from_le_bytes(layout.twap_denominator),
+            nft_update_authority: Pubkey::new_from_array(layout.nft_update_authority),
+            allow_updates_during_auction: unpack_bool(&layout.allow_updates_during_auction)?,
+            trustline_program: Pubkey::new_from_array(layout.trustline_program),
+            trustline_type: layout.trustline_type,
+            auction_type: AuctionType::try_from(layout.auction_type[0])?,
+            exhibitor_collateral: u64::from_le_bytes(layout.exhibitor_collateral),
+            cancel_penalty_bps: u16::from_le_bytes(layout.cancel_penalty_bps),
+            bid_nonce: u64::from_le_bytes(layout.bid_nonce),
+            clock_drift_tolerance_sec: u64::from_le_bytes(layout.clock_drift_tolerance_sec),
+            requires_approval: unpack_bool(&layout.requires_approval)?,
+            anti_sandwich: unpack_bool(&layout.anti_sandwich)?,
+            last_block_bidder: Pubkey::new_from_array(layout.last_block_bidder),
+            last_block_bid_price: u64::from_le_bytes(layout.last_block_bid_price),
+            last_block_bid_slot: u64::from_le_bytes(layout.last_block_bid_slot),
+            price_curve: unpack_price_curve(&layout.price_curve),
+            early_bird_period_bps: u16::from_le_bytes(layout.early_bird_period_bps),
+            early_bird_increment_bps: u16::from_le_bytes(layout.early_bird_increment_bps),
+            late_increment_bps: u16::from_le_bytes(layout.late_increment_bps),
+            reveal_end_at_time: i64::from_le_bytes(layout.reveal_end_at_time),
+            hidden_end_at_revealed: unpack_bool(&layout.hidden_end_at_revealed)?,
+            bid_weight_oracle: Pubkey::new_from_array(layout.bid_weight_oracle),
+            highest_bid_weight_bps: u64::from_le_bytes(layout.highest_bid_weight_bps),
+            has_changelog: unpack_bool(&layout.has_changelog)?,
+            changelog_bump: layout.changelog_bump[0],
+            bid_history_bump: layout.bid_history_bump[0],
+            bidder_registry_bump: layout.bidder_registry_bump[0],
+            insurance_vault_bump: layout.insurance_vault_bump[0],
+            migrated_to: Pubkey::new_from_array(layout.migrated_to),
+        })
+    }
+}
+
+pub struct TombstoneRecord {
+    pub is_initialized: bool,
+    /// Wallet pubkey of the winner
+    pub winner: Pubkey,
+    /// Final settlement price
+    pub final_price: u64,
+    /// Time the record was written
+    pub closed_at: i64,
+    /// Mint of the auctioned NFT
+    pub nft_mint: Pubkey,
+    /// Wallet pubkey of the exhibitor
+    pub exhibitor: Pubkey,
+}
+
+impl Sealed for TombstoneRecord {}
+
+impl IsInitialized for TombstoneRecord {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for TombstoneRecord {
+    const LEN: usize = 113;
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
-        let dst = array_mut_ref![dst, 0, Auction::LEN];
+        let dst = array_mut_ref![dst, 0, TombstoneRecord::LEN];
         let (
             is_initialized_dst,
-            exhibitor_pubkey_dst,
-            exhibiting_nft_temp_pubkey_dst,
-            exhibitor_ft_receiving_pubkey_dst,
-            price_dst,
-            end_at_dst,
-            highest_bidder_pubkey_dst,
-            highest_bidder_ft_temp_pubkey_dst,
-            highest_bidder_ft_returning_pubkey_dst,
-        ) = mut_array_refs![dst, 1, 32, 32, 32, 8, 8, 32, 32, 32];
-
-        let Auction {
+            winner_dst,
+            final_price_dst,
+            closed_at_dst,
+            nft_mint_dst,
+            exhibitor_dst,
+        ) = mut_array_refs![dst, 1, 32, 8, 8, 32, 32];
+
+        let TombstoneRecord {
             is_initialized,
-            exhibitor_pubkey,
-            exhibiting_nft_temp_pubkey,
-            exhibitor_ft_receiving_pubkey,
-            price,
-            end_at,
-            highest_bidder_pubkey,
-            highest_bidder_ft_temp_pubkey,
-            highest_bidder_ft_returning_pubkey,
+            winner,
+            final_price,
+            closed_at,
+            nft_mint,
+            exhibitor,
         } = self;
 
         is_initialized_dst[0] = *is_initialized as u8;
-        exhibitor_pubkey_dst.copy_from_slice(exhibitor_pubkey.as_ref());
-        exhibiting_nft_temp_pubkey_dst.copy_from_slice(exhibiting_nft_temp_pubkey.as_ref());
-        exhibitor_ft_receiving_pubkey_dst.copy_from_slice(exhibitor_ft_receiving_pubkey.as_ref());
-        *price_dst = price.to_le_bytes();
-        *end_at_dst = end_at.to_le_bytes();
-        highest_bidder_pubkey_dst.copy_from_slice(highest_bidder_pubkey.as_ref());
-        highest_bidder_ft_temp_pubkey_dst.copy_from_slice(highest_bidder_ft_temp_pubkey.as_ref());
-        highest_bidder_ft_returning_pubkey_dst
-            .copy_from_slice(highest_bidder_ft_returning_pubkey.as_ref());
+        winner_dst.copy_from_slice(winner.as_ref());
+        *final_price_dst = final_price.to_le_bytes();
+        *closed_at_dst = closed_at.to_le_bytes();
+        nft_mint_dst.copy_from_slice(nft_mint.as_ref());
+        exhibitor_dst.copy_from_slice(exhibitor.as_ref());
     }
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        let src = array_ref![src, 0, Auction::LEN];
+        let src = array_ref![src, 0, TombstoneRecord::LEN];
         let (
             is_initialized,
-            exhibitor_pubkey,
-            exhibiting_nft_temp_pubkey,
-            exhibitor_ft_receiving_pubkey,
-            price,
-            end_at,
-            highest_bidder_pubkey,
-            highest_bidder_ft_temp_pubkey,
-            highest_bidder_ft_returning_pubkey,
-        ) = array_refs![src, 1, 32, 32, 32, 8, 8, 32, 32, 32];
+            winner,
+            final_price,
+            closed_at,
+            nft_mint,
+            exhibitor,
+        ) = array_refs![src, 1, 32, 8, 8, 32, 32];
         let is_initialized = match is_initialized {
             [0] => false,
             [1] => true,
             _ => return Err(ProgramError::InvalidAccountData),
         };
 
-        Ok(Auction {
+        Ok(TombstoneRecord {
             is_initialized,
-            exhibitor_pubkey: Pubkey::new_from_array(*exhibitor_pubkey),
-            exhibiting_nft_temp_pubkey: Pubkey::new_from_array(*exhibiting_nft_temp_pubkey),
-            exhibitor_ft_receiving_pubkey: Pubkey::new_from_array(*exhibitor_ft_receiving_pubkey),
-            price: u64::from_le_bytes(*price),
-            end_at: i64::from_le_bytes(*end_at),
-            highest_bidder_pubkey: Pubkey::new_from_array(*highest_bidder_pubkey),
-            highest_bidder_ft_temp_pubkey: Pubkey::new_from_array(*highest_bidder_ft_temp_pubkey),
-            highest_bidder_ft_returning_pubkey: Pubkey::new_from_array(
-                *highest_bidder_ft_returning_pubkey,
-            ),
+            winner: Pubkey::new_from_array(*winner),
+            final_price: u64::from_le_bytes(*final_price),
+            closed_at: i64::from_le_bytes(*closed_at),
+            nft_mint: Pubkey::new_from_array(*nft_mint),
+            exhibitor: Pubkey::new_from_array(*exhibitor),
+        })
+    }
+}
+
+/// Insurance paid out to the winner of a settled auction, kept apart from its immutable tombstone
+pub struct InsuranceClaimRecord {
+    pub is_initialized: bool,
+    /// Amount paid out of the insurance vault
+    pub amount: u64,
+}
+
+impl Sealed for InsuranceClaimRecord {}
+
+impl IsInitialized for InsuranceClaimRecord {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for InsuranceClaimRecord {
+    const LEN: usize = 9;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, InsuranceClaimRecord::LEN];
+        let (is_initialized_dst, amount_dst) = mut_array_refs![dst, 1, 8];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *amount_dst = self.amount.to_le_bytes();
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, InsuranceClaimRecord::LEN];
+        let (is_initialized, amount) = array_refs![src, 1, 8];
+
+        Ok(InsuranceClaimRecord {
+            is_initialized: unpack_bool(is_initialized)?,
+            amount: u64::from_le_bytes(*amount),
+        })
+    }
+}
+
+pub struct ExhibitorProfile {
+    pub is_initialized: bool,
+    /// Number of auctions started by the exhibitor
+    pub auction_count: u32,
+    /// Escrow pubkey of the latest auction started by the exhibitor
+    pub latest_auction: Pubkey,
+}
+
+impl Sealed for ExhibitorProfile {}
+
+impl IsInitialized for ExhibitorProfile {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for ExhibitorProfile {
+    const LEN: usize = 37;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, ExhibitorProfile::LEN];
+        let (is_initialized_dst, auction_count_dst, latest_auction_dst) =
+            mut_array_refs![dst, 1, 4, 32];
+
+        let ExhibitorProfile {
+            is_initialized,
+            auction_count,
+            latest_auction,
+        } = self;
+
+        is_initialized_dst[0] = *is_initialized as u8;
+        *auction_count_dst = auction_count.to_le_bytes();
+        latest_auction_dst.copy_from_slice(latest_auction.as_ref());
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, ExhibitorProfile::LEN];
+        let (is_initialized, auction_count, latest_auction) = array_refs![src, 1, 4, 32];
+
+        Ok(ExhibitorProfile {
+            is_initialized: unpack_bool(is_initialized)?,
+            auction_count: u32::from_le_bytes(*auction_count),
+            latest_auction: Pubkey::new_from_array(*latest_auction),
+        })
+    }
+}
+
+/// Settings of an exhibitor maintained by the global config authority
+pub struct ExhibitorConfig {
+    pub is_initialized: bool,
+    /// Minimum time the exhibitor's auctions accept bids for, in seconds
+    pub min_duration_sec: u64,
+}
+
+impl Sealed for ExhibitorConfig {}
+
+impl IsInitialized for ExhibitorConfig {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for ExhibitorConfig {
+    const LEN: usize = 9;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, ExhibitorConfig::LEN];
+        let (is_initialized_dst, min_duration_sec_dst) = mut_array_refs![dst, 1, 8];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *min_duration_sec_dst = self.min_duration_sec.to_le_bytes();
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, ExhibitorConfig::LEN];
+        let (is_initialized, min_duration_sec) = array_refs![src, 1, 8];
+
+        Ok(ExhibitorConfig {
+            is_initialized: unpack_bool(is_initialized)?,
+            min_duration_sec: u64::from_le_bytes(*min_duration_sec),
+        })
+    }
+}
+
+/// Length of the description of an `AuctionRoom`
+pub const ROOM_DESCRIPTION_LEN: usize = 64;
+
+/// Venue sharing its fee and bidder whitelist with the auctions exhibited in it
+pub struct AuctionRoom {
+    pub is_initialized: bool,
+    /// Identifier the room PDA is seeded by
+    pub room_id: u64,
+    /// Wallet pubkey of the organizer receiving the room fee
+    pub organizer: Pubkey,
+    /// Share of each bid paid to the organizer, in basis points
+    pub fee_bps: u16,
+    /// Merkle root of the allowed bidders, all zeros when anyone can bid
+    pub whitelist_root: [u8; 32],
+    /// Description of the room
+    pub room_description: [u8; ROOM_DESCRIPTION_LEN],
+}
+
+impl Sealed for AuctionRoom {}
+
+impl IsInitialized for AuctionRoom {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for AuctionRoom {
+    const LEN: usize = 75 + ROOM_DESCRIPTION_LEN;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, AuctionRoom::LEN];
+        let (
+            is_initialized_dst,
+            room_id_dst,
+            organizer_dst,
+            fee_bps_dst,
+            whitelist_root_dst,
+            room_description_dst,
+        ) = mut_array_refs![dst, 1, 8, 32, 2, 32, ROOM_DESCRIPTION_LEN];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *room_id_dst = self.room_id.to_le_bytes();
+        organizer_dst.copy_from_slice(self.organizer.as_ref());
+        *fee_bps_dst = self.fee_bps.to_le_bytes();
+        *whitelist_root_dst = self.whitelist_root;
+        *room_description_dst = self.room_description;
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, AuctionRoom::LEN];
+        let (is_initialized, room_id, organizer, fee_bps, whitelist_root, room_description) =
+            array_refs![src, 1, 8, 32, 2, 32, ROOM_DESCRIPTION_LEN];
+
+        Ok(AuctionRoom {
+            is_initialized: unpack_bool(is_initialized)?,
+            room_id: u64::from_le_bytes(*room_id),
+            organizer: Pubkey::new_from_array(*organizer),
+            fee_bps: u16::from_le_bytes(*fee_bps),
+            whitelist_root: *whitelist_root,
+            room_description: *room_description,
         })
     }
 }
+
+/// FT a sponsor deposited in the bonus vault of an auction
+pub struct BonusDeposit {
+    pub is_initialized: bool,
+    /// Deposited amount in the smallest FT unit
+    pub amount: u64,
+}
+
+impl Sealed for BonusDeposit {}
+
+impl IsInitialized for BonusDeposit {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for BonusDeposit {
+    const LEN: usize = 9;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, BonusDeposit::LEN];
+        let (is_initialized_dst, amount_dst) = mut_array_refs![dst, 1, 8];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *amount_dst = self.amount.to_le_bytes();
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, BonusDeposit::LEN];
+        let (is_initialized, amount) = array_refs![src, 1, 8];
+
+        Ok(BonusDeposit {
+            is_initialized: unpack_bool(is_initialized)?,
+            amount: u64::from_le_bytes(*amount),
+        })
+    }
+}
+
+/// Settlement price certified by the audit oracle of an auction
+pub struct AuditRecord {
+    pub is_initialized: bool,
+    /// Escrow pubkey of the audited auction
+    pub escrow: Pubkey,
+    /// Certified settlement price
+    pub certified_price: u64,
+    /// Time the price was certified
+    pub certified_at: i64,
+}
+
+impl Sealed for AuditRecord {}
+
+impl IsInitialized for AuditRecord {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for AuditRecord {
+    const LEN: usize = 49;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, AuditRecord::LEN];
+        let (is_initialized_dst, escrow_dst, certified_price_dst, certified_at_dst) =
+            mut_array_refs![dst, 1, 32, 8, 8];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        escrow_dst.copy_from_slice(self.escrow.as_ref());
+        *certified_price_dst = self.certified_price.to_le_bytes();
+        *certified_at_dst = self.certified_at.to_le_bytes();
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, AuditRecord::LEN];
+        let (is_initialized, escrow, certified_price, certified_at) = array_refs![src, 1, 32, 8, 8];
+
+        Ok(AuditRecord {
+            is_initialized: unpack_bool(is_initialized)?,
+            escrow: Pubkey::new_from_array(*escrow),
+            certified_price: u64::from_le_bytes(*certified_price),
+            certified_at: i64::from_le_bytes(*certified_at),
+        })
+    }
+}
+
+/// Sum of all bids a wallet ever placed, never decreased on refunds
+pub struct LifetimeBidVolume {
+    pub is_initialized: bool,
+    /// Sum of the bid prices in the smallest FT unit
+    pub total_bid_volume: u64,
+    /// Bump seed of the PDA account, cached by the bid that created it
+    pub bump: u8,
+}
+
+impl Sealed for LifetimeBidVolume {}
+
+impl IsInitialized for LifetimeBidVolume {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for LifetimeBidVolume {
+    const LEN: usize = 10;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, LifetimeBidVolume::LEN];
+        let (is_initialized_dst, total_bid_volume_dst, bump_dst) = mut_array_refs![dst, 1, 8, 1];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *total_bid_volume_dst = self.total_bid_volume.to_le_bytes();
+        bump_dst[0] = self.bump;
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, LifetimeBidVolume::LEN];
+        let (is_initialized, total_bid_volume, bump) = array_refs![src, 1, 8, 1];
+
+        Ok(LifetimeBidVolume {
+            is_initialized: unpack_bool(is_initialized)?,
+            total_bid_volume: u64::from_le_bytes(*total_bid_volume),
+            bump: bump[0],
+        })
+    }
+}
+
+/// Time from which a wallet that won an NFT can list it again
+pub struct NFTLockRecord {
+    pub is_initialized: bool,
+    /// Time the lock ends
+    pub unlocks_at: i64,
+}
+
+impl Sealed for NFTLockRecord {}
+
+impl IsInitialized for NFTLockRecord {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for NFTLockRecord {
+    const LEN: usize = 9;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, NFTLockRecord::LEN];
+        let (is_initialized_dst, unlocks_at_dst) = mut_array_refs![dst, 1, 8];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *unlocks_at_dst = self.unlocks_at.to_le_bytes();
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, NFTLockRecord::LEN];
+        let (is_initialized, unlocks_at) = array_refs![src, 1, 8];
+
+        Ok(NFTLockRecord {
+            is_initialized: unpack_bool(is_initialized)?,
+            unlocks_at: i64::from_le_bytes(*unlocks_at),
+        })
+    }
+}
+
+/// Marks an FT mint the platform refuses for exhibits and bids, closed when the mint is unblacklisted
+pub struct MintBlacklist {
+    pub is_initialized: bool,
+    /// The blacklisted mint
+    pub mint: Pubkey,
+}
+
+impl Sealed for MintBlacklist {}
+
+impl IsInitialized for MintBlacklist {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for MintBlacklist {
+    const LEN: usize = 33;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, MintBlacklist::LEN];
+        let (is_initialized_dst, mint_dst) = mut_array_refs![dst, 1, 32];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        mint_dst.copy_from_slice(self.mint.as_ref());
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, MintBlacklist::LEN];
+        let (is_initialized, mint) = array_refs![src, 1, 32];
+
+        Ok(MintBlacklist {
+            is_initialized: unpack_bool(is_initialized)?,
+            mint: Pubkey::new_from_array(*mint),
+        })
+    }
+}
+
+/// Bars a wallet from bidding on any auction, closed when the wallet is unbanned
+pub struct GlobalBanRecord {
+    pub is_initialized: bool,
+    /// The banned wallet
+    pub bidder: Pubkey,
+}
+
+impl Sealed for GlobalBanRecord {}
+
+impl IsInitialized for GlobalBanRecord {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for GlobalBanRecord {
+    const LEN: usize = 33;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, GlobalBanRecord::LEN];
+        let (is_initialized_dst, bidder_dst) = mut_array_refs![dst, 1, 32];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        bidder_dst.copy_from_slice(self.bidder.as_ref());
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, GlobalBanRecord::LEN];
+        let (is_initialized, bidder) = array_refs![src, 1, 32];
+
+        Ok(GlobalBanRecord {
+            is_initialized: unpack_bool(is_initialized)?,
+            bidder: Pubkey::new_from_array(*bidder),
+        })
+    }
+}
+
+/// Auctions bid on together by `BatchBid`, of which the bidder wants to win a single one
+pub struct BatchBidGroup {
+    pub is_initialized: bool,
+    /// The bidder of the group
+    pub bidder: Pubkey,
+    /// Escrows of the auctions in bid order, default after the last one
+    pub auctions: [Pubkey; MAX_BATCH_BID],
+    /// Price bid on each auction
+    pub price: u64,
+}
+
+impl Sealed for BatchBidGroup {}
+
+impl IsInitialized for BatchBidGroup {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for BatchBidGroup {
+    const LEN: usize = 41 + 32 * MAX_BATCH_BID;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, BatchBidGroup::LEN];
+        let (is_initialized_dst, bidder_dst, auctions_dst, price_dst) =
+            mut_array_refs![dst, 1, 32, 32 * MAX_BATCH_BID, 8];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        bidder_dst.copy_from_slice(self.bidder.as_ref());
+        for (auction_dst, auction) in auctions_dst.chunks_exact_mut(32).zip(&self.auctions) {
+            auction_dst.copy_from_slice(auction.as_ref());
+        }
+        *price_dst = self.price.to_le_bytes();
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, BatchBidGroup::LEN];
+        let (is_initialized, bidder, auctions_src, price) =
+            array_refs![src, 1, 32, 32 * MAX_BATCH_BID, 8];
+
+        let mut auctions = [Pubkey::default(); MAX_BATCH_BID];
+        for (auction, auction_src) in auctions.iter_mut().zip(auctions_src.chunks_exact(32)) {
+            *auction = Pubkey::new_from_array(*array_ref![auction_src, 0, 32]);
+        }
+
+        Ok(BatchBidGroup {
+            is_initialized: unpack_bool(is_initialized)?,
+            bidder: Pubkey::new_from_array(*bidder),
+            auctions,
+            price: u64::from_le_bytes(*price),
+        })
+    }
+}
+
+/// Schedule of auctions exhibited one after the other, closed once its last occurrence is exhibited
+pub struct RecurringAuction {
+    pub is_initialized: bool,
+    /// Wallet pubkey of the exhibitor of every occurrence
+    pub exhibitor: Pubkey,
+    /// Seconds between the starts of two occurrences
+    pub interval_sec: u64,
+    /// Number of auctions in the series
+    pub occurrences: u8,
+    /// Number of auctions already exhibited
+    pub triggered: u8,
+    /// Initial price of each occurrence
+    pub initial_price: u64,
+    /// Duration of each occurrence
+    pub duration_sec: u64,
+    /// Category of each occurrence
+    pub category: AuctionCategory,
+    /// Mint of the FT each occurrence is priced in
+    pub ft_mint: Pubkey,
+    /// Time from which the next occurrence can be exhibited
+    pub next_occurrence_at: i64,
+    /// Escrow of the latest occurrence, default before the first one
+    pub last_escrow: Pubkey,
+}
+
+impl Sealed for RecurringAuction {}
+
+impl IsInitialized for RecurringAuction {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for RecurringAuction {
+    const LEN: usize = 132;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, RecurringAuction::LEN];
+        let (
+            is_initialized_dst,
+            exhibitor_dst,
+            interval_sec_dst,
+            occurrences_dst,
+            triggered_dst,
+            initial_price_dst,
+            duration_sec_dst,
+            category_dst,
+            ft_mint_dst,
+            next_occurrence_at_dst,
+            last_escrow_dst,
+        ) = mut_array_refs![dst, 1, 32, 8, 1, 1, 8, 8, 1, 32, 8, 32];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        exhibitor_dst.copy_from_slice(self.exhibitor.as_ref());
+        *interval_sec_dst = self.interval_sec.to_le_bytes();
+        occurrences_dst[0] = self.occurrences;
+        triggered_dst[0] = self.triggered;
+        *initial_price_dst = self.initial_price.to_le_bytes();
+        *duration_sec_dst = self.duration_sec.to_le_bytes();
+        category_dst[0] = self.category as u8;
+        ft_mint_dst.copy_from_slice(self.ft_mint.as_ref());
+        *next_occurrence_at_dst = self.next_occurrence_at.to_le_bytes();
+        last_escrow_dst.copy_from_slice(self.last_escrow.as_ref());
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, RecurringAuction::LEN];
+        let (
+            is_initialized,
+            exhibitor,
+            interval_sec,
+            occurrences,
+            triggered,
+            initial_price,
+            duration_sec,
+            category,
+            ft_mint,
+            next_occurrence_at,
+            last_escrow,
+        ) = array_refs![src, 1, 32, 8, 1, 1, 8, 8, 1, 32, 8, 32];
+
+        Ok(RecurringAuction {
+            is_initialized: unpack_bool(is_initialized)?,
+            exhibitor: Pubkey::new_from_array(*exhibitor),
+            interval_sec: u64::from_le_bytes(*interval_sec),
+            occurrences: occurrences[0],
+            triggered: triggered[0],
+            initial_price: u64::from_le_bytes(*initial_price),
+            duration_sec: u64::from_le_bytes(*duration_sec),
+            category: AuctionCategory::try_from(category[0])?,
+            ft_mint: Pubkey::new_from_array(*ft_mint),
+            next_occurrence_at: i64::from_le_bytes(*next_occurrence_at),
+            last_escrow: Pubkey::new_from_array(*last_escrow),
+        })
+    }
+}
+
+/// Bid submitted for the exhibitor's approval on an auction requiring it, created by `Bid`
+pub struct BidApproval {
+    pub is_initialized: bool,
+    /// The bidder waiting for approval
+    pub bidder: Pubkey,
+    /// Price of the submitted bid
+    pub price: u64,
+    /// Time the bid was submitted
+    pub submitted_at: i64,
+    /// Whether the exhibitor approved the bidder
+    pub approved: bool,
+}
+
+impl Sealed for BidApproval {}
+
+impl IsInitialized for BidApproval {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for BidApproval {
+    const LEN: usize = 50;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, BidApproval::LEN];
+        let (is_initialized_dst, bidder_dst, price_dst, submitted_at_dst, approved_dst) =
+            mut_array_refs![dst, 1, 32, 8, 8, 1];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        bidder_dst.copy_from_slice(self.bidder.as_ref());
+        *price_dst = self.price.to_le_bytes();
+        *submitted_at_dst = self.submitted_at.to_le_bytes();
+        approved_dst[0] = self.approved as u8;
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, BidApproval::LEN];
+        let (is_initialized, bidder, price, submitted_at, approved) =
+            array_refs![src, 1, 32, 8, 8, 1];
+
+        Ok(BidApproval {
+            is_initialized: unpack_bool(is_initialized)?,
+            bidder: Pubkey::new_from_array(*bidder),
+            price: u64::from_le_bytes(*price),
+            submitted_at: i64::from_le_bytes(*submitted_at),
+            approved: unpack_bool(approved)?,
+        })
+    }
+}
+
+/// Allows a fee payer to submit the bids of a bidder, created by the exhibitor through `PreAuthorize`
+pub struct FeePayerOverride {
+    pub is_initialized: bool,
+    /// The pre-authorized bidder
+    pub bidder: Pubkey,
+}
+
+impl Sealed for FeePayerOverride {}
+
+impl IsInitialized for FeePayerOverride {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for FeePayerOverride {
+    const LEN: usize = 33;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, FeePayerOverride::LEN];
+        let (is_initialized_dst, bidder_dst) = mut_array_refs![dst, 1, 32];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        bidder_dst.copy_from_slice(self.bidder.as_ref());
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, FeePayerOverride::LEN];
+        let (is_initialized, bidder) = array_refs![src, 1, 32];
+
+        Ok(FeePayerOverride {
+            is_initialized: unpack_bool(is_initialized)?,
+            bidder: Pubkey::new_from_array(*bidder),
+        })
+    }
+}
+
+/// Bid committed through `CommitBidHash` and revealed through `SubmitBid`, closed once revealed
+pub struct PendingBid {
+    pub is_initialized: bool,
+    /// SHA256 of the price, the salt and the slot hash
+    pub hash: [u8; 32],
+    /// Slot whose hash the commitment is bound to
+    pub slot: u64,
+}
+
+impl Sealed for PendingBid {}
+
+impl IsInitialized for PendingBid {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for PendingBid {
+    const LEN: usize = 41;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, PendingBid::LEN];
+        let (is_initialized_dst, hash_dst, slot_dst) = mut_array_refs![dst, 1, 32, 8];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *hash_dst = self.hash;
+        *slot_dst = self.slot.to_le_bytes();
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, PendingBid::LEN];
+        let (is_initialized, hash, slot) = array_refs![src, 1, 32, 8];
+
+        Ok(PendingBid {
+            is_initialized: unpack_bool(is_initialized)?,
+            hash: *hash,
+            slot: u64::from_le_bytes(*slot),
+        })
+    }
+}
+
+/// Factor `UserProfile::average_score` is scaled by
+pub const FEEDBACK_SCORE_SCALE: u64 = 100;
+
+/// Feedback left by the winner or the exhibitor of an auction on the other party
+pub struct FeedbackRecord {
+    pub is_initialized: bool,
+    /// Wallet pubkey of the reviewer
+    pub reviewer: Pubkey,
+    /// Score from 1 to 5
+    pub score: u8,
+    /// Hash of the comment stored off chain
+    pub comment_hash: [u8; 32],
+}
+
+impl Sealed for FeedbackRecord {}
+
+impl IsInitialized for FeedbackRecord {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for FeedbackRecord {
+    const LEN: usize = 66;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, FeedbackRecord::LEN];
+        let (is_initialized_dst, reviewer_dst, score_dst, comment_hash_dst) =
+            mut_array_refs![dst, 1, 32, 1, 32];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        reviewer_dst.copy_from_slice(self.reviewer.as_ref());
+        score_dst[0] = self.score;
+        *comment_hash_dst = self.comment_hash;
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, FeedbackRecord::LEN];
+        let (is_initialized, reviewer, score, comment_hash) = array_refs![src, 1, 32, 1, 32];
+
+        Ok(FeedbackRecord {
+            is_initialized: unpack_bool(is_initialized)?,
+            reviewer: Pubkey::new_from_array(*reviewer),
+            score: score[0],
+            comment_hash: *comment_hash,
+        })
+    }
+}
+
+/// Reputation of a wallet built from the feedback it received
+pub struct UserProfile {
+    pub is_initialized: bool,
+    /// Average score received, scaled by `FEEDBACK_SCORE_SCALE`
+    pub average_score: u64,
+    /// Number of feedbacks received
+    pub review_count: u32,
+}
+
+impl UserProfile {
+    /// Folds a new score into the running average
+    pub fn add_score(&mut self, score: u8) -> Option<()> {
+        let review_count = self.review_count.checked_add(1)?;
+        let total = self
+            .average_score
+            .checked_mul(u64::from(self.review_count))?
+            .checked_add(u64::from(score).checked_mul(FEEDBACK_SCORE_SCALE)?)?;
+        self.average_score = total / u64::from(review_count);
+        self.review_count = review_count;
+        Some(())
+    }
+}
+
+impl Sealed for UserProfile {}
+
+impl IsInitialized for UserProfile {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for UserProfile {
+    const LEN: usize = 13;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, UserProfile::LEN];
+        let (is_initialized_dst, average_score_dst, review_count_dst) =
+            mut_array_refs![dst, 1, 8, 4];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *average_score_dst = self.average_score.to_le_bytes();
+        *review_count_dst = self.review_count.to_le_bytes();
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, UserProfile::LEN];
+        let (is_initialized, average_score, review_count) = array_refs![src, 1, 8, 4];
+
+        Ok(UserProfile {
+            is_initialized: unpack_bool(is_initialized)?,
+            average_score: u64::from_le_bytes(*average_score),
+            review_count: u32::from_le_bytes(*review_count),
+        })
+    }
+}
+
+pub struct GlobalConfig {
+    pub is_initialized: bool,
+    /// Wallet pubkey of the program authority
+    pub authority: Pubkey,
+    /// Share of each bid sent to the insurance vault, in basis points
+    pub insurance_fee_bps: u16,
+    /// Balance kept in an insurance vault when the authority withdraws surplus
+    pub insurance_reserve: u64,
+    /// Token-2022 transfer hook program allowed on exhibited NFTs, default when unset
+    pub transfer_hook_program: Pubkey,
+    /// Minimum time auctions accept bids for, in seconds
+    pub min_auction_duration_sec: u64,
+    /// Maximum sum of the bids of a wallet across all auctions, 0 for no cap
+    pub max_lifetime_bid: u64,
+    /// Seconds an auction winner can't list the won NFT again, 0 for no lock
+    pub nft_lock_period_sec: u64,
+    /// Price above which closing an auction needs the compliance signer, 0 for no limit
+    pub transaction_limit: u64,
+    /// Account signing the close of auctions above the transaction limit
+    pub compliance_signer: Pubkey,
+    /// Number of FT mints currently blacklisted
+    pub mint_blacklist_count: u32,
+    /// Number of wallets currently banned from bidding
+    pub global_ban_count: u32,
+}
+
+impl Sealed for GlobalConfig {}
+
+impl IsInitialized for GlobalConfig {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for GlobalConfig {
+    const LEN: usize = 147;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, GlobalConfig::LEN];
+        let (
+            is_initialized_dst,
+            authority_dst,
+            insurance_fee_bps_dst,
+            insurance_reserve_dst,
+            transfer_hook_program_dst,
+            min_auction_duration_sec_dst,
+            max_lifetime_bid_dst,
+            nft_lock_period_sec_dst,
+            transaction_limit_dst,
+            compliance_signer_dst,
+            mint_blacklist_count_dst,
+            global_ban_count_dst,
+        ) = mut_array_refs![dst, 1, 32, 2, 8, 32, 8, 8, 8, 8, 32, 4, 4];
+
+        let GlobalConfig {
+            is_initialized,
+            authority,
+            insurance_fee_bps,
+            insurance_reserve,
+            transfer_hook_program,
+            min_auction_duration_sec,
+            max_lifetime_bid,
+            nft_lock_period_sec,
+            transaction_limit,
+            compliance_signer,
+            mint_blacklist_count,
+            global_ban_count,
+        } = self;
+
+        is_initialized_dst[0] = *is_initialized as u8;
+        authority_dst.copy_from_slice(authority.as_ref());
+        *insurance_fee_bps_dst = insurance_fee_bps.to_le_bytes();
+        *insurance_reserve_dst = insurance_reserve.to_le_bytes();
+        transfer_hook_program_dst.copy_from_slice(transfer_hook_program.as_ref());
+        *min_auction_duration_sec_dst = min_auction_duration_sec.to_le_bytes();
+        *max_lifetime_bid_dst = max_lifetime_bid.to_le_bytes();
+        *nft_lock_period_sec_dst = nft_lock_period_sec.to_le_bytes();
+        *transaction_limit_dst = transaction_limit.to_le_bytes();
+        compliance_signer_dst.copy_from_slice(compliance_signer.as_ref());
+        *mint_blacklist_count_dst = mint_blacklist_count.to_le_bytes();
+        *global_ban_count_dst = global_ban_count.to_le_bytes();
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, GlobalConfig::LEN];
+        let (
+            is_initialized,
+            authority,
+            insurance_fee_bps,
+            insurance_reserve,
+            transfer_hook_program,
+            min_auction_duration_sec,
+            max_lifetime_bid,
+            nft_lock_period_sec,
+            transaction_limit,
+            compliance_signer,
+            mint_blacklist_count,
+            global_ban_count,
+        ) = array_refs![src, 1, 32, 2, 8, 32, 8, 8, 8, 8, 32, 4, 4];
+
+        Ok(GlobalConfig {
+            is_initialized: unpack_bool(is_initialized)?,
+            authority: Pubkey::new_from_array(*authority),
+            insurance_fee_bps: u16::from_le_bytes(*insurance_fee_bps),
+            insurance_reserve: u64::from_le_bytes(*insurance_reserve),
+            transfer_hook_program: Pubkey::new_from_array(*transfer_hook_program),
+            min_auction_duration_sec: u64::from_le_bytes(*min_auction_duration_sec),
+            max_lifetime_bid: u64::from_le_bytes(*max_lifetime_bid),
+            nft_lock_period_sec: u64::from_le_bytes(*nft_lock_period_sec),
+            transaction_limit: u64::from_le_bytes(*transaction_limit),
+            compliance_signer: Pubkey::new_from_array(*compliance_signer),
+            mint_blacklist_count: u32::from_le_bytes(*mint_blacklist_count),
+            global_ban_count: u32::from_le_bytes(*global_ban_count),
+        })
+    }
+}
+
+/// Aggregate metrics of all auctions
+pub struct ProgramStats {
+    pub is_initialized: bool,
+    /// Number of auctions ever exhibited
+    pub total_auctions: u64,
+    /// Sum of all bid prices
+    pub total_volume: u64,
+    /// Number of bids ever placed
+    pub total_bids: u64,
+    /// Number of auctions not closed yet
+    pub active_auctions: u32,
+}
+
+impl Sealed for ProgramStats {}
+
+impl IsInitialized for ProgramStats {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for ProgramStats {
+    const LEN: usize = 29;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, ProgramStats::LEN];
+        let (
+            is_initialized_dst,
+            total_auctions_dst,
+            total_volume_dst,
+            total_bids_dst,
+            active_auctions_dst,
+        ) = mut_array_refs![dst, 1, 8, 8, 8, 4];
+
+        let ProgramStats {
+            is_initialized,
+            total_auctions,
+            total_volume,
+            total_bids,
+            active_auctions,
+        } = self;
+
+        is_initialized_dst[0] = *is_initialized as u8;
+        *total_auctions_dst = total_auctions.to_le_bytes();
+        *total_volume_dst = total_volume.to_le_bytes();
+        *total_bids_dst = total_bids.to_le_bytes();
+        *active_auctions_dst = active_auctions.to_le_bytes();
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, ProgramStats::LEN];
+        let (is_initialized, total_auctions, total_volume, total_bids, active_auctions) =
+            array_refs![src, 1, 8, 8, 8, 4];
+
+        Ok(ProgramStats {
+            is_initialized: unpack_bool(is_initialized)?,
+            total_auctions: u64::from_le_bytes(*total_auctions),
+            total_volume: u64::from_le_bytes(*total_volume),
+            total_bids: u64::from_le_bytes(*total_bids),
+            active_auctions: u32::from_le_bytes(*active_auctions),
+        })
+    }
+}
+
+/// Latest auctions listed, either globally or per category
+pub struct AuctionIndex {
+    pub is_initialized: bool,
+    /// Number of auctions ever added to the index
+    pub total_count: u64,
+    /// Escrow pubkeys of the latest auctions, used as a ring buffer
+    pub escrows: [Pubkey; AUCTION_INDEX_CAPACITY],
+}
+
+impl AuctionIndex {
+    pub fn push(&mut self, escrow: Pubkey) {
+        let slot = (self.total_count % AUCTION_INDEX_CAPACITY as u64) as usize;
+        self.escrows[slot] = escrow;
+        self.total_count = self.total_count.wrapping_add(1);
+    }
+
+    pub fn remove(&mut self, escrow: &Pubkey) {
+        for entry in self.escrows.iter_mut().filter(|entry| *entry == escrow) {
+            *entry = Pubkey::default();
+        }
+    }
+}
+
+impl Sealed for AuctionIndex {}
+
+impl IsInitialized for AuctionIndex {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for AuctionIndex {
+    const LEN: usize = 9 + 32 * AUCTION_INDEX_CAPACITY;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, AuctionIndex::LEN];
+        let (is_initialized_dst, total_count_dst, escrows_dst) =
+            mut_array_refs![dst, 1, 8, 32 * AUCTION_INDEX_CAPACITY];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *total_count_dst = self.total_count.to_le_bytes();
+        for (escrow_dst, escrow) in escrows_dst.chunks_exact_mut(32).zip(&self.escrows) {
+            escrow_dst.copy_from_slice(escrow.as_ref());
+        }
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, AuctionIndex::LEN];
+        let (is_initialized, total_count, escrows_src) =
+            array_refs![src, 1, 8, 32 * AUCTION_INDEX_CAPACITY];
+
+        let mut escrows = [Pubkey::default(); AUCTION_INDEX_CAPACITY];
+        for (escrow, escrow_src) in escrows.iter_mut().zip(escrows_src.chunks_exact(32)) {
+            *escrow = Pubkey::new_from_array(*array_ref![escrow_src, 0, 32]);
+        }
+
+        Ok(AuctionIndex {
+            is_initialized: unpack_bool(is_initialized)?,
+            total_count: u64::from_le_bytes(*total_count),
+            escrows,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct BidRecord {
+    /// Wallet pubkey of the bidder
+    pub bidder: Pubkey,
+    /// Bid price
+    pub price: u64,
+    /// Time of the bid
+    pub bid_at: i64,
+}
+
+impl BidRecord {
+    pub const LEN: usize = 48;
+}
+
+/// Latest bids placed on an auction
+pub struct BidHistory {
+    pub is_initialized: bool,
+    /// Number of bids ever added to the history
+    pub total_count: u64,
+    /// Latest bids, used as a ring buffer
+    pub bids: [BidRecord; BID_HISTORY_CAPACITY],
+}
+
+impl BidHistory {
+    pub fn push(&mut self, bid: BidRecord) {
+        let slot = (self.total_count % BID_HISTORY_CAPACITY as u64) as usize;
+        self.bids[slot] = bid;
+        self.total_count = self.total_count.wrapping_add(1);
+    }
+}
+
+impl Sealed for BidHistory {}
+
+impl IsInitialized for BidHistory {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for BidHistory {
+    const LEN: usize = 9 + BidRecord::LEN * BID_HISTORY_CAPACITY;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, BidHistory::LEN];
+        let (is_initialized_dst, total_count_dst, bids_dst) =
+            mut_array_refs![dst, 1, 8, BidRecord::LEN * BID_HISTORY_CAPACITY];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *total_count_dst = self.total_count.to_le_bytes();
+        for (bid_dst, bid) in bids_dst.chunks_exact_mut(BidRecord::LEN).zip(&self.bids) {
+            let bid_dst = array_mut_ref![bid_dst, 0, BidRecord::LEN];
+            let (bidder_dst, price_dst, bid_at_dst) = mut_array_refs![bid_dst, 32, 8, 8];
+            bidder_dst.copy_from_slice(bid.bidder.as_ref());
+            *price_dst = bid.price.to_le_bytes();
+            *bid_at_dst = bid.bid_at.to_le_bytes();
+        }
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, BidHistory::LEN];
+        let (is_initialized, total_count, bids_src) =
+            array_refs![src, 1, 8, BidRecord::LEN * BID_HISTORY_CAPACITY];
+
+        let mut bids = [BidRecord::default(); BID_HISTORY_CAPACITY];
+        for (bid, bid_src) in bids.iter_mut().zip(bids_src.chunks_exact(BidRecord::LEN)) {
+            let bid_src = array_ref![bid_src, 0, BidRecord::LEN];
+            let (bidder, price, bid_at) = array_refs![bid_src, 32, 8, 8];
+            *bid = BidRecord {
+                bidder: Pubkey::new_from_array(*bidder),
+                price: u64::from_le_bytes(*price),
+                bid_at: i64::from_le_bytes(*bid_at),
+            };
+        }
+
+        Ok(BidHistory {
+            is_initialized: unpack_bool(is_initialized)?,
+            total_count: u64::from_le_bytes(*total_count),
+            bids,
+        })
+    }
+}
+
+/// Unique bidders of an auction capped by `max_bidders`
+pub struct BidderRegistry {
+    pub is_initialized: bool,
+    /// Bidder pubkeys sorted in ascending order, only the first `unique_bidder_count` are used
+    pub bidders: [Pubkey; BIDDER_REGISTRY_CAPACITY],
+}
+
+impl BidderRegistry {
+    /// Inserts the bidder keeping the registry sorted, returns false when already registered
+    pub fn insert(&mut self, count: usize, bidder: Pubkey) -> bool {
+        match self.bidders[..count].binary_search(&bidder) {
+            Ok(_) => false,
+            Err(position) => {
+                self.bidders.copy_within(position..count, position + 1);
+                self.bidders[position] = bidder;
+                true
+            }
+        }
+    }
+
+    pub fn contains(&self, count: usize, bidder: &Pubkey) -> bool {
+        self.bidders[..count].binary_search(bidder).is_ok()
+    }
+}
+
+impl Sealed for BidderRegistry {}
+
+impl IsInitialized for BidderRegistry {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for BidderRegistry {
+    const LEN: usize = 1 + 32 * BIDDER_REGISTRY_CAPACITY;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, BidderRegistry::LEN];
+        let (is_initialized_dst, bidders_dst) =
+            mut_array_refs![dst, 1, 32 * BIDDER_REGISTRY_CAPACITY];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        for (bidder_dst, bidder) in bidders_dst.chunks_exact_mut(32).zip(&self.bidders) {
+            bidder_dst.copy_from_slice(bidder.as_ref());
+        }
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, BidderRegistry::LEN];
+        let (is_initialized, bidders_src) = array_refs![src, 1, 32 * BIDDER_REGISTRY_CAPACITY];
+
+        let mut bidders = [Pubkey::default(); BIDDER_REGISTRY_CAPACITY];
+        for (bidder, bidder_src) in bidders.iter_mut().zip(bidders_src.chunks_exact(32)) {
+            *bidder = Pubkey::new_from_array(*array_ref![bidder_src, 0, 32]);
+        }
+
+        Ok(BidderRegistry {
+            is_initialized: unpack_bool(is_initialized)?,
+            bidders,
+        })
+    }
+}
+
+/// Participant registered to an airdrop auction
+pub struct RegistrationRecord {
+    pub is_initialized: bool,
+    /// Time the participant registered
+    pub registered_at: i64,
+}
+
+impl Sealed for RegistrationRecord {}
+
+impl IsInitialized for RegistrationRecord {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for RegistrationRecord {
+    const LEN: usize = 9;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, RegistrationRecord::LEN];
+        let (is_initialized_dst, registered_at_dst) = mut_array_refs![dst, 1, 8];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *registered_at_dst = self.registered_at.to_le_bytes();
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, RegistrationRecord::LEN];
+        let (is_initialized, registered_at) = array_refs![src, 1, 8];
+
+        Ok(RegistrationRecord {
+            is_initialized: unpack_bool(is_initialized)?,
+            registered_at: i64::from_le_bytes(*registered_at),
+        })
+    }
+}
+
+/// Length in bytes of an exhibitor note
+pub const EXHIBITOR_NOTE_LEN: usize = 256;
+
+pub struct ExhibitorNote {
+    pub is_initialized: bool,
+    /// Provenance note written by the exhibitor
+    pub note: [u8; EXHIBITOR_NOTE_LEN],
+    /// Time the note was set
+    pub signed_at: i64,
+}
+
+impl Sealed for ExhibitorNote {}
+
+impl IsInitialized for ExhibitorNote {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for ExhibitorNote {
+    const LEN: usize = 1 + EXHIBITOR_NOTE_LEN + 8;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, ExhibitorNote::LEN];
+        let (is_initialized_dst, note_dst, signed_at_dst) =
+            mut_array_refs![dst, 1, EXHIBITOR_NOTE_LEN, 8];
+
+        let ExhibitorNote {
+            is_initialized,
+            note,
+            signed_at,
+        } = self;
+
+        is_initialized_dst[0] = *is_initialized as u8;
+        note_dst.copy_from_slice(note);
+        *signed_at_dst = signed_at.to_le_bytes();
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, ExhibitorNote::LEN];
+        let (is_initialized, note, signed_at) = array_refs![src, 1, EXHIBITOR_NOTE_LEN, 8];
+
+        Ok(ExhibitorNote {
+            is_initialized: unpack_bool(is_initialized)?,
+            note: *note,
+            signed_at: i64::from_le_bytes(*signed_at),
+        })
+    }
+}
+
+/// Reference price of an FT denominated collection, maintained by the global config authority
+pub struct PriceOracle {
+    pub is_initialized: bool,
+    /// Reference price in the smallest FT unit
+    pub price: u64,
+    /// Time the price was last updated
+    pub updated_at: i64,
+}
+
+impl Sealed for PriceOracle {}
+
+impl IsInitialized for PriceOracle {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for PriceOracle {
+    const LEN: usize = 17;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, PriceOracle::LEN];
+        let (is_initialized_dst, price_dst, updated_at_dst) = mut_array_refs![dst, 1, 8, 8];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *price_dst = self.price.to_le_bytes();
+        *updated_at_dst = self.updated_at.to_le_bytes();
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, PriceOracle::LEN];
+        let (is_initialized, price, updated_at) = array_refs![src, 1, 8, 8];
+
+        Ok(PriceOracle {
+            is_initialized: unpack_bool(is_initialized)?,
+            price: u64::from_le_bytes(*price),
+            updated_at: i64::from_le_bytes(*updated_at),
+        })
+    }
+}
+
+/// Maximum number of entries kept in an auction changelog
+pub const CHANGELOG_CAPACITY: usize = 32;
+
+#[derive(Clone, Copy, Default)]
+pub struct ChangelogEntry {
+    /// Tag of the executed instruction
+    pub instruction: u8,
+    /// First signer of the instruction
+    pub actor: Pubkey,
+    /// Time the instruction was executed
+    pub timestamp: i64,
+    /// Instruction specific data, such as the price of a Bid
+    pub extra: [u8; 8],
+}
+
+impl ChangelogEntry {
+    pub const LEN: usize = 49;
+}
+
+/// Latest instructions executed on an auction
+pub struct ChangelogAccount {
+    pub is_initialized: bool,
+    /// Escrow account pubkey of the auction
+    pub escrow: Pubkey,
+    /// Number of entries ever added to the changelog
+    pub total_count: u64,
+    /// Latest entries, used as a ring buffer
+    pub entries: [ChangelogEntry; CHANGELOG_CAPACITY],
+}
+
+impl ChangelogAccount {
+    pub fn push(&mut self, entry: ChangelogEntry) {
+        let slot = (self.total_count % CHANGELOG_CAPACITY as u64) as usize;
+        self.entries[slot] = entry;
+        self.total_count = self.total_count.wrapping_add(1);
+    }
+
+    /// Stored entries from the oldest to the latest
+    pub fn ordered_entries(&self) -> impl Iterator<Item = &ChangelogEntry> {
+        let stored = self.total_count.min(CHANGELOG_CAPACITY as u64) as usize;
+        let start = (self.total_count - stored as u64) as usize;
+        (start..start + stored).map(move |i| &self.entries[i % CHANGELOG_CAPACITY])
+    }
+}
+
+impl Sealed for ChangelogAccount {}
+
+impl IsInitialized for ChangelogAccount {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for ChangelogAccount {
+    const LEN: usize = 41 + ChangelogEntry::LEN * CHANGELOG_CAPACITY;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, ChangelogAccount::LEN];
+        let (is_initialized_dst, escrow_dst, total_count_dst, entries_dst) =
+            mut_array_refs![dst, 1, 32, 8, ChangelogEntry::LEN * CHANGELOG_CAPACITY];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        escrow_dst.copy_from_slice(self.escrow.as_ref());
+        *total_count_dst = self.total_count.to_le_bytes();
+        for (entry_dst, entry) in entries_dst
+            .chunks_exact_mut(ChangelogEntry::LEN)
+            .zip(&self.entries)
+        {
+            let entry_dst = array_mut_ref![entry_dst, 0, ChangelogEntry::LEN];
+            let (instruction_dst, actor_dst, timestamp_dst, extra_dst) =
+                mut_array_refs![entry_dst, 1, 32, 8, 8];
+            instruction_dst[0] = entry.instruction;
+            actor_dst.copy_from_slice(entry.actor.as_ref());
+            *timestamp_dst = entry.timestamp.to_le_bytes();
+            *extra_dst = entry.extra;
+        }
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, ChangelogAccount::LEN];
+        let (is_initialized, escrow, total_count, entries_src) =
+            array_refs![src, 1, 32, 8, ChangelogEntry::LEN * CHANGELOG_CAPACITY];
+
+        let mut entries = [ChangelogEntry::default(); CHANGELOG_CAPACITY];
+        for (entry, entry_src) in entries
+            .iter_mut()
+            .zip(entries_src.chunks_exact(ChangelogEntry::LEN))
+        {
+            let entry_src = array_ref![entry_src, 0, ChangelogEntry::LEN];
+            let (instruction, actor, timestamp, extra) = array_refs![entry_src, 1, 32, 8, 8];
+            *entry = ChangelogEntry {
+                instruction: instruction[0],
+                actor: Pubkey::new_from_array(*actor),
+                timestamp: i64::from_le_bytes(*timestamp),
+                extra: *extra,
+            };
+        }
+
+        Ok(ChangelogAccount {
+            is_initialized: unpack_bool(is_initialized)?,
+            escrow: Pubkey::new_from_array(*escrow),
+            total_count: u64::from_le_bytes(*total_count),
+            entries,
+        })
+    }
+}
+
+fn unpack_bool(src: &[u8; 1]) -> Result<bool, ProgramError> {
+    match src {
+        [0] => Ok(false),
+        [1] => Ok(true),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+fn pack_fractional_owners(
+    owners: &[(Pubkey, u16); MAX_FRACTIONAL_OWNERS],
+) -> [u8; 34 * MAX_FRACTIONAL_OWNERS] {
+    let mut dst = [0; 34 * MAX_FRACTIONAL_OWNERS];
+    for (owner_dst, (owner_ft_pubkey, share_bps)) in dst.chunks_exact_mut(34).zip(owners) {
+        let owner_dst = array_mut_ref![owner_dst, 0, 34];
+        let (owner_ft_pubkey_dst, share_bps_dst) = mut_array_refs![owner_dst, 32, 2];
+        owner_ft_pubkey_dst.copy_from_slice(owner_ft_pubkey.as_ref());
+        *share_bps_dst = share_bps.to_le_bytes();
+    }
+    dst
+}
+
+fn unpack_fractional_owners(
+    src: &[u8; 34 * MAX_FRACTIONAL_OWNERS],
+) -> [(Pubkey, u16); MAX_FRACTIONAL_OWNERS] {
+    let mut owners = [(Pubkey::default(), 0); MAX_FRACTIONAL_OWNERS];
+    for (owner, owner_src) in owners.iter_mut().zip(src.chunks_exact(34)) {
+        let owner_src = array_ref![owner_src, 0, 34];
+        let (owner_ft_pubkey, share_bps) = array_refs![owner_src, 32, 2];
+        *owner = (
+            Pubkey::new_from_array(*owner_ft_pubkey),
+            u16::from_le_bytes(*share_bps),
+        );
+    }
+    owners
+}
+
+fn pack_price_curve(tiers: &[(u64, u16); MAX_PRICE_TIERS]) -> [u8; 10 * MAX_PRICE_TIERS] {
+    let mut dst = [0; 10 * MAX_PRICE_TIERS];
+    for (tier_dst, (min_bid, increment_bps)) in dst.chunks_exact_mut(10).zip(tiers) {
+        let tier_dst = array_mut_ref![tier_dst, 0, 10];
+        let (min_bid_dst, increment_bps_dst) = mut_array_refs![tier_dst, 8, 2];
+        *min_bid_dst = min_bid.to_le_bytes();
+        *increment_bps_dst = increment_bps.to_le_bytes();
+    }
+    dst
+}
+
+fn unpack_price_curve(src: &[u8; 10 * MAX_PRICE_TIERS]) -> [(u64, u16); MAX_PRICE_TIERS] {
+    let mut tiers = [(0, 0); MAX_PRICE_TIERS];
+    for (tier, tier_src) in tiers.iter_mut().zip(src.chunks_exact(10)) {
+        let tier_src = array_ref![tier_src, 0, 10];
+        let (min_bid, increment_bps) = array_refs![tier_src, 8, 2];
+        *tier = (
+            u64::from_le_bytes(*min_bid),
+            u16::from_le_bytes(*increment_bps),
+        );
+    }
+    tiers
+}
+
+fn pack_pubkeys(pubkeys: &[Pubkey; MAX_ACCEPTED_MINTS]) -> [u8; 32 * MAX_ACCEPTED_MINTS] {
+    let mut dst = [0; 32 * MAX_ACCEPTED_MINTS];
+    for (pubkey_dst, pubkey) in dst.chunks_exact_mut(32).zip(pubkeys) {
+        pubkey_dst.copy_from_slice(pubkey.as_ref());
+    }
+    dst
+}
+
+fn unpack_pubkeys(src: &[u8; 32 * MAX_ACCEPTED_MINTS]) -> [Pubkey; MAX_ACCEPTED_MINTS] {
+    let mut pubkeys = [Pubkey::default(); MAX_ACCEPTED_MINTS];
+    for (pubkey, pubkey_src) in pubkeys.iter_mut().zip(src.chunks_exact(32)) {
+        *pubkey = Pubkey::new_from_array(*array_ref![pubkey_src, 0, 32]);
+    }
+    pubkeys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Auction whose fields all differ from their default and from each other
+    fn sample_auction() -> Auction {
+        Auction {
+            is_initialized: true,
+            exhibitor_pubkey: Pubkey::new_from_array([2; 32]),
+            exhibiting_nft_temp_pubkey: Pubkey::new_from_array([3; 32]),
+            exhibitor_ft_receiving_pubkey: Pubkey::new_from_array([4; 32]),
+            price: 5,
+            end_at: 6,
+            highest_bidder_pubkey: Pubkey::new_from_array([7; 32]),
+            highest_bidder_ft_temp_pubkey: Pubkey::new_from_array([8; 32]),
+            highest_bidder_ft_returning_pubkey: Pubkey::new_from_array([9; 32]),
+            bid_locked: true,
+            pda_bump: 11,
+            max_bidder_extension_sec: 12,
+            bidder_extended: true,
+            floor_price: 14,
+            category: AuctionCategory::Utility,
+            max_bidders: 16,
+            unique_bidder_count: 17,
+            terms_accepted: true,
+            terms_hash: [19; 32],
+            ft_mint: Pubkey::new_from_array([20; 32]),
+            airdrop_winner: Pubkey::new_from_array([21; 32]),
+            auto_close: true,
+            refund_on_cancel: true,
+            bid_escrow_amount: 24,
+            advanced_amount: 25,
+            bids_visible: true,
+            expected_nft_mint: Pubkey::new_from_array([27; 32]),
+            mediator_pubkey: Pubkey::new_from_array([28; 32]),
+            starts_at: 29,
+            version: AUCTION_VERSION,
+            oracle_pubkey: Pubkey::new_from_array([31; 32]),
+            oracle_min_price: 32,
+            upfront_royalty_bps: 33,
+            royalty_creator_ft_pubkey: Pubkey::new_from_array([34; 32]),
+            upfront_royalty_paid: true,
+            close_grace_period_sec: 36,
+            close_authority: Pubkey::new_from_array([37; 32]),
+            priority_bidder: Pubkey::new_from_array([38; 32]),
+            is_soulbound: true,
+            min_bid_increment: 40,
+            bid_multiplier_bps: 41,
+            room: Pubkey::new_from_array([42; 32]),
+            room_organizer: Pubkey::new_from_array([43; 32]),
+            room_fee_bps: 44,
+            whitelist_root: [45; 32],
+            bonus_amount: 46,
+            cross_program_bid: true,
+            cpi_authority: Pubkey::new_from_array([48; 32]),
+            finality_timeout_sec: 49,
+            finalized_at: 50,
+            is_finalized: true,
+            is_disputed: true,
+            fractional_owners: std::array::from_fn(|i| {
+                (Pubkey::new_from_array([53 + i as u8; 32]), 53 + i as u16)
+            }),
+            highest_bidder_vault_program: Pubkey::new_from_array([63; 32]),
+            highest_bidder_vault: Pubkey::new_from_array([64; 32]),
+            highest_bidder_vault_ft: Pubkey::new_from_array([65; 32]),
+            penalty_bps: 66,
+            payment_grace_period_sec: 67,
+            gate_token_mint: Pubkey::new_from_array([68; 32]),
+            gate_token_min_amount: 69,
+            lot_size: 70,
+            accepted_mints: std::array::from_fn(|i| Pubkey::new_from_array([71 + i as u8; 32])),
+            accepted_ft_receiving_pubkeys: std::array::from_fn(|i| {
+                Pubkey::new_from_array([81 + i as u8; 32])
+            }),
+            idle_period_sec: 91,
+            max_duration_sec: 92,
+            last_bid_at: 93,
+            winner_override: Pubkey::new_from_array([94; 32]),
+            audit_oracle: Pubkey::new_from_array([95; 32]),
+            audit_pending: true,
+            nft_lock_period_sec: 97,
+            transaction_limit: 98,
+            burn_on_cancel: true,
+            twap_numerator: 100,
+            twap_denominator: 101,
+            nft_update_authority: Pubkey::new_from_array([102; 32]),
+            allow_updates_during_auction: true,
+            trustline_program: Pubkey::new_from_array([104; 32]),
+            trustline_type: [105; 8],
+            auction_type: AuctionType::SlidingExpiry,
+            exhibitor_collateral: 107,
+            cancel_penalty_bps: 108,
+            bid_nonce: 109,
+            clock_drift_tolerance_sec: 110,
+            requires_approval: true,
+            anti_sandwich: true,
+            last_block_bidder: Pubkey::new_from_array([113; 32]),
+            last_block_bid_price: 114,
+            last_block_bid_slot: 115,
+            price_curve: std::array::from_fn(|i| (116 + i as u64, 116 + i as u16)),
+            early_bird_period_bps: 126,
+            early_bird_increment_bps: 127,
+            late_increment_bps: 128,
+            reveal_end_at_time: 129,
+            hidden_end_at_revealed: true,
+            bid_weight_oracle: Pubkey::new_from_array([131; 32]),
+            highest_bid_weight_bps: 132,
+            has_changelog: true,
+            changelog_bump: 134,
+            bid_history_bump: 135,
+            bidder_registry_bump: 136,
+            insurance_vault_bump: 137,
+            migrated_to: Pubkey::new_from_array([138; 32]),
+        }
+    }
+
+    #[test]
+    fn auction_round_trips_every_field() {
+        let auction = sample_auction();
+        let mut packed = [0; Auction::LEN];
+        Auction::pack(sample_auction(), &mut packed).unwrap();
+        let unpacked = Auction::unpack(&packed).unwrap();
+
+        assert_eq!(unpacked.is_initialized, auction.is_initialized);
+        assert_eq!(unpacked.exhibitor_pubkey, auction.exhibitor_pubkey);
+        assert_eq!(
+            unpacked.exhibiting_nft_temp_pubkey,
+            auction.exhibiting_nft_temp_pubkey
+        );
+        assert_eq!(
+            unpacked.exhibitor_ft_receiving_pubkey,
+            auction.exhibitor_ft_receiving_pubkey
+        );
+        assert_eq!(unpacked.price, auction.price);
+        assert_eq!(unpacked.end_at, auction.end_at);
+        assert_eq!(
+            unpacked.highest_bidder_pubkey,
+            auction.highest_bidder_pubkey
+        );
+        assert_eq!(
+            unpacked.highest_bidder_ft_temp_pubkey,
+            auction.highest_bidder_ft_temp_pubkey
+        );
+        assert_eq!(
+            unpacked.highest_bidder_ft_returning_pubkey,
+            auction.highest_bidder_ft_returning_pubkey
+        );
+        assert_eq!(unpacked.bid_locked, auction.bid_locked);
+        assert_eq!(unpacked.pda_bump, auction.pda_bump);
+        assert_eq!(
+            unpacked.max_bidder_extension_sec,
+            auction.max_bidder_extension_sec
+        );
+        assert_eq!(unpacked.bidder_extended, auction.bidder_extended);
+        assert_eq!(unpacked.floor_price, auction.floor_price);
+        assert_eq!(unpacked.category, auction.category);
+        assert_eq!(unpacked.max_bidders, auction.max_bidders);
+        assert_eq!(unpacked.unique_bidder_count, auction.unique_bidder_count);
+        assert_eq!(unpacked.terms_accepted, auction.terms_accepted);
+        assert_eq!(unpacked.terms_hash, auction.terms_hash);
+        assert_eq!(unpacked.ft_mint, auction.ft_mint);
+        assert_eq!(unpacked.airdrop_winner, auction.airdrop_winner);
+        assert_eq!(unpacked.auto_close, auction.auto_close);
+        assert_eq!(unpacked.refund_on_cancel, auction.refund_on_cancel);
+        assert_eq!(unpacked.bid_escrow_amount, auction.bid_escrow_amount);
+        assert_eq!(unpacked.advanced_amount, auction.advanced_amount);
+        assert_eq!(unpacked.bids_visible, auction.bids_visible);
+        assert_eq!(unpacked.expected_nft_mint, auction.expected_nft_mint);
+        assert_eq!(unpacked.mediator_pubkey, auction.mediator_pubkey);
+        assert_eq!(unpacked.starts_at, auction.starts_at);
+        assert_eq!(unpacked.version, auction.version);
+        assert_eq!(unpacked.oracle_pubkey, auction.oracle_pubkey);
+        assert_eq!(unpacked.oracle_min_price, auction.oracle_min_price);
+        assert_eq!(unpacked.upfront_royalty_bps, auction.upfront_royalty_bps);
+        assert_eq!(
+            unpacked.royalty_creator_ft_pubkey,
+            auction.royalty_creator_ft_pubkey
+        );
+        assert_eq!(unpacked.upfront_royalty_paid, auction.upfront_royalty_paid);
+        assert_eq!(
+            unpacked.close_grace_period_sec,
+            auction.close_grace_period_sec
+        );
+        assert_eq!(unpacked.close_authority, auction.close_authority);
+        assert_eq!(unpacked.priority_bidder, auction.priority_bidder);
+        assert_eq!(unpacked.is_soulbound, auction.is_soulbound);
+        assert_eq!(unpacked.min_bid_increment, auction.min_bid_increment);
+        assert_eq!(unpacked.bid_multiplier_bps, auction.bid_multiplier_bps);
+        assert_eq!(unpacked.room, auction.room);
+        assert_eq!(unpacked.room_organizer, auction.room_organizer);
+        assert_eq!(unpacked.room_fee_bps, auction.room_fee_bps);
+        assert_eq!(unpacked.whitelist_root, auction.whitelist_root);
+        assert_eq!(unpacked.bonus_amount, auction.bonus_amount);
+        assert_eq!(unpacked.cross_program_bid, auction.cross_program_bid);
+        assert_eq!(unpacked.cpi_authority, auction.cpi_authority);
+        assert_eq!(unpacked.finality_timeout_sec, auction.finality_timeout_sec);
+        assert_eq!(unpacked.finalized_at, auction.finalized_at);
+        assert_eq!(unpacked.is_finalized, auction.is_finalized);
+        assert_eq!(unpacked.is_disputed, auction.is_disputed);
+        assert_eq!(unpacked.fractional_owners, auction.fractional_owners);
+        assert_eq!(
+            unpacked.highest_bidder_vault_program,
+            auction.highest_bidder_vault_program
+        );
+        assert_eq!(unpacked.highest_bidder_vault, auction.highest_bidder_vault);
+        assert_eq!(
+            unpacked.highest_bidder_vault_ft,
+            auction.highest_bidder_vault_ft
+        );
+        assert_eq!(unpacked.penalty_bps, auction.penalty_bps);
+        assert_eq!(
+            unpacked.payment_grace_period_sec,
+            auction.payment_grace_period_sec
+        );
+        assert_eq!(unpacked.gate_token_mint, auction.gate_token_mint);
+        assert_eq!(
+            unpacked.gate_token_min_amount,
+            auction.gate_token_min_amount
+        );
+        assert_eq!(unpacked.lot_size, auction.lot_size);
+        assert_eq!(unpacked.accepted_mints, auction.accepted_mints);
+        assert_eq!(
+            unpacked.accepted_ft_receiving_pubkeys,
+            auction.accepted_ft_receiving_pubkeys
+        );
+        assert_eq!(unpacked.idle_period_sec, auction.idle_period_sec);
+        assert_eq!(unpacked.max_duration_sec, auction.max_duration_sec);
+        assert_eq!(unpacked.last_bid_at, auction.last_bid_at);
+        assert_eq!(unpacked.winner_override, auction.winner_override);
+        assert_eq!(unpacked.audit_oracle, auction.audit_oracle);
+        assert_eq!(unpacked.audit_pending, auction.audit_pending);
+        assert_eq!(unpacked.nft_lock_period_sec, auction.nft_lock_period_sec);
+        assert_eq!(unpacked.transaction_limit, auction.transaction_limit);
+        assert_eq!(unpacked.burn_on_cancel, auction.burn_on_cancel);
+        assert_eq!(unpacked.twap_numerator, auction.twap_numerator);
+        assert_eq!(unpacked.twap_denominator, auction.twap_denominator);
+        assert_eq!(unpacked.nft_update_authority, auction.nft_update_authority);
+        assert_eq!(
+            unpacked.allow_updates_during_auction,
+            auction.allow_updates_during_auction
+        );
+        assert_eq!(unpacked.trustline_program, auction.trustline_program);
+        assert_eq!(unpacked.trustline_type, auction.trustline_type);
+        assert_eq!(unpacked.auction_type, auction.auction_type);
+        assert_eq!(unpacked.exhibitor_collateral, auction.exhibitor_collateral);
+        assert_eq!(unpacked.cancel_penalty_bps, auction.cancel_penalty_bps);
+        assert_eq!(unpacked.bid_nonce, auction.bid_nonce);
+        assert_eq!(
+            unpacked.clock_drift_tolerance_sec,
+            auction.clock_drift_tolerance_sec
+        );
+        assert_eq!(unpacked.requires_approval, auction.requires_approval);
+        assert_eq!(unpacked.anti_sandwich, auction.anti_sandwich);
+        assert_eq!(unpacked.last_block_bidder, auction.last_block_bidder);
+        assert_eq!(unpacked.last_block_bid_price, auction.last_block_bid_price);
+        assert_eq!(unpacked.last_block_bid_slot, auction.last_block_bid_slot);
+        assert_eq!(unpacked.price_curve, auction.price_curve);
+        assert_eq!(
+            unpacked.early_bird_period_bps,
+            auction.early_bird_period_bps
+        );
+        assert_eq!(
+            unpacked.early_bird_increment_bps,
+            auction.early_bird_increment_bps
+        );
+        assert_eq!(unpacked.late_increment_bps, auction.late_increment_bps);
+        assert_eq!(unpacked.reveal_end_at_time, auction.reveal_end_at_time);
+        assert_eq!(
+            unpacked.hidden_end_at_revealed,
+            auction.hidden_end_at_revealed
+        );
+        assert_eq!(unpacked.bid_weight_oracle, auction.bid_weight_oracle);
+        assert_eq!(
+            unpacked.highest_bid_weight_bps,
+            auction.highest_bid_weight_bps
+        );
+        assert_eq!(unpacked.has_changelog, auction.has_changelog);
+        assert_eq!(unpacked.changelog_bump, auction.changelog_bump);
+        assert_eq!(unpacked.bid_history_bump, auction.bid_history_bump);
+        assert_eq!(unpacked.bidder_registry_bump, auction.bidder_registry_bump);
+        assert_eq!(unpacked.insurance_vault_bump, auction.insurance_vault_bump);
+        assert_eq!(unpacked.migrated_to, auction.migrated_to);
+
+        let mut repacked = [0; Auction::LEN];
+        Auction::pack(unpacked, &mut repacked).unwrap();
+        assert_eq!(repacked, packed);
+    }
+
+    #[test]
+    fn layout_version_reads_the_version_byte() {
+        let mut packed = [0; Auction::LEN];
+        Auction::pack(sample_auction(), &mut packed).unwrap();
+        assert_eq!(packed[AUCTION_VERSION_OFFSET], AUCTION_VERSION);
+        assert_eq!(Auction::layout_version(&packed), Some(AUCTION_VERSION));
+
+        packed[AUCTION_VERSION_OFFSET] = AUCTION_VERSION + 1;
+        assert_eq!(Auction::layout_version(&packed), None);
+    }
+
+    #[test]
+    fn only_the_first_release_and_the_current_layout_are_supported() {
+        let mut packed = [0; Auction::LEN];
+        Auction::pack(sample_auction(), &mut packed).unwrap();
+        for version in 2..AUCTION_VERSION {
+            assert_eq!(Auction::layout_len(version), None);
+            packed[AUCTION_VERSION_OFFSET] = version;
+            for len in [AUCTION_VERSION_OFFSET + 1, 1728, 1730, 1733, Auction::LEN] {
+                assert_eq!(Auction::layout_version(&packed[..len]), None);
+            }
+        }
+        assert_eq!(Auction::layout_len(1), Some(AUCTION_V1_LEN));
+        assert_eq!(Auction::layout_len(AUCTION_VERSION), Some(Auction::LEN));
+    }
+
+    #[test]
+    fn layout_version_of_the_first_release_has_no_version_byte() {
+        assert_eq!(Auction::layout_version(&[1; AUCTION_V1_LEN]), Some(1));
+        assert_eq!(Auction::layout_version(&[1; AUCTION_V1_LEN + 1]), None);
+        assert_eq!(Auction::layout_version(&[]), None);
+    }
+}
@@ -0,0 +1,35 @@
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::pubkey::Pubkey;
+
+/// Instruction tag of a trustline verification
+pub const TRUSTLINE_VERIFY_TAG: u8 = 0;
+
+/// Identity verification program gating bids, such as a KYC gateway, as called by `Bid`
+/// Instruction data is the tag followed by the bidder pubkey and the trustline type, the
+/// verification fails the bid by returning an error
+pub trait TrustlineVerifier {
+    /// Program id of the trustline program
+    fn program_id(&self) -> Pubkey;
+
+    /// Checks that `bidder` holds a trustline of `trustline_type`
+    fn verify(&self, bidder: &Pubkey, trustline_type: &[u8; 8]) -> Instruction {
+        let mut data = Vec::with_capacity(41);
+        data.push(TRUSTLINE_VERIFY_TAG);
+        data.extend_from_slice(bidder.as_ref());
+        data.extend_from_slice(trustline_type);
+        Instruction::new_with_bytes(
+            self.program_id(),
+            &data,
+            vec![AccountMeta::new_readonly(*bidder, false)],
+        )
+    }
+}
+
+/// Trustline program implementing the interface as is
+pub struct StandardTrustline(pub Pubkey);
+
+impl TrustlineVerifier for StandardTrustline {
+    fn program_id(&self) -> Pubkey {
+        self.0
+    }
+}
@@ -0,0 +1,70 @@
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::pubkey::Pubkey;
+
+/// Instruction tag of a vault deposit
+pub const VAULT_DEPOSIT_TAG: u8 = 0;
+
+/// Instruction tag of a vault withdrawal
+pub const VAULT_WITHDRAW_TAG: u8 = 1;
+
+/// Accounts taken by both vault instructions, in this order
+pub struct VaultAccounts {
+    /// `[writable]` Position account of the depositor in the vault
+    pub vault: Pubkey,
+    /// `[writable]` FT account of the vault
+    pub vault_ft: Pubkey,
+    /// `[writable]` FT account deposited from or withdrawn to
+    pub ft_account: Pubkey,
+    /// `[signer]` Authority of `ft_account` on deposit, owner of the position on withdrawal
+    pub authority: Pubkey,
+    /// `[]` The token program
+    pub token_program: Pubkey,
+}
+
+/// Yield vault holding FT on behalf of its depositors, as called by `VaultBid`
+/// Instruction data is the tag followed by the little endian amount
+pub trait VaultInterface {
+    /// Program id of the vault
+    fn program_id(&self) -> Pubkey;
+
+    /// Credits `amount` FT from `ft_account` to the position
+    fn deposit(&self, accounts: &VaultAccounts, amount: u64) -> Instruction {
+        vault_instruction(self.program_id(), VAULT_DEPOSIT_TAG, accounts, amount)
+    }
+
+    /// Debits `amount` FT from the position into `ft_account`
+    fn withdraw(&self, accounts: &VaultAccounts, amount: u64) -> Instruction {
+        vault_instruction(self.program_id(), VAULT_WITHDRAW_TAG, accounts, amount)
+    }
+}
+
+/// Vault program implementing the interface as is
+pub struct StandardVault(pub Pubkey);
+
+impl VaultInterface for StandardVault {
+    fn program_id(&self) -> Pubkey {
+        self.0
+    }
+}
+
+fn vault_instruction(
+    program_id: Pubkey,
+    tag: u8,
+    accounts: &VaultAccounts,
+    amount: u64,
+) -> Instruction {
+    let mut data = Vec::with_capacity(9);
+    data.push(tag);
+    data.extend_from_slice(&amount.to_le_bytes());
+    Instruction::new_with_bytes(
+        program_id,
+        &data,
+        vec![
+            AccountMeta::new(accounts.vault, false),
+            AccountMeta::new(accounts.vault_ft, false),
+            AccountMeta::new(accounts.ft_account, false),
+            AccountMeta::new_readonly(accounts.authority, true),
+            AccountMeta::new_readonly(accounts.token_program, false),
+        ],
+    )
+}
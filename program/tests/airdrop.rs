@@ -0,0 +1,214 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use auction_pal::state::{ProgramStats, RegistrationRecord};
+use common::auction::{pda, setup, Listing};
+use common::Bank;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::{system_program, sysvar};
+
+fn registration(bank: &Bank, listing: &Listing, participant: &Pubkey) -> Pubkey {
+    pda(
+        &[
+            b"registration",
+            listing.escrow.as_ref(),
+            participant.as_ref(),
+        ],
+        &bank.program_id,
+    )
+}
+
+fn register_ix(bank: &Bank, listing: &Listing, participant: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[21],
+        vec![
+            AccountMeta::new(*participant, true),
+            AccountMeta::new_readonly(listing.escrow, false),
+            AccountMeta::new(registration(bank, listing, participant), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+fn select_winner_ix(
+    bank: &Bank,
+    listing: &Listing,
+    signer: &Pubkey,
+    winner: &Pubkey,
+) -> Instruction {
+    let mut data = vec![22];
+    data.extend_from_slice(winner.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(registration(bank, listing, winner), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+    )
+}
+
+fn claim_airdrop_ix(
+    bank: &Bank,
+    listing: &Listing,
+    claimer: &Pubkey,
+    claimer_nft: &Pubkey,
+) -> Instruction {
+    let program_id = &bank.program_id;
+    Instruction::new_with_bytes(
+        *program_id,
+        &[23],
+        vec![
+            AccountMeta::new_readonly(*claimer, true),
+            AccountMeta::new(listing.exhibitor, false),
+            AccountMeta::new(listing.nft_temp, false),
+            AccountMeta::new(*claimer_nft, false),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(pda(&[b"escrow"], program_id), false),
+            AccountMeta::new(pda(&[b"stats"], program_id), false),
+        ],
+    )
+}
+
+#[test]
+fn participants_register_once_until_the_auction_ends() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let participant = bank.create_wallet();
+
+    bank.process(register_ix(&bank, &listing, &participant))
+        .unwrap();
+    let record: RegistrationRecord = bank.unpack(&registration(&bank, &listing, &participant));
+    assert_eq!(record.registered_at, bank.clock().unix_timestamp);
+    assert_eq!(
+        bank.process(register_ix(&bank, &listing, &participant)),
+        Err(ProgramError::AccountAlreadyInitialized)
+    );
+
+    listing.expire(&mut bank);
+    let latecomer = bank.create_wallet();
+    assert_eq!(
+        bank.process(register_ix(&bank, &listing, &latecomer)),
+        Err(AuctionError::InactiveAuction.into())
+    );
+    assert!(bank
+        .account(&registration(&bank, &listing, &latecomer))
+        .is_none());
+}
+
+#[test]
+fn exhibitor_selects_only_a_registered_winner() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let participant = bank.create_wallet();
+    bank.process(register_ix(&bank, &listing, &participant))
+        .unwrap();
+    assert_eq!(
+        bank.process(select_winner_ix(
+            &bank,
+            &listing,
+            &listing.exhibitor,
+            &participant
+        )),
+        Err(AuctionError::ActiveAuction.into())
+    );
+
+    listing.expire(&mut bank);
+    let outsider = bank.create_wallet();
+    assert_eq!(
+        bank.process(select_winner_ix(
+            &bank,
+            &listing,
+            &listing.exhibitor,
+            &outsider
+        )),
+        Err(AuctionError::NotRegistered.into())
+    );
+    assert_eq!(
+        bank.process(select_winner_ix(&bank, &listing, &outsider, &participant)),
+        Err(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(listing.auction(&bank).airdrop_winner, Pubkey::default());
+
+    bank.process(select_winner_ix(
+        &bank,
+        &listing,
+        &listing.exhibitor,
+        &participant,
+    ))
+    .unwrap();
+    assert_eq!(listing.auction(&bank).airdrop_winner, participant);
+}
+
+#[test]
+fn winner_cannot_be_selected_once_bid_on() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let participant = bank.create_wallet();
+    bank.process(register_ix(&bank, &listing, &participant))
+        .unwrap();
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+
+    listing.expire(&mut bank);
+    assert_eq!(
+        bank.process(select_winner_ix(
+            &bank,
+            &listing,
+            &listing.exhibitor,
+            &participant
+        )),
+        Err(AuctionError::AlreadyBid.into())
+    );
+}
+
+#[test]
+fn selected_winner_claims_the_airdrop_once() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let winner = bank.create_wallet();
+    let other = bank.create_wallet();
+    for participant in [&winner, &other] {
+        bank.process(register_ix(&bank, &listing, participant))
+            .unwrap();
+    }
+    listing.expire(&mut bank);
+    bank.process(select_winner_ix(
+        &bank,
+        &listing,
+        &listing.exhibitor,
+        &winner,
+    ))
+    .unwrap();
+
+    let other_nft = listing.nft_account(&mut bank, &other);
+    assert_eq!(
+        bank.process(claim_airdrop_ix(&bank, &listing, &other, &other_nft)),
+        Err(AuctionError::Unauthorized.into())
+    );
+
+    let winner_nft = listing.nft_account(&mut bank, &winner);
+    bank.process(claim_airdrop_ix(&bank, &listing, &winner, &winner_nft))
+        .unwrap();
+    assert_eq!(bank.token_balance(&winner_nft), 1);
+    assert!(bank.account(&listing.escrow).is_none());
+    let stats: ProgramStats = bank.unpack(&pda(&[b"stats"], &bank.program_id));
+    assert_eq!(stats.active_auctions, 0);
+
+    let second_nft = listing.nft_account(&mut bank, &winner);
+    assert_eq!(
+        bank.process(claim_airdrop_ix(&bank, &listing, &winner, &second_nft)),
+        Err(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(bank.token_balance(&second_nft), 0);
+}
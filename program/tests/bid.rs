@@ -0,0 +1,2300 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use std::cell::RefCell;
+
+use auction_pal::bid_weight::{BID_WEIGHT_QUERY_TAG, UNIT_BID_WEIGHT_BPS};
+use auction_pal::error::AuctionError;
+use auction_pal::event::BidEvent;
+use auction_pal::state::{
+    AuctionType, BidApproval, BidHistory, LifetimeBidVolume, MAX_BATCH_BID, MAX_PRICE_TIERS,
+};
+use auction_pal::trustline::TRUSTLINE_VERIFY_TAG;
+use auction_pal::vault::{VAULT_DEPOSIT_TAG, VAULT_WITHDRAW_TAG};
+use common::auction::{
+    bid_data, deduct_royalty_before_escrow_ix, pda, setup, Bidder, Listing, AUCTION_DURATION_SEC,
+    BIDDER_BALANCE, INITIAL_PRICE,
+};
+use common::{ed25519_ix, Account, Bank, WALLET_LAMPORTS};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::hash::hashv;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program::{invoke, invoke_signed, set_return_data};
+use solana_program::program_error::ProgramError;
+use solana_program::program_option::COption;
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::{system_program, sysvar};
+
+#[test]
+fn each_bid_is_locked_under_the_escrow_pda() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let escrow_pda = pda(&[b"escrow"], &bank.program_id);
+    assert!(!listing.auction(&bank).bid_locked);
+
+    let first = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &first, 150).unwrap();
+    let auction = listing.auction(&bank);
+    assert!(auction.bid_locked);
+    assert_eq!(
+        bank.token_account(&auction.highest_bidder_ft_temp_pubkey)
+            .owner,
+        escrow_pda
+    );
+
+    let second = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &second, 300).unwrap();
+    let auction = listing.auction(&bank);
+    assert!(auction.bid_locked);
+    assert_eq!(auction.highest_bidder_pubkey, second.wallet);
+    assert_eq!(
+        bank.token_account(&auction.highest_bidder_ft_temp_pubkey)
+            .owner,
+        escrow_pda
+    );
+    assert_eq!(bank.token_balance(&first.ft_account), BIDDER_BALANCE);
+}
+
+#[test]
+fn bid_temp_account_cannot_collide_with_other_ft_accounts() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let first = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &first, 150).unwrap();
+    let highest_temp = listing.auction(&bank).highest_bidder_ft_temp_pubkey;
+    let bidder = listing.bidder(&mut bank);
+
+    for temp in [bidder.ft_account, highest_temp] {
+        let mut bid_ix = listing.bid_ix(&mut bank, &bidder, 300);
+        bid_ix.accounts[4].pubkey = temp;
+        assert_eq!(
+            bank.process(bid_ix),
+            Err(AuctionError::InvalidInstruction.into())
+        );
+    }
+    assert_eq!(listing.auction(&bank).highest_bidder_pubkey, first.wallet);
+    assert_eq!(bank.token_balance(&bidder.ft_account), BIDDER_BALANCE);
+}
+
+fn increase_bid_escrow_ix(
+    bank: &Bank,
+    listing: &Listing,
+    bidder: &Bidder,
+    additional: u64,
+) -> Instruction {
+    let mut data = vec![15];
+    data.extend_from_slice(&additional.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(bidder.wallet, true),
+            AccountMeta::new(bidder.ft_account, false),
+            AccountMeta::new(listing.auction(bank).highest_bidder_ft_temp_pubkey, false),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+    )
+}
+
+#[test]
+fn highest_bidder_increases_the_escrowed_bid() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    let auction = listing.auction(&bank);
+
+    bank.process(increase_bid_escrow_ix(&bank, &listing, &bidder, 100))
+        .unwrap();
+    let increased = listing.auction(&bank);
+    assert_eq!(increased.price, 250);
+    assert_eq!(increased.end_at, auction.end_at);
+    assert_eq!(
+        bank.token_balance(&auction.highest_bidder_ft_temp_pubkey),
+        250
+    );
+    assert_eq!(bank.token_balance(&bidder.ft_account), BIDDER_BALANCE - 250);
+    assert_eq!(
+        Bank::events(),
+        vec![vec![
+            BidEvent::NAME.to_vec(),
+            listing.escrow.to_bytes().to_vec(),
+            bidder.wallet.to_bytes().to_vec(),
+            250u64.to_le_bytes().to_vec(),
+        ]]
+    );
+}
+
+#[test]
+fn only_the_highest_bidder_increases_the_escrow() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    let other = listing.bidder(&mut bank);
+
+    assert_eq!(
+        bank.process(increase_bid_escrow_ix(&bank, &listing, &other, 100)),
+        Err(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(listing.auction(&bank).price, 150);
+    assert_eq!(bank.token_balance(&other.ft_account), BIDDER_BALANCE);
+}
+
+fn bidder_registry(bank: &Bank, listing: &Listing) -> Pubkey {
+    pda(
+        &[b"bidder_registry", listing.escrow.as_ref()],
+        &bank.program_id,
+    )
+}
+
+/// `Bid` with the bidder registry trailing the bid accounts
+fn registered_bid(
+    bank: &mut Bank,
+    listing: &Listing,
+    bidder: &Bidder,
+    price: u64,
+) -> ProgramResult {
+    let mut bid_ix = listing.bid_ix(bank, bidder, price);
+    bid_ix
+        .accounts
+        .push(AccountMeta::new(bidder_registry(bank, listing), false));
+    bank.process(bid_ix)
+}
+
+#[test]
+fn bidders_beyond_the_cap_are_refused() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(Instruction::new_with_bytes(
+        bank.program_id,
+        &[18, 2],
+        vec![
+            AccountMeta::new(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new(bidder_registry(&bank, &listing), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    ))
+    .unwrap();
+    assert_eq!(listing.auction(&bank).max_bidders, 2);
+
+    let first = listing.bidder(&mut bank);
+    let second = listing.bidder(&mut bank);
+    let third = listing.bidder(&mut bank);
+    registered_bid(&mut bank, &listing, &first, 150).unwrap();
+    registered_bid(&mut bank, &listing, &second, 200).unwrap();
+    assert_eq!(
+        registered_bid(&mut bank, &listing, &third, 300),
+        Err(AuctionError::MaxBiddersReached.into())
+    );
+    assert_eq!(listing.auction(&bank).unique_bidder_count, 2);
+
+    registered_bid(&mut bank, &listing, &first, 300).unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.highest_bidder_pubkey, first.wallet);
+    assert_eq!(auction.unique_bidder_count, 2);
+}
+
+#[test]
+fn first_bid_creates_the_bid_history() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bid_history = pda(&[b"bid_history", listing.escrow.as_ref()], &bank.program_id);
+    assert!(bank.account(&bid_history).is_none());
+
+    let first = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &first, 150).unwrap();
+    let first_bid_at = bank.clock().unix_timestamp;
+    assert_eq!(
+        bank.lamports(&first.wallet),
+        WALLET_LAMPORTS - Rent::default().minimum_balance(BidHistory::LEN)
+    );
+    assert_eq!(bank.account(&bid_history).unwrap().owner, bank.program_id);
+
+    bank.warp_to_timestamp(first_bid_at + 10);
+    let second = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &second, 200).unwrap();
+    assert_eq!(bank.lamports(&second.wallet), WALLET_LAMPORTS);
+
+    let history: BidHistory = bank.unpack(&bid_history);
+    assert_eq!(history.total_count, 2);
+    let records: Vec<_> = history.bids[..2]
+        .iter()
+        .map(|record| (record.bidder, record.price, record.bid_at))
+        .collect();
+    assert_eq!(
+        records,
+        [
+            (first.wallet, 150, first_bid_at),
+            (second.wallet, 200, first_bid_at + 10)
+        ]
+    );
+}
+
+#[test]
+fn bids_wait_for_the_start_delay() {
+    let (mut bank, _) = setup();
+    let listing = Listing::new(&mut bank);
+    let mut exhibit_ix = listing.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC);
+    exhibit_ix.data.extend_from_slice(&60u64.to_le_bytes());
+    bank.process(exhibit_ix).unwrap();
+    listing.accept_terms(&mut bank);
+    let exhibited_at = bank.clock().unix_timestamp;
+    assert_eq!(listing.auction(&bank).starts_at, exhibited_at + 60);
+    let bidder = listing.bidder(&mut bank);
+
+    assert_eq!(
+        listing.bid(&mut bank, &bidder, 150),
+        Err(AuctionError::AuctionNotStarted.into())
+    );
+    bank.warp_to_timestamp(exhibited_at + 59);
+    assert_eq!(
+        listing.bid(&mut bank, &bidder, 150),
+        Err(AuctionError::AuctionNotStarted.into())
+    );
+    bank.warp_to_timestamp(exhibited_at + 60);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    assert_eq!(listing.auction(&bank).highest_bidder_pubkey, bidder.wallet);
+}
+
+#[test]
+fn first_bid_pays_the_upfront_royalty() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let creator = bank.create_wallet();
+    bank.process(deduct_royalty_before_escrow_ix(
+        &bank, &listing, &creator, 500,
+    ))
+    .unwrap();
+    let royalty_vault = listing.auction(&bank).royalty_creator_ft_pubkey;
+    assert_eq!(bank.token_balance(&royalty_vault), 0);
+
+    let first = listing.bidder(&mut bank);
+    let mut bid_ix = listing.bid_ix(&mut bank, &first, 200);
+    bid_ix.accounts.push(AccountMeta::new(royalty_vault, false));
+    bank.process(bid_ix).unwrap();
+    let auction = listing.auction(&bank);
+    assert!(auction.upfront_royalty_paid);
+    assert_eq!(bank.token_balance(&royalty_vault), 10);
+    assert_eq!(auction.price, 190);
+    assert_eq!(auction.bid_escrow_amount, 190);
+    assert_eq!(
+        bank.token_balance(&auction.highest_bidder_ft_temp_pubkey),
+        190
+    );
+    assert_eq!(bank.token_balance(&first.ft_account), BIDDER_BALANCE - 200);
+
+    // Only the first bid pays, later bids take the standard account list
+    let second = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &second, 300).unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(bank.token_balance(&royalty_vault), 10);
+    assert_eq!(auction.price, 300);
+    assert_eq!(
+        bank.token_balance(&auction.highest_bidder_ft_temp_pubkey),
+        300
+    );
+    assert_eq!(bank.token_balance(&first.ft_account), BIDDER_BALANCE - 10);
+    assert_eq!(
+        bank.process(deduct_royalty_before_escrow_ix(
+            &bank, &listing, &creator, 500
+        )),
+        Err(AuctionError::AlreadyBid.into())
+    );
+}
+
+fn claim_creator_royalty_ix(
+    bank: &Bank,
+    creator: &Pubkey,
+    creator_ft: &Pubkey,
+    royalty_vault: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[96],
+        vec![
+            AccountMeta::new_readonly(*creator, true),
+            AccountMeta::new(*creator_ft, false),
+            AccountMeta::new(*royalty_vault, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+    )
+}
+
+#[test]
+fn creator_claims_the_royalties_of_the_vault() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let creator = bank.create_wallet();
+    let creator_ft = bank.create_token_account(&listing.ft_mint, &creator, 0);
+    bank.process(deduct_royalty_before_escrow_ix(
+        &bank, &listing, &creator, 500,
+    ))
+    .unwrap();
+    let royalty_vault = listing.auction(&bank).royalty_creator_ft_pubkey;
+    assert_eq!(
+        bank.process(claim_creator_royalty_ix(
+            &bank,
+            &creator,
+            &creator_ft,
+            &royalty_vault
+        )),
+        Err(AuctionError::NoRoyaltyToClaim.into())
+    );
+
+    let bidder = listing.bidder(&mut bank);
+    let mut bid_ix = listing.bid_ix(&mut bank, &bidder, 200);
+    bid_ix.accounts.push(AccountMeta::new(royalty_vault, false));
+    bank.process(bid_ix).unwrap();
+    let intruder = bank.create_wallet();
+    assert_eq!(
+        bank.process(claim_creator_royalty_ix(
+            &bank,
+            &intruder,
+            &creator_ft,
+            &royalty_vault
+        )),
+        Err(ProgramError::InvalidSeeds)
+    );
+    bank.process(claim_creator_royalty_ix(
+        &bank,
+        &creator,
+        &creator_ft,
+        &royalty_vault,
+    ))
+    .unwrap();
+    assert_eq!(bank.token_balance(&creator_ft), 10);
+    assert_eq!(bank.token_balance(&royalty_vault), 0);
+    assert_eq!(
+        bank.process(claim_creator_royalty_ix(
+            &bank,
+            &creator,
+            &creator_ft,
+            &royalty_vault
+        )),
+        Err(AuctionError::NoRoyaltyToClaim.into())
+    );
+}
+
+/// Native mint and a new auction priced in WSOL
+fn wsol_listing(bank: &mut Bank) -> Listing {
+    bank.pack(
+        spl_token::native_mint::id(),
+        spl_token::id(),
+        spl_token::state::Mint {
+            decimals: spl_token::native_mint::DECIMALS,
+            is_initialized: true,
+            ..spl_token::state::Mint::default()
+        },
+    );
+    let exhibitor = bank.create_wallet();
+    let listing = Listing::with_ft_mint(bank, exhibitor, spl_token::native_mint::id());
+    bank.process(listing.exhibit_ix(bank, INITIAL_PRICE, AUCTION_DURATION_SEC))
+        .unwrap();
+    listing.accept_terms(bank);
+    listing
+}
+
+/// WSOL account of `owner` holding `amount` wrapped lamports on top of its rent reserve
+fn wsol_account(bank: &mut Bank, owner: &Pubkey, amount: u64) -> Pubkey {
+    let rent_reserve = Rent::default().minimum_balance(spl_token::state::Account::LEN);
+    let wsol_account = Pubkey::new_unique();
+    bank.pack(
+        wsol_account,
+        spl_token::id(),
+        spl_token::state::Account {
+            mint: spl_token::native_mint::id(),
+            owner: *owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            is_native: Some(rent_reserve).into(),
+            ..spl_token::state::Account::default()
+        },
+    );
+    bank.set_account(
+        wsol_account,
+        common::Account {
+            lamports: rent_reserve + amount,
+            ..bank.account(&wsol_account).unwrap().clone()
+        },
+    );
+    wsol_account
+}
+
+fn bid_with_sol_balance_ix(
+    bank: &mut Bank,
+    listing: &Listing,
+    bidder: &Bidder,
+    price: u64,
+    existing_wsol: u64,
+) -> Instruction {
+    let bidder_ft_temp = wsol_account(bank, &bidder.wallet, 0);
+    let mut data = vec![48];
+    data.extend_from_slice(&price.to_le_bytes());
+    data.extend_from_slice(&existing_wsol.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        listing.bid_accounts(bank, bidder, &bidder_ft_temp),
+    )
+}
+
+#[test]
+fn sol_is_wrapped_into_the_bid_and_refunded_as_wsol() {
+    let (mut bank, _) = setup();
+    let listing = wsol_listing(&mut bank);
+    let rent_reserve = Rent::default().minimum_balance(spl_token::state::Account::LEN);
+    let bid_history_rent = Rent::default().minimum_balance(BidHistory::LEN);
+
+    let first_wallet = bank.create_wallet();
+    let first = Bidder {
+        wallet: first_wallet,
+        ft_account: wsol_account(&mut bank, &first_wallet, 50),
+    };
+    let bid_ix = bid_with_sol_balance_ix(&mut bank, &listing, &first, 200, 50);
+    bank.process(bid_ix).unwrap();
+    let auction = listing.auction(&bank);
+    let first_temp = auction.highest_bidder_ft_temp_pubkey;
+    assert_eq!(auction.highest_bidder_pubkey, first.wallet);
+    assert_eq!(auction.price, 200);
+    assert_eq!(
+        bank.lamports(&first.wallet),
+        WALLET_LAMPORTS - bid_history_rent - 150
+    );
+    assert_eq!(bank.token_balance(&first.ft_account), 0);
+    assert_eq!(bank.lamports(&first.ft_account), rent_reserve);
+    assert_eq!(bank.token_balance(&first_temp), 200);
+    assert_eq!(bank.lamports(&first_temp), rent_reserve + 200);
+
+    let second_wallet = bank.create_wallet();
+    let second = Bidder {
+        wallet: second_wallet,
+        ft_account: wsol_account(&mut bank, &second_wallet, 0),
+    };
+    let bid_ix = bid_with_sol_balance_ix(&mut bank, &listing, &second, 300, 0);
+    bank.process(bid_ix).unwrap();
+    assert_eq!(listing.auction(&bank).highest_bidder_pubkey, second.wallet);
+    assert_eq!(bank.lamports(&second.wallet), WALLET_LAMPORTS - 300);
+
+    // The outbid WSOL is back in the first bidder's WSOL account and the temporary account
+    // rent in the wallet
+    assert_eq!(bank.token_balance(&first.ft_account), 200);
+    assert_eq!(bank.lamports(&first.ft_account), rent_reserve + 200);
+    assert!(bank.account(&first_temp).is_none());
+    assert_eq!(
+        bank.lamports(&first.wallet),
+        WALLET_LAMPORTS - bid_history_rent - 150 + rent_reserve
+    );
+}
+
+#[test]
+fn bid_with_sol_balance_checks_the_wsol_account() {
+    let (mut bank, _) = setup();
+    let listing = wsol_listing(&mut bank);
+    let wallet = bank.create_wallet();
+    let bidder = Bidder {
+        wallet,
+        ft_account: wsol_account(&mut bank, &wallet, 50),
+    };
+
+    let bid_ix = bid_with_sol_balance_ix(&mut bank, &listing, &bidder, 200, 60);
+    assert_eq!(
+        bank.process(bid_ix),
+        Err(AuctionError::ExpectedAmountMismatch.into())
+    );
+
+    let stranger = bank.create_wallet();
+    let other = Bidder {
+        wallet,
+        ft_account: wsol_account(&mut bank, &stranger, 50),
+    };
+    let bid_ix = bid_with_sol_balance_ix(&mut bank, &listing, &other, 200, 50);
+    assert_eq!(bank.process(bid_ix), Err(ProgramError::InvalidAccountData));
+
+    let ft_listing = Listing::exhibit(&mut bank);
+    let ft_bidder = ft_listing.bidder(&mut bank);
+    let bid_ix = bid_with_sol_balance_ix(&mut bank, &ft_listing, &ft_bidder, 200, 0);
+    assert_eq!(bank.process(bid_ix), Err(ProgramError::InvalidAccountData));
+    assert_eq!(bank.lamports(&wallet), WALLET_LAMPORTS);
+}
+
+fn set_sliding_expiry_ix(
+    bank: &Bank,
+    listing: &Listing,
+    idle_period_sec: u64,
+    max_duration_sec: u64,
+) -> Instruction {
+    let mut data = vec![74];
+    data.extend_from_slice(&idle_period_sec.to_le_bytes());
+    data.extend_from_slice(&max_duration_sec.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+    )
+}
+
+fn register_priority_bidder_ix(
+    bank: &Bank,
+    listing: &Listing,
+    signer: &Pubkey,
+    bidder: &Pubkey,
+) -> Instruction {
+    let mut data = vec![49];
+    data.extend_from_slice(bidder.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(pda(&[b"global_config"], &bank.program_id), false),
+        ],
+    )
+}
+
+#[test]
+fn priority_bidder_does_not_push_the_end_back() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let start = bank.clock().unix_timestamp;
+    bank.process(set_sliding_expiry_ix(&bank, &listing, 300, 3_600))
+        .unwrap();
+    assert_eq!(listing.auction(&bank).end_at, start + 300);
+    let normal = listing.bidder(&mut bank);
+    let priority = listing.bidder(&mut bank);
+    bank.process(register_priority_bidder_ix(
+        &bank,
+        &listing,
+        &listing.exhibitor,
+        &priority.wallet,
+    ))
+    .unwrap();
+    assert_eq!(listing.auction(&bank).priority_bidder, priority.wallet);
+
+    bank.warp_to_timestamp(start + 250);
+    listing.bid(&mut bank, &normal, 150).unwrap();
+    assert_eq!(listing.auction(&bank).end_at, start + 550);
+
+    bank.warp_to_timestamp(start + 500);
+    listing.bid(&mut bank, &priority, 200).unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.highest_bidder_pubkey, priority.wallet);
+    assert_eq!(auction.end_at, start + 550);
+
+    bank.warp_to_timestamp(start + 540);
+    listing.bid(&mut bank, &normal, 250).unwrap();
+    assert_eq!(listing.auction(&bank).end_at, start + 840);
+}
+
+#[test]
+fn sliding_expiry_ends_after_the_idle_period() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let start = bank.clock().unix_timestamp;
+    bank.process(set_sliding_expiry_ix(&bank, &listing, 300, 1_000))
+        .unwrap();
+    let first = listing.bidder(&mut bank);
+    let second = listing.bidder(&mut bank);
+
+    bank.warp_to_timestamp(start + 200);
+    listing.bid(&mut bank, &first, 150).unwrap();
+    assert_eq!(listing.auction(&bank).end_at, start + 500);
+    bank.warp_to_timestamp(start + 800);
+    assert_eq!(
+        listing.bid(&mut bank, &second, 200),
+        Err(AuctionError::InactiveAuction.into())
+    );
+}
+
+#[test]
+fn sliding_expiry_is_capped_by_the_maximum_duration() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let start = bank.clock().unix_timestamp;
+    bank.process(set_sliding_expiry_ix(&bank, &listing, 300, 600))
+        .unwrap();
+    let first = listing.bidder(&mut bank);
+    let second = listing.bidder(&mut bank);
+
+    bank.warp_to_timestamp(start + 250);
+    listing.bid(&mut bank, &first, 150).unwrap();
+    bank.warp_to_timestamp(start + 500);
+    listing.bid(&mut bank, &second, 200).unwrap();
+    assert_eq!(listing.auction(&bank).end_at, start + 600);
+    bank.warp_to_timestamp(start + 600);
+    assert_eq!(
+        listing.bid(&mut bank, &first, 250),
+        Err(AuctionError::InactiveAuction.into())
+    );
+}
+
+#[test]
+fn sliding_expiry_needs_an_idle_period_within_the_maximum_duration() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    assert_eq!(
+        bank.process(set_sliding_expiry_ix(&bank, &listing, 0, 600)),
+        Err(AuctionError::InvalidInstruction.into())
+    );
+    assert_eq!(
+        bank.process(set_sliding_expiry_ix(&bank, &listing, 600, 300)),
+        Err(AuctionError::InvalidInstruction.into())
+    );
+
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    assert_eq!(
+        bank.process(set_sliding_expiry_ix(&bank, &listing, 300, 600)),
+        Err(AuctionError::AlreadyBid.into())
+    );
+}
+
+#[test]
+fn exhibitor_or_authority_registers_the_priority_bidder() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = Pubkey::new_unique();
+    let intruder = bank.create_wallet();
+
+    assert_eq!(
+        bank.process(register_priority_bidder_ix(
+            &bank, &listing, &intruder, &bidder
+        )),
+        Err(AuctionError::Unauthorized.into())
+    );
+    bank.process(register_priority_bidder_ix(
+        &bank, &listing, &authority, &bidder,
+    ))
+    .unwrap();
+    assert_eq!(listing.auction(&bank).priority_bidder, bidder);
+}
+
+fn set_bid_multiplier_ix(
+    bank: &Bank,
+    listing: &Listing,
+    signer: &Pubkey,
+    min_bid_increment: u64,
+    bid_multiplier_bps: u16,
+) -> Instruction {
+    let mut data = vec![54];
+    data.extend_from_slice(&min_bid_increment.to_le_bytes());
+    data.extend_from_slice(&bid_multiplier_bps.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+#[test]
+fn bid_multiplier_requires_a_proportional_increment() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_bid_multiplier_ix(
+        &bank,
+        &listing,
+        &listing.exhibitor,
+        0,
+        1_000,
+    ))
+    .unwrap();
+    let bidder = listing.bidder(&mut bank);
+
+    assert_eq!(
+        listing.bid(&mut bank, &bidder, 109),
+        Err(AuctionError::InsufficientBidPrice.into())
+    );
+    listing.bid(&mut bank, &bidder, 110).unwrap();
+    assert_eq!(listing.auction(&bank).price, 110);
+}
+
+#[test]
+fn larger_of_the_flat_and_proportional_increments_applies() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_bid_multiplier_ix(
+        &bank,
+        &listing,
+        &listing.exhibitor,
+        50,
+        1_000,
+    ))
+    .unwrap();
+    let first = listing.bidder(&mut bank);
+    let second = listing.bidder(&mut bank);
+
+    // At 100 the flat increment of 50 exceeds the 10% one
+    assert_eq!(
+        listing.bid(&mut bank, &first, 149),
+        Err(AuctionError::InsufficientBidPrice.into())
+    );
+    listing.bid(&mut bank, &first, 150).unwrap();
+
+    // At 1000 the 10% increment of 100 exceeds the flat one
+    listing.bid(&mut bank, &second, 1_000).unwrap();
+    assert_eq!(
+        listing.bid(&mut bank, &first, 1_099),
+        Err(AuctionError::InsufficientBidPrice.into())
+    );
+    listing.bid(&mut bank, &first, 1_100).unwrap();
+}
+
+#[test]
+fn only_the_exhibitor_sets_the_bid_multiplier_before_bids() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+
+    assert_eq!(
+        bank.process(set_bid_multiplier_ix(
+            &bank,
+            &listing,
+            &bidder.wallet,
+            0,
+            1_000
+        )),
+        Err(ProgramError::InvalidAccountData)
+    );
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    assert_eq!(
+        bank.process(set_bid_multiplier_ix(
+            &bank,
+            &listing,
+            &listing.exhibitor,
+            0,
+            1_000
+        )),
+        Err(AuctionError::AlreadyBid.into())
+    );
+}
+
+/// Program bidding on behalf of its users, the bid FT being owned by its `[b"bidder"]` PDA
+/// The auction program comes first among its accounts, followed by the accounts of the bid
+fn bidding_program(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (auction_program, bid_accounts) = accounts
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let (authority, bump_seed) = Pubkey::find_program_address(&[b"bidder"], program_id);
+    let bid_ix = Instruction::new_with_bytes(
+        *auction_program.key,
+        data,
+        bid_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: *account.key,
+                is_signer: *account.key == authority,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+    );
+    invoke_signed(&bid_ix, accounts, &[&[b"bidder", &[bump_seed]]])
+}
+
+fn enable_cross_program_ix(
+    bank: &Bank,
+    listing: &Listing,
+    signer: &Pubkey,
+    cpi_authority: &Pubkey,
+) -> Instruction {
+    let mut data = vec![62];
+    data.extend_from_slice(cpi_authority.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+/// Deploys the bidding program, returning its id and its PDA funded to pay for the bid history
+fn deploy_bidding_program(bank: &mut Bank) -> (Pubkey, Pubkey) {
+    let bidding_program_id = Pubkey::new_unique();
+    bank.add_program(bidding_program_id, bidding_program);
+    let authority = pda(&[b"bidder"], &bidding_program_id);
+    bank.set_account(
+        authority,
+        Account {
+            lamports: WALLET_LAMPORTS,
+            owner: system_program::id(),
+            ..Account::default()
+        },
+    );
+    (bidding_program_id, authority)
+}
+
+/// `Bid` of `user` at `price` sent through the bidding program, with FT held by `authority`
+fn cross_program_bid_ix(
+    bank: &mut Bank,
+    listing: &Listing,
+    bidding_program_id: &Pubkey,
+    authority: &Pubkey,
+    user: &Pubkey,
+    price: u64,
+) -> Instruction {
+    let bidder = Bidder {
+        wallet: *user,
+        ft_account: bank.create_token_account(&listing.ft_mint, authority, BIDDER_BALANCE),
+    };
+    let mut bid_ix = listing.bid_ix(bank, &bidder, price);
+    let bidder_ft_temp = bid_ix.accounts[4].pubkey;
+    bank.set_token_account(bidder_ft_temp, &listing.ft_mint, authority, 0);
+    bid_ix.accounts[0].is_signer = false;
+    bid_ix.accounts.push(AccountMeta::new(*authority, false));
+    bid_ix
+        .accounts
+        .insert(0, AccountMeta::new_readonly(bank.program_id, false));
+    Instruction::new_with_bytes(*bidding_program_id, &bid_ix.data, bid_ix.accounts)
+}
+
+#[test]
+fn other_program_bids_through_its_cpi_authority() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let (bidding_program_id, authority) = deploy_bidding_program(&mut bank);
+    bank.process(enable_cross_program_ix(
+        &bank,
+        &listing,
+        &listing.exhibitor,
+        &authority,
+    ))
+    .unwrap();
+    let user = bank.create_wallet();
+
+    let bid_ix = cross_program_bid_ix(
+        &mut bank,
+        &listing,
+        &bidding_program_id,
+        &authority,
+        &user,
+        150,
+    );
+    bank.process(bid_ix).unwrap();
+
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.highest_bidder_pubkey, user);
+    assert_eq!(auction.price, 150);
+    assert_eq!(
+        bank.token_balance(&auction.highest_bidder_ft_temp_pubkey),
+        150
+    );
+}
+
+#[test]
+fn cross_program_bids_need_the_enabled_authority_in_a_cpi() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let (bidding_program_id, authority) = deploy_bidding_program(&mut bank);
+    let user = bank.create_wallet();
+
+    let bid_ix = cross_program_bid_ix(
+        &mut bank,
+        &listing,
+        &bidding_program_id,
+        &authority,
+        &user,
+        150,
+    );
+    assert_eq!(
+        bank.process(bid_ix),
+        Err(ProgramError::MissingRequiredSignature)
+    );
+
+    let other_authority = Pubkey::new_unique();
+    assert_eq!(
+        bank.process(enable_cross_program_ix(&bank, &listing, &user, &authority)),
+        Err(ProgramError::InvalidAccountData)
+    );
+    bank.process(enable_cross_program_ix(
+        &bank,
+        &listing,
+        &listing.exhibitor,
+        &other_authority,
+    ))
+    .unwrap();
+    let bid_ix = cross_program_bid_ix(
+        &mut bank,
+        &listing,
+        &bidding_program_id,
+        &authority,
+        &user,
+        150,
+    );
+    assert_eq!(
+        bank.process(bid_ix),
+        Err(ProgramError::MissingRequiredSignature)
+    );
+    assert_eq!(
+        listing.auction(&bank).highest_bidder_pubkey,
+        Pubkey::default()
+    );
+}
+
+/// Yield vault whose positions are PDAs seeded by `[b"position", depositor_pubkey]`, holding the
+/// position balance, and each owning the FT account of the vault for that position
+fn vault_program(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [position, vault_ft, ft_account, authority, token_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let (&tag, amount) = data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let amount = u64::from_le_bytes(
+        amount
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let balance = u64::from_le_bytes(position.try_borrow_data()?[..8].try_into().unwrap());
+    let balance = match tag {
+        VAULT_DEPOSIT_TAG => {
+            let deposit_ix = spl_token::instruction::transfer(
+                token_program.key,
+                ft_account.key,
+                vault_ft.key,
+                authority.key,
+                &[],
+                amount,
+            )?;
+            invoke(&deposit_ix, accounts)?;
+            balance.checked_add(amount)
+        }
+        VAULT_WITHDRAW_TAG => {
+            let (position_pda, bump_seed) =
+                Pubkey::find_program_address(&[b"position", authority.key.as_ref()], program_id);
+            if position_pda != *position.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            let withdraw_ix = spl_token::instruction::transfer(
+                token_program.key,
+                vault_ft.key,
+                ft_account.key,
+                position.key,
+                &[],
+                amount,
+            )?;
+            invoke_signed(
+                &withdraw_ix,
+                accounts,
+                &[&[b"position", authority.key.as_ref(), &[bump_seed]]],
+            )?;
+            balance.checked_sub(amount)
+        }
+        _ => return Err(ProgramError::InvalidInstructionData),
+    }
+    .ok_or(ProgramError::InsufficientFunds)?;
+    position.try_borrow_mut_data()?[..8].copy_from_slice(&balance.to_le_bytes());
+    Ok(())
+}
+
+/// Vault program with a position of `amount` deposited by `bidder`, returning the program, the
+/// position and the FT account of the vault
+fn deposit_in_vault(
+    bank: &mut Bank,
+    listing: &Listing,
+    bidder: &Bidder,
+    amount: u64,
+) -> [Pubkey; 3] {
+    let vault_program_id = Pubkey::new_unique();
+    bank.add_program(vault_program_id, vault_program);
+    let position = pda(&[b"position", bidder.wallet.as_ref()], &vault_program_id);
+    bank.set_account(
+        position,
+        Account {
+            lamports: Rent::default().minimum_balance(8),
+            data: amount.to_le_bytes().to_vec(),
+            owner: vault_program_id,
+            executable: false,
+        },
+    );
+    let vault_ft = bank.create_token_account(&listing.ft_mint, &position, amount);
+    [vault_program_id, position, vault_ft]
+}
+
+fn vault_bid_ix(
+    bank: &mut Bank,
+    listing: &Listing,
+    bidder: &Bidder,
+    vault: &[Pubkey; 3],
+    price: u64,
+) -> Instruction {
+    let [vault_program_id, position, vault_ft] = *vault;
+    let bid_ix = listing.bid_ix(bank, bidder, price);
+    let mut data = vec![67];
+    data.extend_from_slice(vault_program_id.as_ref());
+    data.extend_from_slice(&price.to_le_bytes());
+    let mut accounts = vec![
+        AccountMeta::new_readonly(vault_program_id, false),
+        AccountMeta::new(position, false),
+        AccountMeta::new(vault_ft, false),
+    ];
+    accounts.extend(bid_ix.accounts);
+    Instruction::new_with_bytes(bank.program_id, &data, accounts)
+}
+
+fn vault_balance(bank: &Bank, position: &Pubkey) -> u64 {
+    u64::from_le_bytes(
+        bank.account(position).unwrap().data[..8]
+            .try_into()
+            .unwrap(),
+    )
+}
+
+#[test]
+fn vault_bid_is_deposited_back_when_outbid() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let wallet = bank.create_wallet();
+    let vault_bidder = Bidder {
+        wallet,
+        ft_account: bank.create_token_account(&listing.ft_mint, &wallet, 0),
+    };
+    let vault = deposit_in_vault(&mut bank, &listing, &vault_bidder, 500);
+    let [vault_program_id, position, vault_ft] = vault;
+
+    let vault_bid_ix = vault_bid_ix(&mut bank, &listing, &vault_bidder, &vault, 150);
+    bank.process(vault_bid_ix).unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.highest_bidder_pubkey, vault_bidder.wallet);
+    assert_eq!(auction.highest_bidder_vault_program, vault_program_id);
+    assert_eq!(auction.highest_bidder_vault, position);
+    assert_eq!(auction.highest_bidder_vault_ft, vault_ft);
+    assert_eq!(
+        bank.token_balance(&auction.highest_bidder_ft_temp_pubkey),
+        150
+    );
+    assert_eq!(vault_balance(&bank, &position), 350);
+    assert_eq!(bank.token_balance(&vault_ft), 350);
+
+    let other = listing.bidder(&mut bank);
+    let mut bid_ix = listing.bid_ix(&mut bank, &other, 200);
+    bid_ix.accounts.extend([
+        AccountMeta::new_readonly(vault_program_id, false),
+        AccountMeta::new(position, false),
+        AccountMeta::new(vault_ft, false),
+    ]);
+    bank.process(bid_ix).unwrap();
+
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.highest_bidder_pubkey, other.wallet);
+    assert_eq!(auction.highest_bidder_vault_program, Pubkey::default());
+    assert_eq!(vault_balance(&bank, &position), 500);
+    assert_eq!(bank.token_balance(&vault_ft), 500);
+    assert_eq!(bank.token_balance(&vault_bidder.ft_account), 0);
+}
+
+#[test]
+fn vault_bid_names_the_vault_program_it_passes() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    let vault = deposit_in_vault(&mut bank, &listing, &bidder, 500);
+
+    let mut vault_bid = vault_bid_ix(&mut bank, &listing, &bidder, &vault, 150);
+    vault_bid.data[1..33].copy_from_slice(Pubkey::new_unique().as_ref());
+    assert_eq!(
+        bank.process(vault_bid),
+        Err(AuctionError::InvalidInstruction.into())
+    );
+
+    let mut vault_bid = vault_bid_ix(&mut bank, &listing, &bidder, &vault, 150);
+    vault_bid.data[1..33].copy_from_slice(bank.program_id.as_ref());
+    vault_bid.accounts[0].pubkey = bank.program_id;
+    assert_eq!(
+        bank.process(vault_bid),
+        Err(ProgramError::IncorrectProgramId)
+    );
+    assert_eq!(vault_balance(&bank, &vault[1]), 500);
+}
+
+fn set_bid_token_gate_ix(
+    bank: &Bank,
+    listing: &Listing,
+    gate_token_mint: &Pubkey,
+    gate_token_min_amount: u64,
+) -> Instruction {
+    let mut data = vec![70];
+    data.extend_from_slice(gate_token_mint.as_ref());
+    data.extend_from_slice(&gate_token_min_amount.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+fn gated_bid(
+    bank: &mut Bank,
+    listing: &Listing,
+    bidder: &Bidder,
+    gate_token: &Pubkey,
+    price: u64,
+) -> ProgramResult {
+    let mut bid_ix = listing.bid_ix(bank, bidder, price);
+    bid_ix
+        .accounts
+        .push(AccountMeta::new_readonly(*gate_token, false));
+    bank.process(bid_ix)
+}
+
+#[test]
+fn only_holders_of_the_gate_token_bid() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let dao = bank.create_wallet();
+    let gate_mint = bank.create_mint(&dao);
+    bank.process(set_bid_token_gate_ix(&bank, &listing, &gate_mint, 10))
+        .unwrap();
+    let holder = listing.bidder(&mut bank);
+    let holder_gate = bank.create_token_account(&gate_mint, &holder.wallet, 10);
+    let small_holder = listing.bidder(&mut bank);
+    let small_holder_gate = bank.create_token_account(&gate_mint, &small_holder.wallet, 9);
+
+    assert_eq!(
+        gated_bid(&mut bank, &listing, &small_holder, &small_holder_gate, 150),
+        Err(AuctionError::GateTokenRequirementNotMet.into())
+    );
+    // The gate token account must be the bidder's own
+    assert_eq!(
+        gated_bid(&mut bank, &listing, &small_holder, &holder_gate, 150),
+        Err(AuctionError::GateTokenRequirementNotMet.into())
+    );
+    // Holding enough of another token doesn't count
+    let other_gate = bank.create_token_account(&listing.ft_mint, &small_holder.wallet, 10);
+    assert_eq!(
+        gated_bid(&mut bank, &listing, &small_holder, &other_gate, 150),
+        Err(AuctionError::GateTokenRequirementNotMet.into())
+    );
+
+    gated_bid(&mut bank, &listing, &holder, &holder_gate, 150).unwrap();
+    assert_eq!(listing.auction(&bank).highest_bidder_pubkey, holder.wallet);
+}
+
+#[test]
+fn default_gate_mint_removes_the_gate() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let gate_mint = bank.create_mint(&listing.exhibitor);
+    bank.process(set_bid_token_gate_ix(&bank, &listing, &gate_mint, 1))
+        .unwrap();
+    bank.process(set_bid_token_gate_ix(
+        &bank,
+        &listing,
+        &Pubkey::default(),
+        0,
+    ))
+    .unwrap();
+
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    assert_eq!(
+        bank.process(set_bid_token_gate_ix(&bank, &listing, &gate_mint, 1)),
+        Err(AuctionError::AlreadyBid.into())
+    );
+}
+
+fn set_bid_cap_ix(bank: &Bank, authority: &Pubkey, max_lifetime_bid: u64) -> Instruction {
+    let mut data = vec![78];
+    data.extend_from_slice(&max_lifetime_bid.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(pda(&[b"global_config"], &bank.program_id), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+fn lifetime_bid_volume(bank: &Bank, bidder: &Bidder) -> Pubkey {
+    pda(&[b"ltv", bidder.wallet.as_ref()], &bank.program_id)
+}
+
+fn capped_bid(bank: &mut Bank, listing: &Listing, bidder: &Bidder, price: u64) -> ProgramResult {
+    let mut bid_ix = listing.bid_ix(bank, bidder, price);
+    bid_ix
+        .accounts
+        .push(AccountMeta::new(lifetime_bid_volume(bank, bidder), false));
+    bank.process(bid_ix)
+}
+
+#[test]
+fn lifetime_bid_volume_is_capped_across_auctions() {
+    let (mut bank, authority) = setup();
+    bank.process(set_bid_cap_ix(&bank, &authority, 300))
+        .unwrap();
+    let first = Listing::exhibit(&mut bank);
+    let second = Listing::with_ft_mint(&mut bank, first.exhibitor, first.ft_mint);
+    bank.process(second.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC))
+        .unwrap();
+    second.accept_terms(&mut bank);
+    let bidder = first.bidder(&mut bank);
+
+    capped_bid(&mut bank, &first, &bidder, 150).unwrap();
+    let volume = bank.unpack::<LifetimeBidVolume>(&lifetime_bid_volume(&bank, &bidder));
+    assert_eq!(volume.total_bid_volume, 150);
+
+    assert_eq!(
+        capped_bid(&mut bank, &second, &bidder, 151),
+        Err(AuctionError::LifetimeBidCapExceeded.into())
+    );
+    capped_bid(&mut bank, &second, &bidder, 150).unwrap();
+    let volume = bank.unpack::<LifetimeBidVolume>(&lifetime_bid_volume(&bank, &bidder));
+    assert_eq!(volume.total_bid_volume, 300);
+}
+
+#[test]
+fn only_the_authority_sets_the_bid_cap() {
+    let (mut bank, _) = setup();
+    let intruder = bank.create_wallet();
+    assert_eq!(
+        bank.process(set_bid_cap_ix(&bank, &intruder, 300)),
+        Err(AuctionError::Unauthorized.into())
+    );
+
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    assert!(bank.account(&lifetime_bid_volume(&bank, &bidder)).is_none());
+}
+
+fn fee_payer_override(bank: &Bank, listing: &Listing, bidder: &Pubkey) -> Pubkey {
+    pda(
+        &[
+            b"fee_payer_override",
+            listing.escrow.as_ref(),
+            bidder.as_ref(),
+        ],
+        &bank.program_id,
+    )
+}
+
+fn pre_authorize_ix(bank: &Bank, listing: &Listing, bidder: &Pubkey) -> Instruction {
+    let mut data = vec![84];
+    data.extend_from_slice(bidder.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(listing.exhibitor, true),
+            AccountMeta::new_readonly(listing.escrow, false),
+            AccountMeta::new(fee_payer_override(bank, listing, bidder), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// `BidWithFeePayer` at `price` and `nonce` preceded by the bidder's signature, the bidder delegating its FT to the fee payer
+fn fee_payer_bid(
+    bank: &mut Bank,
+    listing: &Listing,
+    bidder: &Bidder,
+    fee_payer: &Pubkey,
+    price: u64,
+    nonce: u64,
+) -> ProgramResult {
+    let mut ft_account = bank.token_account(&bidder.ft_account);
+    ft_account.delegate = COption::Some(*fee_payer);
+    ft_account.delegated_amount = price;
+    bank.pack(bidder.ft_account, spl_token::id(), ft_account);
+    let bidder_ft_temp = bank.create_token_account(&listing.ft_mint, fee_payer, 0);
+
+    let mut accounts = listing.bid_accounts(bank, bidder, &bidder_ft_temp);
+    accounts[0] = AccountMeta::new(bidder.wallet, false);
+    accounts.extend([
+        AccountMeta::new_readonly(fee_payer_override(bank, listing, &bidder.wallet), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new(*fee_payer, true),
+    ]);
+    let mut data = vec![85];
+    data.extend_from_slice(&price.to_le_bytes());
+    data.extend_from_slice(&nonce.to_le_bytes());
+    let bid_message = [
+        listing.escrow.as_ref(),
+        &price.to_le_bytes(),
+        &nonce.to_le_bytes(),
+    ]
+    .concat();
+    bank.process_transaction(&[
+        ed25519_ix(&bidder.wallet, &bid_message),
+        Instruction::new_with_bytes(bank.program_id, &data, accounts),
+    ])
+}
+
+#[test]
+fn fee_payer_bids_for_a_pre_authorized_bidder() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    let fee_payer = bank.create_wallet();
+    bank.process(pre_authorize_ix(&bank, &listing, &bidder.wallet))
+        .unwrap();
+
+    let nonce = listing.auction(&bank).bid_nonce;
+    fee_payer_bid(&mut bank, &listing, &bidder, &fee_payer, 150, nonce).unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.highest_bidder_pubkey, bidder.wallet);
+    assert_eq!(auction.price, 150);
+    assert_eq!(bank.token_balance(&bidder.ft_account), BIDDER_BALANCE - 150);
+}
+
+#[test]
+fn fee_payer_cannot_submit_a_signed_bid_again() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    let fee_payer = bank.create_wallet();
+    bank.process(pre_authorize_ix(&bank, &listing, &bidder.wallet))
+        .unwrap();
+    let nonce = listing.auction(&bank).bid_nonce;
+    fee_payer_bid(&mut bank, &listing, &bidder, &fee_payer, 150, nonce).unwrap();
+
+    let other = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &other, 200).unwrap();
+    assert_eq!(
+        fee_payer_bid(&mut bank, &listing, &bidder, &fee_payer, 150, nonce),
+        Err(AuctionError::InvalidBidNonce.into())
+    );
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.highest_bidder_pubkey, other.wallet);
+    assert_eq!(bank.token_balance(&bidder.ft_account), BIDDER_BALANCE);
+}
+
+#[test]
+fn fee_payer_needs_the_pre_authorization_of_the_bidder() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    let fee_payer = bank.create_wallet();
+    let nonce = listing.auction(&bank).bid_nonce;
+    assert_eq!(
+        fee_payer_bid(&mut bank, &listing, &bidder, &fee_payer, 150, nonce),
+        Err(AuctionError::NotPreAuthorized.into())
+    );
+
+    let other = listing.bidder(&mut bank);
+    bank.process(pre_authorize_ix(&bank, &listing, &other.wallet))
+        .unwrap();
+    assert_eq!(
+        fee_payer_bid(&mut bank, &listing, &bidder, &fee_payer, 150, nonce),
+        Err(AuctionError::NotPreAuthorized.into())
+    );
+    assert_eq!(
+        bank.process(pre_authorize_ix(&bank, &listing, &other.wallet)),
+        Err(ProgramError::AccountAlreadyInitialized)
+    );
+}
+
+fn pending_bid(bank: &Bank, listing: &Listing, bidder: &Bidder) -> Pubkey {
+    pda(
+        &[
+            b"pending_bid",
+            listing.escrow.as_ref(),
+            bidder.wallet.as_ref(),
+        ],
+        &bank.program_id,
+    )
+}
+
+/// Slot hashes sysvar holding the hashes of the given slots
+fn set_slot_hashes(bank: &mut Bank, slot_hashes: &[(u64, [u8; 32])]) {
+    let mut data = (slot_hashes.len() as u64).to_le_bytes().to_vec();
+    for (slot, hash) in slot_hashes {
+        data.extend_from_slice(&slot.to_le_bytes());
+        data.extend_from_slice(hash);
+    }
+    bank.set_account(
+        sysvar::slot_hashes::id(),
+        Account {
+            lamports: 1,
+            data,
+            owner: sysvar::id(),
+            executable: false,
+        },
+    );
+}
+
+fn commit_bid_hash_ix(
+    bank: &Bank,
+    listing: &Listing,
+    bidder: &Bidder,
+    hash: &[u8; 32],
+    slot: u64,
+) -> Instruction {
+    let mut data = vec![90];
+    data.extend_from_slice(hash);
+    data.extend_from_slice(&slot.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(bidder.wallet, true),
+            AccountMeta::new_readonly(listing.escrow, false),
+            AccountMeta::new(pending_bid(bank, listing, bidder), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+fn submit_bid_ix(
+    bank: &mut Bank,
+    listing: &Listing,
+    bidder: &Bidder,
+    price: u64,
+    salt: &[u8; 32],
+) -> Instruction {
+    let bid_ix = listing.bid_ix(bank, bidder, price);
+    let mut data = vec![91];
+    data.extend_from_slice(&price.to_le_bytes());
+    data.extend_from_slice(salt);
+    let mut accounts = vec![
+        AccountMeta::new(pending_bid(bank, listing, bidder), false),
+        AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+    ];
+    accounts.extend(bid_ix.accounts);
+    Instruction::new_with_bytes(bank.program_id, &data, accounts)
+}
+
+#[test]
+fn committed_bid_is_placed_once_revealed() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    let slot_hash = [7; 32];
+    let salt = [9; 32];
+    bank.warp_to_slot(11);
+    set_slot_hashes(&mut bank, &[(10, slot_hash)]);
+    let hash = hashv(&[&150u64.to_le_bytes(), &salt, &slot_hash]).to_bytes();
+
+    bank.process(commit_bid_hash_ix(&bank, &listing, &bidder, &hash, 10))
+        .unwrap();
+    let pending_bid = pending_bid(&bank, &listing, &bidder);
+    assert!(bank.account(&pending_bid).is_some());
+
+    let submit_ix = submit_bid_ix(&mut bank, &listing, &bidder, 150, &salt);
+    bank.process(submit_ix).unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.highest_bidder_pubkey, bidder.wallet);
+    assert_eq!(auction.price, 150);
+    assert!(bank.account(&pending_bid).is_none());
+}
+
+#[test]
+fn revealed_bid_must_match_a_recent_commitment() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    let slot_hash = [7; 32];
+    let salt = [9; 32];
+    bank.warp_to_slot(11);
+    set_slot_hashes(&mut bank, &[(10, slot_hash)]);
+    let hash = hashv(&[&150u64.to_le_bytes(), &salt, &slot_hash]).to_bytes();
+
+    assert_eq!(
+        bank.process(commit_bid_hash_ix(&bank, &listing, &bidder, &hash, 11)),
+        Err(AuctionError::StaleSlotHash.into())
+    );
+    bank.process(commit_bid_hash_ix(&bank, &listing, &bidder, &hash, 10))
+        .unwrap();
+    let submit_ix = submit_bid_ix(&mut bank, &listing, &bidder, 200, &salt);
+    assert_eq!(
+        bank.process(submit_ix),
+        Err(AuctionError::BidCommitmentMismatch.into())
+    );
+
+    set_slot_hashes(&mut bank, &[(11, slot_hash)]);
+    let submit_ix = submit_bid_ix(&mut bank, &listing, &bidder, 150, &salt);
+    assert_eq!(
+        bank.process(submit_ix),
+        Err(AuctionError::StaleSlotHash.into())
+    );
+}
+
+const KYC_TRUSTLINE: [u8; 8] = *b"kyc\0\0\0\0\0";
+
+thread_local! {
+    /// Bidders holding a trustline of the mock trustline program
+    static TRUSTLINES: RefCell<Vec<(Pubkey, [u8; 8])>> = const { RefCell::new(vec![]) };
+}
+
+/// Trustline program verifying the bidders registered in `TRUSTLINES`
+fn trustline_program(_: &Pubkey, _: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if data.len() != 41 || data[0] != TRUSTLINE_VERIFY_TAG {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let bidder = Pubkey::try_from(&data[1..33]).unwrap();
+    let trustline_type: [u8; 8] = data[33..].try_into().unwrap();
+    if TRUSTLINES.with(|trustlines| trustlines.borrow().contains(&(bidder, trustline_type))) {
+        Ok(())
+    } else {
+        Err(ProgramError::InvalidArgument)
+    }
+}
+
+fn set_trustline_requirement_ix(
+    bank: &Bank,
+    listing: &Listing,
+    trustline_program: &Pubkey,
+    trustline_type: &[u8; 8],
+) -> Instruction {
+    let mut data = vec![92];
+    data.extend_from_slice(trustline_program.as_ref());
+    data.extend_from_slice(trustline_type);
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+fn trustline_bid(
+    bank: &mut Bank,
+    listing: &Listing,
+    bidder: &Bidder,
+    trustline_program: &Pubkey,
+    price: u64,
+) -> ProgramResult {
+    let mut bid_ix = listing.bid_ix(bank, bidder, price);
+    bid_ix
+        .accounts
+        .push(AccountMeta::new_readonly(*trustline_program, false));
+    bank.process(bid_ix)
+}
+
+#[test]
+fn only_bidders_with_a_trustline_bid() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let program = Pubkey::new_unique();
+    bank.add_program(program, trustline_program);
+    bank.process(set_trustline_requirement_ix(
+        &bank,
+        &listing,
+        &program,
+        &KYC_TRUSTLINE,
+    ))
+    .unwrap();
+    let verified = listing.bidder(&mut bank);
+    let unverified = listing.bidder(&mut bank);
+    TRUSTLINES.with(|trustlines| {
+        trustlines.borrow_mut().extend([
+            (verified.wallet, KYC_TRUSTLINE),
+            (unverified.wallet, *b"other\0\0\0"),
+        ])
+    });
+
+    assert_eq!(
+        trustline_bid(&mut bank, &listing, &unverified, &program, 150),
+        Err(AuctionError::TrustlineNotVerified.into())
+    );
+    let other_program = Pubkey::new_unique();
+    bank.add_program(other_program, trustline_program);
+    assert_eq!(
+        trustline_bid(&mut bank, &listing, &verified, &other_program, 150),
+        Err(ProgramError::IncorrectProgramId)
+    );
+    trustline_bid(&mut bank, &listing, &verified, &program, 150).unwrap();
+    assert_eq!(
+        listing.auction(&bank).highest_bidder_pubkey,
+        verified.wallet
+    );
+}
+
+#[test]
+fn trustline_requirement_is_set_by_the_exhibitor_before_bids() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let program = Pubkey::new_unique();
+    let mut set_ix = set_trustline_requirement_ix(&bank, &listing, &program, &KYC_TRUSTLINE);
+    let intruder = bank.create_wallet();
+    set_ix.accounts[0] = AccountMeta::new_readonly(intruder, true);
+    assert_eq!(bank.process(set_ix), Err(ProgramError::InvalidAccountData));
+
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    assert_eq!(
+        bank.process(set_trustline_requirement_ix(
+            &bank,
+            &listing,
+            &program,
+            &KYC_TRUSTLINE
+        )),
+        Err(AuctionError::AlreadyBid.into())
+    );
+}
+
+fn set_auction_type_ix(bank: &Bank, listing: &Listing, auction_type: u8) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[93, auction_type],
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+#[test]
+fn sealed_bid_auctions_hide_bids_and_only_take_committed_ones() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_auction_type_ix(&bank, &listing, 1))
+        .unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.auction_type, AuctionType::SealedBid);
+    assert!(!auction.bids_visible);
+
+    let bidder = listing.bidder(&mut bank);
+    assert_eq!(
+        listing.bid(&mut bank, &bidder, 150),
+        Err(AuctionError::BidNotCommitted.into())
+    );
+    let slot_hash = [7; 32];
+    let salt = [9; 32];
+    bank.warp_to_slot(11);
+    set_slot_hashes(&mut bank, &[(10, slot_hash)]);
+    let hash = hashv(&[&150u64.to_le_bytes(), &salt, &slot_hash]).to_bytes();
+    bank.process(commit_bid_hash_ix(&bank, &listing, &bidder, &hash, 10))
+        .unwrap();
+    let submit_ix = submit_bid_ix(&mut bank, &listing, &bidder, 150, &salt);
+    bank.process(submit_ix).unwrap();
+    assert_eq!(listing.auction(&bank).highest_bidder_pubkey, bidder.wallet);
+}
+
+#[test]
+fn auction_type_is_set_by_the_exhibitor_before_bids() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    assert_eq!(listing.auction(&bank).auction_type, AuctionType::English);
+    assert_eq!(
+        bank.process(set_auction_type_ix(&bank, &listing, 2)),
+        Err(AuctionError::InvalidInstruction.into())
+    );
+    let mut set_ix = set_auction_type_ix(&bank, &listing, 1);
+    let intruder = bank.create_wallet();
+    set_ix.accounts[0] = AccountMeta::new_readonly(intruder, true);
+    assert_eq!(bank.process(set_ix), Err(ProgramError::InvalidAccountData));
+
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    assert_eq!(
+        bank.process(set_auction_type_ix(&bank, &listing, 1)),
+        Err(AuctionError::AlreadyBid.into())
+    );
+}
+
+#[test]
+fn bids_with_an_outdated_nonce_are_rejected() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    assert_eq!(listing.auction(&bank).bid_nonce, 0);
+    let first = listing.bidder(&mut bank);
+    let second = listing.bidder(&mut bank);
+    let mut replayed_ix = listing.bid_ix(&mut bank, &second, 200);
+    listing.bid(&mut bank, &first, 150).unwrap();
+    assert_eq!(listing.auction(&bank).bid_nonce, 1);
+
+    // Only the nonce is outdated, the accounts follow the first bid
+    replayed_ix.accounts = listing.bid_accounts(&bank, &second, &replayed_ix.accounts[4].pubkey);
+    assert_eq!(
+        bank.process(replayed_ix.clone()),
+        Err(AuctionError::InvalidBidNonce.into())
+    );
+    replayed_ix.data = bid_data(200, 1);
+    bank.process(replayed_ix).unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.highest_bidder_pubkey, second.wallet);
+    assert_eq!(auction.bid_nonce, 2);
+}
+
+fn batch_bid_group(bank: &Bank, bidder: &Bidder, first: &Listing) -> Pubkey {
+    pda(
+        &[b"batch_bid", bidder.wallet.as_ref(), first.escrow.as_ref()],
+        &bank.program_id,
+    )
+}
+
+/// Auctions exhibited by the same exhibitor in the same FT mint
+fn batch_listings(bank: &mut Bank, count: usize) -> Vec<Listing> {
+    let first = Listing::exhibit(bank);
+    let mut listings = vec![];
+    for _ in 1..count {
+        let listing = Listing::with_ft_mint(bank, first.exhibitor, first.ft_mint);
+        bank.process(listing.exhibit_ix(bank, INITIAL_PRICE, AUCTION_DURATION_SEC))
+            .unwrap();
+        listing.accept_terms(bank);
+        listings.push(listing);
+    }
+    listings.insert(0, first);
+    listings
+}
+
+fn batch_bid_ix(
+    bank: &mut Bank,
+    listings: &[&Listing],
+    bidder: &Bidder,
+    price: u64,
+) -> Instruction {
+    let mut data = vec![99];
+    for i in 0..MAX_BATCH_BID {
+        let escrow = listings
+            .get(i)
+            .map_or(Pubkey::default(), |listing| listing.escrow);
+        data.extend_from_slice(escrow.as_ref());
+    }
+    data.extend_from_slice(&price.to_le_bytes());
+    let mut accounts = vec![
+        AccountMeta::new(bidder.wallet, true),
+        AccountMeta::new(batch_bid_group(bank, bidder, listings[0]), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    for listing in listings {
+        accounts.extend(listing.bid_ix(bank, bidder, price).accounts);
+    }
+    Instruction::new_with_bytes(bank.program_id, &data, accounts)
+}
+
+fn claim_batch_winner_ix(
+    bank: &Bank,
+    listings: &[&Listing],
+    bidder: &Bidder,
+    won: &Listing,
+) -> Instruction {
+    let program_id = &bank.program_id;
+    let mut accounts = vec![
+        AccountMeta::new(bidder.wallet, true),
+        AccountMeta::new(batch_bid_group(bank, bidder, listings[0]), false),
+        AccountMeta::new_readonly(won.escrow, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(pda(&[b"escrow"], program_id), false),
+    ];
+    for listing in listings
+        .iter()
+        .filter(|listing| listing.escrow != won.escrow)
+    {
+        let auction = listing.auction(bank);
+        accounts.extend([
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new(auction.highest_bidder_ft_temp_pubkey, false),
+            AccountMeta::new(bidder.ft_account, false),
+        ]);
+    }
+    Instruction::new_with_bytes(*program_id, &[100], accounts)
+}
+
+#[test]
+fn batch_bidder_gets_the_other_bids_back_once_one_auction_is_won() {
+    let (mut bank, _) = setup();
+    let listings = batch_listings(&mut bank, 3);
+    let listings: Vec<&Listing> = listings.iter().collect();
+    let bidder = listings[0].bidder(&mut bank);
+    let batch_bid_ix = batch_bid_ix(&mut bank, &listings, &bidder, 150);
+    bank.process(batch_bid_ix).unwrap();
+    for listing in &listings {
+        assert_eq!(listing.auction(&bank).highest_bidder_pubkey, bidder.wallet);
+    }
+    assert_eq!(bank.token_balance(&bidder.ft_account), BIDDER_BALANCE - 450);
+
+    // Outbid on the last auction, whose bid was already refunded
+    let other = listings[2].bidder(&mut bank);
+    listings[2].bid(&mut bank, &other, 200).unwrap();
+    listings[1].expire(&mut bank);
+    let group = batch_bid_group(&bank, &bidder, listings[0]);
+    bank.process(claim_batch_winner_ix(
+        &bank,
+        &listings,
+        &bidder,
+        listings[1],
+    ))
+    .unwrap();
+    assert_eq!(bank.token_balance(&bidder.ft_account), BIDDER_BALANCE - 150);
+    assert_eq!(
+        listings[0].auction(&bank).highest_bidder_pubkey,
+        Pubkey::default()
+    );
+    assert_eq!(
+        listings[1].auction(&bank).highest_bidder_pubkey,
+        bidder.wallet
+    );
+    assert_eq!(
+        listings[2].auction(&bank).highest_bidder_pubkey,
+        other.wallet
+    );
+    assert!(bank.account(&group).is_none());
+}
+
+#[test]
+fn batch_winner_is_claimed_once_a_group_auction_is_won() {
+    let (mut bank, _) = setup();
+    let listings = batch_listings(&mut bank, 2);
+    let listings: Vec<&Listing> = listings.iter().collect();
+    let bidder = listings[0].bidder(&mut bank);
+    let duplicate_ix = batch_bid_ix(&mut bank, &[listings[0], listings[0]], &bidder, 150);
+    assert_eq!(
+        bank.process(duplicate_ix),
+        Err(AuctionError::InvalidInstruction.into())
+    );
+    let batch_bid_ix = batch_bid_ix(&mut bank, &listings, &bidder, 150);
+    bank.process(batch_bid_ix).unwrap();
+
+    assert_eq!(
+        bank.process(claim_batch_winner_ix(
+            &bank,
+            &listings,
+            &bidder,
+            listings[0]
+        )),
+        Err(AuctionError::ActiveAuction.into())
+    );
+    let other = listings[0].bidder(&mut bank);
+    listings[0].bid(&mut bank, &other, 200).unwrap();
+    listings[0].expire(&mut bank);
+    assert_eq!(
+        bank.process(claim_batch_winner_ix(
+            &bank,
+            &listings,
+            &bidder,
+            listings[0]
+        )),
+        Err(ProgramError::InvalidAccountData)
+    );
+}
+
+fn bid_approval(bank: &Bank, listing: &Listing, bidder: &Bidder) -> Pubkey {
+    pda(
+        &[
+            b"bid_approval",
+            listing.escrow.as_ref(),
+            bidder.wallet.as_ref(),
+        ],
+        &bank.program_id,
+    )
+}
+
+fn set_pre_bid_approval_ix(bank: &Bank, listing: &Listing, requires_approval: bool) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[104, requires_approval as u8],
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+fn approve_bid_ix(bank: &Bank, listing: &Listing, signer: &Pubkey, bidder: &Bidder) -> Instruction {
+    let mut data = vec![105];
+    data.extend_from_slice(bidder.wallet.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new_readonly(listing.escrow, false),
+            AccountMeta::new(bid_approval(bank, listing, bidder), false),
+        ],
+    )
+}
+
+fn reject_bid_ix(bank: &Bank, listing: &Listing, bidder: &Bidder) -> Instruction {
+    let mut data = vec![106];
+    data.extend_from_slice(bidder.wallet.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new_readonly(listing.escrow, false),
+            AccountMeta::new(bid_approval(bank, listing, bidder), false),
+            AccountMeta::new(bidder.wallet, false),
+        ],
+    )
+}
+
+fn approval_bid(bank: &mut Bank, listing: &Listing, bidder: &Bidder, price: u64) -> ProgramResult {
+    let mut bid_ix = listing.bid_ix(bank, bidder, price);
+    bid_ix
+        .accounts
+        .push(AccountMeta::new(bid_approval(bank, listing, bidder), false));
+    bank.process(bid_ix)
+}
+
+#[test]
+fn bids_are_placed_once_the_exhibitor_approves_the_bidder() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_pre_bid_approval_ix(&bank, &listing, true))
+        .unwrap();
+    let bidder = listing.bidder(&mut bank);
+    assert_eq!(
+        listing.bid(&mut bank, &bidder, 150),
+        Err(ProgramError::NotEnoughAccountKeys)
+    );
+
+    approval_bid(&mut bank, &listing, &bidder, 150).unwrap();
+    let approval: BidApproval = bank.unpack(&bid_approval(&bank, &listing, &bidder));
+    assert_eq!(approval.bidder, bidder.wallet);
+    assert_eq!(approval.price, 150);
+    assert!(!approval.approved);
+    assert_eq!(
+        listing.auction(&bank).highest_bidder_pubkey,
+        Pubkey::default()
+    );
+    assert_eq!(bank.token_balance(&bidder.ft_account), BIDDER_BALANCE);
+
+    let intruder = bank.create_wallet();
+    assert_eq!(
+        bank.process(approve_bid_ix(&bank, &listing, &intruder, &bidder)),
+        Err(ProgramError::InvalidAccountData)
+    );
+    bank.process(approve_bid_ix(&bank, &listing, &listing.exhibitor, &bidder))
+        .unwrap();
+    approval_bid(&mut bank, &listing, &bidder, 150).unwrap();
+    assert_eq!(listing.auction(&bank).highest_bidder_pubkey, bidder.wallet);
+    assert_eq!(bank.token_balance(&bidder.ft_account), BIDDER_BALANCE - 150);
+}
+
+#[test]
+fn rejected_bidders_get_the_approval_rent_back() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_pre_bid_approval_ix(&bank, &listing, true))
+        .unwrap();
+    let bidder = listing.bidder(&mut bank);
+    assert_eq!(
+        bank.process(approve_bid_ix(&bank, &listing, &listing.exhibitor, &bidder)),
+        Err(ProgramError::IncorrectProgramId)
+    );
+    approval_bid(&mut bank, &listing, &bidder, 150).unwrap();
+
+    let approval = bid_approval(&bank, &listing, &bidder);
+    let lamports = bank.lamports(&bidder.wallet);
+    let rent = bank.lamports(&approval);
+    bank.process(reject_bid_ix(&bank, &listing, &bidder))
+        .unwrap();
+    assert_eq!(bank.lamports(&bidder.wallet), lamports + rent);
+    assert!(bank.account(&approval).is_none());
+}
+
+fn set_anti_sandwich_protection_ix(
+    bank: &Bank,
+    listing: &Listing,
+    anti_sandwich: bool,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[107, anti_sandwich as u8],
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+#[test]
+fn same_slot_bids_must_beat_the_previous_one_by_ten_percent() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_anti_sandwich_protection_ix(&bank, &listing, true))
+        .unwrap();
+    bank.warp_to_slot(20);
+    let first = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &first, 150).unwrap();
+
+    let second = listing.bidder(&mut bank);
+    assert_eq!(
+        listing.bid(&mut bank, &second, 165),
+        Err(AuctionError::SameSlotBidTooLow.into())
+    );
+    listing.bid(&mut bank, &second, 166).unwrap();
+    bank.warp_to_slot(21);
+    let third = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &third, 170).unwrap();
+    assert_eq!(listing.auction(&bank).highest_bidder_pubkey, third.wallet);
+}
+
+#[test]
+fn same_slot_bids_only_need_the_usual_increment_without_protection() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    assert!(!listing.auction(&bank).anti_sandwich);
+    bank.warp_to_slot(20);
+    let first = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &first, 150).unwrap();
+    let second = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &second, 151).unwrap();
+
+    assert_eq!(
+        bank.process(set_anti_sandwich_protection_ix(&bank, &listing, true)),
+        Err(AuctionError::AlreadyBid.into())
+    );
+}
+
+fn set_auction_price_curve_ix(bank: &Bank, listing: &Listing, tiers: &[(u64, u16)]) -> Instruction {
+    let mut data = vec![108];
+    for i in 0..MAX_PRICE_TIERS {
+        let (min_bid, increment_bps) = tiers.get(i).copied().unwrap_or_default();
+        data.extend_from_slice(&min_bid.to_le_bytes());
+        data.extend_from_slice(&increment_bps.to_le_bytes());
+    }
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+#[test]
+fn bid_increment_follows_the_price_curve_tier() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_auction_price_curve_ix(
+        &bank,
+        &listing,
+        &[(0, 5_000), (1_000, 1_000)],
+    ))
+    .unwrap();
+
+    let bidders: Vec<Bidder> = (0..4).map(|_| listing.bidder(&mut bank)).collect();
+    // 50% increments below 1_000, then 10%
+    for (bidder, min_next_bid) in [(&bidders[0], 150), (&bidders[1], 225)] {
+        assert_eq!(
+            listing.bid(&mut bank, bidder, min_next_bid - 1),
+            Err(AuctionError::InsufficientBidPrice.into())
+        );
+        listing.bid(&mut bank, bidder, min_next_bid).unwrap();
+    }
+    listing.bid(&mut bank, &bidders[2], 1_000).unwrap();
+    assert_eq!(
+        listing.bid(&mut bank, &bidders[3], 1_099),
+        Err(AuctionError::InsufficientBidPrice.into())
+    );
+    listing.bid(&mut bank, &bidders[3], 1_100).unwrap();
+    assert_eq!(listing.auction(&bank).price, 1_100);
+}
+
+#[test]
+fn price_curve_tiers_must_be_sorted() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    for tiers in [
+        vec![(1_000, 1_000), (500, 5_000)],
+        vec![(500, 1_000), (500, 5_000)],
+        vec![(0, 5_000), (0, 0), (1_000, 1_000)],
+    ] {
+        assert_eq!(
+            bank.process(set_auction_price_curve_ix(&bank, &listing, &tiers)),
+            Err(AuctionError::InvalidPriceCurve.into())
+        );
+    }
+
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    assert_eq!(
+        bank.process(set_auction_price_curve_ix(&bank, &listing, &[(0, 5_000)])),
+        Err(AuctionError::AlreadyBid.into())
+    );
+}
+
+fn set_early_bird_discount_ix(
+    bank: &Bank,
+    listing: &Listing,
+    early_bird_period_bps: u16,
+    early_bird_increment_bps: u16,
+    late_increment_bps: u16,
+) -> Instruction {
+    let mut data = vec![109];
+    data.extend_from_slice(&early_bird_period_bps.to_le_bytes());
+    data.extend_from_slice(&early_bird_increment_bps.to_le_bytes());
+    data.extend_from_slice(&late_increment_bps.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+#[test]
+fn early_bids_need_a_smaller_increment() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_early_bird_discount_ix(&bank, &listing, 1_000, 100, 500))
+        .unwrap();
+    let bidders: Vec<Bidder> = (0..3).map(|_| listing.bidder(&mut bank)).collect();
+    listing.bid(&mut bank, &bidders[0], 1_000).unwrap();
+    assert_eq!(
+        listing.bid(&mut bank, &bidders[1], 1_009),
+        Err(AuctionError::InsufficientBidPrice.into())
+    );
+    listing.bid(&mut bank, &bidders[1], 1_010).unwrap();
+
+    // Past the first 10% of the duration
+    let auction = listing.auction(&bank);
+    bank.warp_to_timestamp(auction.starts_at + (auction.end_at - auction.starts_at) / 10);
+    assert_eq!(
+        listing.bid(&mut bank, &bidders[2], 1_059),
+        Err(AuctionError::InsufficientBidPrice.into())
+    );
+    listing.bid(&mut bank, &bidders[2], 1_060).unwrap();
+}
+
+#[test]
+fn early_bird_period_is_a_share_of_the_duration() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    assert_eq!(
+        bank.process(set_early_bird_discount_ix(
+            &bank, &listing, 10_001, 100, 500
+        )),
+        Err(AuctionError::InvalidFeeBasisPoints.into())
+    );
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    assert_eq!(
+        bank.process(set_early_bird_discount_ix(&bank, &listing, 1_000, 100, 500)),
+        Err(AuctionError::AlreadyBid.into())
+    );
+}
+
+thread_local! {
+    /// Weights returned by the mock bid weight oracle, unit for the other bidders
+    static BID_WEIGHTS: RefCell<Vec<(Pubkey, u64)>> = const { RefCell::new(vec![]) };
+}
+
+/// Bid weight oracle returning the weights registered in `BID_WEIGHTS`
+fn bid_weight_oracle(_: &Pubkey, _: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if data.len() != 33 || data[0] != BID_WEIGHT_QUERY_TAG {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let bidder = Pubkey::try_from(&data[1..]).unwrap();
+    let weight_bps = BID_WEIGHTS.with(|weights| {
+        weights
+            .borrow()
+            .iter()
+            .find(|(weighted, _)| *weighted == bidder)
+            .map_or(UNIT_BID_WEIGHT_BPS, |(_, weight_bps)| *weight_bps)
+    });
+    set_return_data(&weight_bps.to_le_bytes());
+    Ok(())
+}
+
+fn set_bid_weighting_ix(bank: &Bank, listing: &Listing, bid_weight_oracle: &Pubkey) -> Instruction {
+    let mut data = vec![112];
+    data.extend_from_slice(bid_weight_oracle.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+fn weighted_bid(
+    bank: &mut Bank,
+    listing: &Listing,
+    bidder: &Bidder,
+    oracle: &Pubkey,
+    price: u64,
+) -> ProgramResult {
+    let mut bid_ix = listing.bid_ix(bank, bidder, price);
+    bid_ix
+        .accounts
+        .push(AccountMeta::new_readonly(*oracle, false));
+    bank.process(bid_ix)
+}
+
+#[test]
+fn weighted_bids_beat_the_highest_bid_by_price_times_weight() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let oracle = Pubkey::new_unique();
+    bank.add_program(oracle, bid_weight_oracle);
+    bank.process(set_bid_weighting_ix(&bank, &listing, &oracle))
+        .unwrap();
+    let holder = listing.bidder(&mut bank);
+    let first = listing.bidder(&mut bank);
+    let second = listing.bidder(&mut bank);
+    BID_WEIGHTS.with(|weights| weights.borrow_mut().push((holder.wallet, 15_000)));
+
+    assert_eq!(
+        listing.bid(&mut bank, &first, 200),
+        Err(ProgramError::NotEnoughAccountKeys)
+    );
+    weighted_bid(&mut bank, &listing, &first, &oracle, 200).unwrap();
+    // 150 weighs 225 and beats 200
+    weighted_bid(&mut bank, &listing, &holder, &oracle, 150).unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.highest_bidder_pubkey, holder.wallet);
+    assert_eq!(auction.price, 150);
+    assert_eq!(bank.token_balance(&holder.ft_account), BIDDER_BALANCE - 150);
+
+    assert_eq!(
+        weighted_bid(&mut bank, &listing, &second, &oracle, 225),
+        Err(AuctionError::InsufficientBidPrice.into())
+    );
+    weighted_bid(&mut bank, &listing, &second, &oracle, 226).unwrap();
+    assert_eq!(listing.auction(&bank).highest_bidder_pubkey, second.wallet);
+}
+
+#[test]
+fn bid_weights_must_be_positive() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let oracle = Pubkey::new_unique();
+    bank.add_program(oracle, bid_weight_oracle);
+    bank.process(set_bid_weighting_ix(&bank, &listing, &oracle))
+        .unwrap();
+    let bidder = listing.bidder(&mut bank);
+    BID_WEIGHTS.with(|weights| weights.borrow_mut().push((bidder.wallet, 0)));
+    assert_eq!(
+        weighted_bid(&mut bank, &listing, &bidder, &oracle, 200),
+        Err(AuctionError::InvalidBidWeight.into())
+    );
+
+    let other = listing.bidder(&mut bank);
+    weighted_bid(&mut bank, &listing, &other, &oracle, 200).unwrap();
+    assert_eq!(
+        bank.process(set_bid_weighting_ix(&bank, &listing, &Pubkey::default())),
+        Err(AuctionError::AlreadyBid.into())
+    );
+}
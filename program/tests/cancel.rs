@@ -0,0 +1,261 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use common::auction::{pda, setup, Listing, BIDDER_BALANCE};
+use common::Bank;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::{system_program, sysvar};
+use spl_token::state::Mint;
+
+fn set_refund_policy_ix(bank: &Bank, listing: &Listing, refund_on_cancel: bool) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[26, refund_on_cancel as u8],
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+fn force_cancel_ix(bank: &Bank, listing: &Listing) -> Instruction {
+    let program_id = &bank.program_id;
+    let auction = listing.auction(bank);
+    Instruction::new_with_bytes(
+        *program_id,
+        &[27],
+        vec![
+            AccountMeta::new(listing.exhibitor, true),
+            AccountMeta::new(listing.nft_temp, false),
+            AccountMeta::new(listing.exhibitor_nft, false),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new(auction.highest_bidder_pubkey, false),
+            AccountMeta::new(auction.highest_bidder_ft_temp_pubkey, false),
+            AccountMeta::new(auction.highest_bidder_ft_returning_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(pda(&[b"escrow"], program_id), false),
+            AccountMeta::new(pda(&[b"stats"], program_id), false),
+        ],
+    )
+}
+
+#[test]
+fn force_cancel_with_refund_policy_refunds_the_bid() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_refund_policy_ix(&bank, &listing, true))
+        .unwrap();
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let auction = listing.auction(&bank);
+
+    bank.process(force_cancel_ix(&bank, &listing)).unwrap();
+    assert_eq!(bank.token_balance(&listing.exhibitor_nft), 1);
+    assert_eq!(bank.token_balance(&bidder.ft_account), BIDDER_BALANCE);
+    assert!(bank
+        .account(&auction.highest_bidder_ft_temp_pubkey)
+        .is_none());
+    assert!(bank.account(&listing.escrow).is_none());
+}
+
+#[test]
+fn force_cancel_without_refund_policy_compensates_the_bidder() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    assert!(!listing.auction(&bank).refund_on_cancel);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let exhibitor_ft = bank.create_token_account(&listing.ft_mint, &listing.exhibitor, 1_000);
+
+    let mut force_cancel_ix = force_cancel_ix(&bank, &listing);
+    force_cancel_ix
+        .accounts
+        .push(AccountMeta::new(exhibitor_ft, false));
+    bank.process(force_cancel_ix).unwrap();
+    assert_eq!(bank.token_balance(&listing.exhibitor_nft), 1);
+    assert_eq!(bank.token_balance(&bidder.ft_account), BIDDER_BALANCE + 250);
+    assert_eq!(bank.token_balance(&exhibitor_ft), 750);
+    assert!(bank.account(&listing.escrow).is_none());
+}
+
+fn set_burn_on_cancel_ix(bank: &Bank, listing: &Listing, burn_on_cancel: bool) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[86, burn_on_cancel as u8],
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+#[test]
+fn cancelling_burns_the_nft_when_the_exhibitor_opted_in() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_burn_on_cancel_ix(&bank, &listing, true))
+        .unwrap();
+    assert!(listing.auction(&bank).burn_on_cancel);
+
+    let mut cancel_ix = listing.cancel_ix(&bank);
+    cancel_ix
+        .accounts
+        .push(AccountMeta::new(listing.nft_mint, false));
+    bank.process(cancel_ix).unwrap();
+    assert_eq!(bank.unpack::<Mint>(&listing.nft_mint).supply, 0);
+    assert_eq!(bank.token_balance(&listing.exhibitor_nft), 0);
+    assert!(bank.account(&listing.escrow).is_none());
+}
+
+#[test]
+fn burn_on_cancel_is_set_by_the_exhibitor_before_bids() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let intruder = bank.create_wallet();
+    let mut set_ix = set_burn_on_cancel_ix(&bank, &listing, true);
+    set_ix.accounts[0] = AccountMeta::new_readonly(intruder, true);
+    assert_eq!(bank.process(set_ix), Err(ProgramError::InvalidAccountData));
+
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    assert_eq!(
+        bank.process(set_burn_on_cancel_ix(&bank, &listing, true)),
+        Err(AuctionError::AlreadyBid.into())
+    );
+    assert!(!listing.auction(&bank).burn_on_cancel);
+}
+
+fn collateral_vault(bank: &Bank, listing: &Listing) -> Pubkey {
+    pda(
+        &[
+            b"collateral",
+            listing.escrow.as_ref(),
+            listing.exhibitor.as_ref(),
+        ],
+        &bank.program_id,
+    )
+}
+
+fn set_exhibitor_collateral_ix(
+    bank: &Bank,
+    listing: &Listing,
+    exhibitor_ft: &Pubkey,
+    amount: u64,
+    cancel_penalty_bps: u16,
+) -> Instruction {
+    let mut data = vec![94];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&cancel_penalty_bps.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(listing.exhibitor, true),
+            AccountMeta::new(*exhibitor_ft, false),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new(collateral_vault(bank, listing), false),
+            AccountMeta::new_readonly(listing.ft_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+fn reclaim_collateral_ix(bank: &Bank, listing: &Listing, exhibitor_ft: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[95],
+        vec![
+            AccountMeta::new(listing.exhibitor, true),
+            AccountMeta::new(*exhibitor_ft, false),
+            AccountMeta::new_readonly(listing.escrow, false),
+            AccountMeta::new(collateral_vault(bank, listing), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+    )
+}
+
+#[test]
+fn force_cancel_pays_the_penalty_from_the_collateral() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let exhibitor_ft = bank.create_token_account(&listing.ft_mint, &listing.exhibitor, 1_000);
+    bank.process(set_refund_policy_ix(&bank, &listing, true))
+        .unwrap();
+    bank.process(set_exhibitor_collateral_ix(
+        &bank,
+        &listing,
+        &exhibitor_ft,
+        1_000,
+        2_000,
+    ))
+    .unwrap();
+    assert_eq!(bank.token_balance(&exhibitor_ft), 0);
+    assert_eq!(
+        bank.token_balance(&collateral_vault(&bank, &listing)),
+        1_000
+    );
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+
+    let mut force_cancel_ix = force_cancel_ix(&bank, &listing);
+    force_cancel_ix
+        .accounts
+        .push(AccountMeta::new(collateral_vault(&bank, &listing), false));
+    bank.process(force_cancel_ix).unwrap();
+    assert_eq!(bank.token_balance(&bidder.ft_account), BIDDER_BALANCE + 200);
+
+    bank.process(reclaim_collateral_ix(&bank, &listing, &exhibitor_ft))
+        .unwrap();
+    assert_eq!(bank.token_balance(&exhibitor_ft), 800);
+    assert!(bank.account(&collateral_vault(&bank, &listing)).is_none());
+}
+
+#[test]
+fn collateral_is_locked_before_bids_until_the_escrow_is_closed() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let exhibitor_ft = bank.create_token_account(&listing.ft_mint, &listing.exhibitor, 1_000);
+    assert_eq!(
+        bank.process(set_exhibitor_collateral_ix(
+            &bank,
+            &listing,
+            &exhibitor_ft,
+            500,
+            10_001
+        )),
+        Err(AuctionError::InvalidFeeBasisPoints.into())
+    );
+    bank.process(set_exhibitor_collateral_ix(
+        &bank,
+        &listing,
+        &exhibitor_ft,
+        500,
+        1_000,
+    ))
+    .unwrap();
+    assert_eq!(
+        bank.process(reclaim_collateral_ix(&bank, &listing, &exhibitor_ft)),
+        Err(AuctionError::ActiveAuction.into())
+    );
+
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    assert_eq!(
+        bank.process(set_exhibitor_collateral_ix(
+            &bank,
+            &listing,
+            &exhibitor_ft,
+            500,
+            1_000
+        )),
+        Err(AuctionError::AlreadyBid.into())
+    );
+    assert_eq!(listing.auction(&bank).exhibitor_collateral, 500);
+}
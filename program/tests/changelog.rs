@@ -0,0 +1,327 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::event::ChangelogEntryEvent;
+use auction_pal::state::{AuctionType, MAX_BATCH_BID};
+use common::auction::{pda, setup, Bidder, Listing, AUCTION_DURATION_SEC, INITIAL_PRICE};
+use common::Bank;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::{system_program, sysvar};
+
+fn changelog(bank: &Bank, listing: &Listing) -> Pubkey {
+    pda(&[b"changelog", listing.escrow.as_ref()], &bank.program_id)
+}
+
+fn init_changelog_ix(bank: &Bank, listing: &Listing) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[39],
+        vec![
+            AccountMeta::new(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new(changelog(bank, listing), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+fn read_changelog_ix(bank: &Bank, listing: &Listing) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[40],
+        vec![
+            AccountMeta::new_readonly(listing.escrow, false),
+            AccountMeta::new_readonly(changelog(bank, listing), false),
+        ],
+    )
+}
+
+/// `instruction` with the changelog PDA account appended last
+fn logged(bank: &Bank, listing: &Listing, mut instruction: Instruction) -> Instruction {
+    instruction
+        .accounts
+        .push(AccountMeta::new(changelog(bank, listing), false));
+    instruction
+}
+
+fn exhibitor_ix(bank: &Bank, listing: &Listing, data: &[u8]) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+    )
+}
+
+/// `(instruction, actor, timestamp, extra)` of each entry logged by `ReadChangelog`
+fn read_changelog(bank: &mut Bank, listing: &Listing) -> Vec<(u8, Pubkey, i64, u64)> {
+    bank.process(read_changelog_ix(bank, listing)).unwrap();
+    Bank::events()
+        .into_iter()
+        .enumerate()
+        .map(|(index, fields)| {
+            assert_eq!(fields[0], ChangelogEntryEvent::NAME);
+            assert_eq!(fields[1], listing.escrow.to_bytes());
+            assert_eq!(fields[2], (index as u64).to_le_bytes());
+            (
+                fields[3][0],
+                Pubkey::try_from(fields[4].as_slice()).unwrap(),
+                i64::from_le_bytes(fields[5].as_slice().try_into().unwrap()),
+                u64::from_le_bytes(fields[6].as_slice().try_into().unwrap()),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn changelog_records_each_state_change_in_order() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(init_changelog_ix(&bank, &listing)).unwrap();
+    let auction = listing.auction(&bank);
+    assert!(auction.has_changelog);
+    let start = bank.clock().unix_timestamp;
+
+    let mut floor_ix = exhibitor_ix(&bank, &listing, &[8]);
+    floor_ix.data.extend_from_slice(&120u64.to_le_bytes());
+    bank.process(logged(&bank, &listing, floor_ix)).unwrap();
+    bank.warp_to_timestamp(start + 10);
+    bank.process(logged(
+        &bank,
+        &listing,
+        exhibitor_ix(&bank, &listing, &[24, 1]),
+    ))
+    .unwrap();
+    bank.warp_to_timestamp(start + 20);
+    let first = listing.bidder(&mut bank);
+    let bid_ix = listing.bid_ix(&mut bank, &first, 150);
+    bank.process(logged(&bank, &listing, bid_ix)).unwrap();
+    bank.warp_to_timestamp(start + 30);
+    let second = listing.bidder(&mut bank);
+    let bid_ix = listing.bid_ix(&mut bank, &second, 200);
+    bank.process(logged(&bank, &listing, bid_ix)).unwrap();
+    bank.warp_to_timestamp(start + 40);
+    let mut floor_ix = exhibitor_ix(&bank, &listing, &[8]);
+    floor_ix.data.extend_from_slice(&250u64.to_le_bytes());
+    bank.process(logged(&bank, &listing, floor_ix)).unwrap();
+
+    assert_eq!(
+        read_changelog(&mut bank, &listing),
+        [
+            (8, listing.exhibitor, start, 120),
+            (24, listing.exhibitor, start + 10, 0),
+            (1, first.wallet, start + 20, 150),
+            (1, second.wallet, start + 30, 200),
+            (8, listing.exhibitor, start + 40, 250),
+        ]
+    );
+}
+
+#[test]
+fn queries_are_not_recorded() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(init_changelog_ix(&bank, &listing)).unwrap();
+    let bidder = listing.bidder(&mut bank);
+
+    let mut validate_ix = exhibitor_ix(&bank, &listing, &[32]);
+    validate_ix.accounts[0].pubkey = bidder.wallet;
+    validate_ix.accounts[1].is_writable = false;
+    validate_ix.data.extend_from_slice(&150u64.to_le_bytes());
+    bank.process(logged(&bank, &listing, validate_ix)).unwrap();
+
+    assert!(read_changelog(&mut bank, &listing).is_empty());
+}
+
+#[test]
+fn state_changes_require_the_changelog_of_their_auction() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let other = Listing::exhibit(&mut bank);
+    bank.process(init_changelog_ix(&bank, &listing)).unwrap();
+    bank.process(init_changelog_ix(&bank, &other)).unwrap();
+
+    assert_eq!(
+        bank.process(exhibitor_ix(&bank, &listing, &[24, 1])),
+        Err(ProgramError::InvalidSeeds)
+    );
+    let mut foreign_ix = exhibitor_ix(&bank, &listing, &[24, 1]);
+    foreign_ix
+        .accounts
+        .push(AccountMeta::new(changelog(&bank, &other), false));
+    assert_eq!(bank.process(foreign_ix), Err(ProgramError::InvalidSeeds));
+    let mut read_only_ix = logged(&bank, &listing, exhibitor_ix(&bank, &listing, &[24, 1]));
+    read_only_ix.accounts[3].is_writable = false;
+    assert_eq!(
+        bank.process(read_only_ix),
+        Err(ProgramError::InvalidAccountData)
+    );
+
+    assert!(!listing.auction(&bank).auto_close);
+    assert!(read_changelog(&mut bank, &listing).is_empty());
+    assert!(read_changelog(&mut bank, &other).is_empty());
+}
+
+#[test]
+fn hidden_bid_prices_are_masked_in_the_changelog() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(init_changelog_ix(&bank, &listing)).unwrap();
+    let start = bank.clock().unix_timestamp;
+    let bidder = listing.bidder(&mut bank);
+    let bid_ix = listing.bid_ix(&mut bank, &bidder, 150);
+    bank.process(logged(&bank, &listing, bid_ix)).unwrap();
+    let mut floor_ix = exhibitor_ix(&bank, &listing, &[8]);
+    floor_ix.data.extend_from_slice(&200u64.to_le_bytes());
+    bank.process(logged(&bank, &listing, floor_ix)).unwrap();
+    bank.process(logged(
+        &bank,
+        &listing,
+        exhibitor_ix(&bank, &listing, &[33, 0]),
+    ))
+    .unwrap();
+
+    assert_eq!(
+        read_changelog(&mut bank, &listing),
+        [
+            (1, bidder.wallet, start, 0),
+            (8, listing.exhibitor, start, 200),
+            (33, listing.exhibitor, start, 0),
+        ]
+    );
+
+    listing.update_auction(&mut bank, |auction| auction.bids_visible = true);
+    assert_eq!(read_changelog(&mut bank, &listing)[0].3, 150);
+    listing.update_auction(&mut bank, |auction| {
+        auction.auction_type = AuctionType::SealedBid
+    });
+    assert_eq!(read_changelog(&mut bank, &listing)[0].3, 0);
+}
+
+#[test]
+fn sealed_end_time_is_not_logged_by_the_changelog() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(init_changelog_ix(&bank, &listing)).unwrap();
+    let start = bank.clock().unix_timestamp;
+    let end_at = listing.auction(&bank).end_at;
+    let reveal_at = end_at - 60;
+    let sealed_end_time_ix = listing.set_sealed_end_time_ix(&bank, reveal_at);
+    bank.process(logged(&bank, &listing, sealed_end_time_ix))
+        .unwrap();
+    let expiry_ix = listing.set_auction_expiry_ix(&bank, &authority, end_at + 600);
+    bank.process(logged(&bank, &listing, expiry_ix)).unwrap();
+
+    assert_eq!(
+        read_changelog(&mut bank, &listing),
+        [
+            (110, listing.exhibitor, start, 0),
+            (31, authority, start, 0)
+        ]
+    );
+    bank.warp_to_timestamp(reveal_at);
+    assert_eq!(
+        read_changelog(&mut bank, &listing),
+        [
+            (110, listing.exhibitor, start, 0),
+            (31, authority, start, (end_at + 600) as u64)
+        ]
+    );
+}
+
+fn batch_bid_group(bank: &Bank, bidder: &Bidder, first: &Listing) -> Pubkey {
+    pda(
+        &[b"batch_bid", bidder.wallet.as_ref(), first.escrow.as_ref()],
+        &bank.program_id,
+    )
+}
+
+fn batch_bid_ix(bank: &mut Bank, listings: &[Listing], bidder: &Bidder, price: u64) -> Instruction {
+    let mut data = vec![99];
+    for i in 0..MAX_BATCH_BID {
+        let escrow = listings
+            .get(i)
+            .map_or(Pubkey::default(), |listing| listing.escrow);
+        data.extend_from_slice(escrow.as_ref());
+    }
+    data.extend_from_slice(&price.to_le_bytes());
+    let mut accounts = vec![
+        AccountMeta::new(bidder.wallet, true),
+        AccountMeta::new(batch_bid_group(bank, bidder, &listings[0]), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    for listing in listings {
+        accounts.extend(listing.bid_ix(bank, bidder, price).accounts);
+    }
+    Instruction::new_with_bytes(bank.program_id, &data, accounts)
+}
+
+#[test]
+fn batch_instructions_are_recorded_in_the_changelog_of_each_auction() {
+    let (mut bank, _) = setup();
+    let first = Listing::exhibit(&mut bank);
+    let second = Listing::with_ft_mint(&mut bank, first.exhibitor, first.ft_mint);
+    bank.process(second.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC))
+        .unwrap();
+    second.accept_terms(&mut bank);
+    let listings = [first, second];
+    for listing in &listings {
+        bank.process(init_changelog_ix(&bank, listing)).unwrap();
+    }
+    let start = bank.clock().unix_timestamp;
+    let bidder = listings[0].bidder(&mut bank);
+
+    let partially_logged_ix = batch_bid_ix(&mut bank, &listings, &bidder, 150);
+    assert_eq!(
+        bank.process(logged(&bank, &listings[0], partially_logged_ix)),
+        Err(ProgramError::InvalidSeeds)
+    );
+    let mut batch_bid_ix = batch_bid_ix(&mut bank, &listings, &bidder, 150);
+    for listing in &listings {
+        batch_bid_ix = logged(&bank, listing, batch_bid_ix);
+    }
+    bank.process(batch_bid_ix).unwrap();
+    for listing in &listings {
+        assert_eq!(
+            read_changelog(&mut bank, listing),
+            [(99, bidder.wallet, start, 150)]
+        );
+    }
+
+    listings[1].expire(&mut bank);
+    let program_id = bank.program_id;
+    let refunded = listings[0].auction(&bank);
+    let claim_ix = Instruction::new_with_bytes(
+        program_id,
+        &[100],
+        vec![
+            AccountMeta::new(bidder.wallet, true),
+            AccountMeta::new(batch_bid_group(&bank, &bidder, &listings[0]), false),
+            AccountMeta::new_readonly(listings[1].escrow, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(pda(&[b"escrow"], &program_id), false),
+            AccountMeta::new(listings[0].escrow, false),
+            AccountMeta::new(refunded.highest_bidder_ft_temp_pubkey, false),
+            AccountMeta::new(bidder.ft_account, false),
+        ],
+    );
+    bank.process(logged(&bank, &listings[0], claim_ix)).unwrap();
+    let end = bank.clock().unix_timestamp;
+    assert_eq!(
+        read_changelog(&mut bank, &listings[0]),
+        [
+            (99, bidder.wallet, start, 150),
+            (100, bidder.wallet, end, 0),
+        ]
+    );
+    assert_eq!(read_changelog(&mut bank, &listings[1]).len(), 1);
+}
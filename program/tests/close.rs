@@ -0,0 +1,1312 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use auction_pal::event::ForfeitedAuctionEvent;
+use auction_pal::state::{
+    Auction, AuditRecord, BonusDeposit, TombstoneRecord, DEFAULT_MAX_BIDDER_EXTENSION_SEC,
+    MAX_CLOCK_DRIFT_TOLERANCE_SEC,
+};
+use common::auction::{pda, setup, Listing, AUCTION_DURATION_SEC, BIDDER_BALANCE, INITIAL_PRICE};
+use common::{Account, Bank, WALLET_LAMPORTS};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::{system_program, sysvar};
+
+fn tombstone(bank: &Bank, listing: &Listing) -> Pubkey {
+    pda(&[b"tombstone", listing.escrow.as_ref()], &bank.program_id)
+}
+
+#[test]
+fn close_writes_a_tombstone_that_outlives_the_auction() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    listing.expire(&mut bank);
+
+    let closed_at = bank.clock().unix_timestamp;
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    bank.process(listing.close_ix(&bank, &winner_nft)).unwrap();
+
+    assert_eq!(bank.lamports(&listing.escrow), 0);
+    let record: TombstoneRecord = bank.unpack(&tombstone(&bank, &listing));
+    assert_eq!(record.winner, bidder.wallet);
+    assert_eq!(record.final_price, 250);
+    assert_eq!(record.closed_at, closed_at);
+    assert_eq!(record.nft_mint, listing.nft_mint);
+    assert_eq!(record.exhibitor, listing.exhibitor);
+}
+
+#[test]
+fn tombstone_is_not_written_before_the_winner_is_final() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let first = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &first, 250).unwrap();
+    let winner_nft = listing.nft_account(&mut bank, &first.wallet);
+    assert_eq!(
+        bank.process(listing.close_ix(&bank, &winner_nft)),
+        Err(AuctionError::ActiveAuction.into())
+    );
+    assert!(bank.account(&tombstone(&bank, &listing)).is_none());
+
+    let second = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &second, 300).unwrap();
+    listing.expire(&mut bank);
+    let winner_nft = listing.nft_account(&mut bank, &second.wallet);
+    bank.process(listing.close_ix(&bank, &winner_nft)).unwrap();
+
+    let record: TombstoneRecord = bank.unpack(&tombstone(&bank, &listing));
+    assert_eq!(record.winner, second.wallet);
+    assert_eq!(record.final_price, 300);
+}
+
+#[test]
+fn closed_escrow_cannot_hold_another_auction() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    listing.expire(&mut bank);
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    bank.process(listing.close_ix(&bank, &winner_nft)).unwrap();
+
+    // The exhibitor funds the closed escrow key again and exhibits a new NFT on it
+    let program_id = bank.program_id;
+    bank.set_account(
+        listing.escrow,
+        Account {
+            lamports: Rent::default().minimum_balance(Auction::LEN),
+            data: vec![0; Auction::LEN],
+            owner: program_id,
+            executable: false,
+        },
+    );
+    let reused = Listing {
+        escrow: listing.escrow,
+        ..Listing::with_ft_mint(&mut bank, listing.exhibitor, listing.ft_mint)
+    };
+    assert_eq!(
+        bank.process(reused.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC)),
+        Err(ProgramError::AccountAlreadyInitialized)
+    );
+    assert_eq!(bank.token_balance(&reused.exhibitor_nft), 1);
+
+    // On a new escrow the same NFT is auctioned and closed with its own tombstone
+    let relisted = Listing {
+        escrow: bank.create_account(&program_id, Auction::LEN),
+        ..reused
+    };
+    bank.process(relisted.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC))
+        .unwrap();
+    relisted.accept_terms(&mut bank);
+    let bidder = relisted.bidder(&mut bank);
+    relisted.bid(&mut bank, &bidder, 300).unwrap();
+    relisted.expire(&mut bank);
+    let winner_nft = relisted.nft_account(&mut bank, &bidder.wallet);
+    bank.process(relisted.close_ix(&bank, &winner_nft)).unwrap();
+
+    assert_eq!(bank.token_balance(&winner_nft), 1);
+    let record: TombstoneRecord = bank.unpack(&tombstone(&bank, &relisted));
+    assert_eq!(record.final_price, 300);
+    assert_eq!(record.nft_mint, relisted.nft_mint);
+}
+
+fn extend_bid_deadline_ix(
+    bank: &Bank,
+    listing: &Listing,
+    signer: &Pubkey,
+    additional_seconds: u64,
+) -> Instruction {
+    let mut data = vec![7];
+    data.extend_from_slice(&additional_seconds.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+    )
+}
+
+#[test]
+fn winner_extends_then_closes_at_the_new_deadline() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    listing.expire(&mut bank);
+    let end_at = listing.auction(&bank).end_at;
+
+    bank.process(extend_bid_deadline_ix(&bank, &listing, &bidder.wallet, 600))
+        .unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.end_at, end_at + 600);
+    assert!(auction.bidder_extended);
+
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    assert_eq!(
+        bank.process(listing.close_ix(&bank, &winner_nft)),
+        Err(AuctionError::ActiveAuction.into())
+    );
+    assert_eq!(
+        bank.process(extend_bid_deadline_ix(&bank, &listing, &bidder.wallet, 600)),
+        Err(AuctionError::ActiveAuction.into())
+    );
+
+    listing.expire(&mut bank);
+    assert_eq!(
+        bank.process(extend_bid_deadline_ix(&bank, &listing, &bidder.wallet, 600)),
+        Err(AuctionError::AlreadyExtended.into())
+    );
+    bank.process(listing.close_ix(&bank, &winner_nft)).unwrap();
+    assert_eq!(bank.token_balance(&winner_nft), 1);
+}
+
+#[test]
+fn third_parties_cannot_bid_during_the_extension() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    listing.expire(&mut bank);
+    bank.process(extend_bid_deadline_ix(&bank, &listing, &bidder.wallet, 600))
+        .unwrap();
+
+    let latecomer = listing.bidder(&mut bank);
+    assert_eq!(
+        listing.bid(&mut bank, &latecomer, 1_000),
+        Err(AuctionError::InactiveAuction.into())
+    );
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.highest_bidder_pubkey, bidder.wallet);
+    assert_eq!(auction.price, 250);
+    assert_eq!(bank.token_balance(&latecomer.ft_account), BIDDER_BALANCE);
+}
+
+#[test]
+fn extension_is_capped_at_the_auction_maximum() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    listing.expire(&mut bank);
+    let auction = listing.auction(&bank);
+
+    bank.process(extend_bid_deadline_ix(
+        &bank,
+        &listing,
+        &bidder.wallet,
+        u64::MAX,
+    ))
+    .unwrap();
+    assert_eq!(
+        listing.auction(&bank).end_at,
+        auction.end_at + DEFAULT_MAX_BIDDER_EXTENSION_SEC as i64
+    );
+}
+
+#[test]
+fn non_winner_cannot_extend() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    listing.expire(&mut bank);
+    let end_at = listing.auction(&bank).end_at;
+
+    assert_eq!(
+        bank.process(extend_bid_deadline_ix(
+            &bank,
+            &listing,
+            &listing.exhibitor,
+            600
+        )),
+        Err(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(listing.auction(&bank).end_at, end_at);
+}
+
+fn recover_stuck_bid_ix(bank: &Bank, listing: &Listing, authority: &Pubkey) -> Instruction {
+    let program_id = &bank.program_id;
+    let auction = listing.auction(bank);
+    Instruction::new_with_bytes(
+        *program_id,
+        &[10],
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(pda(&[b"global_config"], program_id), false),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new(auction.highest_bidder_pubkey, false),
+            AccountMeta::new(auction.highest_bidder_ft_temp_pubkey, false),
+            AccountMeta::new(auction.highest_bidder_ft_returning_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(pda(&[b"escrow"], program_id), false),
+        ],
+    )
+}
+
+#[test]
+fn authority_recovers_a_stuck_bid() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let auction = listing.auction(&bank);
+    let bidder_lamports = bank.lamports(&bidder.wallet);
+    let temp_lamports = bank.lamports(&auction.highest_bidder_ft_temp_pubkey);
+
+    listing.expire(&mut bank);
+    assert_eq!(
+        bank.process(recover_stuck_bid_ix(&bank, &listing, &authority)),
+        Err(AuctionError::GracePeriodActive.into())
+    );
+
+    bank.warp_to_timestamp(auction.close_allowed_at().unwrap());
+    let intruder = bank.create_wallet();
+    assert_eq!(
+        bank.process(recover_stuck_bid_ix(&bank, &listing, &intruder)),
+        Err(AuctionError::Unauthorized.into())
+    );
+    bank.process(recover_stuck_bid_ix(&bank, &listing, &authority))
+        .unwrap();
+
+    assert_eq!(bank.token_balance(&bidder.ft_account), BIDDER_BALANCE);
+    assert!(bank
+        .account(&auction.highest_bidder_ft_temp_pubkey)
+        .is_none());
+    assert_eq!(
+        bank.lamports(&bidder.wallet),
+        bidder_lamports + temp_lamports
+    );
+    assert_eq!(
+        listing.auction(&bank).highest_bidder_pubkey,
+        Pubkey::default()
+    );
+}
+
+/// Program failing unless its first account holds a zeroed `Auction` sized buffer
+fn assert_zeroed_escrow(_: &Pubkey, accounts: &[AccountInfo], _: &[u8]) -> ProgramResult {
+    let data = accounts[0].try_borrow_data()?;
+    if data.len() != Auction::LEN || data.iter().any(|&byte| byte != 0) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
+
+#[test]
+fn close_zeroes_the_escrow_data() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    listing.expire(&mut bank);
+    let probe = Pubkey::new_unique();
+    bank.add_program(probe, assert_zeroed_escrow);
+
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    bank.process_transaction(&[
+        listing.close_ix(&bank, &winner_nft),
+        Instruction::new_with_bytes(
+            probe,
+            &[],
+            vec![AccountMeta::new_readonly(listing.escrow, false)],
+        ),
+    ])
+    .unwrap();
+    assert!(bank.account(&listing.escrow).is_none());
+}
+
+#[test]
+fn cancel_zeroes_the_escrow_data() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let probe = Pubkey::new_unique();
+    bank.add_program(probe, assert_zeroed_escrow);
+
+    bank.process_transaction(&[
+        listing.cancel_ix(&bank),
+        Instruction::new_with_bytes(
+            probe,
+            &[],
+            vec![AccountMeta::new_readonly(listing.escrow, false)],
+        ),
+    ])
+    .unwrap();
+    assert!(bank.account(&listing.escrow).is_none());
+    assert_eq!(bank.token_balance(&listing.exhibitor_nft), 1);
+}
+
+fn transfer_nft_after_expiry_ix(bank: &Bank, listing: &Listing) -> Instruction {
+    let program_id = &bank.program_id;
+    let auction = listing.auction(bank);
+    Instruction::new_with_bytes(
+        *program_id,
+        &[19],
+        vec![
+            AccountMeta::new(listing.exhibitor, true),
+            AccountMeta::new(listing.nft_temp, false),
+            AccountMeta::new(listing.exhibitor_nft, false),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new(auction.highest_bidder_pubkey, false),
+            AccountMeta::new(auction.highest_bidder_ft_temp_pubkey, false),
+            AccountMeta::new(auction.highest_bidder_ft_returning_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(pda(&[b"escrow"], program_id), false),
+            AccountMeta::new(pda(&[b"stats"], program_id), false),
+        ],
+    )
+}
+
+#[test]
+fn exhibitor_takes_the_nft_back_from_an_unresponsive_winner() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let auction = listing.auction(&bank);
+
+    listing.expire(&mut bank);
+    assert_eq!(
+        bank.process(transfer_nft_after_expiry_ix(&bank, &listing)),
+        Err(AuctionError::GracePeriodActive.into())
+    );
+
+    bank.warp_to_timestamp(auction.close_allowed_at().unwrap());
+    bank.process(transfer_nft_after_expiry_ix(&bank, &listing))
+        .unwrap();
+    assert_eq!(bank.token_balance(&listing.exhibitor_nft), 1);
+    assert_eq!(bank.token_balance(&bidder.ft_account), BIDDER_BALANCE);
+    assert_eq!(bank.token_balance(&listing.ft_receiving), 0);
+    assert!(bank
+        .account(&auction.highest_bidder_ft_temp_pubkey)
+        .is_none());
+    assert!(bank.account(&listing.nft_temp).is_none());
+    assert!(bank.account(&listing.escrow).is_none());
+    assert!(Bank::events().contains(&vec![
+        ForfeitedAuctionEvent::NAME.to_vec(),
+        listing.escrow.to_bytes().to_vec(),
+        listing.exhibitor.to_bytes().to_vec(),
+        bidder.wallet.to_bytes().to_vec(),
+        250u64.to_le_bytes().to_vec(),
+    ]));
+}
+
+/// Lamports the program tips the caller of `TriggerAutoClose`
+const AUTO_CLOSE_TIP_LAMPORTS: u64 = 10_000;
+
+fn set_auto_close_ix(bank: &Bank, listing: &Listing, enabled: bool) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[24, enabled as u8],
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+fn trigger_auto_close_ix(
+    bank: &Bank,
+    listing: &Listing,
+    caller: &Pubkey,
+    winner_nft: &Pubkey,
+) -> Instruction {
+    let program_id = &bank.program_id;
+    let auction = listing.auction(bank);
+    Instruction::new_with_bytes(
+        *program_id,
+        &[25],
+        vec![
+            AccountMeta::new(*caller, true),
+            AccountMeta::new(auction.highest_bidder_pubkey, false),
+            AccountMeta::new(listing.exhibitor, false),
+            AccountMeta::new(listing.nft_temp, false),
+            AccountMeta::new(listing.ft_receiving, false),
+            AccountMeta::new(auction.highest_bidder_ft_temp_pubkey, false),
+            AccountMeta::new(*winner_nft, false),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(pda(&[b"escrow"], program_id), false),
+            AccountMeta::new(pda(&[b"stats"], program_id), false),
+        ],
+    )
+}
+
+#[test]
+fn third_party_auto_closes_an_expired_auction() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_auto_close_ix(&bank, &listing, true))
+        .unwrap();
+    assert!(listing.auction(&bank).auto_close);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let auction = listing.auction(&bank);
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    let crank = bank.create_wallet();
+
+    assert_eq!(
+        bank.process(trigger_auto_close_ix(&bank, &listing, &crank, &winner_nft)),
+        Err(AuctionError::ActiveAuction.into())
+    );
+
+    listing.expire(&mut bank);
+    let exhibitor_lamports = bank.lamports(&listing.exhibitor);
+    let escrow_lamports = bank.lamports(&listing.escrow);
+    let nft_temp_lamports = bank.lamports(&listing.nft_temp);
+    let bidder_lamports = bank.lamports(&bidder.wallet);
+    let ft_temp_lamports = bank.lamports(&auction.highest_bidder_ft_temp_pubkey);
+    bank.process(trigger_auto_close_ix(&bank, &listing, &crank, &winner_nft))
+        .unwrap();
+
+    assert_eq!(bank.token_balance(&winner_nft), 1);
+    assert_eq!(bank.token_balance(&listing.ft_receiving), 250);
+    assert!(bank.account(&listing.escrow).is_none());
+    assert!(bank.account(&listing.nft_temp).is_none());
+    assert!(bank
+        .account(&auction.highest_bidder_ft_temp_pubkey)
+        .is_none());
+    assert_eq!(
+        bank.lamports(&crank),
+        WALLET_LAMPORTS + AUTO_CLOSE_TIP_LAMPORTS
+    );
+    assert_eq!(
+        bank.lamports(&listing.exhibitor),
+        exhibitor_lamports + escrow_lamports + nft_temp_lamports - AUTO_CLOSE_TIP_LAMPORTS
+    );
+    assert_eq!(
+        bank.lamports(&bidder.wallet),
+        bidder_lamports + ft_temp_lamports
+    );
+}
+
+#[test]
+fn auto_close_needs_the_exhibitor_opt_in() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    listing.expire(&mut bank);
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    let crank = bank.create_wallet();
+
+    assert_eq!(
+        bank.process(trigger_auto_close_ix(&bank, &listing, &crank, &winner_nft)),
+        Err(AuctionError::AutoCloseDisabled.into())
+    );
+    assert_eq!(bank.token_balance(&listing.nft_temp), 1);
+}
+
+fn withdraw_partial_proceeds_ix(bank: &Bank, listing: &Listing, amount: u64) -> Instruction {
+    let mut data = vec![30];
+    data.extend_from_slice(&amount.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new(listing.auction(bank).highest_bidder_ft_temp_pubkey, false),
+            AccountMeta::new(listing.ft_receiving, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(pda(&[b"escrow"], &bank.program_id), false),
+        ],
+    )
+}
+
+#[test]
+fn close_pays_the_exhibitor_only_the_rest_of_the_advanced_proceeds() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 300).unwrap();
+    let ft_temp = listing.auction(&bank).highest_bidder_ft_temp_pubkey;
+
+    bank.process(withdraw_partial_proceeds_ix(&bank, &listing, 100))
+        .unwrap();
+    assert_eq!(bank.token_balance(&listing.ft_receiving), 100);
+    assert_eq!(bank.token_balance(&ft_temp), 200);
+    assert_eq!(listing.auction(&bank).advanced_amount, 100);
+    assert_eq!(
+        bank.process(withdraw_partial_proceeds_ix(&bank, &listing, 201)),
+        Err(AuctionError::ExpectedAmountMismatch.into())
+    );
+    let other = listing.bidder(&mut bank);
+    assert_eq!(
+        listing.bid(&mut bank, &other, 400),
+        Err(AuctionError::ProceedsAdvanced.into())
+    );
+
+    listing.expire(&mut bank);
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    bank.process(listing.close_ix(&bank, &winner_nft)).unwrap();
+    assert_eq!(bank.token_balance(&listing.ft_receiving), 300);
+    assert_eq!(bank.token_balance(&winner_nft), 1);
+    assert_eq!(bank.token_balance(&bidder.ft_account), BIDDER_BALANCE - 300);
+}
+
+fn set_grace_period_ix(bank: &Bank, listing: &Listing, close_grace_period_sec: u64) -> Instruction {
+    let mut data = vec![46];
+    data.extend_from_slice(&close_grace_period_sec.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+/// `Close` signed by `closer` on behalf of the highest bidder
+fn close_on_behalf_ix(
+    bank: &Bank,
+    listing: &Listing,
+    closer: &Pubkey,
+    winner_nft: &Pubkey,
+) -> Instruction {
+    let winner = listing.auction(bank).highest_bidder_pubkey;
+    let mut accounts = listing.close_accounts(bank, winner_nft);
+    accounts[0] = AccountMeta::new(*closer, true);
+    accounts.push(AccountMeta::new(winner, false));
+    Instruction::new_with_bytes(bank.program_id, &[3], accounts)
+}
+
+#[test]
+fn third_parties_close_once_the_grace_period_elapsed() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_grace_period_ix(&bank, &listing, 600))
+        .unwrap();
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.close_allowed_at(), Some(auction.end_at + 600));
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    let keeper = bank.create_wallet();
+
+    listing.expire(&mut bank);
+    assert_eq!(
+        bank.process(close_on_behalf_ix(&bank, &listing, &keeper, &winner_nft)),
+        Err(AuctionError::GracePeriodActive.into())
+    );
+
+    bank.warp_to_timestamp(auction.end_at + 599);
+    assert_eq!(
+        bank.process(close_on_behalf_ix(&bank, &listing, &keeper, &winner_nft)),
+        Err(AuctionError::GracePeriodActive.into())
+    );
+
+    bank.warp_to_timestamp(auction.end_at + 600);
+    bank.process(close_on_behalf_ix(&bank, &listing, &keeper, &winner_nft))
+        .unwrap();
+    assert_eq!(bank.token_balance(&winner_nft), 1);
+    assert!(bank.account(&listing.escrow).is_none());
+}
+
+#[test]
+fn winner_closes_during_the_grace_period() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_grace_period_ix(&bank, &listing, 600))
+        .unwrap();
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+
+    listing.expire(&mut bank);
+    bank.process(listing.close_ix(&bank, &winner_nft)).unwrap();
+    assert_eq!(bank.token_balance(&winner_nft), 1);
+    assert!(bank.account(&listing.escrow).is_none());
+}
+
+#[test]
+fn grace_period_is_fixed_by_the_first_bid() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let grace_period = listing.auction(&bank).close_grace_period_sec;
+
+    assert_eq!(
+        bank.process(set_grace_period_ix(&bank, &listing, 600)),
+        Err(AuctionError::AlreadyBid.into())
+    );
+    assert_eq!(listing.auction(&bank).close_grace_period_sec, grace_period);
+}
+
+fn set_close_authority_ix(bank: &Bank, listing: &Listing, close_authority: &Pubkey) -> Instruction {
+    let mut data = vec![47];
+    data.extend_from_slice(close_authority.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+#[test]
+fn crank_close_authority_settles_for_the_winner() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let crank = pda(&[b"crank"], &Pubkey::new_unique());
+    bank.process(set_close_authority_ix(&bank, &listing, &crank))
+        .unwrap();
+    assert_eq!(listing.auction(&bank).close_authority, crank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+
+    listing.expire(&mut bank);
+    let other_crank = pda(&[b"crank"], &Pubkey::new_unique());
+    assert_eq!(
+        bank.process(close_on_behalf_ix(
+            &bank,
+            &listing,
+            &other_crank,
+            &winner_nft
+        )),
+        Err(AuctionError::GracePeriodActive.into())
+    );
+
+    // The crank pays for the tombstone like any other closer
+    bank.set_account(
+        crank,
+        Account {
+            lamports: WALLET_LAMPORTS,
+            owner: system_program::id(),
+            ..Account::default()
+        },
+    );
+    bank.process(close_on_behalf_ix(&bank, &listing, &crank, &winner_nft))
+        .unwrap();
+    assert_eq!(bank.token_balance(&winner_nft), 1);
+    assert!(bank.account(&listing.escrow).is_none());
+}
+
+#[test]
+fn only_the_exhibitor_sets_the_close_authority() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let crank = Pubkey::new_unique();
+    let mut set_close_authority = set_close_authority_ix(&bank, &listing, &crank);
+    set_close_authority.accounts[0] = AccountMeta::new_readonly(bank.create_wallet(), true);
+
+    assert_eq!(
+        bank.process(set_close_authority),
+        Err(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(listing.auction(&bank).close_authority, Pubkey::default());
+}
+
+#[test]
+fn winner_receives_the_bonus_of_all_sponsors_on_close() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let first = listing.bidder(&mut bank);
+    let second = listing.bidder(&mut bank);
+    bank.process(listing.deposit_bonus_ix(&bank, &first, 30))
+        .unwrap();
+    bank.process(listing.deposit_bonus_ix(&bank, &second, 20))
+        .unwrap();
+    bank.process(listing.deposit_bonus_ix(&bank, &first, 10))
+        .unwrap();
+    assert_eq!(listing.auction(&bank).bonus_amount, 60);
+    assert_eq!(bank.token_balance(&listing.bonus_vault(&bank)), 60);
+    let deposit: BonusDeposit = bank.unpack(&listing.bonus_deposit(&bank, &first.wallet));
+    assert_eq!(deposit.amount, 40);
+
+    let winner = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &winner, 250).unwrap();
+    listing.expire(&mut bank);
+    let winner_nft = listing.nft_account(&mut bank, &winner.wallet);
+    let mut close_ix = listing.close_ix(&bank, &winner_nft);
+    close_ix.accounts.extend([
+        AccountMeta::new(listing.bonus_vault(&bank), false),
+        AccountMeta::new(winner.ft_account, false),
+    ]);
+    bank.process(close_ix).unwrap();
+
+    assert_eq!(bank.token_balance(&listing.bonus_vault(&bank)), 0);
+    assert_eq!(
+        bank.token_balance(&winner.ft_account),
+        BIDDER_BALANCE - 250 + 60
+    );
+}
+
+#[test]
+fn sponsors_reclaim_the_bonus_of_a_cancelled_auction() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let sponsor = listing.bidder(&mut bank);
+    bank.process(listing.deposit_bonus_ix(&bank, &sponsor, 40))
+        .unwrap();
+
+    assert_eq!(
+        bank.process(listing.reclaim_bonus_ix(&bank, &sponsor)),
+        Err(AuctionError::ActiveAuction.into())
+    );
+    bank.process(listing.cancel_ix(&bank)).unwrap();
+    bank.process(listing.reclaim_bonus_ix(&bank, &sponsor))
+        .unwrap();
+
+    assert_eq!(bank.token_balance(&sponsor.ft_account), BIDDER_BALANCE);
+    assert_eq!(
+        bank.lamports(&listing.bonus_deposit(&bank, &sponsor.wallet)),
+        0
+    );
+}
+
+#[test]
+fn bonus_is_deposited_while_the_auction_runs() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let sponsor = listing.bidder(&mut bank);
+
+    assert_eq!(
+        bank.process(listing.deposit_bonus_ix(&bank, &sponsor, 0)),
+        Err(AuctionError::InvalidInstruction.into())
+    );
+    listing.expire(&mut bank);
+    assert_eq!(
+        bank.process(listing.deposit_bonus_ix(&bank, &sponsor, 40)),
+        Err(AuctionError::InactiveAuction.into())
+    );
+}
+
+fn set_fractional_ownership_ix(
+    bank: &Bank,
+    listing: &Listing,
+    owners: &[(Pubkey, u16)],
+) -> Instruction {
+    let mut data = vec![66, owners.len() as u8];
+    for (owner_ft, share_bps) in owners {
+        data.extend_from_slice(owner_ft.as_ref());
+        data.extend_from_slice(&share_bps.to_le_bytes());
+    }
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+#[test]
+fn proceeds_are_split_among_the_fractional_owners() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let owners: Vec<Pubkey> = (0..3)
+        .map(|_| {
+            let owner = bank.create_wallet();
+            bank.create_token_account(&listing.ft_mint, &owner, 0)
+        })
+        .collect();
+    bank.process(set_fractional_ownership_ix(
+        &bank,
+        &listing,
+        &[(owners[0], 5_000), (owners[1], 3_333), (owners[2], 1_667)],
+    ))
+    .unwrap();
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    listing.expire(&mut bank);
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+
+    let mut close_ix = listing.close_ix(&bank, &winner_nft);
+    close_ix.accounts.extend(
+        owners[..2]
+            .iter()
+            .map(|owner| AccountMeta::new(*owner, false)),
+    );
+    assert_eq!(
+        bank.process(close_ix.clone()),
+        Err(ProgramError::NotEnoughAccountKeys)
+    );
+    close_ix.accounts.push(AccountMeta::new(owners[2], false));
+    bank.process(close_ix).unwrap();
+
+    // The last owner receives the rounding remainder
+    let shares: Vec<u64> = owners
+        .iter()
+        .map(|owner| bank.token_balance(owner))
+        .collect();
+    assert_eq!(shares, [125, 83, 42]);
+    assert_eq!(bank.token_balance(&listing.ft_receiving), 0);
+    assert_eq!(bank.token_balance(&winner_nft), 1);
+}
+
+#[test]
+fn fractional_shares_must_sum_to_the_whole() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let first = Pubkey::new_unique();
+    let second = Pubkey::new_unique();
+
+    for owners in [
+        vec![(first, 5_000), (second, 4_999)],
+        vec![(first, 10_000), (second, 0)],
+    ] {
+        assert_eq!(
+            bank.process(set_fractional_ownership_ix(&bank, &listing, &owners)),
+            Err(AuctionError::InvalidOwnerShares.into())
+        );
+    }
+    bank.process(set_fractional_ownership_ix(
+        &bank,
+        &listing,
+        &[(first, 5_000), (second, 5_000)],
+    ))
+    .unwrap();
+    bank.process(set_fractional_ownership_ix(&bank, &listing, &[]))
+        .unwrap();
+    assert!(listing
+        .auction(&bank)
+        .fractional_owners
+        .iter()
+        .all(|(_, share_bps)| *share_bps == 0));
+
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    assert_eq!(
+        bank.process(set_fractional_ownership_ix(
+            &bank,
+            &listing,
+            &[(first, 10_000)]
+        )),
+        Err(AuctionError::AlreadyBid.into())
+    );
+}
+
+fn set_penalty_ix(
+    bank: &Bank,
+    listing: &Listing,
+    penalty_bps: u16,
+    payment_grace_period_sec: u64,
+) -> Instruction {
+    let mut data = vec![68];
+    data.extend_from_slice(&penalty_bps.to_le_bytes());
+    data.extend_from_slice(&payment_grace_period_sec.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+fn slash_bidder_ix(bank: &Bank, listing: &Listing, caller: &Pubkey) -> Instruction {
+    let program_id = &bank.program_id;
+    let auction = listing.auction(bank);
+    Instruction::new_with_bytes(
+        *program_id,
+        &[69],
+        vec![
+            AccountMeta::new_readonly(*caller, true),
+            AccountMeta::new(listing.exhibitor, false),
+            AccountMeta::new(listing.nft_temp, false),
+            AccountMeta::new(listing.exhibitor_nft, false),
+            AccountMeta::new(listing.ft_receiving, false),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new(auction.highest_bidder_pubkey, false),
+            AccountMeta::new(auction.highest_bidder_ft_temp_pubkey, false),
+            AccountMeta::new(auction.highest_bidder_ft_returning_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(pda(&[b"escrow"], program_id), false),
+            AccountMeta::new(pda(&[b"stats"], program_id), false),
+        ],
+    )
+}
+
+#[test]
+fn winner_who_never_closes_is_slashed() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_penalty_ix(&bank, &listing, 1_000, 600))
+        .unwrap();
+    let winner = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &winner, 250).unwrap();
+    let end_at = listing.auction(&bank).end_at;
+    let caller = bank.create_wallet();
+
+    bank.warp_to_timestamp(end_at + 599);
+    assert_eq!(
+        bank.process(slash_bidder_ix(&bank, &listing, &caller)),
+        Err(AuctionError::GracePeriodActive.into())
+    );
+    bank.warp_to_timestamp(end_at + 600);
+    bank.process(slash_bidder_ix(&bank, &listing, &caller))
+        .unwrap();
+
+    assert_eq!(bank.token_balance(&listing.ft_receiving), 25);
+    assert_eq!(bank.token_balance(&winner.ft_account), BIDDER_BALANCE - 25);
+    assert_eq!(bank.token_balance(&listing.exhibitor_nft), 1);
+    assert!(bank.account(&listing.escrow).is_none());
+}
+
+#[test]
+fn slashing_needs_a_penalty_set_before_the_first_bid() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let winner = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &winner, 250).unwrap();
+
+    assert_eq!(
+        bank.process(set_penalty_ix(&bank, &listing, 1_000, 600)),
+        Err(AuctionError::AlreadyBid.into())
+    );
+    listing.expire(&mut bank);
+    assert_eq!(
+        bank.process(slash_bidder_ix(&bank, &listing, &listing.exhibitor)),
+        Err(AuctionError::InvalidInstruction.into())
+    );
+    assert_eq!(bank.token_balance(&listing.nft_temp), 1);
+}
+
+fn set_winner_override_ix(bank: &Bank, listing: &Listing, winner: &Pubkey) -> Instruction {
+    let mut data = vec![75];
+    data.extend_from_slice(winner.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+    )
+}
+
+#[test]
+fn selected_winner_pays_and_the_highest_bidder_is_refunded() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let winner = listing.bidder(&mut bank);
+    let winner_nft = listing.nft_account(&mut bank, &winner.wallet);
+
+    listing.expire(&mut bank);
+    bank.process(set_winner_override_ix(&bank, &listing, &winner.wallet))
+        .unwrap();
+    let bidder_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    assert_eq!(
+        bank.process(listing.close_ix(&bank, &bidder_nft)),
+        Err(AuctionError::WinnerOverrideRequired.into())
+    );
+
+    let mut accounts = listing.close_accounts(&bank, &winner_nft);
+    accounts[0] = AccountMeta::new(winner.wallet, true);
+    accounts.extend([
+        AccountMeta::new(bidder.wallet, false),
+        AccountMeta::new(bidder.ft_account, false),
+        AccountMeta::new(winner.ft_account, false),
+    ]);
+    bank.process(Instruction::new_with_bytes(bank.program_id, &[3], accounts))
+        .unwrap();
+    assert_eq!(bank.token_balance(&winner_nft), 1);
+    assert_eq!(bank.token_balance(&winner.ft_account), BIDDER_BALANCE - 250);
+    assert_eq!(bank.token_balance(&bidder.ft_account), BIDDER_BALANCE);
+    assert!(bank.account(&listing.escrow).is_none());
+}
+
+#[test]
+fn winner_is_overridden_only_once_an_auction_with_bids_expired() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let winner = bank.create_wallet();
+    listing.expire(&mut bank);
+    assert_eq!(
+        bank.process(set_winner_override_ix(&bank, &listing, &winner)),
+        Err(AuctionError::NoBidderFound.into())
+    );
+
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    assert_eq!(
+        bank.process(set_winner_override_ix(&bank, &listing, &winner)),
+        Err(AuctionError::ActiveAuction.into())
+    );
+}
+
+fn set_audit_oracle_ix(bank: &Bank, listing: &Listing, audit_oracle: &Pubkey) -> Instruction {
+    let mut data = vec![76];
+    data.extend_from_slice(audit_oracle.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+fn audit_record(bank: &Bank, listing: &Listing) -> Pubkey {
+    pda(&[b"audit", listing.escrow.as_ref()], &bank.program_id)
+}
+
+fn certify_price_ix(
+    bank: &Bank,
+    listing: &Listing,
+    audit_oracle: &Pubkey,
+    certified_price: u64,
+) -> Instruction {
+    let mut data = vec![77];
+    data.extend_from_slice(&certified_price.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(*audit_oracle, true),
+            AccountMeta::new(listing.exhibitor, false),
+            AccountMeta::new(listing.nft_temp, false),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new(audit_record(bank, listing), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(pda(&[b"escrow"], &bank.program_id), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+#[test]
+fn audit_oracle_certifies_the_price_and_closes_the_escrow() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let audit_oracle = bank.create_wallet();
+    bank.process(set_audit_oracle_ix(&bank, &listing, &audit_oracle))
+        .unwrap();
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+
+    listing.expire(&mut bank);
+    bank.process(listing.close_ix(&bank, &winner_nft)).unwrap();
+    assert_eq!(bank.token_balance(&winner_nft), 1);
+    assert!(listing.auction(&bank).audit_pending);
+
+    assert_eq!(
+        bank.process(certify_price_ix(&bank, &listing, &audit_oracle, 249)),
+        Err(AuctionError::ExpectedAmountMismatch.into())
+    );
+    bank.process(certify_price_ix(&bank, &listing, &audit_oracle, 250))
+        .unwrap();
+    let record = bank.unpack::<AuditRecord>(&audit_record(&bank, &listing));
+    assert_eq!(record.escrow, listing.escrow);
+    assert_eq!(record.certified_price, 250);
+    assert_eq!(record.certified_at, bank.clock().unix_timestamp);
+    assert!(bank.account(&listing.escrow).is_none());
+}
+
+#[test]
+fn only_the_audit_oracle_certifies_a_settled_auction() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let audit_oracle = bank.create_wallet();
+    bank.process(set_audit_oracle_ix(&bank, &listing, &audit_oracle))
+        .unwrap();
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    assert_eq!(
+        bank.process(certify_price_ix(&bank, &listing, &audit_oracle, 250)),
+        Err(AuctionError::InvalidInstruction.into())
+    );
+
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    listing.expire(&mut bank);
+    bank.process(listing.close_ix(&bank, &winner_nft)).unwrap();
+    let intruder = bank.create_wallet();
+    assert_eq!(
+        bank.process(certify_price_ix(&bank, &listing, &intruder, 250)),
+        Err(AuctionError::Unauthorized.into())
+    );
+}
+
+fn set_transaction_limit_ix(
+    bank: &Bank,
+    authority: &Pubkey,
+    transaction_limit: u64,
+    compliance_signer: &Pubkey,
+) -> Instruction {
+    let mut data = vec![80];
+    data.extend_from_slice(&transaction_limit.to_le_bytes());
+    data.extend_from_slice(compliance_signer.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(pda(&[b"global_config"], &bank.program_id), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+fn compliant_close_ix(
+    bank: &Bank,
+    listing: &Listing,
+    winner_nft: &Pubkey,
+    compliance_signer: &Pubkey,
+) -> Instruction {
+    let mut close_ix = listing.close_ix(bank, winner_nft);
+    close_ix.accounts.extend([
+        AccountMeta::new_readonly(pda(&[b"global_config"], &bank.program_id), false),
+        AccountMeta::new_readonly(*compliance_signer, true),
+    ]);
+    close_ix
+}
+
+#[test]
+fn closes_above_the_transaction_limit_need_the_compliance_signer() {
+    let (mut bank, authority) = setup();
+    let compliance_signer = bank.create_wallet();
+    bank.process(set_transaction_limit_ix(
+        &bank,
+        &authority,
+        200,
+        &compliance_signer,
+    ))
+    .unwrap();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+
+    listing.expire(&mut bank);
+    let intruder = bank.create_wallet();
+    assert_eq!(
+        bank.process(compliant_close_ix(&bank, &listing, &winner_nft, &intruder)),
+        Err(AuctionError::TransactionLimitExceeded.into())
+    );
+    bank.process(compliant_close_ix(
+        &bank,
+        &listing,
+        &winner_nft,
+        &compliance_signer,
+    ))
+    .unwrap();
+    assert_eq!(bank.token_balance(&winner_nft), 1);
+
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 200).unwrap();
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    listing.expire(&mut bank);
+    bank.process(listing.close_ix(&bank, &winner_nft)).unwrap();
+    assert_eq!(bank.token_balance(&winner_nft), 1);
+}
+
+#[test]
+fn transaction_limit_needs_a_compliance_signer() {
+    let (mut bank, authority) = setup();
+    assert_eq!(
+        bank.process(set_transaction_limit_ix(
+            &bank,
+            &authority,
+            200,
+            &Pubkey::default(),
+        )),
+        Err(AuctionError::InvalidInstruction.into())
+    );
+    let intruder = bank.create_wallet();
+    assert_eq!(
+        bank.process(set_transaction_limit_ix(&bank, &intruder, 200, &intruder)),
+        Err(AuctionError::Unauthorized.into())
+    );
+}
+
+fn set_auction_timer_ix(
+    bank: &Bank,
+    listing: &Listing,
+    clock_drift_tolerance_sec: u64,
+) -> Instruction {
+    let mut data = vec![101];
+    data.extend_from_slice(&clock_drift_tolerance_sec.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+#[test]
+fn clock_drift_tolerance_stops_bids_early_and_delays_closing() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_auction_timer_ix(&bank, &listing, 60))
+        .unwrap();
+    let end_at = listing.auction(&bank).end_at;
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+
+    bank.warp_to_timestamp(end_at - 60);
+    let late = listing.bidder(&mut bank);
+    assert_eq!(
+        listing.bid(&mut bank, &late, 300),
+        Err(AuctionError::InactiveAuction.into())
+    );
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    bank.warp_to_timestamp(end_at + 59);
+    assert_eq!(
+        bank.process(listing.close_ix(&bank, &winner_nft)),
+        Err(AuctionError::ActiveAuction.into())
+    );
+    bank.warp_to_timestamp(end_at + 60);
+    bank.process(listing.close_ix(&bank, &winner_nft)).unwrap();
+    assert_eq!(bank.token_balance(&winner_nft), 1);
+}
+
+#[test]
+fn auction_timer_is_set_by_the_exhibitor_before_bids() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    assert_eq!(
+        bank.process(set_auction_timer_ix(
+            &bank,
+            &listing,
+            MAX_CLOCK_DRIFT_TOLERANCE_SEC + 1
+        )),
+        Err(AuctionError::InvalidInstruction.into())
+    );
+    let mut set_ix = set_auction_timer_ix(&bank, &listing, 60);
+    let intruder = bank.create_wallet();
+    set_ix.accounts[0] = AccountMeta::new_readonly(intruder, true);
+    assert_eq!(bank.process(set_ix), Err(ProgramError::InvalidAccountData));
+
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    assert_eq!(
+        bank.process(set_auction_timer_ix(&bank, &listing, 60)),
+        Err(AuctionError::AlreadyBid.into())
+    );
+    assert_eq!(listing.auction(&bank).clock_drift_tolerance_sec, 0);
+}
@@ -0,0 +1,430 @@
+//! Auctions set up through the program instructions, with the account lists of each instruction
+
+use auction_pal::state::{Auction, AuctionCategory};
+use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+use solana_program::{system_program, sysvar};
+
+use super::Bank;
+
+/// Duration of the auctions exhibited by `Listing::exhibit`
+pub const AUCTION_DURATION_SEC: u64 = 3_600;
+
+/// Initial price of the auctions exhibited by `Listing::exhibit`
+pub const INITIAL_PRICE: u64 = 100;
+
+/// FT balance of the bidders created by `Listing::bidder`
+pub const BIDDER_BALANCE: u64 = 1_000_000;
+
+pub fn pda(seeds: &[&[u8]], program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(seeds, program_id).0
+}
+
+/// Bank whose global config is initialized, with its authority
+pub fn setup() -> (Bank, Pubkey) {
+    let mut bank = Bank::new();
+    let authority = bank.create_wallet();
+    let program_id = bank.program_id;
+    bank.process(Instruction::new_with_bytes(
+        program_id,
+        &[9],
+        vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(pda(&[b"global_config"], &program_id), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    ))
+    .expect("global config initializes");
+    (bank, authority)
+}
+
+/// Payload of an `Exhibit` instruction
+pub fn exhibit_data(
+    initial_price: u64,
+    seconds: u64,
+    category: AuctionCategory,
+    ft_mint: &Pubkey,
+) -> Vec<u8> {
+    let mut data = vec![0];
+    data.extend_from_slice(&initial_price.to_le_bytes());
+    data.extend_from_slice(&seconds.to_le_bytes());
+    data.extend_from_slice(ft_mint.as_ref());
+    data.push(category as u8);
+    data
+}
+
+/// Payload of a `Bid` instruction
+pub fn bid_data(price: u64, nonce: u64) -> Vec<u8> {
+    let mut data = vec![1];
+    data.extend_from_slice(&price.to_le_bytes());
+    data.extend_from_slice(&nonce.to_le_bytes());
+    data
+}
+
+/// Bidder wallet and its FT account
+#[derive(Clone, Copy)]
+pub struct Bidder {
+    pub wallet: Pubkey,
+    pub ft_account: Pubkey,
+}
+
+/// Accounts of an auction and of its exhibitor
+#[derive(Clone, Copy)]
+pub struct Listing {
+    pub exhibitor: Pubkey,
+    pub nft_mint: Pubkey,
+    pub exhibitor_nft: Pubkey,
+    pub nft_temp: Pubkey,
+    pub ft_mint: Pubkey,
+    pub ft_receiving: Pubkey,
+    pub escrow: Pubkey,
+    pub category: AuctionCategory,
+}
+
+impl Listing {
+    /// Accounts of an auction about to be exhibited, priced in a new FT mint
+    pub fn new(bank: &mut Bank) -> Self {
+        let exhibitor = bank.create_wallet();
+        let ft_mint = bank.create_mint(&exhibitor);
+        Self::with_ft_mint(bank, exhibitor, ft_mint)
+    }
+
+    /// Accounts of an auction about to be exhibited by `exhibitor`, priced in `ft_mint`
+    pub fn with_ft_mint(bank: &mut Bank, exhibitor: Pubkey, ft_mint: Pubkey) -> Self {
+        let nft_mint = bank.create_mint(&exhibitor);
+        let exhibitor_nft = bank.create_token_account(&nft_mint, &exhibitor, 1);
+        let nft_temp = bank.create_token_account(&nft_mint, &exhibitor, 0);
+        let ft_receiving = bank.create_token_account(&ft_mint, &exhibitor, 0);
+        let program_id = bank.program_id;
+        let escrow = bank.create_account(&program_id, Auction::LEN);
+        Listing {
+            exhibitor,
+            nft_mint,
+            exhibitor_nft,
+            nft_temp,
+            ft_mint,
+            ft_receiving,
+            escrow,
+            category: AuctionCategory::Other,
+        }
+    }
+
+    /// Auction exhibited at `INITIAL_PRICE` for `AUCTION_DURATION_SEC` whose terms are accepted
+    pub fn exhibit(bank: &mut Bank) -> Self {
+        let listing = Self::new(bank);
+        bank.process(listing.exhibit_ix(bank, INITIAL_PRICE, AUCTION_DURATION_SEC))
+            .expect("auction exhibits");
+        listing.accept_terms(bank);
+        listing
+    }
+
+    /// The accounts expected by `Exhibit`
+    pub fn exhibit_accounts(&self, bank: &Bank) -> Vec<AccountMeta> {
+        let program_id = &bank.program_id;
+        vec![
+            AccountMeta::new(self.exhibitor, true),
+            AccountMeta::new(self.exhibitor_nft, false),
+            AccountMeta::new(self.nft_temp, false),
+            AccountMeta::new_readonly(self.ft_receiving, false),
+            AccountMeta::new(self.escrow, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(
+                pda(&[b"exhibitor_profile", self.exhibitor.as_ref()], program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(pda(&[b"auction_index"], program_id), false),
+            AccountMeta::new(
+                pda(&[b"category_index", &[self.category as u8]], program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(self.ft_mint, false),
+            AccountMeta::new(pda(&[b"stats"], program_id), false),
+            AccountMeta::new_readonly(pda(&[b"global_config"], program_id), false),
+            AccountMeta::new_readonly(
+                pda(&[b"exhibitor_config", self.exhibitor.as_ref()], program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(
+                pda(&[b"tombstone", self.escrow.as_ref()], program_id),
+                false,
+            ),
+        ]
+    }
+
+    pub fn exhibit_ix(&self, bank: &Bank, initial_price: u64, seconds: u64) -> Instruction {
+        Instruction::new_with_bytes(
+            bank.program_id,
+            &exhibit_data(initial_price, seconds, self.category, &self.ft_mint),
+            self.exhibit_accounts(bank),
+        )
+    }
+
+    /// `SetFloorPrice` to `floor` signed by the exhibitor
+    pub fn set_floor_price_ix(&self, bank: &Bank, floor: u64) -> Instruction {
+        let mut data = vec![8];
+        data.extend_from_slice(&floor.to_le_bytes());
+        Instruction::new_with_bytes(
+            bank.program_id,
+            &data,
+            vec![
+                AccountMeta::new_readonly(self.exhibitor, true),
+                AccountMeta::new(self.escrow, false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+            ],
+        )
+    }
+
+    pub fn auction(&self, bank: &Bank) -> Auction {
+        bank.unpack(&self.escrow)
+    }
+
+    /// Changes the auction state in place, for states no instruction reaches directly
+    pub fn update_auction(&self, bank: &mut Bank, update: impl FnOnce(&mut Auction)) {
+        let mut auction = self.auction(bank);
+        update(&mut auction);
+        let program_id = bank.program_id;
+        bank.pack(self.escrow, program_id, auction);
+    }
+
+    /// Marks the auction terms as attested, which `VerifySignedMessage` does with an Ed25519 signature
+    pub fn accept_terms(&self, bank: &mut Bank) {
+        self.update_auction(bank, |auction| auction.terms_accepted = true);
+    }
+
+    /// Wallet holding `BIDDER_BALANCE` of the auction FT
+    pub fn bidder(&self, bank: &mut Bank) -> Bidder {
+        let wallet = bank.create_wallet();
+        let ft_account = bank.create_token_account(&self.ft_mint, &wallet, BIDDER_BALANCE);
+        Bidder { wallet, ft_account }
+    }
+
+    /// The accounts expected by `Bid` before the optional ones, with the temporary FT account of the bidder
+    pub fn bid_accounts(
+        &self,
+        bank: &Bank,
+        bidder: &Bidder,
+        bidder_ft_temp: &Pubkey,
+    ) -> Vec<AccountMeta> {
+        let program_id = &bank.program_id;
+        let auction = self.auction(bank);
+        vec![
+            AccountMeta::new(bidder.wallet, true),
+            AccountMeta::new(auction.highest_bidder_pubkey, false),
+            AccountMeta::new(auction.highest_bidder_ft_temp_pubkey, false),
+            AccountMeta::new(auction.highest_bidder_ft_returning_pubkey, false),
+            AccountMeta::new(*bidder_ft_temp, false),
+            AccountMeta::new(bidder.ft_account, false),
+            AccountMeta::new(self.escrow, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(pda(&[b"escrow"], program_id), false),
+            AccountMeta::new_readonly(pda(&[b"global_config"], program_id), false),
+            AccountMeta::new(
+                pda(&[b"bid_history", self.escrow.as_ref()], program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(pda(&[b"stats"], program_id), false),
+        ]
+    }
+
+    /// `Bid` at `price` with the current nonce, escrowing the bid in a new temporary FT account
+    pub fn bid_ix(&self, bank: &mut Bank, bidder: &Bidder, price: u64) -> Instruction {
+        let mint = bank.token_account(&bidder.ft_account).mint;
+        let bidder_ft_temp = bank.create_token_account(&mint, &bidder.wallet, 0);
+        let nonce = self.auction(bank).bid_nonce;
+        Instruction::new_with_bytes(
+            bank.program_id,
+            &bid_data(price, nonce),
+            self.bid_accounts(bank, bidder, &bidder_ft_temp),
+        )
+    }
+
+    pub fn bid(&self, bank: &mut Bank, bidder: &Bidder, price: u64) -> ProgramResult {
+        let bid_ix = self.bid_ix(bank, bidder, price);
+        bank.process(bid_ix)
+    }
+
+    /// Token account of `owner` the NFT can be sent to
+    pub fn nft_account(&self, bank: &mut Bank, owner: &Pubkey) -> Pubkey {
+        bank.create_token_account(&self.nft_mint, owner, 0)
+    }
+
+    /// The accounts expected by `Close` when the highest bidder closes, sending the NFT to `winner_nft`
+    pub fn close_accounts(&self, bank: &Bank, winner_nft: &Pubkey) -> Vec<AccountMeta> {
+        let program_id = &bank.program_id;
+        let auction = self.auction(bank);
+        vec![
+            AccountMeta::new(auction.highest_bidder_pubkey, true),
+            AccountMeta::new(self.exhibitor, false),
+            AccountMeta::new(self.nft_temp, false),
+            AccountMeta::new(self.ft_receiving, false),
+            AccountMeta::new(auction.highest_bidder_ft_temp_pubkey, false),
+            AccountMeta::new(*winner_nft, false),
+            AccountMeta::new(self.escrow, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(pda(&[b"escrow"], program_id), false),
+            AccountMeta::new(pda(&[b"stats"], program_id), false),
+            AccountMeta::new(
+                pda(&[b"tombstone", self.escrow.as_ref()], program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]
+    }
+
+    pub fn close_ix(&self, bank: &Bank, winner_nft: &Pubkey) -> Instruction {
+        Instruction::new_with_bytes(bank.program_id, &[3], self.close_accounts(bank, winner_nft))
+    }
+
+    /// The accounts expected by `Cancel`
+    pub fn cancel_accounts(&self, bank: &Bank) -> Vec<AccountMeta> {
+        let program_id = &bank.program_id;
+        vec![
+            AccountMeta::new(self.exhibitor, true),
+            AccountMeta::new(self.nft_temp, false),
+            AccountMeta::new(self.exhibitor_nft, false),
+            AccountMeta::new(self.escrow, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(pda(&[b"escrow"], program_id), false),
+            AccountMeta::new(pda(&[b"stats"], program_id), false),
+        ]
+    }
+
+    pub fn cancel_ix(&self, bank: &Bank) -> Instruction {
+        Instruction::new_with_bytes(bank.program_id, &[2], self.cancel_accounts(bank))
+    }
+
+    /// Vault holding the bonus the sponsors deposited for the winner
+    pub fn bonus_vault(&self, bank: &Bank) -> Pubkey {
+        pda(&[b"bonus", self.escrow.as_ref()], &bank.program_id)
+    }
+
+    /// Record of the bonus deposited by `sponsor`
+    pub fn bonus_deposit(&self, bank: &Bank, sponsor: &Pubkey) -> Pubkey {
+        pda(
+            &[b"bonus_deposit", self.escrow.as_ref(), sponsor.as_ref()],
+            &bank.program_id,
+        )
+    }
+
+    /// `DepositBonus` of `amount` from the FT account of `sponsor`
+    pub fn deposit_bonus_ix(&self, bank: &Bank, sponsor: &Bidder, amount: u64) -> Instruction {
+        let mut data = vec![60];
+        data.extend_from_slice(&amount.to_le_bytes());
+        Instruction::new_with_bytes(
+            bank.program_id,
+            &data,
+            vec![
+                AccountMeta::new(sponsor.wallet, true),
+                AccountMeta::new(sponsor.ft_account, false),
+                AccountMeta::new(self.escrow, false),
+                AccountMeta::new(self.bonus_vault(bank), false),
+                AccountMeta::new(self.bonus_deposit(bank, &sponsor.wallet), false),
+                AccountMeta::new_readonly(self.ft_mint, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        )
+    }
+
+    /// `ReclaimBonus` of the deposit of `sponsor` back to its FT account
+    pub fn reclaim_bonus_ix(&self, bank: &Bank, sponsor: &Bidder) -> Instruction {
+        Instruction::new_with_bytes(
+            bank.program_id,
+            &[61],
+            vec![
+                AccountMeta::new(sponsor.wallet, true),
+                AccountMeta::new(sponsor.ft_account, false),
+                AccountMeta::new_readonly(self.escrow, false),
+                AccountMeta::new(self.bonus_vault(bank), false),
+                AccountMeta::new(self.bonus_deposit(bank, &sponsor.wallet), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+            ],
+        )
+    }
+
+    /// `SetAuctionExpiry` to `new_end_at` signed by `authority`
+    pub fn set_auction_expiry_ix(
+        &self,
+        bank: &Bank,
+        authority: &Pubkey,
+        new_end_at: i64,
+    ) -> Instruction {
+        let mut data = vec![31];
+        data.extend_from_slice(&new_end_at.to_le_bytes());
+        Instruction::new_with_bytes(
+            bank.program_id,
+            &data,
+            vec![
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new_readonly(pda(&[b"global_config"], &bank.program_id), false),
+                AccountMeta::new(self.escrow, false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+            ],
+        )
+    }
+
+    /// `SetSealedEndTime` revealing the end time at `reveal_end_at_time`
+    pub fn set_sealed_end_time_ix(&self, bank: &Bank, reveal_end_at_time: i64) -> Instruction {
+        let mut data = vec![110];
+        data.extend_from_slice(&reveal_end_at_time.to_le_bytes());
+        Instruction::new_with_bytes(
+            bank.program_id,
+            &data,
+            vec![
+                AccountMeta::new_readonly(self.exhibitor, true),
+                AccountMeta::new(self.escrow, false),
+            ],
+        )
+    }
+
+    /// Moves the clock past the end of the auction
+    pub fn expire(&self, bank: &mut Bank) {
+        let end_at = self.auction(bank).end_at;
+        bank.warp_to_timestamp(end_at + 1);
+    }
+}
+
+/// Vault PDA collecting the upfront royalties of `creator` in `mint`
+pub fn royalty_vault(bank: &Bank, mint: &Pubkey, creator: &Pubkey) -> Pubkey {
+    pda(
+        &[b"royalty", mint.as_ref(), creator.as_ref()],
+        &bank.program_id,
+    )
+}
+
+/// `DeductRoyaltyBeforeEscrow` paying `upfront_royalty_bps` of each bid to `creator`
+pub fn deduct_royalty_before_escrow_ix(
+    bank: &Bank,
+    listing: &Listing,
+    creator: &Pubkey,
+    upfront_royalty_bps: u16,
+) -> Instruction {
+    let mut data = vec![45];
+    data.extend_from_slice(&upfront_royalty_bps.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(*creator, false),
+            AccountMeta::new(royalty_vault(bank, &listing.ft_mint, creator), false),
+            AccountMeta::new_readonly(listing.ft_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
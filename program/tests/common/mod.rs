@@ -0,0 +1,672 @@
+//! In-memory bank running the program and the cross-program invocations it makes, so instructions
+//! can be tested end to end without a validator
+//!
+//! The token program runs as is, the system program is emulated for the instructions the program
+//! makes, other programs are registered through `Bank::add_program`
+//!
+//! Ed25519 program instructions are accepted without checking their signatures, and the
+//! instructions sysvar lists the instructions of the transaction being processed
+
+#![allow(dead_code)]
+
+pub mod auction;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Once;
+
+use solana_program::account_info::AccountInfo;
+use solana_program::clock::Clock;
+use solana_program::entrypoint::{ProcessInstruction, ProgramResult, MAX_PERMITTED_DATA_INCREASE};
+use solana_program::instruction::Instruction;
+use solana_program::program_error::ProgramError;
+use solana_program::program_pack::{IsInitialized, Pack};
+use solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use solana_program::program_utils::limited_deserialize;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::system_instruction::{SystemError, SystemInstruction};
+use solana_program::sysvar::instructions::{
+    construct_instructions_data, store_current_index, BorrowedAccountMeta, BorrowedInstruction,
+};
+use solana_program::sysvar::{self, Sysvar};
+use solana_program::{bpf_loader, ed25519_program, system_program};
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+
+/// Error returned when an invocation signs or writes an account its caller can't
+pub const PRIVILEGE_ESCALATION: ProgramError = ProgramError::Custom(u32::MAX);
+
+/// Time the bank clock starts at
+pub const GENESIS_TIMESTAMP: i64 = 1_700_000_000;
+
+/// Lamports given to wallets created by the bank
+pub const WALLET_LAMPORTS: u64 = 100_000_000_000;
+
+thread_local! {
+    static CLOCK: RefCell<Clock> = RefCell::new(Clock::default());
+    static LOGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    static EVENTS: RefCell<Vec<Vec<Vec<u8>>>> = const { RefCell::new(Vec::new()) };
+    static RETURN_DATA: RefCell<Option<(Pubkey, Vec<u8>)>> = const { RefCell::new(None) };
+    static PROGRAMS: RefCell<HashMap<Pubkey, ProcessInstruction>> = RefCell::new(HashMap::new());
+    static CALL_STACK: RefCell<Vec<Pubkey>> = const { RefCell::new(Vec::new()) };
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Account {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+/// Accounts of the bank, changed by transactions only when all their instructions succeed
+pub struct Bank {
+    pub program_id: Pubkey,
+    accounts: HashMap<Pubkey, Account>,
+    clock: Clock,
+}
+
+impl Bank {
+    /// Bank with the program under test deployed at a new program id
+    pub fn new() -> Self {
+        static INSTALL_STUBS: Once = Once::new();
+        INSTALL_STUBS.call_once(|| {
+            set_syscall_stubs(Box::new(BankStubs));
+        });
+        PROGRAMS.with(|programs| programs.borrow_mut().clear());
+        CALL_STACK.with(|call_stack| call_stack.borrow_mut().clear());
+
+        let mut bank = Bank {
+            program_id: Pubkey::new_unique(),
+            accounts: HashMap::new(),
+            clock: Clock {
+                slot: 100,
+                unix_timestamp: GENESIS_TIMESTAMP,
+                ..Clock::default()
+            },
+        };
+        bank.add_program(bank.program_id, auction_pal::processor::Processor::process);
+        for program_id in [system_program::id(), spl_token::id()] {
+            bank.set_account(program_id, Self::program_account());
+        }
+        let mut rent_sysvar = Self::sysvar_account(Rent::size_of());
+        Self::write_sysvar(&Rent::default(), sysvar::rent::id(), &mut rent_sysvar);
+        bank.set_account(sysvar::rent::id(), rent_sysvar);
+        bank.set_account(sysvar::clock::id(), Self::sysvar_account(Clock::size_of()));
+        bank
+    }
+
+    /// Deploys a program whose instructions are processed by `process_instruction`
+    pub fn add_program(&mut self, program_id: Pubkey, process_instruction: ProcessInstruction) {
+        PROGRAMS.with(|programs| {
+            programs
+                .borrow_mut()
+                .insert(program_id, process_instruction)
+        });
+        self.set_account(program_id, Self::program_account());
+    }
+
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    pub fn warp_to_timestamp(&mut self, unix_timestamp: i64) {
+        self.clock.unix_timestamp = unix_timestamp;
+    }
+
+    pub fn warp_to_slot(&mut self, slot: u64) {
+        self.clock.slot = slot;
+    }
+
+    pub fn account(&self, key: &Pubkey) -> Option<&Account> {
+        self.accounts.get(key)
+    }
+
+    pub fn set_account(&mut self, key: Pubkey, account: Account) {
+        self.accounts.insert(key, account);
+    }
+
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.account(key).map_or(0, |account| account.lamports)
+    }
+
+    /// Unpacks the initialized state held by the account
+    pub fn unpack<T: Pack + IsInitialized>(&self, key: &Pubkey) -> T {
+        let account = self.account(key).expect("account exists");
+        T::unpack(&account.data).expect("account holds an initialized state")
+    }
+
+    /// Packs the state into the account, created owned by `owner` when missing
+    pub fn pack<T: Pack>(&mut self, key: Pubkey, owner: Pubkey, state: T) {
+        let mut data = vec![0; T::LEN];
+        T::pack(state, &mut data).expect("state packs");
+        let lamports = Rent::default().minimum_balance(T::LEN);
+        let account = self.accounts.entry(key).or_insert(Account {
+            lamports,
+            owner,
+            ..Account::default()
+        });
+        account.lamports = account.lamports.max(lamports);
+        account.data = data;
+    }
+
+    /// Rent exempt system account
+    pub fn create_wallet(&mut self) -> Pubkey {
+        let wallet = Pubkey::new_unique();
+        self.set_account(
+            wallet,
+            Account {
+                lamports: WALLET_LAMPORTS,
+                owner: system_program::id(),
+                ..Account::default()
+            },
+        );
+        wallet
+    }
+
+    /// Rent exempt zeroed account of `len` bytes owned by `owner`
+    pub fn create_account(&mut self, owner: &Pubkey, len: usize) -> Pubkey {
+        let key = Pubkey::new_unique();
+        self.set_account(
+            key,
+            Account {
+                lamports: Rent::default().minimum_balance(len),
+                data: vec![0; len],
+                owner: *owner,
+                executable: false,
+            },
+        );
+        key
+    }
+
+    /// Mint with no decimals, `authority` being its mint and freeze authority
+    pub fn create_mint(&mut self, authority: &Pubkey) -> Pubkey {
+        let mint = Pubkey::new_unique();
+        self.pack(
+            mint,
+            spl_token::id(),
+            Mint {
+                mint_authority: Some(*authority).into(),
+                supply: 0,
+                decimals: 0,
+                is_initialized: true,
+                freeze_authority: Some(*authority).into(),
+            },
+        );
+        mint
+    }
+
+    /// Token account of `owner` holding `amount` tokens minted for it
+    pub fn create_token_account(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey {
+        let token_account = Pubkey::new_unique();
+        self.set_token_account(token_account, mint, owner, amount);
+        token_account
+    }
+
+    /// Creates or overwrites the token account at `key`
+    pub fn set_token_account(&mut self, key: Pubkey, mint: &Pubkey, owner: &Pubkey, amount: u64) {
+        let mut mint_state: Mint = self.unpack(mint);
+        mint_state.supply = mint_state.supply.checked_add(amount).expect("supply fits");
+        self.pack(*mint, spl_token::id(), mint_state);
+        self.pack(
+            key,
+            spl_token::id(),
+            TokenAccount {
+                mint: *mint,
+                owner: *owner,
+                amount,
+                state: AccountState::Initialized,
+                ..TokenAccount::default()
+            },
+        );
+    }
+
+    pub fn token_account(&self, key: &Pubkey) -> TokenAccount {
+        self.unpack(key)
+    }
+
+    /// Balance of the token account, 0 once closed
+    pub fn token_balance(&self, key: &Pubkey) -> u64 {
+        match self.account(key) {
+            Some(account) if !account.data.is_empty() => self.token_account(key).amount,
+            _ => 0,
+        }
+    }
+
+    /// Messages logged by the last transaction
+    pub fn logs() -> Vec<String> {
+        LOGS.with(|logs| logs.borrow().clone())
+    }
+
+    /// Data logged through `sol_log_data` by the last transaction
+    pub fn events() -> Vec<Vec<Vec<u8>>> {
+        EVENTS.with(|events| events.borrow().clone())
+    }
+
+    /// Processes a transaction of a single instruction
+    pub fn process(&mut self, instruction: Instruction) -> ProgramResult {
+        self.process_transaction(&[instruction])
+    }
+
+    /// Processes the instructions in order, keeping none of their changes if one fails
+    /// The accounts flagged as signers in the instructions are signed for
+    pub fn process_transaction(&mut self, instructions: &[Instruction]) -> ProgramResult {
+        LOGS.with(|logs| logs.borrow_mut().clear());
+        EVENTS.with(|events| events.borrow_mut().clear());
+        CLOCK.with(|clock| *clock.borrow_mut() = self.clock.clone());
+        let mut clock_sysvar = Self::sysvar_account(Clock::size_of());
+        Self::write_sysvar(&self.clock, sysvar::clock::id(), &mut clock_sysvar);
+        self.set_account(sysvar::clock::id(), clock_sysvar);
+
+        let snapshot = self.accounts.clone();
+        let mut instructions_sysvar = Self::instructions_sysvar_account(instructions);
+        for (index, instruction) in instructions.iter().enumerate() {
+            store_current_index(&mut instructions_sysvar.data, index as u16);
+            self.set_account(sysvar::instructions::id(), instructions_sysvar.clone());
+            if let Err(error) = self.process_instruction(instruction) {
+                self.accounts = snapshot;
+                return Err(error);
+            }
+        }
+        // Accounts left without lamports are garbage collected once the transaction ends
+        self.accounts.retain(|_, account| account.lamports > 0);
+        Ok(())
+    }
+
+    fn process_instruction(&mut self, instruction: &Instruction) -> ProgramResult {
+        // Duplicated accounts share their state and the union of their privileges
+        let mut keys: Vec<(Pubkey, bool, bool)> = Vec::new();
+        for meta in &instruction.accounts {
+            match keys.iter_mut().find(|(key, _, _)| *key == meta.pubkey) {
+                Some((_, is_signer, is_writable)) => {
+                    *is_signer |= meta.is_signer;
+                    *is_writable |= meta.is_writable;
+                }
+                None => keys.push((meta.pubkey, meta.is_signer, meta.is_writable)),
+            }
+        }
+        let mut slots: Vec<AccountSlot> = keys
+            .iter()
+            .map(|(key, _, _)| AccountSlot::new(*key, self.accounts.get(key)))
+            .collect();
+        let account_infos: Vec<AccountInfo> = slots
+            .iter_mut()
+            .zip(&keys)
+            .map(|(slot, (_, is_signer, is_writable))| slot.account_info(*is_signer, *is_writable))
+            .collect();
+        let instruction_account_infos: Vec<AccountInfo> = instruction
+            .accounts
+            .iter()
+            .map(|meta| {
+                account_infos
+                    .iter()
+                    .find(|account_info| *account_info.key == meta.pubkey)
+                    .cloned()
+                    .expect("account info of each meta")
+            })
+            .collect();
+
+        RETURN_DATA.with(|return_data| *return_data.borrow_mut() = None);
+        call(
+            &instruction.program_id,
+            &instruction_account_infos,
+            &instruction.data,
+        )?;
+
+        for account_info in &account_infos {
+            let account = Account {
+                lamports: account_info.lamports(),
+                data: account_info.data.borrow().to_vec(),
+                owner: unsafe { std::ptr::read_volatile(account_info.owner) },
+                executable: account_info.executable,
+            };
+            self.accounts.insert(*account_info.key, account);
+        }
+        Ok(())
+    }
+
+    fn program_account() -> Account {
+        Account {
+            lamports: 1,
+            owner: bpf_loader::id(),
+            executable: true,
+            ..Account::default()
+        }
+    }
+
+    /// Instructions sysvar listing the instructions of the transaction
+    fn instructions_sysvar_account(instructions: &[Instruction]) -> Account {
+        let instructions: Vec<BorrowedInstruction> = instructions
+            .iter()
+            .map(|instruction| BorrowedInstruction {
+                program_id: &instruction.program_id,
+                accounts: instruction
+                    .accounts
+                    .iter()
+                    .map(|meta| BorrowedAccountMeta {
+                        pubkey: &meta.pubkey,
+                        is_signer: meta.is_signer,
+                        is_writable: meta.is_writable,
+                    })
+                    .collect(),
+                data: &instruction.data,
+            })
+            .collect();
+        let data = construct_instructions_data(&instructions);
+        Account {
+            data,
+            ..Self::sysvar_account(0)
+        }
+    }
+
+    fn sysvar_account(len: usize) -> Account {
+        Account {
+            lamports: Rent::default().minimum_balance(len),
+            data: vec![0; len],
+            owner: sysvar::id(),
+            executable: false,
+        }
+    }
+
+    fn write_sysvar<S: Sysvar>(sysvar: &S, key: Pubkey, account: &mut Account) {
+        let mut lamports = account.lamports;
+        let owner = account.owner;
+        let mut account_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut account.data,
+            &owner,
+            false,
+            0,
+        );
+        sysvar
+            .to_account_info(&mut account_info)
+            .expect("sysvar fits its account");
+    }
+}
+
+/// Ed25519 program instruction carrying the signature of `signer` over `message`
+pub fn ed25519_ix(signer: &Pubkey, message: &[u8]) -> Instruction {
+    const PUBLIC_KEY_OFFSET: u16 = 16;
+    const SIGNATURE_OFFSET: u16 = PUBLIC_KEY_OFFSET + 32;
+    const MESSAGE_OFFSET: u16 = SIGNATURE_OFFSET + 64;
+    let mut data = vec![1, 0];
+    for field in [
+        SIGNATURE_OFFSET,
+        u16::MAX,
+        PUBLIC_KEY_OFFSET,
+        u16::MAX,
+        MESSAGE_OFFSET,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(&[0; 64]);
+    data.extend_from_slice(message);
+    Instruction::new_with_bytes(ed25519_program::id(), &data, vec![])
+}
+
+/// Original data length laid out right before the key, as `AccountInfo::realloc` expects it
+#[repr(C)]
+struct KeyCell {
+    original_data_len: u32,
+    key: Pubkey,
+}
+
+/// Memory backing the `AccountInfo` of an account during an instruction, laid out as by the
+/// runtime so the account can be reallocated
+struct AccountSlot {
+    key: KeyCell,
+    lamports: u64,
+    owner: Pubkey,
+    executable: bool,
+    data_len: usize,
+    /// The data length followed by the data and the room it can grow into, 8 bytes aligned
+    buffer: Vec<u64>,
+}
+
+impl AccountSlot {
+    fn new(key: Pubkey, account: Option<&Account>) -> Self {
+        let account = account.cloned().unwrap_or_else(|| Account {
+            owner: system_program::id(),
+            ..Account::default()
+        });
+        let data_len = account.data.len();
+        let mut buffer = vec![0u64; 1 + (data_len + MAX_PERMITTED_DATA_INCREASE).div_ceil(8)];
+        buffer[0] = data_len as u64;
+        let mut slot = AccountSlot {
+            key: KeyCell {
+                original_data_len: data_len as u32,
+                key,
+            },
+            lamports: account.lamports,
+            owner: account.owner,
+            executable: account.executable,
+            data_len,
+            buffer,
+        };
+        slot.bytes()[8..8 + data_len].copy_from_slice(&account.data);
+        slot
+    }
+
+    fn bytes(&mut self) -> &mut [u8] {
+        let len = self.buffer.len() * 8;
+        unsafe { std::slice::from_raw_parts_mut(self.buffer.as_mut_ptr() as *mut u8, len) }
+    }
+
+    fn account_info(&mut self, is_signer: bool, is_writable: bool) -> AccountInfo<'_> {
+        let len = self.buffer.len() * 8;
+        let bytes: &mut [u8] =
+            unsafe { std::slice::from_raw_parts_mut(self.buffer.as_mut_ptr() as *mut u8, len) };
+        AccountInfo::new(
+            &self.key.key,
+            is_signer,
+            is_writable,
+            &mut self.lamports,
+            &mut bytes[8..8 + self.data_len],
+            &self.owner,
+            self.executable,
+            0,
+        )
+    }
+}
+
+/// Runs the program with the accounts of the instruction, in instruction order
+fn call(program_id: &Pubkey, account_infos: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let process_instruction: ProcessInstruction = if *program_id == system_program::id() {
+        process_system_instruction
+    } else if *program_id == ed25519_program::id() {
+        process_ed25519_instruction
+    } else if *program_id == spl_token::id() {
+        spl_token::processor::Processor::process
+    } else {
+        PROGRAMS
+            .with(|programs| programs.borrow().get(program_id).copied())
+            .ok_or(ProgramError::IncorrectProgramId)?
+    };
+    CALL_STACK.with(|call_stack| call_stack.borrow_mut().push(*program_id));
+    let result = process_instruction(program_id, account_infos, data);
+    CALL_STACK.with(|call_stack| call_stack.borrow_mut().pop());
+    result
+}
+
+fn caller_program_id() -> Pubkey {
+    CALL_STACK.with(|call_stack| *call_stack.borrow().last().expect("called from a program"))
+}
+
+/// Ed25519 signatures are checked by the runtime before the transaction runs, the bank takes
+/// them as valid so tests don't need signing keys
+fn process_ed25519_instruction(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _data: &[u8],
+) -> ProgramResult {
+    Ok(())
+}
+
+/// The instructions of the system program the program invokes
+fn process_system_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let instruction: SystemInstruction =
+        limited_deserialize(data, 1232).map_err(|_| ProgramError::InvalidInstructionData)?;
+    let account = |index: usize| {
+        accounts
+            .get(index)
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    };
+    match instruction {
+        SystemInstruction::CreateAccount {
+            lamports,
+            space,
+            owner,
+        } => {
+            let (from, to) = (account(0)?, account(1)?);
+            if !from.is_signer || !to.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if to.lamports() > 0 || !to.data_is_empty() || *to.owner != system_program::id() {
+                return Err(ProgramError::Custom(
+                    SystemError::AccountAlreadyInUse as u32,
+                ));
+            }
+            move_lamports(from, to, lamports)?;
+            to.realloc(space as usize, true)?;
+            to.assign(&owner);
+        }
+        SystemInstruction::Transfer { lamports } => {
+            let (from, to) = (account(0)?, account(1)?);
+            if !from.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if !from.data_is_empty() {
+                return Err(ProgramError::InvalidArgument);
+            }
+            move_lamports(from, to, lamports)?;
+        }
+        SystemInstruction::Assign { owner } => {
+            let account = account(0)?;
+            if !account.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            account.assign(&owner);
+        }
+        SystemInstruction::Allocate { space } => {
+            let account = account(0)?;
+            if !account.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            account.realloc(space as usize, true)?;
+        }
+        _ => return Err(ProgramError::InvalidInstructionData),
+    }
+    Ok(())
+}
+
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    let from_lamports = from
+        .lamports()
+        .checked_sub(lamports)
+        .ok_or(ProgramError::Custom(
+            SystemError::ResultWithNegativeLamports as u32,
+        ))?;
+    **from.try_borrow_mut_lamports()? = from_lamports;
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+/// Syscalls of the program answered from the state of the bank of the test thread
+struct BankStubs;
+
+impl SyscallStubs for BankStubs {
+    fn sol_log(&self, message: &str) {
+        LOGS.with(|logs| logs.borrow_mut().push(message.to_string()));
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        EVENTS.with(|events| {
+            events
+                .borrow_mut()
+                .push(fields.iter().map(|field| field.to_vec()).collect())
+        });
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let caller_program_id = caller_program_id();
+        let signer_pdas = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &caller_program_id))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+        if !account_infos
+            .iter()
+            .any(|account_info| *account_info.key == instruction.program_id)
+        {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        let mut callee_account_infos = Vec::with_capacity(instruction.accounts.len());
+        for meta in &instruction.accounts {
+            let account_info = account_infos
+                .iter()
+                .find(|account_info| *account_info.key == meta.pubkey)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            if meta.is_writable && !account_info.is_writable {
+                return Err(PRIVILEGE_ESCALATION);
+            }
+            if meta.is_signer && !account_info.is_signer && !signer_pdas.contains(&meta.pubkey) {
+                return Err(PRIVILEGE_ESCALATION);
+            }
+            callee_account_infos.push(AccountInfo {
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+                ..account_info.clone()
+            });
+        }
+
+        RETURN_DATA.with(|return_data| *return_data.borrow_mut() = None);
+        call(
+            &instruction.program_id,
+            &callee_account_infos,
+            &instruction.data,
+        )
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        CLOCK.with(|clock| unsafe { *(var_addr as *mut Clock) = clock.borrow().clone() });
+        solana_program::entrypoint::SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        solana_program::entrypoint::SUCCESS
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        RETURN_DATA.with(|return_data| return_data.borrow().clone())
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        let program_id = caller_program_id();
+        RETURN_DATA
+            .with(|return_data| *return_data.borrow_mut() = Some((program_id, data.to_vec())));
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        CALL_STACK.with(|call_stack| call_stack.borrow().len() as u64)
+    }
+}
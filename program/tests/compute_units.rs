@@ -0,0 +1,148 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::state::LifetimeBidVolume;
+use common::auction::{pda, setup, Bidder, Listing};
+use common::Bank;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::pubkey::Pubkey;
+use solana_program::{system_program, sysvar};
+
+/// Compute units the runtime charges for every bump tried when deriving a PDA account
+const CREATE_PROGRAM_ADDRESS_UNITS: u64 = 1_500;
+
+/// Number of auctions the savings are averaged over
+const SAMPLE_AUCTIONS: u64 = 32;
+
+/// Compute units `find_program_address` spends on a PDA account of bump `bump`, trying bumps from 255 down
+fn find_units(bump: u8) -> u64 {
+    (256 - u64::from(bump)) * CREATE_PROGRAM_ADDRESS_UNITS
+}
+
+fn bump_of(seeds: &[&[u8]], program_id: &Pubkey) -> u8 {
+    Pubkey::find_program_address(seeds, program_id).1
+}
+
+fn configure(bank: &mut Bank, authority: &Pubkey) {
+    let global_config = pda(&[b"global_config"], &bank.program_id);
+    let mut insurance_fee_data = vec![12];
+    insurance_fee_data.extend_from_slice(&100u16.to_le_bytes());
+    insurance_fee_data.extend_from_slice(&0u64.to_le_bytes());
+    let mut bid_cap_data = vec![78];
+    bid_cap_data.extend_from_slice(&u64::MAX.to_le_bytes());
+    for instruction in [
+        Instruction::new_with_bytes(
+            bank.program_id,
+            &insurance_fee_data,
+            vec![
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new(global_config, false),
+            ],
+        ),
+        Instruction::new_with_bytes(
+            bank.program_id,
+            &bid_cap_data,
+            vec![
+                AccountMeta::new(*authority, true),
+                AccountMeta::new(global_config, false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        ),
+    ] {
+        bank.process(instruction).unwrap();
+    }
+}
+
+/// Exhibits an auction whose bids go through every PDA account `Bid` derives
+fn exhibit_with_every_pda(bank: &mut Bank) -> Listing {
+    let listing = Listing::exhibit(bank);
+    bank.process(Instruction::new_with_bytes(
+        bank.program_id,
+        &[18, 2],
+        vec![
+            AccountMeta::new(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new(
+                pda(
+                    &[b"bidder_registry", listing.escrow.as_ref()],
+                    &bank.program_id,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    ))
+    .unwrap();
+    listing
+}
+
+fn bid(bank: &mut Bank, listing: &Listing, bidder: &Bidder, price: u64) {
+    let program_id = bank.program_id;
+    let mut bid_ix = listing.bid_ix(bank, bidder, price);
+    bid_ix.accounts.extend([
+        AccountMeta::new(
+            pda(&[b"insurance_vault", listing.ft_mint.as_ref()], &program_id),
+            false,
+        ),
+        AccountMeta::new_readonly(listing.ft_mint, false),
+        AccountMeta::new(
+            pda(&[b"bidder_registry", listing.escrow.as_ref()], &program_id),
+            false,
+        ),
+        AccountMeta::new(pda(&[b"ltv", bidder.wallet.as_ref()], &program_id), false),
+    ]);
+    bank.process(bid_ix).unwrap();
+}
+
+#[test]
+fn cached_bumps_save_compute_units_on_every_bid() {
+    let (mut bank, authority) = setup();
+    configure(&mut bank, &authority);
+    let program_id = bank.program_id;
+
+    let mut saved_units = 0;
+    for _ in 0..SAMPLE_AUCTIONS {
+        let listing = exhibit_with_every_pda(&mut bank);
+        let first = listing.bidder(&mut bank);
+        let second = listing.bidder(&mut bank);
+        bid(&mut bank, &listing, &first, 200);
+        bid(&mut bank, &listing, &second, 300);
+        // Once every PDA account exists, a bid only verifies their addresses from the cached bumps
+        bid(&mut bank, &listing, &first, 400);
+
+        let auction = listing.auction(&bank);
+        let ltv =
+            bank.unpack::<LifetimeBidVolume>(&pda(&[b"ltv", first.wallet.as_ref()], &program_id));
+        let cached_bumps = [
+            (
+                auction.bid_history_bump,
+                bump_of(&[b"bid_history", listing.escrow.as_ref()], &program_id),
+            ),
+            (
+                auction.bidder_registry_bump,
+                bump_of(&[b"bidder_registry", listing.escrow.as_ref()], &program_id),
+            ),
+            (
+                auction.insurance_vault_bump,
+                bump_of(&[b"insurance_vault", listing.ft_mint.as_ref()], &program_id),
+            ),
+            (
+                ltv.bump,
+                bump_of(&[b"ltv", first.wallet.as_ref()], &program_id),
+            ),
+        ];
+        for (cached_bump, bump) in cached_bumps {
+            assert_eq!(cached_bump, bump);
+            saved_units += find_units(bump) - CREATE_PROGRAM_ADDRESS_UNITS;
+        }
+    }
+
+    assert!(
+        saved_units / SAMPLE_AUCTIONS >= 5_000,
+        "saved {} compute units per bid",
+        saved_units / SAMPLE_AUCTIONS
+    );
+}
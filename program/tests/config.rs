@@ -0,0 +1,317 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use auction_pal::state::{GlobalConfig, GLOBAL_CONFIG_V1_LEN};
+use common::auction::{
+    pda, setup, Bidder, Listing, AUCTION_DURATION_SEC, BIDDER_BALANCE, INITIAL_PRICE,
+};
+use common::{Account, Bank};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::{system_program, sysvar};
+
+/// Transfer hook program accepting every transfer
+fn transfer_hook(_: &Pubkey, _: &[AccountInfo], _: &[u8]) -> ProgramResult {
+    Ok(())
+}
+
+fn set_token_extensions_ix(
+    bank: &Bank,
+    authority: &Pubkey,
+    transfer_hook_program: &Pubkey,
+) -> Instruction {
+    let mut data = vec![51];
+    data.extend_from_slice(transfer_hook_program.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(pda(&[b"global_config"], &bank.program_id), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+#[test]
+fn authority_whitelists_a_transfer_hook_program() {
+    let (mut bank, authority) = setup();
+    let hook_program = Pubkey::new_unique();
+    bank.add_program(hook_program, transfer_hook);
+    let global_config = pda(&[b"global_config"], &bank.program_id);
+
+    bank.process(set_token_extensions_ix(&bank, &authority, &hook_program))
+        .unwrap();
+    let config: GlobalConfig = bank.unpack(&global_config);
+    assert_eq!(config.transfer_hook_program, hook_program);
+    assert_eq!(config.authority, authority);
+
+    bank.process(set_token_extensions_ix(
+        &bank,
+        &authority,
+        &Pubkey::default(),
+    ))
+    .unwrap();
+    let config: GlobalConfig = bank.unpack(&global_config);
+    assert_eq!(config.transfer_hook_program, Pubkey::default());
+}
+
+#[test]
+fn only_the_authority_whitelists_a_transfer_hook_program() {
+    let (mut bank, _) = setup();
+    let hook_program = Pubkey::new_unique();
+    bank.add_program(hook_program, transfer_hook);
+    let intruder = bank.create_wallet();
+
+    assert_eq!(
+        bank.process(set_token_extensions_ix(&bank, &intruder, &hook_program)),
+        Err(AuctionError::Unauthorized.into())
+    );
+    let config: GlobalConfig = bank.unpack(&pda(&[b"global_config"], &bank.program_id));
+    assert_eq!(config.transfer_hook_program, Pubkey::default());
+}
+
+#[test]
+fn first_release_config_grows_to_hold_the_whitelist() {
+    let (mut bank, authority) = setup();
+    let hook_program = Pubkey::new_unique();
+    bank.add_program(hook_program, transfer_hook);
+    let global_config = pda(&[b"global_config"], &bank.program_id);
+    let account = bank.account(&global_config).unwrap().clone();
+    let v1_rent = Rent::default().minimum_balance(GLOBAL_CONFIG_V1_LEN);
+    bank.set_account(
+        global_config,
+        Account {
+            lamports: v1_rent,
+            data: account.data[..GLOBAL_CONFIG_V1_LEN].to_vec(),
+            ..account
+        },
+    );
+    let authority_lamports = bank.lamports(&authority);
+
+    bank.process(set_token_extensions_ix(&bank, &authority, &hook_program))
+        .unwrap();
+    let account = bank.account(&global_config).unwrap();
+    assert_eq!(account.data.len(), GlobalConfig::LEN);
+    let config_rent = Rent::default().minimum_balance(GlobalConfig::LEN);
+    assert_eq!(account.lamports, config_rent);
+    assert_eq!(
+        bank.lamports(&authority),
+        authority_lamports - (config_rent - v1_rent)
+    );
+    let config = GlobalConfig::unpack(&account.data).unwrap();
+    assert_eq!(config.authority, authority);
+    assert_eq!(config.transfer_hook_program, hook_program);
+}
+
+fn mint_blacklist(bank: &Bank, mint: &Pubkey) -> Pubkey {
+    pda(&[b"blacklist", mint.as_ref()], &bank.program_id)
+}
+
+fn blacklist_mint_ix(bank: &Bank, authority: &Pubkey, mint: &Pubkey) -> Instruction {
+    let mut data = vec![81];
+    data.extend_from_slice(mint.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(pda(&[b"global_config"], &bank.program_id), false),
+            AccountMeta::new(mint_blacklist(bank, mint), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+fn unblacklist_mint_ix(bank: &Bank, authority: &Pubkey, mint: &Pubkey) -> Instruction {
+    let mut data = vec![82];
+    data.extend_from_slice(mint.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(pda(&[b"global_config"], &bank.program_id), false),
+            AccountMeta::new(mint_blacklist(bank, mint), false),
+        ],
+    )
+}
+
+#[test]
+fn blacklisted_mints_are_refused_until_unblacklisted() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(blacklist_mint_ix(&bank, &authority, &listing.ft_mint))
+        .unwrap();
+    let config: GlobalConfig = bank.unpack(&pda(&[b"global_config"], &bank.program_id));
+    assert_eq!(config.mint_blacklist_count, 1);
+
+    let bidder = listing.bidder(&mut bank);
+    let mut bid_ix = listing.bid_ix(&mut bank, &bidder, 150);
+    bid_ix.accounts.push(AccountMeta::new_readonly(
+        mint_blacklist(&bank, &listing.ft_mint),
+        false,
+    ));
+    assert_eq!(
+        bank.process(bid_ix),
+        Err(AuctionError::MintBlacklisted.into())
+    );
+
+    let relisting = Listing::with_ft_mint(&mut bank, listing.exhibitor, listing.ft_mint);
+    let mut exhibit_ix = relisting.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC);
+    exhibit_ix.accounts.push(AccountMeta::new_readonly(
+        mint_blacklist(&bank, &listing.ft_mint),
+        false,
+    ));
+    assert_eq!(
+        bank.process(exhibit_ix),
+        Err(AuctionError::MintBlacklisted.into())
+    );
+    let other = Listing::new(&mut bank);
+    let mut exhibit_ix = other.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC);
+    exhibit_ix.accounts.push(AccountMeta::new_readonly(
+        mint_blacklist(&bank, &other.ft_mint),
+        false,
+    ));
+    bank.process(exhibit_ix).unwrap();
+
+    let lamports = bank.lamports(&authority);
+    let rent = bank.lamports(&mint_blacklist(&bank, &listing.ft_mint));
+    bank.process(unblacklist_mint_ix(&bank, &authority, &listing.ft_mint))
+        .unwrap();
+    assert_eq!(bank.lamports(&authority), lamports + rent);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+}
+
+#[test]
+fn only_the_authority_blacklists_mints() {
+    let (mut bank, authority) = setup();
+    let mint = Pubkey::new_unique();
+    let intruder = bank.create_wallet();
+    assert_eq!(
+        bank.process(blacklist_mint_ix(&bank, &intruder, &mint)),
+        Err(AuctionError::Unauthorized.into())
+    );
+    assert_eq!(
+        bank.process(unblacklist_mint_ix(&bank, &authority, &mint)),
+        Err(AuctionError::InvalidInstruction.into())
+    );
+
+    bank.process(blacklist_mint_ix(&bank, &authority, &mint))
+        .unwrap();
+    assert_eq!(
+        bank.process(unblacklist_mint_ix(&bank, &intruder, &mint)),
+        Err(AuctionError::Unauthorized.into())
+    );
+}
+
+fn global_ban(bank: &Bank, bidder: &Pubkey) -> Pubkey {
+    pda(&[b"global_ban", bidder.as_ref()], &bank.program_id)
+}
+
+fn global_ban_bidder_ix(bank: &Bank, authority: &Pubkey, bidder: &Pubkey) -> Instruction {
+    let mut data = vec![97];
+    data.extend_from_slice(bidder.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(pda(&[b"global_config"], &bank.program_id), false),
+            AccountMeta::new(global_ban(bank, bidder), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+fn global_unban_bidder_ix(bank: &Bank, authority: &Pubkey, bidder: &Pubkey) -> Instruction {
+    let mut data = vec![98];
+    data.extend_from_slice(bidder.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(pda(&[b"global_config"], &bank.program_id), false),
+            AccountMeta::new(global_ban(bank, bidder), false),
+        ],
+    )
+}
+
+#[test]
+fn globally_banned_bidders_are_refused_on_every_auction() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let other = Listing::exhibit(&mut bank);
+    let banned = listing.bidder(&mut bank);
+    bank.process(global_ban_bidder_ix(&bank, &authority, &banned.wallet))
+        .unwrap();
+    let config: GlobalConfig = bank.unpack(&pda(&[b"global_config"], &bank.program_id));
+    assert_eq!(config.global_ban_count, 1);
+
+    let banned_on_other = Bidder {
+        wallet: banned.wallet,
+        ft_account: bank.create_token_account(&other.ft_mint, &banned.wallet, BIDDER_BALANCE),
+    };
+    for (listing, banned) in [(&listing, &banned), (&other, &banned_on_other)] {
+        let mut bid_ix = listing.bid_ix(&mut bank, banned, 150);
+        bid_ix.accounts.push(AccountMeta::new_readonly(
+            global_ban(&bank, &banned.wallet),
+            false,
+        ));
+        assert_eq!(
+            bank.process(bid_ix),
+            Err(AuctionError::GloballyBanned.into())
+        );
+    }
+    let bidder = listing.bidder(&mut bank);
+    let mut bid_ix = listing.bid_ix(&mut bank, &bidder, 150);
+    bid_ix.accounts.push(AccountMeta::new_readonly(
+        global_ban(&bank, &banned.wallet),
+        false,
+    ));
+    assert_eq!(bank.process(bid_ix), Err(ProgramError::InvalidSeeds));
+
+    let lamports = bank.lamports(&authority);
+    let rent = bank.lamports(&global_ban(&bank, &banned.wallet));
+    bank.process(global_unban_bidder_ix(&bank, &authority, &banned.wallet))
+        .unwrap();
+    assert_eq!(bank.lamports(&authority), lamports + rent);
+    other.bid(&mut bank, &banned_on_other, 150).unwrap();
+}
+
+#[test]
+fn only_the_authority_bans_bidders() {
+    let (mut bank, authority) = setup();
+    let bidder = Pubkey::new_unique();
+    let intruder = bank.create_wallet();
+    assert_eq!(
+        bank.process(global_ban_bidder_ix(&bank, &intruder, &bidder)),
+        Err(AuctionError::Unauthorized.into())
+    );
+    assert_eq!(
+        bank.process(global_unban_bidder_ix(&bank, &authority, &bidder)),
+        Err(AuctionError::InvalidInstruction.into())
+    );
+
+    bank.process(global_ban_bidder_ix(&bank, &authority, &bidder))
+        .unwrap();
+    assert_eq!(
+        bank.process(global_ban_bidder_ix(&bank, &authority, &bidder)),
+        Err(ProgramError::AccountAlreadyInitialized)
+    );
+    assert_eq!(
+        bank.process(global_unban_bidder_ix(&bank, &intruder, &bidder)),
+        Err(AuctionError::Unauthorized.into())
+    );
+}
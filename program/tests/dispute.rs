@@ -0,0 +1,235 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use common::auction::{pda, setup, Bidder, Listing};
+use common::Bank;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::sysvar;
+
+const FINALITY_TIMEOUT_SEC: u64 = 600;
+
+fn assign_mediator_ix(bank: &Bank, listing: &Listing, mediator: &Pubkey) -> Instruction {
+    let mut data = vec![36];
+    data.extend_from_slice(mediator.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new_readonly(*mediator, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+fn revoke_mediator_ix(bank: &Bank, listing: &Listing) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[37],
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+fn raise_dispute_ix(bank: &Bank, listing: &Listing, winner: &Bidder) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[64],
+        vec![
+            AccountMeta::new_readonly(winner.wallet, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+    )
+}
+
+fn finalize_settlement_ix(
+    bank: &Bank,
+    listing: &Listing,
+    winner: &Bidder,
+    ft_temp: &Pubkey,
+    mediator: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(winner.wallet, true),
+        AccountMeta::new(listing.exhibitor, false),
+        AccountMeta::new(listing.nft_temp, false),
+        AccountMeta::new(listing.ft_receiving, false),
+        AccountMeta::new(*ft_temp, false),
+        AccountMeta::new(winner.wallet, false),
+        AccountMeta::new(listing.escrow, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(pda(&[b"escrow"], &bank.program_id), false),
+    ];
+    if let Some(mediator) = mediator {
+        accounts.push(AccountMeta::new_readonly(*mediator, true));
+    }
+    Instruction::new_with_bytes(bank.program_id, &[65], accounts)
+}
+
+/// Auction won by `winner` and closed, its FT held until the finality timeout
+fn closed_with_finality_timeout(bank: &mut Bank, listing: &Listing) -> Bidder {
+    let mut data = vec![63];
+    data.extend_from_slice(&FINALITY_TIMEOUT_SEC.to_le_bytes());
+    bank.process(Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    ))
+    .unwrap();
+    let winner = listing.bidder(bank);
+    listing.bid(bank, &winner, 250).unwrap();
+    listing.expire(bank);
+    let winner_nft = listing.nft_account(bank, &winner.wallet);
+    bank.process(listing.close_ix(bank, &winner_nft)).unwrap();
+    winner
+}
+
+#[test]
+fn mediator_cosigns_the_assignment() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let mediator = bank.create_wallet();
+
+    let mut unsigned_ix = assign_mediator_ix(&bank, &listing, &mediator);
+    unsigned_ix.accounts[1].is_signer = false;
+    assert_eq!(
+        bank.process(unsigned_ix),
+        Err(ProgramError::MissingRequiredSignature)
+    );
+    assert_eq!(
+        bank.process(assign_mediator_ix(&bank, &listing, &listing.exhibitor)),
+        Err(ProgramError::InvalidArgument)
+    );
+
+    bank.process(assign_mediator_ix(&bank, &listing, &mediator))
+        .unwrap();
+    assert_eq!(listing.auction(&bank).mediator_pubkey, mediator);
+}
+
+#[test]
+fn exhibitor_revokes_the_mediator() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let mediator = bank.create_wallet();
+    bank.process(assign_mediator_ix(&bank, &listing, &mediator))
+        .unwrap();
+
+    bank.process(revoke_mediator_ix(&bank, &listing)).unwrap();
+    assert_eq!(listing.auction(&bank).mediator_pubkey, Pubkey::default());
+
+    let winner = closed_with_finality_timeout(&mut bank, &listing);
+    assert_eq!(
+        bank.process(raise_dispute_ix(&bank, &listing, &winner)),
+        Err(AuctionError::MediatorRequired.into())
+    );
+}
+
+#[test]
+fn assigned_mediator_resolves_the_dispute() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let mediator = bank.create_wallet();
+    bank.process(assign_mediator_ix(&bank, &listing, &mediator))
+        .unwrap();
+    let winner = closed_with_finality_timeout(&mut bank, &listing);
+    let auction = listing.auction(&bank);
+    let ft_temp = auction.highest_bidder_ft_temp_pubkey;
+
+    bank.process(raise_dispute_ix(&bank, &listing, &winner))
+        .unwrap();
+    assert!(listing.auction(&bank).is_disputed);
+    bank.warp_to_timestamp(auction.finalized_at);
+
+    let intruder = bank.create_wallet();
+    assert_eq!(
+        bank.process(finalize_settlement_ix(
+            &bank,
+            &listing,
+            &winner,
+            &ft_temp,
+            Some(&intruder)
+        )),
+        Err(AuctionError::Unauthorized.into())
+    );
+    assert_eq!(bank.token_balance(&listing.ft_receiving), 0);
+
+    bank.process(finalize_settlement_ix(
+        &bank,
+        &listing,
+        &winner,
+        &ft_temp,
+        Some(&mediator),
+    ))
+    .unwrap();
+    assert_eq!(bank.token_balance(&listing.ft_receiving), 250);
+    assert!(bank.account(&ft_temp).is_none());
+    assert!(bank.account(&listing.escrow).is_none());
+}
+
+#[test]
+fn ft_is_released_once_the_finality_timeout_elapsed() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let winner = closed_with_finality_timeout(&mut bank, &listing);
+    let closed_at = bank.clock().unix_timestamp;
+    let auction = listing.auction(&bank);
+    let ft_temp = auction.highest_bidder_ft_temp_pubkey;
+    assert_eq!(
+        auction.finalized_at,
+        closed_at + FINALITY_TIMEOUT_SEC as i64
+    );
+    assert_eq!(bank.token_balance(&ft_temp), 250);
+    assert_eq!(bank.token_balance(&listing.ft_receiving), 0);
+
+    bank.warp_to_timestamp(auction.finalized_at - 1);
+    assert_eq!(
+        bank.process(finalize_settlement_ix(
+            &bank, &listing, &winner, &ft_temp, None
+        )),
+        Err(AuctionError::SettlementPending.into())
+    );
+    bank.warp_to_timestamp(auction.finalized_at);
+    bank.process(finalize_settlement_ix(
+        &bank, &listing, &winner, &ft_temp, None,
+    ))
+    .unwrap();
+    assert_eq!(bank.token_balance(&listing.ft_receiving), 250);
+    assert!(bank.account(&listing.escrow).is_none());
+}
+
+#[test]
+fn disputes_are_raised_within_the_finality_timeout() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let mediator = bank.create_wallet();
+    bank.process(assign_mediator_ix(&bank, &listing, &mediator))
+        .unwrap();
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    assert_eq!(
+        bank.process(raise_dispute_ix(&bank, &listing, &bidder)),
+        Err(AuctionError::ActiveAuction.into())
+    );
+
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(assign_mediator_ix(&bank, &listing, &mediator))
+        .unwrap();
+    let winner = closed_with_finality_timeout(&mut bank, &listing);
+    bank.warp_to_timestamp(listing.auction(&bank).finalized_at);
+    assert_eq!(
+        bank.process(raise_dispute_ix(&bank, &listing, &winner)),
+        Err(AuctionError::DisputeWindowClosed.into())
+    );
+    assert!(!listing.auction(&bank).is_disputed);
+}
@@ -0,0 +1,1123 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use auction_pal::event::NewAuctionEvent;
+use auction_pal::state::{
+    Auction, AuctionCategory, AuctionIndex, ExhibitorNote, ExhibitorProfile, NFTLockRecord,
+    RecurringAuction, EXHIBITOR_NOTE_LEN,
+};
+use common::auction::{exhibit_data, pda, setup, Listing, AUCTION_DURATION_SEC, INITIAL_PRICE};
+use common::{ed25519_ix, Bank};
+use solana_program::hash::hash;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+use solana_program::{system_program, sysvar};
+use spl_token::state::AccountState;
+
+fn change_ft_receiver_ix(
+    bank: &Bank,
+    listing: &Listing,
+    signer: &Pubkey,
+    new_receiver: &Pubkey,
+) -> Instruction {
+    let mut data = vec![6];
+    data.extend_from_slice(new_receiver.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new_readonly(*new_receiver, false),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+#[test]
+fn changed_ft_receiver_gets_the_proceeds() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+
+    let new_receiver = bank.create_token_account(&listing.ft_mint, &listing.exhibitor, 0);
+    bank.process(change_ft_receiver_ix(
+        &bank,
+        &listing,
+        &listing.exhibitor,
+        &new_receiver,
+    ))
+    .unwrap();
+    assert_eq!(
+        listing.auction(&bank).exhibitor_ft_receiving_pubkey,
+        new_receiver
+    );
+
+    listing.expire(&mut bank);
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    let mut close_ix = listing.close_ix(&bank, &winner_nft);
+    close_ix.accounts[3] = AccountMeta::new(new_receiver, false);
+    bank.process(close_ix).unwrap();
+    assert_eq!(bank.token_balance(&new_receiver), 250);
+    assert_eq!(bank.token_balance(&listing.ft_receiving), 0);
+}
+
+#[test]
+fn non_exhibitor_cannot_change_ft_receiver() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let intruder = bank.create_wallet();
+    let new_receiver = bank.create_token_account(&listing.ft_mint, &intruder, 0);
+
+    assert_eq!(
+        bank.process(change_ft_receiver_ix(
+            &bank,
+            &listing,
+            &intruder,
+            &new_receiver
+        )),
+        Err(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(
+        listing.auction(&bank).exhibitor_ft_receiving_pubkey,
+        listing.ft_receiving
+    );
+}
+
+#[test]
+fn changed_ft_receiver_must_be_a_token_account_of_the_auction_mint() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let other_mint = bank.create_mint(&listing.exhibitor);
+    let other_receiver = bank.create_token_account(&other_mint, &listing.exhibitor, 0);
+    assert_eq!(
+        bank.process(change_ft_receiver_ix(
+            &bank,
+            &listing,
+            &listing.exhibitor,
+            &other_receiver
+        )),
+        Err(ProgramError::InvalidAccountData)
+    );
+
+    // A copy of a valid receiver owned by another program
+    let fake_receiver = Pubkey::new_unique();
+    let program_id = bank.program_id;
+    let token_account = bank.token_account(&listing.ft_receiving);
+    bank.pack(fake_receiver, program_id, token_account);
+    assert_eq!(
+        bank.process(change_ft_receiver_ix(
+            &bank,
+            &listing,
+            &listing.exhibitor,
+            &fake_receiver
+        )),
+        Err(ProgramError::IncorrectProgramId)
+    );
+    assert_eq!(
+        listing.auction(&bank).exhibitor_ft_receiving_pubkey,
+        listing.ft_receiving
+    );
+}
+
+#[test]
+fn raised_floor_rejects_lower_bids() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+
+    bank.process(listing.set_floor_price_ix(&bank, 500))
+        .unwrap();
+    assert_eq!(listing.auction(&bank).floor_price, 500);
+
+    let other = listing.bidder(&mut bank);
+    assert_eq!(
+        listing.bid(&mut bank, &other, 499),
+        Err(AuctionError::BelowFloorPrice.into())
+    );
+    listing.bid(&mut bank, &other, 500).unwrap();
+    assert_eq!(listing.auction(&bank).highest_bidder_pubkey, other.wallet);
+}
+
+#[test]
+fn floor_cannot_drop_to_the_highest_bid() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+
+    assert_eq!(
+        bank.process(listing.set_floor_price_ix(&bank, 150)),
+        Err(AuctionError::InsufficientBidPrice.into())
+    );
+    let mut set_floor_ix = listing.set_floor_price_ix(&bank, 500);
+    set_floor_ix.accounts[0] = AccountMeta::new_readonly(bidder.wallet, true);
+    assert_eq!(
+        bank.process(set_floor_ix),
+        Err(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(listing.auction(&bank).floor_price, 0);
+}
+
+fn exhibitor_profile(bank: &Bank, exhibitor: &Pubkey) -> ExhibitorProfile {
+    bank.unpack(&pda(
+        &[b"exhibitor_profile", exhibitor.as_ref()],
+        &bank.program_id,
+    ))
+}
+
+#[test]
+fn exhibits_are_counted_in_the_exhibitor_profile() {
+    let (mut bank, _) = setup();
+    let first = Listing::exhibit(&mut bank);
+    let profile = exhibitor_profile(&bank, &first.exhibitor);
+    assert_eq!(profile.auction_count, 1);
+    assert_eq!(profile.latest_auction, first.escrow);
+
+    let second = Listing::with_ft_mint(&mut bank, first.exhibitor, first.ft_mint);
+    bank.process(second.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC))
+        .unwrap();
+    let profile = exhibitor_profile(&bank, &first.exhibitor);
+    assert_eq!(profile.auction_count, 2);
+    assert_eq!(profile.latest_auction, second.escrow);
+
+    let end_at = bank.clock().unix_timestamp + AUCTION_DURATION_SEC as i64;
+    let event = Bank::events()
+        .into_iter()
+        .rev()
+        .find(|fields| fields[0] == NewAuctionEvent::NAME)
+        .expect("new auction logged");
+    assert_eq!(
+        event,
+        vec![
+            NewAuctionEvent::NAME.to_vec(),
+            first.exhibitor.to_bytes().to_vec(),
+            second.escrow.to_bytes().to_vec(),
+            second.nft_mint.to_bytes().to_vec(),
+            INITIAL_PRICE.to_le_bytes().to_vec(),
+            end_at.to_le_bytes().to_vec(),
+        ]
+    );
+}
+
+fn batch_exhibit_ix(bank: &Bank, lots: &[(Listing, u64)]) -> Instruction {
+    let mut data = vec![11, lots.len() as u8];
+    let mut accounts = vec![];
+    for (listing, seconds) in lots {
+        let exhibit = exhibit_data(INITIAL_PRICE, *seconds, listing.category, &listing.ft_mint);
+        data.extend_from_slice(&exhibit[1..]);
+        accounts.extend(listing.exhibit_accounts(bank));
+    }
+    Instruction::new_with_bytes(bank.program_id, &data, accounts)
+}
+
+fn lots(bank: &mut Bank, count: usize) -> Vec<(Listing, u64)> {
+    let first = Listing::new(bank);
+    let mut lots = vec![(first, AUCTION_DURATION_SEC)];
+    for i in 1..count {
+        let listing = Listing::with_ft_mint(bank, first.exhibitor, first.ft_mint);
+        lots.push((listing, AUCTION_DURATION_SEC * (i as u64 + 1)));
+    }
+    lots
+}
+
+#[test]
+fn batch_exhibit_starts_every_lot() {
+    for count in [2, 4] {
+        let (mut bank, _) = setup();
+        let lots = lots(&mut bank, count);
+
+        bank.process(batch_exhibit_ix(&bank, &lots)).unwrap();
+        let start = bank.clock().unix_timestamp;
+        for (listing, seconds) in &lots {
+            let auction = listing.auction(&bank);
+            assert_eq!(auction.exhibitor_pubkey, listing.exhibitor);
+            assert_eq!(auction.price, INITIAL_PRICE);
+            assert_eq!(auction.end_at, start + *seconds as i64);
+            assert_eq!(
+                bank.token_account(&listing.nft_temp).owner,
+                pda(&[b"escrow"], &bank.program_id)
+            );
+        }
+        let profile = exhibitor_profile(&bank, &lots[0].0.exhibitor);
+        assert_eq!(profile.auction_count, count as u32);
+    }
+}
+
+#[test]
+fn invalid_lot_aborts_the_whole_batch() {
+    let (mut bank, _) = setup();
+    let mut lots = lots(&mut bank, 4);
+    lots[2].1 = u64::MAX;
+
+    assert_eq!(
+        bank.process(batch_exhibit_ix(&bank, &lots)),
+        Err(AuctionError::AmountOverflow.into())
+    );
+    for (listing, _) in &lots {
+        assert!(bank
+            .account(&listing.escrow)
+            .unwrap()
+            .data
+            .iter()
+            .all(|&byte| byte == 0));
+        assert_eq!(bank.token_balance(&listing.exhibitor_nft), 1);
+    }
+}
+
+#[test]
+fn batch_lots_are_settled_one_by_one() {
+    let (mut bank, _) = setup();
+    let lots = lots(&mut bank, 2);
+    bank.process(batch_exhibit_ix(&bank, &lots)).unwrap();
+    let mut winners = vec![];
+    for (listing, _) in &lots {
+        listing.accept_terms(&mut bank);
+        let bidder = listing.bidder(&mut bank);
+        listing.bid(&mut bank, &bidder, 250).unwrap();
+        winners.push(listing.nft_account(&mut bank, &bidder.wallet));
+    }
+
+    let (first, _) = &lots[0];
+    first.expire(&mut bank);
+    bank.process(first.close_ix(&bank, &winners[0])).unwrap();
+    assert_eq!(bank.token_balance(&winners[0]), 1);
+    assert!(bank.account(&first.escrow).is_none());
+    let (second, _) = &lots[1];
+    assert_eq!(second.auction(&bank).price, 250);
+    assert_eq!(bank.token_balance(&second.nft_temp), 1);
+
+    second.expire(&mut bank);
+    bank.process(second.close_ix(&bank, &winners[1])).unwrap();
+    assert_eq!(bank.token_balance(&winners[1]), 1);
+    assert_eq!(bank.token_balance(&first.ft_receiving), 250);
+    assert_eq!(bank.token_balance(&second.ft_receiving), 250);
+}
+
+#[test]
+fn batch_exhibit_caps_the_lot_count() {
+    let (mut bank, _) = setup();
+    let lots = lots(&mut bank, 5);
+
+    assert_eq!(
+        bank.process(batch_exhibit_ix(&bank, &lots)),
+        Err(AuctionError::InvalidInstruction.into())
+    );
+}
+
+#[test]
+fn frozen_nft_account_cannot_be_exhibited() {
+    let (mut bank, _) = setup();
+    let listing = Listing::new(&mut bank);
+    let mut exhibitor_nft = bank.token_account(&listing.exhibitor_nft);
+    exhibitor_nft.state = AccountState::Frozen;
+    bank.pack(listing.exhibitor_nft, spl_token::id(), exhibitor_nft);
+
+    assert_eq!(
+        bank.process(listing.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC)),
+        Err(AuctionError::TokenAccountFrozen.into())
+    );
+    assert!(!Bank::logs()
+        .iter()
+        .any(|log| log.starts_with("Instruction: ")));
+    assert_eq!(bank.token_balance(&listing.exhibitor_nft), 1);
+    assert_eq!(
+        bank.token_account(&listing.nft_temp).owner,
+        listing.exhibitor
+    );
+}
+
+#[test]
+fn ft_receiver_cannot_collide_with_the_nft_accounts() {
+    let (mut bank, _) = setup();
+    let listing = Listing::new(&mut bank);
+
+    for nft_account in [listing.nft_temp, listing.exhibitor_nft] {
+        let mut exhibit_ix = listing.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC);
+        exhibit_ix.accounts[3].pubkey = nft_account;
+        assert_eq!(
+            bank.process(exhibit_ix),
+            Err(AuctionError::InvalidInstruction.into())
+        );
+    }
+    assert_eq!(bank.token_balance(&listing.exhibitor_nft), 1);
+}
+
+fn auction_index(bank: &Bank, seeds: &[&[u8]]) -> AuctionIndex {
+    bank.unpack(&pda(seeds, &bank.program_id))
+}
+
+#[test]
+fn exhibit_without_category_is_filed_under_other() {
+    let (mut bank, _) = setup();
+    let listing = Listing::new(&mut bank);
+    let mut exhibit_ix = listing.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC);
+    exhibit_ix.data.pop();
+
+    bank.process(exhibit_ix).unwrap();
+    assert_eq!(listing.auction(&bank).category, AuctionCategory::Other);
+    let other_index = auction_index(&bank, &[b"category_index", &[AuctionCategory::Other as u8]]);
+    assert_eq!(other_index.total_count, 1);
+    assert_eq!(other_index.escrows[0], listing.escrow);
+}
+
+#[test]
+fn each_category_has_its_own_index() {
+    let (mut bank, _) = setup();
+    let mut art = Listing::new(&mut bank);
+    art.category = AuctionCategory::Art;
+    bank.process(art.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC))
+        .unwrap();
+    let mut gaming = Listing::new(&mut bank);
+    gaming.category = AuctionCategory::Gaming;
+    bank.process(gaming.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC))
+        .unwrap();
+
+    assert_eq!(art.auction(&bank).category, AuctionCategory::Art);
+    assert_eq!(gaming.auction(&bank).category, AuctionCategory::Gaming);
+    let global_index = auction_index(&bank, &[b"auction_index"]);
+    assert_eq!(global_index.total_count, 2);
+    assert_eq!(global_index.escrows[..2], [art.escrow, gaming.escrow]);
+    for listing in [art, gaming] {
+        let category_index = auction_index(&bank, &[b"category_index", &[listing.category as u8]]);
+        assert_eq!(category_index.total_count, 1);
+        assert_eq!(category_index.escrows[0], listing.escrow);
+    }
+}
+
+fn note_account(bank: &Bank, listing: &Listing) -> Pubkey {
+    pda(&[b"note", listing.escrow.as_ref()], &bank.program_id)
+}
+
+fn set_exhibitor_note_ix(
+    bank: &Bank,
+    listing: &Listing,
+    signer: &Pubkey,
+    note: &[u8; EXHIBITOR_NOTE_LEN],
+    note_hash: &[u8; 32],
+) -> Instruction {
+    let mut data = vec![17];
+    data.extend_from_slice(note);
+    data.extend_from_slice(note_hash);
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new_readonly(listing.escrow, false),
+            AccountMeta::new(note_account(bank, listing), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+fn provenance_note() -> [u8; EXHIBITOR_NOTE_LEN] {
+    let mut note = [0; EXHIBITOR_NOTE_LEN];
+    let text = b"Minted 2021, held by the artist since";
+    note[..text.len()].copy_from_slice(text);
+    note
+}
+
+#[test]
+fn exhibitor_note_is_set_once() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let note = provenance_note();
+    let note_hash = hash(&note).to_bytes();
+
+    bank.process(set_exhibitor_note_ix(
+        &bank,
+        &listing,
+        &listing.exhibitor,
+        &note,
+        &note_hash,
+    ))
+    .unwrap();
+    let stored: ExhibitorNote = bank.unpack(&note_account(&bank, &listing));
+    assert_eq!(stored.note, note);
+    assert_eq!(stored.signed_at, bank.clock().unix_timestamp);
+
+    let mut other_note = note;
+    other_note[0] = b'X';
+    assert_eq!(
+        bank.process(set_exhibitor_note_ix(
+            &bank,
+            &listing,
+            &listing.exhibitor,
+            &other_note,
+            &hash(&other_note).to_bytes(),
+        )),
+        Err(AuctionError::NoteAlreadySet.into())
+    );
+    let stored: ExhibitorNote = bank.unpack(&note_account(&bank, &listing));
+    assert_eq!(stored.note, note);
+}
+
+#[test]
+fn exhibitor_note_needs_the_exhibitor_and_a_matching_hash() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let note = provenance_note();
+    let intruder = bank.create_wallet();
+
+    assert_eq!(
+        bank.process(set_exhibitor_note_ix(
+            &bank,
+            &listing,
+            &intruder,
+            &note,
+            &hash(&note).to_bytes(),
+        )),
+        Err(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(
+        bank.process(set_exhibitor_note_ix(
+            &bank,
+            &listing,
+            &listing.exhibitor,
+            &note,
+            &[0; 32],
+        )),
+        Err(AuctionError::NoteHashMismatch.into())
+    );
+    assert!(bank.account(&note_account(&bank, &listing)).is_none());
+}
+
+fn verify_signed_message_ix(
+    bank: &Bank,
+    listing: &Listing,
+    message_hash: &[u8; 32],
+) -> Instruction {
+    let mut data = vec![20];
+    data.extend_from_slice(message_hash);
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+#[test]
+fn bids_wait_for_the_attested_terms() {
+    let (mut bank, _) = setup();
+    let listing = Listing::new(&mut bank);
+    bank.process(listing.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC))
+        .unwrap();
+    let bidder = listing.bidder(&mut bank);
+    assert_eq!(
+        listing.bid(&mut bank, &bidder, 150),
+        Err(AuctionError::TermsNotAccepted.into())
+    );
+
+    let terms_hash = hash(b"Sold as is, no returns").to_bytes();
+    bank.process_transaction(&[
+        ed25519_ix(&listing.exhibitor, &terms_hash),
+        verify_signed_message_ix(&bank, &listing, &terms_hash),
+    ])
+    .unwrap();
+    let auction = listing.auction(&bank);
+    assert!(auction.terms_accepted);
+    assert_eq!(auction.terms_hash, terms_hash);
+
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    assert_eq!(listing.auction(&bank).highest_bidder_pubkey, bidder.wallet);
+}
+
+#[test]
+fn terms_signature_must_match_the_hash_and_the_exhibitor() {
+    let (mut bank, _) = setup();
+    let listing = Listing::new(&mut bank);
+    bank.process(listing.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC))
+        .unwrap();
+    let terms_hash = hash(b"Sold as is, no returns").to_bytes();
+    let other_hash = hash(b"Returns accepted").to_bytes();
+    let stranger = bank.create_wallet();
+
+    for signature in [
+        ed25519_ix(&listing.exhibitor, &other_hash),
+        ed25519_ix(&stranger, &terms_hash),
+    ] {
+        assert_eq!(
+            bank.process_transaction(&[
+                signature,
+                verify_signed_message_ix(&bank, &listing, &terms_hash),
+            ]),
+            Err(AuctionError::InvalidTermsSignature.into())
+        );
+    }
+    assert_eq!(
+        bank.process(verify_signed_message_ix(&bank, &listing, &terms_hash)),
+        Err(AuctionError::InvalidTermsSignature.into())
+    );
+    assert!(!listing.auction(&bank).terms_accepted);
+}
+
+#[test]
+fn ft_receiver_must_hold_the_auction_mint() {
+    let (mut bank, _) = setup();
+    let listing = Listing::new(&mut bank);
+    let other_mint = bank.create_mint(&listing.exhibitor);
+    let other_receiver = bank.create_token_account(&other_mint, &listing.exhibitor, 0);
+
+    let mut exhibit_ix = listing.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC);
+    exhibit_ix.accounts[3].pubkey = other_receiver;
+    assert_eq!(
+        bank.process(exhibit_ix),
+        Err(ProgramError::InvalidAccountData)
+    );
+
+    let mut exhibit_ix = listing.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC);
+    exhibit_ix.accounts[12].pubkey = other_mint;
+    assert_eq!(
+        bank.process(exhibit_ix),
+        Err(ProgramError::InvalidAccountData)
+    );
+
+    bank.process(listing.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC))
+        .unwrap();
+    assert_eq!(listing.auction(&bank).ft_mint, listing.ft_mint);
+}
+
+fn set_nft_mint_validation_ix(
+    bank: &Bank,
+    listing: &Listing,
+    expected_mint: &Pubkey,
+) -> Instruction {
+    let mut data = vec![35];
+    data.extend_from_slice(expected_mint.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(listing.nft_temp, false),
+        ],
+    )
+}
+
+#[test]
+fn nft_mint_validation_accepts_the_exhibited_mint() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    assert_eq!(listing.auction(&bank).expected_nft_mint, Pubkey::default());
+
+    bank.process(set_nft_mint_validation_ix(
+        &bank,
+        &listing,
+        &listing.nft_mint,
+    ))
+    .unwrap();
+    assert_eq!(listing.auction(&bank).expected_nft_mint, listing.nft_mint);
+}
+
+#[test]
+fn nft_mint_validation_rejects_another_mint() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let other_mint = bank.create_mint(&listing.exhibitor);
+
+    assert_eq!(
+        bank.process(set_nft_mint_validation_ix(&bank, &listing, &other_mint)),
+        Err(AuctionError::UnexpectedNFTMint.into())
+    );
+    assert_eq!(listing.auction(&bank).expected_nft_mint, Pubkey::default());
+}
+
+#[test]
+fn nft_mint_validation_is_set_before_the_first_bid() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+
+    assert_eq!(
+        bank.process(set_nft_mint_validation_ix(
+            &bank,
+            &listing,
+            &listing.nft_mint
+        )),
+        Err(AuctionError::AlreadyBid.into())
+    );
+}
+
+fn set_minimum_duration_ix(bank: &Bank, authority: &Pubkey, min_sec: u64) -> Instruction {
+    let mut data = vec![52];
+    data.extend_from_slice(&min_sec.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(pda(&[b"global_config"], &bank.program_id), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+fn set_exhibitor_min_duration_ix(
+    bank: &Bank,
+    authority: &Pubkey,
+    exhibitor: &Pubkey,
+    min_sec: u64,
+) -> Instruction {
+    let program_id = &bank.program_id;
+    let mut data = vec![53];
+    data.extend_from_slice(&min_sec.to_le_bytes());
+    Instruction::new_with_bytes(
+        *program_id,
+        &data,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(pda(&[b"global_config"], program_id), false),
+            AccountMeta::new(
+                pda(&[b"exhibitor_config", exhibitor.as_ref()], program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(*exhibitor, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+#[test]
+fn auctions_last_at_least_the_global_minimum() {
+    let (mut bank, authority) = setup();
+    bank.process(set_minimum_duration_ix(&bank, &authority, 600))
+        .unwrap();
+    let listing = Listing::new(&mut bank);
+
+    assert_eq!(
+        bank.process(listing.exhibit_ix(&bank, INITIAL_PRICE, 599)),
+        Err(AuctionError::AuctionTooShort.into())
+    );
+    bank.process(listing.exhibit_ix(&bank, INITIAL_PRICE, 600))
+        .unwrap();
+    assert!(listing.auction(&bank).is_initialized);
+}
+
+#[test]
+fn exhibitor_minimum_adds_to_the_global_minimum() {
+    let (mut bank, authority) = setup();
+    bank.process(set_minimum_duration_ix(&bank, &authority, 600))
+        .unwrap();
+    let strict = Listing::new(&mut bank);
+    let lenient = Listing::new(&mut bank);
+    let other = Listing::new(&mut bank);
+    bank.process(set_exhibitor_min_duration_ix(
+        &bank,
+        &authority,
+        &strict.exhibitor,
+        1_200,
+    ))
+    .unwrap();
+    bank.process(set_exhibitor_min_duration_ix(
+        &bank,
+        &authority,
+        &lenient.exhibitor,
+        300,
+    ))
+    .unwrap();
+
+    assert_eq!(
+        bank.process(strict.exhibit_ix(&bank, INITIAL_PRICE, 1_199)),
+        Err(AuctionError::AuctionTooShort.into())
+    );
+    bank.process(strict.exhibit_ix(&bank, INITIAL_PRICE, 1_200))
+        .unwrap();
+
+    // The global minimum holds below a lower exhibitor minimum
+    assert_eq!(
+        bank.process(lenient.exhibit_ix(&bank, INITIAL_PRICE, 599)),
+        Err(AuctionError::AuctionTooShort.into())
+    );
+    bank.process(lenient.exhibit_ix(&bank, INITIAL_PRICE, 600))
+        .unwrap();
+
+    bank.process(other.exhibit_ix(&bank, INITIAL_PRICE, 600))
+        .unwrap();
+}
+
+#[test]
+fn only_the_authority_sets_minimum_durations() {
+    let (mut bank, _) = setup();
+    let listing = Listing::new(&mut bank);
+
+    assert_eq!(
+        bank.process(set_minimum_duration_ix(&bank, &listing.exhibitor, 600)),
+        Err(AuctionError::Unauthorized.into())
+    );
+    assert_eq!(
+        bank.process(set_exhibitor_min_duration_ix(
+            &bank,
+            &listing.exhibitor,
+            &listing.exhibitor,
+            0
+        )),
+        Err(AuctionError::Unauthorized.into())
+    );
+    bank.process(listing.exhibit_ix(&bank, INITIAL_PRICE, 1))
+        .unwrap();
+}
+
+fn exhibit_with_timestamps_ix(
+    bank: &Bank,
+    listing: &Listing,
+    start_at: i64,
+    end_at: i64,
+) -> Instruction {
+    let mut data = vec![55];
+    data.extend_from_slice(&INITIAL_PRICE.to_le_bytes());
+    data.extend_from_slice(&start_at.to_le_bytes());
+    data.extend_from_slice(&end_at.to_le_bytes());
+    data.push(listing.category as u8);
+    data.extend_from_slice(listing.ft_mint.as_ref());
+    Instruction::new_with_bytes(bank.program_id, &data, listing.exhibit_accounts(bank))
+}
+
+#[test]
+fn auction_runs_between_the_given_timestamps() {
+    let (mut bank, _) = setup();
+    let listing = Listing::new(&mut bank);
+    let now = bank.clock().unix_timestamp;
+    bank.process(exhibit_with_timestamps_ix(
+        &bank,
+        &listing,
+        now + 60,
+        now + 660,
+    ))
+    .unwrap();
+    listing.accept_terms(&mut bank);
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.starts_at, now + 60);
+    assert_eq!(auction.end_at, now + 660);
+    let bidder = listing.bidder(&mut bank);
+
+    bank.warp_to_timestamp(now + 59);
+    assert_eq!(
+        listing.bid(&mut bank, &bidder, 150),
+        Err(AuctionError::AuctionNotStarted.into())
+    );
+    bank.warp_to_timestamp(now + 60);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    bank.warp_to_timestamp(now + 660);
+    assert_eq!(
+        listing.bid(&mut bank, &bidder, 200),
+        Err(AuctionError::InactiveAuction.into())
+    );
+}
+
+#[test]
+fn timestamps_must_be_ordered_and_not_in_the_past() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::new(&mut bank);
+    let now = bank.clock().unix_timestamp;
+
+    for (start_at, end_at) in [
+        (now - 1, now + 600),
+        (now + 600, now + 600),
+        (now + 600, now),
+    ] {
+        assert_eq!(
+            bank.process(exhibit_with_timestamps_ix(
+                &bank, &listing, start_at, end_at
+            )),
+            Err(AuctionError::InvalidExpiry.into())
+        );
+    }
+
+    // The minimum duration counts from the start time
+    bank.process(set_minimum_duration_ix(&bank, &authority, 600))
+        .unwrap();
+    assert_eq!(
+        bank.process(exhibit_with_timestamps_ix(
+            &bank,
+            &listing,
+            now + 60,
+            now + 659
+        )),
+        Err(AuctionError::AuctionTooShort.into())
+    );
+    bank.process(exhibit_with_timestamps_ix(&bank, &listing, now, now + 600))
+        .unwrap();
+    assert!(listing.auction(&bank).is_initialized);
+}
+
+fn set_lot_size_ix(bank: &Bank, listing: &Listing, lot_size: u64) -> Instruction {
+    let mut data = vec![71];
+    data.extend_from_slice(&lot_size.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.exhibitor_nft, false),
+            AccountMeta::new(listing.nft_temp, false),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(pda(&[b"escrow"], &bank.program_id), false),
+        ],
+    )
+}
+
+#[test]
+fn lot_size_moves_tokens_into_and_out_of_escrow() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.set_token_account(
+        listing.exhibitor_nft,
+        &listing.nft_mint,
+        &listing.exhibitor,
+        9,
+    );
+
+    bank.process(set_lot_size_ix(&bank, &listing, 5)).unwrap();
+    assert_eq!(bank.token_balance(&listing.nft_temp), 5);
+    assert_eq!(bank.token_balance(&listing.exhibitor_nft), 5);
+    bank.process(set_lot_size_ix(&bank, &listing, 3)).unwrap();
+    assert_eq!(bank.token_balance(&listing.nft_temp), 3);
+    assert_eq!(bank.token_balance(&listing.exhibitor_nft), 7);
+    assert_eq!(listing.auction(&bank).lot_size, 3);
+
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    listing.expire(&mut bank);
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    bank.process(listing.close_ix(&bank, &winner_nft)).unwrap();
+    assert_eq!(bank.token_balance(&winner_nft), 3);
+}
+
+#[test]
+fn lot_size_is_set_before_bids_and_never_empty() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    assert_eq!(listing.auction(&bank).lot_size, 1);
+
+    assert_eq!(
+        bank.process(set_lot_size_ix(&bank, &listing, 0)),
+        Err(AuctionError::InvalidLotSize.into())
+    );
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    assert_eq!(
+        bank.process(set_lot_size_ix(&bank, &listing, 1)),
+        Err(AuctionError::AlreadyBid.into())
+    );
+}
+
+fn set_nft_lock_period_ix(bank: &Bank, authority: &Pubkey, lock_period_sec: u64) -> Instruction {
+    let mut data = vec![79];
+    data.extend_from_slice(&lock_period_sec.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(pda(&[b"global_config"], &bank.program_id), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+fn nft_lock(bank: &Bank, nft_mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+    pda(
+        &[b"nft_lock", nft_mint.as_ref(), owner.as_ref()],
+        &bank.program_id,
+    )
+}
+
+fn locked_exhibit_ix(bank: &Bank, listing: &Listing) -> Instruction {
+    let mut exhibit_ix = listing.exhibit_ix(bank, INITIAL_PRICE, AUCTION_DURATION_SEC);
+    exhibit_ix.accounts.push(AccountMeta::new_readonly(
+        nft_lock(bank, &listing.nft_mint, &listing.exhibitor),
+        false,
+    ));
+    exhibit_ix
+}
+
+#[test]
+fn winner_relists_the_nft_once_the_lock_period_elapsed() {
+    let (mut bank, authority) = setup();
+    bank.process(set_nft_lock_period_ix(&bank, &authority, 86_400))
+        .unwrap();
+    let listing = Listing::new(&mut bank);
+    bank.process(locked_exhibit_ix(&bank, &listing)).unwrap();
+    listing.accept_terms(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+
+    listing.expire(&mut bank);
+    let lock = nft_lock(&bank, &listing.nft_mint, &bidder.wallet);
+    let mut close_ix = listing.close_ix(&bank, &winner_nft);
+    close_ix.accounts.push(AccountMeta::new(lock, false));
+    bank.process(close_ix).unwrap();
+    let unlocks_at = bank.clock().unix_timestamp + 86_400;
+    assert_eq!(bank.unpack::<NFTLockRecord>(&lock).unlocks_at, unlocks_at);
+
+    let program_id = bank.program_id;
+    let relisting = Listing {
+        exhibitor: bidder.wallet,
+        nft_mint: listing.nft_mint,
+        exhibitor_nft: winner_nft,
+        nft_temp: listing.nft_account(&mut bank, &bidder.wallet),
+        ft_mint: listing.ft_mint,
+        ft_receiving: bank.create_token_account(&listing.ft_mint, &bidder.wallet, 0),
+        escrow: bank.create_account(&program_id, Auction::LEN),
+        category: listing.category,
+    };
+    assert_eq!(
+        bank.process(locked_exhibit_ix(&bank, &relisting)),
+        Err(AuctionError::NFTLockedForRelisting.into())
+    );
+    bank.warp_to_timestamp(unlocks_at);
+    bank.process(locked_exhibit_ix(&bank, &relisting)).unwrap();
+    assert_eq!(bank.token_balance(&relisting.nft_temp), 1);
+}
+
+#[test]
+fn only_the_authority_sets_the_nft_lock_period() {
+    let (mut bank, _) = setup();
+    let intruder = bank.create_wallet();
+    assert_eq!(
+        bank.process(set_nft_lock_period_ix(&bank, &intruder, 86_400)),
+        Err(AuctionError::Unauthorized.into())
+    );
+}
+
+fn recurring_auction(bank: &Bank, exhibitor: &Pubkey) -> Pubkey {
+    pda(&[b"recurring", exhibitor.as_ref()], &bank.program_id)
+}
+
+fn setup_recurring_ix(
+    bank: &Bank,
+    listing: &Listing,
+    interval_sec: u64,
+    occurrences: u8,
+    duration_sec: u64,
+) -> Instruction {
+    let mut data = vec![102];
+    data.extend_from_slice(&interval_sec.to_le_bytes());
+    data.push(occurrences);
+    data.extend_from_slice(&INITIAL_PRICE.to_le_bytes());
+    data.extend_from_slice(&duration_sec.to_le_bytes());
+    data.push(listing.category as u8);
+    data.extend_from_slice(listing.ft_mint.as_ref());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(listing.exhibitor, true),
+            AccountMeta::new(recurring_auction(bank, &listing.exhibitor), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+fn trigger_next_occurrence_ix(
+    bank: &Bank,
+    listing: &Listing,
+    previous_escrow: &Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(recurring_auction(bank, &listing.exhibitor), false),
+        AccountMeta::new_readonly(*previous_escrow, false),
+    ];
+    accounts.extend(listing.exhibit_accounts(bank));
+    Instruction::new_with_bytes(bank.program_id, &[103], accounts)
+}
+
+#[test]
+fn recurring_occurrences_are_exhibited_one_interval_apart() {
+    let (mut bank, _) = setup();
+    let first = Listing::new(&mut bank);
+    let recurring = recurring_auction(&bank, &first.exhibitor);
+    let interval = 2 * AUCTION_DURATION_SEC;
+    bank.process(setup_recurring_ix(
+        &bank,
+        &first,
+        interval,
+        2,
+        AUCTION_DURATION_SEC,
+    ))
+    .unwrap();
+    bank.process(trigger_next_occurrence_ix(&bank, &first, &first.escrow))
+        .unwrap();
+    let auction = first.auction(&bank);
+    assert_eq!(auction.exhibitor_pubkey, first.exhibitor);
+    assert_eq!(auction.price, INITIAL_PRICE);
+    let series: RecurringAuction = bank.unpack(&recurring);
+    assert_eq!(series.triggered, 1);
+    assert_eq!(series.last_escrow, first.escrow);
+
+    let second = Listing::with_ft_mint(&mut bank, first.exhibitor, first.ft_mint);
+    assert_eq!(
+        bank.process(trigger_next_occurrence_ix(&bank, &second, &first.escrow)),
+        Err(AuctionError::AuctionNotStarted.into())
+    );
+    bank.warp_to_timestamp(series.next_occurrence_at);
+    assert_eq!(
+        bank.process(trigger_next_occurrence_ix(&bank, &second, &second.escrow)),
+        Err(ProgramError::InvalidAccountData)
+    );
+    bank.process(trigger_next_occurrence_ix(&bank, &second, &first.escrow))
+        .unwrap();
+    assert_eq!(
+        second.auction(&bank).end_at,
+        series.next_occurrence_at + AUCTION_DURATION_SEC as i64
+    );
+    // The series is closed after its last occurrence
+    assert!(bank.account(&recurring).is_none());
+}
+
+#[test]
+fn recurring_occurrences_never_overlap() {
+    let (mut bank, _) = setup();
+    let listing = Listing::new(&mut bank);
+    for (interval, occurrences) in [(AUCTION_DURATION_SEC - 1, 2), (AUCTION_DURATION_SEC, 0)] {
+        assert_eq!(
+            bank.process(setup_recurring_ix(
+                &bank,
+                &listing,
+                interval,
+                occurrences,
+                AUCTION_DURATION_SEC
+            )),
+            Err(AuctionError::InvalidInstruction.into())
+        );
+    }
+    bank.process(setup_recurring_ix(
+        &bank,
+        &listing,
+        AUCTION_DURATION_SEC,
+        2,
+        AUCTION_DURATION_SEC,
+    ))
+    .unwrap();
+    assert_eq!(
+        bank.process(setup_recurring_ix(
+            &bank,
+            &listing,
+            AUCTION_DURATION_SEC,
+            2,
+            AUCTION_DURATION_SEC
+        )),
+        Err(ProgramError::AccountAlreadyInitialized)
+    );
+}
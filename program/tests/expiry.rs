@@ -0,0 +1,96 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use auction_pal::event::ExpiryUpdatedEvent;
+use common::auction::{setup, Listing};
+use common::Bank;
+
+const MAX_GOVERNANCE_EXTENSION_SEC: i64 = 30 * 86_400;
+
+#[test]
+fn authority_extends_the_auction_up_to_the_cap() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let end_at = listing.auction(&bank).end_at;
+    let new_end_at = end_at + MAX_GOVERNANCE_EXTENSION_SEC;
+
+    bank.process(listing.set_auction_expiry_ix(&bank, &authority, new_end_at))
+        .unwrap();
+    assert_eq!(listing.auction(&bank).end_at, new_end_at);
+    assert_eq!(
+        Bank::events(),
+        vec![vec![
+            ExpiryUpdatedEvent::NAME.to_vec(),
+            listing.escrow.to_bytes().to_vec(),
+            end_at.to_le_bytes().to_vec(),
+            new_end_at.to_le_bytes().to_vec(),
+        ]]
+    );
+}
+
+#[test]
+fn only_the_authority_sets_the_expiry() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let end_at = listing.auction(&bank).end_at;
+
+    assert_eq!(
+        bank.process(listing.set_auction_expiry_ix(&bank, &listing.exhibitor, end_at + 60)),
+        Err(AuctionError::Unauthorized.into())
+    );
+    assert_eq!(listing.auction(&bank).end_at, end_at);
+}
+
+#[test]
+fn expiry_stays_in_the_future_and_under_the_cap() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let end_at = listing.auction(&bank).end_at;
+    let now = bank.clock().unix_timestamp;
+
+    for new_end_at in [now - 1, now, end_at + MAX_GOVERNANCE_EXTENSION_SEC + 1] {
+        assert_eq!(
+            bank.process(listing.set_auction_expiry_ix(&bank, &authority, new_end_at)),
+            Err(AuctionError::InvalidExpiry.into())
+        );
+    }
+    assert_eq!(listing.auction(&bank).end_at, end_at);
+}
+
+#[test]
+fn expiry_update_of_a_sealed_auction_keeps_the_end_time_sealed() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let end_at = listing.auction(&bank).end_at;
+    let reveal_at = end_at - 60;
+    bank.process(listing.set_sealed_end_time_ix(&bank, reveal_at))
+        .unwrap();
+
+    bank.process(listing.set_auction_expiry_ix(&bank, &authority, end_at + 600))
+        .unwrap();
+    assert_eq!(listing.auction(&bank).end_at, end_at + 600);
+    assert_eq!(
+        Bank::events(),
+        vec![vec![
+            ExpiryUpdatedEvent::NAME.to_vec(),
+            listing.escrow.to_bytes().to_vec(),
+            i64::MAX.to_le_bytes().to_vec(),
+            i64::MAX.to_le_bytes().to_vec(),
+        ]]
+    );
+
+    bank.warp_to_timestamp(reveal_at);
+    bank.process(listing.set_auction_expiry_ix(&bank, &authority, end_at + 1_200))
+        .unwrap();
+    assert_eq!(
+        Bank::events(),
+        vec![vec![
+            ExpiryUpdatedEvent::NAME.to_vec(),
+            listing.escrow.to_bytes().to_vec(),
+            (end_at + 600).to_le_bytes().to_vec(),
+            (end_at + 1_200).to_le_bytes().to_vec(),
+        ]]
+    );
+}
@@ -0,0 +1,197 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use auction_pal::state::{FeedbackRecord, UserProfile};
+use common::auction::{pda, setup, Bidder, Listing, AUCTION_DURATION_SEC, INITIAL_PRICE};
+use common::Bank;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::{system_program, sysvar};
+
+/// Feedback window of the program, from the time the tombstone was written
+const FEEDBACK_WINDOW_SEC: i64 = 30 * 86_400;
+
+fn user_profile(bank: &Bank, wallet: &Pubkey) -> Pubkey {
+    pda(&[b"user_profile", wallet.as_ref()], &bank.program_id)
+}
+
+fn feedback(bank: &Bank, listing: &Listing, reviewer: &Pubkey) -> Pubkey {
+    pda(
+        &[b"feedback", listing.escrow.as_ref(), reviewer.as_ref()],
+        &bank.program_id,
+    )
+}
+
+/// Wins the exhibited auction by a new bidder at 250 and closes it, writing its tombstone
+fn settle(bank: &mut Bank, listing: &Listing) -> Bidder {
+    let bidder = listing.bidder(bank);
+    listing.bid(bank, &bidder, 250).unwrap();
+    listing.expire(bank);
+    let winner_nft = listing.nft_account(bank, &bidder.wallet);
+    bank.process(listing.close_ix(bank, &winner_nft)).unwrap();
+    bidder
+}
+
+fn leave_feedback_ix(
+    bank: &Bank,
+    listing: &Listing,
+    reviewer: &Pubkey,
+    reviewed: &Pubkey,
+    score: u8,
+) -> Instruction {
+    let program_id = &bank.program_id;
+    let mut data = vec![56];
+    data.extend_from_slice(listing.escrow.as_ref());
+    data.push(score);
+    data.extend_from_slice(&[score; 32]);
+    Instruction::new_with_bytes(
+        *program_id,
+        &data,
+        vec![
+            AccountMeta::new(*reviewer, true),
+            AccountMeta::new_readonly(
+                pda(&[b"tombstone", listing.escrow.as_ref()], program_id),
+                false,
+            ),
+            AccountMeta::new(feedback(bank, listing, reviewer), false),
+            AccountMeta::new(user_profile(bank, reviewed), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+#[test]
+fn winner_and_exhibitor_review_each_other() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let winner = settle(&mut bank, &listing);
+
+    bank.process(leave_feedback_ix(
+        &bank,
+        &listing,
+        &winner.wallet,
+        &listing.exhibitor,
+        4,
+    ))
+    .unwrap();
+    bank.process(leave_feedback_ix(
+        &bank,
+        &listing,
+        &listing.exhibitor,
+        &winner.wallet,
+        2,
+    ))
+    .unwrap();
+
+    let record: FeedbackRecord = bank.unpack(&feedback(&bank, &listing, &winner.wallet));
+    assert_eq!(record.reviewer, winner.wallet);
+    assert_eq!(record.score, 4);
+    assert_eq!(record.comment_hash, [4; 32]);
+    let exhibitor_profile: UserProfile = bank.unpack(&user_profile(&bank, &listing.exhibitor));
+    assert_eq!(exhibitor_profile.average_score, 400);
+    assert_eq!(exhibitor_profile.review_count, 1);
+    let winner_profile: UserProfile = bank.unpack(&user_profile(&bank, &winner.wallet));
+    assert_eq!(winner_profile.average_score, 200);
+    assert_eq!(winner_profile.review_count, 1);
+}
+
+#[test]
+fn scores_are_averaged_across_auctions() {
+    let (mut bank, _) = setup();
+    let first = Listing::exhibit(&mut bank);
+    let first_winner = settle(&mut bank, &first);
+    let second = Listing::with_ft_mint(&mut bank, first.exhibitor, first.ft_mint);
+    bank.process(second.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC))
+        .unwrap();
+    second.accept_terms(&mut bank);
+    let second_winner = settle(&mut bank, &second);
+
+    bank.process(leave_feedback_ix(
+        &bank,
+        &first,
+        &first_winner.wallet,
+        &first.exhibitor,
+        4,
+    ))
+    .unwrap();
+    bank.process(leave_feedback_ix(
+        &bank,
+        &second,
+        &second_winner.wallet,
+        &second.exhibitor,
+        5,
+    ))
+    .unwrap();
+
+    let profile: UserProfile = bank.unpack(&user_profile(&bank, &first.exhibitor));
+    assert_eq!(profile.average_score, 450);
+    assert_eq!(profile.review_count, 2);
+}
+
+#[test]
+fn feedback_is_left_once_by_the_parties_within_the_window() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let winner = settle(&mut bank, &listing);
+    let outsider = bank.create_wallet();
+
+    assert_eq!(
+        bank.process(leave_feedback_ix(
+            &bank,
+            &listing,
+            &outsider,
+            &listing.exhibitor,
+            5
+        )),
+        Err(AuctionError::Unauthorized.into())
+    );
+    for score in [0, 6] {
+        assert_eq!(
+            bank.process(leave_feedback_ix(
+                &bank,
+                &listing,
+                &winner.wallet,
+                &listing.exhibitor,
+                score
+            )),
+            Err(AuctionError::InvalidScore.into())
+        );
+    }
+
+    bank.process(leave_feedback_ix(
+        &bank,
+        &listing,
+        &winner.wallet,
+        &listing.exhibitor,
+        5,
+    ))
+    .unwrap();
+    assert_eq!(
+        bank.process(leave_feedback_ix(
+            &bank,
+            &listing,
+            &winner.wallet,
+            &listing.exhibitor,
+            1
+        )),
+        Err(ProgramError::AccountAlreadyInitialized)
+    );
+
+    let closed_at = bank.clock().unix_timestamp;
+    bank.warp_to_timestamp(closed_at + FEEDBACK_WINDOW_SEC + 1);
+    assert_eq!(
+        bank.process(leave_feedback_ix(
+            &bank,
+            &listing,
+            &listing.exhibitor,
+            &winner.wallet,
+            3
+        )),
+        Err(AuctionError::FeedbackWindowClosed.into())
+    );
+}
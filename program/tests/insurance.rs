@@ -0,0 +1,318 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use auction_pal::state::{InsuranceClaimRecord, TombstoneRecord};
+use common::auction::{
+    deduct_royalty_before_escrow_ix, pda, setup, Bidder, Listing, BIDDER_BALANCE,
+};
+use common::Bank;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::system_program;
+
+const INSURANCE_FEE_BPS: u16 = 100;
+
+fn insurance_vault(bank: &Bank, listing: &Listing) -> Pubkey {
+    pda(
+        &[b"insurance_vault", listing.ft_mint.as_ref()],
+        &bank.program_id,
+    )
+}
+
+fn set_insurance_fee_ix(
+    bank: &Bank,
+    authority: &Pubkey,
+    fee_bps: u16,
+    reserve: u64,
+) -> Instruction {
+    let mut data = vec![12];
+    data.extend_from_slice(&fee_bps.to_le_bytes());
+    data.extend_from_slice(&reserve.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(pda(&[b"global_config"], &bank.program_id), false),
+        ],
+    )
+}
+
+/// `Bid` paying the insurance fee, with the insurance vault and FT mint trailing the bid accounts
+fn insured_bid(bank: &mut Bank, listing: &Listing, bidder: &Bidder, price: u64) {
+    let mut bid_ix = listing.bid_ix(bank, bidder, price);
+    bid_ix.accounts.extend([
+        AccountMeta::new(insurance_vault(bank, listing), false),
+        AccountMeta::new_readonly(listing.ft_mint, false),
+    ]);
+    bank.process(bid_ix).unwrap();
+}
+
+fn claim_insurance_ix(
+    bank: &Bank,
+    listing: &Listing,
+    winner: &Bidder,
+    authority: &Pubkey,
+) -> Instruction {
+    let program_id = &bank.program_id;
+    let tombstone = pda(&[b"tombstone", listing.escrow.as_ref()], program_id);
+    Instruction::new_with_bytes(
+        *program_id,
+        &[13],
+        vec![
+            AccountMeta::new(winner.wallet, true),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(pda(&[b"global_config"], program_id), false),
+            AccountMeta::new_readonly(tombstone, false),
+            AccountMeta::new(
+                pda(&[b"insurance_claim", tombstone.as_ref()], program_id),
+                false,
+            ),
+            AccountMeta::new(insurance_vault(bank, listing), false),
+            AccountMeta::new(winner.ft_account, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+fn withdraw_insurance_ix(
+    bank: &Bank,
+    listing: &Listing,
+    authority: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let mut data = vec![14];
+    data.extend_from_slice(&amount.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(pda(&[b"global_config"], &bank.program_id), false),
+            AccountMeta::new(insurance_vault(bank, listing), false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+    )
+}
+
+/// Tops the insurance vault up as the fees of other auctions would
+fn fund_vault(bank: &mut Bank, listing: &Listing, amount: u64) {
+    let donor = listing.bidder(bank);
+    let vault = insurance_vault(bank, listing);
+    bank.process(
+        spl_token::instruction::transfer(
+            &spl_token::id(),
+            &donor.ft_account,
+            &vault,
+            &donor.wallet,
+            &[],
+            amount,
+        )
+        .unwrap(),
+    )
+    .unwrap();
+}
+
+/// Closes the auction won by `winner`, leaving its tombstone record
+fn settle(bank: &mut Bank, listing: &Listing, winner: &Bidder) {
+    listing.expire(bank);
+    let winner_nft = listing.nft_account(bank, &winner.wallet);
+    bank.process(listing.close_ix(bank, &winner_nft)).unwrap();
+}
+
+#[test]
+fn insurance_fee_accumulates_in_the_vault() {
+    let (mut bank, authority) = setup();
+    bank.process(set_insurance_fee_ix(
+        &bank,
+        &authority,
+        INSURANCE_FEE_BPS,
+        0,
+    ))
+    .unwrap();
+    let listing = Listing::exhibit(&mut bank);
+
+    let first = listing.bidder(&mut bank);
+    insured_bid(&mut bank, &listing, &first, 1_000);
+    let vault = insurance_vault(&bank, &listing);
+    assert_eq!(bank.token_balance(&vault), 10);
+    assert_eq!(
+        bank.token_balance(&listing.auction(&bank).highest_bidder_ft_temp_pubkey),
+        990
+    );
+
+    let second = listing.bidder(&mut bank);
+    insured_bid(&mut bank, &listing, &second, 2_000);
+    assert_eq!(bank.token_balance(&vault), 30);
+    assert_eq!(bank.token_balance(&first.ft_account), BIDDER_BALANCE - 10);
+    assert_eq!(bank.token_account(&vault).owner, vault);
+}
+
+#[test]
+fn winner_claims_insurance_once() {
+    let (mut bank, authority) = setup();
+    bank.process(set_insurance_fee_ix(
+        &bank,
+        &authority,
+        INSURANCE_FEE_BPS,
+        0,
+    ))
+    .unwrap();
+    let listing = Listing::exhibit(&mut bank);
+    let winner = listing.bidder(&mut bank);
+    insured_bid(&mut bank, &listing, &winner, 1_000);
+    fund_vault(&mut bank, &listing, 5_000);
+    settle(&mut bank, &listing, &winner);
+    let balance = bank.token_balance(&winner.ft_account);
+
+    let intruder = bank.create_wallet();
+    assert_eq!(
+        bank.process(claim_insurance_ix(&bank, &listing, &winner, &intruder)),
+        Err(AuctionError::Unauthorized.into())
+    );
+    bank.process(claim_insurance_ix(&bank, &listing, &winner, &authority))
+        .unwrap();
+    assert_eq!(bank.token_balance(&winner.ft_account), balance + 1_000);
+    assert_eq!(bank.token_balance(&insurance_vault(&bank, &listing)), 4_010);
+    let tombstone = pda(&[b"tombstone", listing.escrow.as_ref()], &bank.program_id);
+    let claim: InsuranceClaimRecord = bank.unpack(&pda(
+        &[b"insurance_claim", tombstone.as_ref()],
+        &bank.program_id,
+    ));
+    assert_eq!(claim.amount, 1_000);
+    let record: TombstoneRecord = bank.unpack(&tombstone);
+    assert_eq!(record.winner, winner.wallet);
+
+    assert_eq!(
+        bank.process(claim_insurance_ix(&bank, &listing, &winner, &authority)),
+        Err(AuctionError::InsuranceAlreadyClaimed.into())
+    );
+}
+
+#[test]
+fn authority_withdraws_only_the_surplus() {
+    let (mut bank, authority) = setup();
+    bank.process(set_insurance_fee_ix(
+        &bank,
+        &authority,
+        INSURANCE_FEE_BPS,
+        500,
+    ))
+    .unwrap();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    insured_bid(&mut bank, &listing, &bidder, 1_000);
+    fund_vault(&mut bank, &listing, 990);
+    let treasury = bank.create_token_account(&listing.ft_mint, &authority, 0);
+
+    assert_eq!(
+        bank.process(withdraw_insurance_ix(
+            &bank, &listing, &authority, &treasury, 501
+        )),
+        Err(ProgramError::InsufficientFunds)
+    );
+    bank.process(withdraw_insurance_ix(
+        &bank, &listing, &authority, &treasury, 500,
+    ))
+    .unwrap();
+    assert_eq!(bank.token_balance(&treasury), 500);
+    assert_eq!(bank.token_balance(&insurance_vault(&bank, &listing)), 500);
+}
+
+/// Insured `Bid` also paying the upfront royalty into `royalty_vault`
+fn insured_royalty_bid_ix(
+    bank: &mut Bank,
+    listing: &Listing,
+    bidder: &Bidder,
+    price: u64,
+    royalty_vault: &Pubkey,
+) -> Instruction {
+    let mut bid_ix = listing.bid_ix(bank, bidder, price);
+    bid_ix.accounts.extend([
+        AccountMeta::new(insurance_vault(bank, listing), false),
+        AccountMeta::new_readonly(listing.ft_mint, false),
+        AccountMeta::new(*royalty_vault, false),
+    ]);
+    bid_ix
+}
+
+#[test]
+fn deductions_leave_the_exhibitor_five_percent() {
+    let (mut bank, authority) = setup();
+    bank.process(set_insurance_fee_ix(&bank, &authority, 9_000, 0))
+        .unwrap();
+    let listing = Listing::exhibit(&mut bank);
+    let creator = bank.create_wallet();
+    assert_eq!(
+        bank.process(deduct_royalty_before_escrow_ix(
+            &bank, &listing, &creator, 9_501
+        )),
+        Err(AuctionError::InvalidFeeBasisPoints.into())
+    );
+    bank.process(deduct_royalty_before_escrow_ix(
+        &bank, &listing, &creator, 600,
+    ))
+    .unwrap();
+    let royalty_vault = listing.auction(&bank).royalty_creator_ft_pubkey;
+
+    // 90% insurance and 6% royalty leave less than 5%
+    let bidder = listing.bidder(&mut bank);
+    let bid_ix = insured_royalty_bid_ix(&mut bank, &listing, &bidder, 1_000, &royalty_vault);
+    assert_eq!(
+        bank.process(bid_ix),
+        Err(AuctionError::InvalidFeeBasisPoints.into())
+    );
+
+    bank.process(deduct_royalty_before_escrow_ix(
+        &bank, &listing, &creator, 500,
+    ))
+    .unwrap();
+    let bid_ix = insured_royalty_bid_ix(&mut bank, &listing, &bidder, 1_000, &royalty_vault);
+    bank.process(bid_ix).unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(bank.token_balance(&insurance_vault(&bank, &listing)), 900);
+    assert_eq!(bank.token_balance(&royalty_vault), 50);
+    assert_eq!(auction.bid_escrow_amount, 50);
+    assert_eq!(
+        bank.token_balance(&auction.highest_bidder_ft_temp_pubkey),
+        50
+    );
+
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    listing.expire(&mut bank);
+    bank.process(listing.close_ix(&bank, &winner_nft)).unwrap();
+    assert_eq!(bank.token_balance(&listing.ft_receiving), 50);
+    assert_eq!(
+        bank.token_balance(&bidder.ft_account),
+        BIDDER_BALANCE - 1_000
+    );
+}
+
+#[test]
+fn insurance_fee_alone_is_capped_at_bid_time() {
+    let (mut bank, authority) = setup();
+    bank.process(set_insurance_fee_ix(&bank, &authority, 10_000, 0))
+        .unwrap();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    let mut bid_ix = listing.bid_ix(&mut bank, &bidder, 1_000);
+    bid_ix.accounts.extend([
+        AccountMeta::new(insurance_vault(&bank, &listing), false),
+        AccountMeta::new_readonly(listing.ft_mint, false),
+    ]);
+
+    assert_eq!(
+        bank.process(bid_ix),
+        Err(AuctionError::InvalidFeeBasisPoints.into())
+    );
+    assert_eq!(
+        bank.process(set_insurance_fee_ix(&bank, &authority, 10_001, 0)),
+        Err(AuctionError::InvalidFeeBasisPoints.into())
+    );
+}
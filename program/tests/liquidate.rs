@@ -0,0 +1,108 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use common::auction::{pda, setup, Bidder, Listing, BIDDER_BALANCE};
+use common::Bank;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::pubkey::Pubkey;
+
+fn liquidate_bid_ix(bank: &Bank, listing: &Listing, bidder: &Bidder) -> Instruction {
+    let program_id = &bank.program_id;
+    let auction = listing.auction(bank);
+    Instruction::new_with_bytes(
+        *program_id,
+        &[28],
+        vec![
+            AccountMeta::new_readonly(Pubkey::new_unique(), true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new(bidder.wallet, false),
+            AccountMeta::new(auction.highest_bidder_ft_temp_pubkey, false),
+            AccountMeta::new(bidder.ft_account, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(pda(&[b"escrow"], program_id), false),
+        ],
+    )
+}
+
+/// Leaves `escrowed` of the highest bid in its temporary FT account
+fn undercollateralize(bank: &mut Bank, listing: &Listing, escrowed: u64) {
+    let temp = listing.auction(bank).highest_bidder_ft_temp_pubkey;
+    let escrow_pda = pda(&[b"escrow"], &bank.program_id);
+    bank.set_token_account(temp, &listing.ft_mint, &escrow_pda, escrowed);
+}
+
+#[test]
+fn undercollateralized_bid_is_voided_and_refunded() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let temp = listing.auction(&bank).highest_bidder_ft_temp_pubkey;
+    let temp_rent = bank.lamports(&temp);
+    let wallet_lamports = bank.lamports(&bidder.wallet);
+    undercollateralize(&mut bank, &listing, 60);
+
+    bank.process(liquidate_bid_ix(&bank, &listing, &bidder))
+        .unwrap();
+    assert_eq!(
+        bank.token_balance(&bidder.ft_account),
+        BIDDER_BALANCE - 250 + 60
+    );
+    assert!(bank.account(&temp).is_none());
+    assert_eq!(bank.lamports(&bidder.wallet), wallet_lamports + temp_rent);
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.highest_bidder_pubkey, Pubkey::default());
+    assert_eq!(auction.highest_bidder_ft_temp_pubkey, Pubkey::default());
+    assert_eq!(auction.bid_escrow_amount, 0);
+    assert!(!auction.bid_locked);
+    assert_eq!(auction.price, 0);
+}
+
+#[test]
+fn fully_escrowed_bid_cannot_be_liquidated() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+
+    assert_eq!(
+        bank.process(liquidate_bid_ix(&bank, &listing, &bidder)),
+        Err(AuctionError::ExpectedAmountMismatch.into())
+    );
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.highest_bidder_pubkey, bidder.wallet);
+    assert_eq!(auction.price, 250);
+    assert_eq!(
+        bank.token_balance(&auction.highest_bidder_ft_temp_pubkey),
+        250
+    );
+}
+
+#[test]
+fn bidding_resumes_from_the_floor_price_after_liquidation() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(listing.set_floor_price_ix(&bank, 200))
+        .unwrap();
+    let voided = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &voided, 400).unwrap();
+    undercollateralize(&mut bank, &listing, 100);
+    bank.process(liquidate_bid_ix(&bank, &listing, &voided))
+        .unwrap();
+    assert_eq!(listing.auction(&bank).price, 200);
+
+    let bidder = listing.bidder(&mut bank);
+    assert_eq!(
+        listing.bid(&mut bank, &bidder, 150),
+        Err(AuctionError::BelowFloorPrice.into())
+    );
+    listing.bid(&mut bank, &bidder, 300).unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.highest_bidder_pubkey, bidder.wallet);
+    assert_eq!(auction.price, 300);
+    assert_eq!(auction.bid_escrow_amount, 300);
+    assert_eq!(bank.token_balance(&bidder.ft_account), BIDDER_BALANCE - 300);
+    assert_eq!(bank.token_balance(&voided.ft_account), BIDDER_BALANCE - 300);
+}
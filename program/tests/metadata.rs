@@ -0,0 +1,202 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use auction_pal::metadata::{
+    find_metadata_address, TOKEN_METADATA_PROGRAM_ID, UPDATE_METADATA_ACCOUNT_V2_TAG,
+};
+use common::auction::{setup, Listing};
+use common::{Account, Bank, WALLET_LAMPORTS};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+/// Size of the metadata accounts of the mock, large enough for any URI of the tests
+const METADATA_LEN: usize = 400;
+
+/// Offset of the name in a metadata account, after the account key, the update authority and the mint
+const METADATA_DATA_OFFSET: usize = 65;
+
+/// Token Metadata program writing the `DataV2` of `UpdateMetadataAccountV2` in place, signed by
+/// the update authority stored in the metadata account
+fn token_metadata_program(_: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [metadata, update_authority] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if data.len() < 5 || data[..2] != [UPDATE_METADATA_ACCOUNT_V2_TAG, 1] {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let mut metadata_data = metadata.try_borrow_mut_data()?;
+    if !update_authority.is_signer || metadata_data[1..33] != update_authority.key.to_bytes() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let data_v2 = &data[2..data.len() - 3];
+    metadata_data[METADATA_DATA_OFFSET..].fill(0);
+    metadata_data[METADATA_DATA_OFFSET..METADATA_DATA_OFFSET + data_v2.len()]
+        .copy_from_slice(data_v2);
+    Ok(())
+}
+
+/// Metadata account of the NFT of the listing, without creators, collection or uses
+fn create_metadata(bank: &mut Bank, listing: &Listing, update_authority: &Pubkey, uri: &[u8]) {
+    bank.add_program(TOKEN_METADATA_PROGRAM_ID, token_metadata_program);
+    let mut data = vec![4];
+    data.extend_from_slice(update_authority.as_ref());
+    data.extend_from_slice(listing.nft_mint.as_ref());
+    for field in [&b"Evolving"[..], b"EVO", uri] {
+        data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        data.extend_from_slice(field);
+    }
+    // Seller fee, no creators, primary sale, mutable, no edition nonce, token standard, collection or uses
+    data.extend_from_slice(&[0, 0, 0, 0, 1, 0, 0, 0, 0]);
+    data.resize(METADATA_LEN, 0);
+    bank.set_account(
+        find_metadata_address(&listing.nft_mint),
+        Account {
+            lamports: WALLET_LAMPORTS,
+            data,
+            owner: TOKEN_METADATA_PROGRAM_ID,
+            executable: false,
+        },
+    );
+}
+
+/// URI stored in the metadata account of the NFT of the listing
+fn metadata_uri(bank: &Bank, listing: &Listing) -> Vec<u8> {
+    let data = &bank
+        .account(&find_metadata_address(&listing.nft_mint))
+        .expect("metadata exists")
+        .data;
+    let mut offset = METADATA_DATA_OFFSET;
+    let mut field = || {
+        let len = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+        offset += 4 + len;
+        data[offset - len..offset].to_vec()
+    };
+    field();
+    field();
+    field()
+}
+
+fn set_nft_update_authority_ix(
+    bank: &Bank,
+    listing: &Listing,
+    nft_update_authority: &Pubkey,
+    allow_updates_during_auction: bool,
+) -> Instruction {
+    let mut data = vec![88];
+    data.extend_from_slice(nft_update_authority.as_ref());
+    data.push(allow_updates_during_auction as u8);
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+fn update_nft_during_auction_ix(
+    bank: &Bank,
+    listing: &Listing,
+    nft_update_authority: &Pubkey,
+    new_uri: &[u8],
+) -> Instruction {
+    let mut data = vec![89];
+    data.extend_from_slice(new_uri);
+    data.resize(1 + 64, 0);
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*nft_update_authority, true),
+            AccountMeta::new_readonly(listing.escrow, false),
+            AccountMeta::new_readonly(listing.nft_temp, false),
+            AccountMeta::new(find_metadata_address(&listing.nft_mint), false),
+            AccountMeta::new_readonly(TOKEN_METADATA_PROGRAM_ID, false),
+        ],
+    )
+}
+
+#[test]
+fn update_authority_evolves_the_nft_in_escrow() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let artist = bank.create_wallet();
+    create_metadata(
+        &mut bank,
+        &listing,
+        &artist,
+        b"https://nft.example/egg.json",
+    );
+    bank.process(set_nft_update_authority_ix(&bank, &listing, &artist, true))
+        .unwrap();
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+
+    bank.process(update_nft_during_auction_ix(
+        &bank,
+        &listing,
+        &artist,
+        b"https://nft.example/dragon.json",
+    ))
+    .unwrap();
+    assert_eq!(
+        metadata_uri(&bank, &listing),
+        b"https://nft.example/dragon.json"
+    );
+}
+
+#[test]
+fn nft_updates_need_the_exhibitor_opt_in_and_the_update_authority() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let artist = bank.create_wallet();
+    create_metadata(
+        &mut bank,
+        &listing,
+        &artist,
+        b"https://nft.example/egg.json",
+    );
+    let new_uri = b"https://nft.example/dragon.json";
+
+    bank.process(set_nft_update_authority_ix(&bank, &listing, &artist, false))
+        .unwrap();
+    assert_eq!(
+        bank.process(update_nft_during_auction_ix(
+            &bank, &listing, &artist, new_uri
+        )),
+        Err(AuctionError::NFTUpdatesDisabled.into())
+    );
+
+    bank.process(set_nft_update_authority_ix(&bank, &listing, &artist, true))
+        .unwrap();
+    let intruder = bank.create_wallet();
+    assert_eq!(
+        bank.process(update_nft_during_auction_ix(
+            &bank, &listing, &intruder, new_uri
+        )),
+        Err(AuctionError::Unauthorized.into())
+    );
+    assert_eq!(
+        bank.process(update_nft_during_auction_ix(&bank, &listing, &artist, b"")),
+        Err(AuctionError::InvalidInstruction.into())
+    );
+    assert_eq!(
+        metadata_uri(&bank, &listing),
+        b"https://nft.example/egg.json"
+    );
+
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    assert_eq!(
+        bank.process(set_nft_update_authority_ix(
+            &bank, &listing, &intruder, true
+        )),
+        Err(AuctionError::AlreadyBid.into())
+    );
+}
@@ -0,0 +1,207 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use auction_pal::state::{
+    Auction, AuctionCategory, AUCTION_V1_LEN, AUCTION_VERSION, DEFAULT_CLOSE_GRACE_PERIOD_SEC,
+    DEFAULT_MAX_BIDDER_EXTENSION_SEC,
+};
+use common::auction::{pda, setup, Listing};
+use common::{Account, Bank};
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::{system_program, sysvar};
+
+/// Offset of the version byte in the current `Auction` layout
+const VERSION_OFFSET: usize = 419;
+
+fn migrate_state_ix(
+    bank: &Bank,
+    listing: &Listing,
+    signer: &Pubkey,
+    target_version: u8,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[42, target_version],
+        vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(pda(&[b"global_config"], &bank.program_id), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// `MigrateState` of an account of the first release, followed by the exhibitor's FT account
+fn migrate_v1_ix(bank: &Bank, listing: &Listing, signer: &Pubkey) -> Instruction {
+    let mut migrate_ix = migrate_state_ix(bank, listing, signer, AUCTION_VERSION);
+    migrate_ix
+        .accounts
+        .push(AccountMeta::new_readonly(listing.ft_receiving, false));
+    migrate_ix
+}
+
+/// Rewrites the exhibited escrow as written by an older program, keeping the first `len` bytes
+/// and recording `version` in the layouts that have a version byte
+fn downgrade(bank: &mut Bank, listing: &Listing, len: usize, version: u8) {
+    let mut data = bank.account(&listing.escrow).unwrap().data.clone();
+    data.truncate(len);
+    if len > VERSION_OFFSET {
+        data[VERSION_OFFSET] = version;
+    }
+    let program_id = bank.program_id;
+    bank.set_account(
+        listing.escrow,
+        Account {
+            lamports: Rent::default().minimum_balance(len),
+            data,
+            owner: program_id,
+            executable: false,
+        },
+    );
+}
+
+#[test]
+fn first_release_account_migrates_to_the_current_layout() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    downgrade(&mut bank, &listing, AUCTION_V1_LEN, 1);
+    assert_eq!(
+        bank.process(listing.set_floor_price_ix(&bank, 200)),
+        Err(ProgramError::InvalidAccountData)
+    );
+
+    let exhibitor_lamports = bank.lamports(&listing.exhibitor);
+
+    bank.process(migrate_v1_ix(&bank, &listing, &listing.exhibitor))
+        .unwrap();
+    let escrow = bank.account(&listing.escrow).unwrap();
+    assert_eq!(escrow.data.len(), Auction::LEN);
+    assert_eq!(
+        escrow.lamports,
+        Rent::default().minimum_balance(Auction::LEN)
+    );
+    let rent_top_up = Rent::default().minimum_balance(Auction::LEN)
+        - Rent::default().minimum_balance(AUCTION_V1_LEN);
+    assert_eq!(
+        bank.lamports(&listing.exhibitor),
+        exhibitor_lamports - rent_top_up
+    );
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.version, AUCTION_VERSION);
+    assert_eq!(auction.exhibitor_pubkey, listing.exhibitor);
+    assert_eq!(auction.ft_mint, listing.ft_mint);
+    assert_eq!(
+        auction.pda_bump,
+        Pubkey::find_program_address(&[b"escrow"], &bank.program_id).1
+    );
+    assert_eq!(auction.category, AuctionCategory::Other);
+    assert_eq!(
+        auction.max_bidder_extension_sec,
+        DEFAULT_MAX_BIDDER_EXTENSION_SEC
+    );
+    assert_eq!(
+        auction.close_grace_period_sec,
+        DEFAULT_CLOSE_GRACE_PERIOD_SEC
+    );
+    assert!(auction.terms_accepted);
+    assert!(auction.bids_visible);
+    assert_eq!(auction.lot_size, 1);
+
+    bank.process(listing.set_floor_price_ix(&bank, 200))
+        .unwrap();
+    let bidder = listing.bidder(&mut bank);
+    assert_eq!(
+        listing.bid(&mut bank, &bidder, 150),
+        Err(AuctionError::BelowFloorPrice.into())
+    );
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    assert_eq!(listing.auction(&bank).highest_bidder_pubkey, bidder.wallet);
+}
+
+#[test]
+fn accounts_of_the_unreleased_layouts_are_not_migrated() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let data = bank.account(&listing.escrow).unwrap().data.clone();
+
+    for (len, version) in [(VERSION_OFFSET + 1, 2), (1_728, 2), (1_730, 3), (1_733, 4)] {
+        downgrade(&mut bank, &listing, len, version);
+        assert_eq!(
+            bank.process(migrate_v1_ix(&bank, &listing, &listing.exhibitor)),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(bank.account(&listing.escrow).unwrap().data.len(), len);
+        let program_id = bank.program_id;
+        bank.set_account(
+            listing.escrow,
+            Account {
+                lamports: Rent::default().minimum_balance(Auction::LEN),
+                data: data.clone(),
+                owner: program_id,
+                executable: false,
+            },
+        );
+    }
+}
+
+#[test]
+fn migration_checks_the_versions_and_the_signer() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    assert_eq!(
+        bank.process(migrate_state_ix(
+            &bank,
+            &listing,
+            &listing.exhibitor,
+            AUCTION_VERSION
+        )),
+        Err(ProgramError::AccountAlreadyInitialized)
+    );
+
+    downgrade(&mut bank, &listing, AUCTION_V1_LEN, 1);
+    assert_eq!(
+        bank.process(migrate_state_ix(
+            &bank,
+            &listing,
+            &listing.exhibitor,
+            AUCTION_VERSION + 1
+        )),
+        Err(AuctionError::UnsupportedVersion.into())
+    );
+    assert_eq!(
+        bank.process(migrate_state_ix(
+            &bank,
+            &listing,
+            &listing.exhibitor,
+            AUCTION_VERSION
+        )),
+        Err(ProgramError::NotEnoughAccountKeys)
+    );
+    let intruder = bank.create_wallet();
+    assert_eq!(
+        bank.process(migrate_v1_ix(&bank, &listing, &intruder)),
+        Err(AuctionError::Unauthorized.into())
+    );
+    assert_eq!(
+        bank.account(&listing.escrow).unwrap().data.len(),
+        AUCTION_V1_LEN
+    );
+}
+
+#[test]
+fn global_config_authority_migrates_any_auction() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    downgrade(&mut bank, &listing, AUCTION_V1_LEN, 1);
+
+    bank.process(migrate_v1_ix(&bank, &listing, &authority))
+        .unwrap();
+    assert_eq!(listing.auction(&bank).version, AUCTION_VERSION);
+}
@@ -0,0 +1,494 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use auction_pal::state::PriceOracle;
+use auction_pal::state::{MAX_ACCEPTED_MINTS, ROOM_DESCRIPTION_LEN};
+use common::auction::{
+    deduct_royalty_before_escrow_ix, pda, royalty_vault, setup, Bidder, Listing,
+    AUCTION_DURATION_SEC, BIDDER_BALANCE, INITIAL_PRICE,
+};
+use common::Bank;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::{system_program, sysvar};
+
+fn set_oracle_price_ix(
+    bank: &Bank,
+    authority: &Pubkey,
+    ft_mint: &Pubkey,
+    price: u64,
+) -> Instruction {
+    let mut data = vec![43];
+    data.extend_from_slice(&price.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(pda(&[b"global_config"], &bank.program_id), false),
+            AccountMeta::new(
+                pda(&[b"price_oracle", ft_mint.as_ref()], &bank.program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(*ft_mint, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+fn set_trustless_oracle_ix(
+    bank: &Bank,
+    listing: &Listing,
+    oracle: &Pubkey,
+    floor_bps: u16,
+) -> Instruction {
+    let mut data = vec![44];
+    data.extend_from_slice(&floor_bps.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(*oracle, false),
+        ],
+    )
+}
+
+fn set_accepted_currencies_ix(
+    bank: &Bank,
+    listing: &Listing,
+    accepted: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let mut accepted_mints = [Pubkey::default(); MAX_ACCEPTED_MINTS];
+    for (accepted_mint, (mint, _)) in accepted_mints.iter_mut().zip(accepted) {
+        *accepted_mint = *mint;
+    }
+    let mut data = vec![72];
+    for accepted_mint in &accepted_mints {
+        data.extend_from_slice(accepted_mint.as_ref());
+    }
+    let mut accounts = vec![
+        AccountMeta::new(listing.exhibitor, true),
+        AccountMeta::new(listing.escrow, false),
+    ];
+    accounts.extend(
+        accepted
+            .iter()
+            .map(|(_, ft_receiving)| AccountMeta::new_readonly(*ft_receiving, false)),
+    );
+    Instruction::new_with_bytes(bank.program_id, &data, accounts)
+}
+
+/// `BidInCurrency` at `price`, in the mint of the FT account of the bidder
+fn bid_in_currency_ix(
+    bank: &mut Bank,
+    listing: &Listing,
+    bidder: &Bidder,
+    price: u64,
+) -> Instruction {
+    let bid_mint = bank.token_account(&bidder.ft_account).mint;
+    let bid_ix = listing.bid_ix(bank, bidder, price);
+    let mut data = vec![73];
+    data.extend_from_slice(&price.to_le_bytes());
+    data.extend_from_slice(bid_mint.as_ref());
+    let mut accounts = vec![
+        AccountMeta::new_readonly(
+            pda(
+                &[b"price_oracle", listing.ft_mint.as_ref()],
+                &bank.program_id,
+            ),
+            false,
+        ),
+        AccountMeta::new_readonly(
+            pda(&[b"price_oracle", bid_mint.as_ref()], &bank.program_id),
+            false,
+        ),
+    ];
+    accounts.extend(bid_ix.accounts);
+    Instruction::new_with_bytes(bank.program_id, &data, accounts)
+}
+
+/// Oracle account of the FT mint of the listing, packed directly instead of going through the authority
+fn mock_oracle(bank: &mut Bank, listing: &Listing, owner: Pubkey, price: u64) -> Pubkey {
+    let oracle = pda(
+        &[b"price_oracle", listing.ft_mint.as_ref()],
+        &bank.program_id,
+    );
+    let updated_at = bank.clock().unix_timestamp;
+    bank.pack(
+        oracle,
+        owner,
+        PriceOracle {
+            is_initialized: true,
+            price,
+            updated_at,
+        },
+    );
+    oracle
+}
+
+#[test]
+fn authority_sets_the_oracle_price() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let oracle = pda(
+        &[b"price_oracle", listing.ft_mint.as_ref()],
+        &bank.program_id,
+    );
+
+    bank.process(set_oracle_price_ix(
+        &bank,
+        &authority,
+        &listing.ft_mint,
+        200,
+    ))
+    .unwrap();
+    let price_oracle = bank.unpack::<PriceOracle>(&oracle);
+    assert_eq!(price_oracle.price, 200);
+    assert_eq!(price_oracle.updated_at, bank.clock().unix_timestamp);
+
+    bank.warp_to_timestamp(bank.clock().unix_timestamp + 60);
+    bank.process(set_oracle_price_ix(
+        &bank,
+        &authority,
+        &listing.ft_mint,
+        150,
+    ))
+    .unwrap();
+    let price_oracle = bank.unpack::<PriceOracle>(&oracle);
+    assert_eq!(price_oracle.price, 150);
+    assert_eq!(price_oracle.updated_at, bank.clock().unix_timestamp);
+
+    bank.process(set_trustless_oracle_ix(&bank, &listing, &oracle, 5_000))
+        .unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.oracle_pubkey, oracle);
+    assert_eq!(auction.oracle_min_price, 75);
+}
+
+#[test]
+fn only_the_authority_sets_the_oracle_price() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+
+    assert_eq!(
+        bank.process(set_oracle_price_ix(
+            &bank,
+            &listing.exhibitor,
+            &listing.ft_mint,
+            200
+        )),
+        Err(AuctionError::Unauthorized.into())
+    );
+    assert!(bank
+        .account(&pda(
+            &[b"price_oracle", listing.ft_mint.as_ref()],
+            &bank.program_id
+        ))
+        .is_none());
+}
+
+#[test]
+fn price_reaches_the_share_of_a_mock_oracle() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let program_id = bank.program_id;
+    let oracle = mock_oracle(&mut bank, &listing, program_id, 2 * INITIAL_PRICE);
+
+    // 60% of 200 is above the initial price of 100
+    assert_eq!(
+        bank.process(set_trustless_oracle_ix(&bank, &listing, &oracle, 6_000)),
+        Err(AuctionError::BelowFloorPrice.into())
+    );
+    assert_eq!(listing.auction(&bank).oracle_pubkey, Pubkey::default());
+
+    bank.process(set_trustless_oracle_ix(&bank, &listing, &oracle, 5_000))
+        .unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.oracle_pubkey, oracle);
+    assert_eq!(auction.oracle_min_price, INITIAL_PRICE);
+}
+
+#[test]
+fn trustless_oracle_rejects_foreign_oracles_and_bid_auctions() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let program_id = bank.program_id;
+    let foreign = Pubkey::new_unique();
+    let oracle = mock_oracle(&mut bank, &listing, foreign, INITIAL_PRICE);
+    assert_eq!(
+        bank.process(set_trustless_oracle_ix(&bank, &listing, &oracle, 5_000)),
+        Err(ProgramError::IncorrectProgramId)
+    );
+
+    let other_mint = bank.create_mint(&listing.exhibitor);
+    let other_oracle = pda(&[b"price_oracle", other_mint.as_ref()], &bank.program_id);
+    bank.pack(
+        other_oracle,
+        program_id,
+        PriceOracle {
+            is_initialized: true,
+            price: INITIAL_PRICE,
+            updated_at: 0,
+        },
+    );
+    assert_eq!(
+        bank.process(set_trustless_oracle_ix(
+            &bank,
+            &listing,
+            &other_oracle,
+            5_000
+        )),
+        Err(ProgramError::InvalidSeeds)
+    );
+
+    let oracle = mock_oracle(&mut bank, &listing, program_id, INITIAL_PRICE);
+    assert_eq!(
+        bank.process(set_trustless_oracle_ix(&bank, &listing, &oracle, 10_001)),
+        Err(AuctionError::InvalidFeeBasisPoints.into())
+    );
+
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, INITIAL_PRICE + 1).unwrap();
+    assert_eq!(
+        bank.process(set_trustless_oracle_ix(&bank, &listing, &oracle, 5_000)),
+        Err(AuctionError::AlreadyBid.into())
+    );
+}
+
+#[test]
+fn bids_in_an_accepted_currency_are_valued_with_both_oracles() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let other_mint = bank.create_mint(&authority);
+    let exhibitor_other_ft = bank.create_token_account(&other_mint, &listing.exhibitor, 0);
+    bank.process(set_accepted_currencies_ix(
+        &bank,
+        &listing,
+        &[(other_mint, exhibitor_other_ft)],
+    ))
+    .unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.accepted_mints[0], other_mint);
+    assert_eq!(auction.accepted_ft_receiving_pubkeys[0], exhibitor_other_ft);
+
+    bank.process(set_oracle_price_ix(&bank, &authority, &listing.ft_mint, 2))
+        .unwrap();
+    bank.process(set_oracle_price_ix(&bank, &authority, &other_mint, 1))
+        .unwrap();
+
+    // 60 units of the other mint are worth 120 units of the auction FT mint
+    let wallet = bank.create_wallet();
+    let other_bidder = Bidder {
+        wallet,
+        ft_account: bank.create_token_account(&other_mint, &wallet, BIDDER_BALANCE),
+    };
+    let bid_ix = bid_in_currency_ix(&mut bank, &listing, &other_bidder, 60);
+    bank.process(bid_ix).unwrap();
+    assert_eq!(
+        bank.token_balance(&other_bidder.ft_account),
+        BIDDER_BALANCE - 60
+    );
+
+    let bidder = listing.bidder(&mut bank);
+    assert_eq!(
+        listing.bid(&mut bank, &bidder, 120),
+        Err(AuctionError::InsufficientBidPrice.into())
+    );
+    listing.bid(&mut bank, &bidder, 121).unwrap();
+    assert_eq!(bank.token_balance(&other_bidder.ft_account), BIDDER_BALANCE);
+}
+
+#[test]
+fn bids_in_currencies_the_exhibitor_did_not_accept_are_rejected() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let other_mint = bank.create_mint(&authority);
+    bank.process(set_oracle_price_ix(&bank, &authority, &listing.ft_mint, 1))
+        .unwrap();
+    bank.process(set_oracle_price_ix(&bank, &authority, &other_mint, 1))
+        .unwrap();
+
+    let wallet = bank.create_wallet();
+    let other_bidder = Bidder {
+        wallet,
+        ft_account: bank.create_token_account(&other_mint, &wallet, BIDDER_BALANCE),
+    };
+    let bid_ix = bid_in_currency_ix(&mut bank, &listing, &other_bidder, INITIAL_PRICE + 1);
+    assert_eq!(
+        bank.process(bid_ix),
+        Err(AuctionError::InvalidInstruction.into())
+    );
+
+    // The auction FT mint is accepted through `Bid` only
+    let exhibitor_ft = bank.create_token_account(&listing.ft_mint, &listing.exhibitor, 0);
+    assert_eq!(
+        bank.process(set_accepted_currencies_ix(
+            &bank,
+            &listing,
+            &[(listing.ft_mint, exhibitor_ft)],
+        )),
+        Err(AuctionError::InvalidInstruction.into())
+    );
+    let mismatched_ft = bank.create_token_account(&listing.ft_mint, &listing.exhibitor, 0);
+    assert_eq!(
+        bank.process(set_accepted_currencies_ix(
+            &bank,
+            &listing,
+            &[(other_mint, mismatched_ft)],
+        )),
+        Err(ProgramError::InvalidAccountData)
+    );
+}
+
+/// Exhibits the auction in a room charging `fee_bps` to bidders, paid to `organizer`
+fn exhibit_in_room(bank: &mut Bank, organizer: &Pubkey, fee_bps: u16) -> Listing {
+    let room_id = 7u64;
+    let room = pda(&[b"room", &room_id.to_le_bytes()], &bank.program_id);
+    let mut data = vec![57];
+    data.extend_from_slice(&room_id.to_le_bytes());
+    data.extend_from_slice(&fee_bps.to_le_bytes());
+    data.extend_from_slice(&[0; 32]);
+    data.extend_from_slice(&[b'r'; ROOM_DESCRIPTION_LEN]);
+    bank.process(Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(*organizer, true),
+            AccountMeta::new(room, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    ))
+    .unwrap();
+
+    let listing = Listing::new(bank);
+    let mut exhibit_ix = listing.exhibit_ix(bank, INITIAL_PRICE, AUCTION_DURATION_SEC);
+    exhibit_ix
+        .accounts
+        .push(AccountMeta::new_readonly(room, false));
+    bank.process(exhibit_ix).unwrap();
+    listing.accept_terms(bank);
+    listing
+}
+
+fn set_insurance_fee_ix(bank: &Bank, authority: &Pubkey, fee_bps: u16) -> Instruction {
+    let mut data = vec![12];
+    data.extend_from_slice(&fee_bps.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(pda(&[b"global_config"], &bank.program_id), false),
+        ],
+    )
+}
+
+fn insurance_vault(bank: &Bank, mint: &Pubkey) -> Pubkey {
+    pda(&[b"insurance_vault", mint.as_ref()], &bank.program_id)
+}
+
+#[test]
+fn fees_of_a_bid_in_an_accepted_currency_are_charged_in_the_bid_mint() {
+    let (mut bank, authority) = setup();
+    let organizer = bank.create_wallet();
+    let listing = exhibit_in_room(&mut bank, &organizer, 500);
+    let creator = bank.create_wallet();
+    bank.process(deduct_royalty_before_escrow_ix(
+        &bank, &listing, &creator, 500,
+    ))
+    .unwrap();
+    bank.process(set_insurance_fee_ix(&bank, &authority, 100))
+        .unwrap();
+    let other_mint = bank.create_mint(&authority);
+    let exhibitor_other_ft = bank.create_token_account(&other_mint, &listing.exhibitor, 0);
+    bank.process(set_accepted_currencies_ix(
+        &bank,
+        &listing,
+        &[(other_mint, exhibitor_other_ft)],
+    ))
+    .unwrap();
+    bank.process(set_oracle_price_ix(&bank, &authority, &listing.ft_mint, 2))
+        .unwrap();
+    bank.process(set_oracle_price_ix(&bank, &authority, &other_mint, 1))
+        .unwrap();
+
+    let wallet = bank.create_wallet();
+    let other_bidder = Bidder {
+        wallet,
+        ft_account: bank.create_token_account(&other_mint, &wallet, BIDDER_BALANCE),
+    };
+    let organizer_other_ft = bank.create_token_account(&other_mint, &organizer, 0);
+    let other_royalty_vault = royalty_vault(&bank, &other_mint, &creator);
+    let mut bid_ix = bid_in_currency_ix(&mut bank, &listing, &other_bidder, 1_000);
+    bid_ix.accounts.extend([
+        AccountMeta::new(insurance_vault(&bank, &other_mint), false),
+        AccountMeta::new_readonly(other_mint, false),
+        AccountMeta::new(other_royalty_vault, false),
+        AccountMeta::new_readonly(creator, false),
+        AccountMeta::new_readonly(other_mint, false),
+    ]);
+    let mut mismatched_ix = bid_ix.clone();
+    let organizer_ft = bank.create_token_account(&listing.ft_mint, &organizer, 0);
+    mismatched_ix
+        .accounts
+        .push(AccountMeta::new(organizer_ft, false));
+    assert_eq!(
+        bank.process(mismatched_ix),
+        Err(ProgramError::InvalidAccountData)
+    );
+    bid_ix
+        .accounts
+        .push(AccountMeta::new(organizer_other_ft, false));
+    bank.process(bid_ix).unwrap();
+
+    // 1000 of the other mint pay 10 of insurance, 50 of room fee and 50 of royalty in that mint,
+    // the 950 left after the royalty are worth 1900 of the auction FT mint
+    assert_eq!(
+        bank.token_balance(&other_bidder.ft_account),
+        BIDDER_BALANCE - 1_000
+    );
+    assert_eq!(bank.token_balance(&insurance_vault(&bank, &other_mint)), 10);
+    assert_eq!(bank.token_balance(&organizer_other_ft), 50);
+    assert_eq!(bank.token_balance(&other_royalty_vault), 50);
+    let auction = listing.auction(&bank);
+    assert!(auction.upfront_royalty_paid);
+    assert_eq!(auction.price, 1_900);
+    assert_eq!(auction.bid_escrow_amount, 890);
+    assert_eq!(
+        bank.token_balance(&auction.highest_bidder_ft_temp_pubkey),
+        890
+    );
+
+    let bidder = listing.bidder(&mut bank);
+    let native_bid_ix = |bank: &mut Bank, price| {
+        let mut bid_ix = listing.bid_ix(bank, &bidder, price);
+        bid_ix.accounts.extend([
+            AccountMeta::new(insurance_vault(bank, &listing.ft_mint), false),
+            AccountMeta::new_readonly(listing.ft_mint, false),
+            AccountMeta::new(organizer_ft, false),
+        ]);
+        bid_ix
+    };
+    let bid_ix = native_bid_ix(&mut bank, 1_900);
+    assert_eq!(
+        bank.process(bid_ix),
+        Err(AuctionError::InsufficientBidPrice.into())
+    );
+    let bid_ix = native_bid_ix(&mut bank, 1_901);
+    bank.process(bid_ix).unwrap();
+    assert_eq!(listing.auction(&bank).price, 1_901);
+    assert_eq!(bank.token_balance(&organizer_ft), 95);
+    assert_eq!(
+        bank.token_balance(&other_bidder.ft_account),
+        BIDDER_BALANCE - 110
+    );
+}
@@ -0,0 +1,90 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use auction_pal::state::Auction;
+use common::auction::{Listing, AUCTION_DURATION_SEC, INITIAL_PRICE};
+use solana_program::program_error::ProgramError;
+
+use common::auction::setup;
+
+fn amount_overflow() -> ProgramError {
+    AuctionError::AmountOverflow.into()
+}
+
+#[test]
+fn exhibit_and_bid_then_close_settles() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.price, 150);
+    assert_eq!(auction.highest_bidder_pubkey, bidder.wallet);
+    assert_eq!(
+        bank.token_balance(&auction.highest_bidder_ft_temp_pubkey),
+        150
+    );
+
+    listing.expire(&mut bank);
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    bank.process(listing.close_ix(&bank, &winner_nft)).unwrap();
+    assert_eq!(bank.token_balance(&winner_nft), 1);
+    assert_eq!(bank.token_balance(&listing.ft_receiving), 150);
+    assert_eq!(bank.lamports(&listing.escrow), 0);
+}
+
+#[test]
+fn exhibit_duration_beyond_i64_overflows() {
+    let (mut bank, _) = setup();
+    let listing = Listing::new(&mut bank);
+
+    let exhibit_ix = listing.exhibit_ix(&bank, INITIAL_PRICE, u64::MAX);
+    assert_eq!(bank.process(exhibit_ix), Err(amount_overflow()));
+}
+
+#[test]
+fn exhibit_end_past_i64_max_overflows() {
+    let (mut bank, _) = setup();
+    let listing = Listing::new(&mut bank);
+
+    let exhibit_ix = listing.exhibit_ix(&bank, INITIAL_PRICE, i64::MAX as u64);
+    assert_eq!(bank.process(exhibit_ix), Err(amount_overflow()));
+}
+
+#[test]
+fn bid_over_u64_max_price_overflows() {
+    let (mut bank, _) = setup();
+    let listing = Listing::new(&mut bank);
+    bank.process(listing.exhibit_ix(&bank, u64::MAX, AUCTION_DURATION_SEC))
+        .unwrap();
+    listing.accept_terms(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+
+    assert_eq!(
+        listing.bid(&mut bank, &bidder, u64::MAX),
+        Err(amount_overflow())
+    );
+    assert_eq!(
+        listing.auction(&bank).highest_bidder_pubkey,
+        Default::default()
+    );
+}
+
+#[test]
+fn close_countdown_overflow_is_reported() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, INITIAL_PRICE + 1).unwrap();
+    listing.update_auction(&mut bank, |auction: &mut Auction| auction.end_at = i64::MAX);
+
+    bank.warp_to_timestamp(-1);
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    assert_eq!(
+        bank.process(listing.close_ix(&bank, &winner_nft)),
+        Err(amount_overflow())
+    );
+}
@@ -0,0 +1,491 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use auction_pal::event::{
+    AuctionStateEvent, BidEvent, NextBidInfoEvent, TWAPEvent, ValidationPassedEvent,
+};
+use auction_pal::state::{Auction, AuctionType, AUCTION_VERSION};
+use common::auction::{pda, setup, Listing, AUCTION_DURATION_SEC, INITIAL_PRICE};
+use common::Bank;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+use solana_program::sysvar;
+
+fn get_auction_state_ix(bank: &Bank, escrow: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[4],
+        vec![AccountMeta::new_readonly(*escrow, false)],
+    )
+}
+
+/// Fields of the last `AuctionStateEvent` logged
+fn logged_auction_state() -> Vec<Vec<u8>> {
+    Bank::events()
+        .into_iter()
+        .rev()
+        .find(|fields| fields[0] == AuctionStateEvent::NAME)
+        .expect("auction state logged")
+}
+
+#[test]
+fn get_auction_state_logs_exhibited_auction() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let auction = listing.auction(&bank);
+    let escrow_data = bank.account(&listing.escrow).unwrap().data.clone();
+
+    bank.process(get_auction_state_ix(&bank, &listing.escrow))
+        .unwrap();
+
+    let end_at = bank.clock().unix_timestamp + AUCTION_DURATION_SEC as i64;
+    let default_pubkey = Pubkey::default().to_bytes().to_vec();
+    assert_eq!(
+        logged_auction_state(),
+        vec![
+            AuctionStateEvent::NAME.to_vec(),
+            listing.escrow.to_bytes().to_vec(),
+            listing.exhibitor.to_bytes().to_vec(),
+            listing.nft_temp.to_bytes().to_vec(),
+            listing.ft_receiving.to_bytes().to_vec(),
+            INITIAL_PRICE.to_le_bytes().to_vec(),
+            end_at.to_le_bytes().to_vec(),
+            default_pubkey.clone(),
+            default_pubkey.clone(),
+            default_pubkey,
+            listing.ft_mint.to_bytes().to_vec(),
+            auction.floor_price.to_le_bytes().to_vec(),
+            auction.starts_at.to_le_bytes().to_vec(),
+            vec![AuctionType::English as u8],
+            vec![listing.category as u8],
+            vec![AUCTION_VERSION],
+            vec![1],
+            vec![0],
+            vec![0],
+            vec![auction.max_bidders],
+            vec![0],
+            auction.min_bid_increment.to_le_bytes().to_vec(),
+            auction.lot_size.to_le_bytes().to_vec(),
+            auction.upfront_royalty_bps.to_le_bytes().to_vec(),
+            auction.room_fee_bps.to_le_bytes().to_vec(),
+            auction.last_bid_at.to_le_bytes().to_vec(),
+            0u64.to_le_bytes().to_vec(),
+            vec![0],
+            vec![0],
+        ]
+    );
+    assert_eq!(bank.account(&listing.escrow).unwrap().data, escrow_data);
+}
+
+#[test]
+fn get_auction_state_logs_highest_bid() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let auction = listing.auction(&bank);
+
+    bank.process(get_auction_state_ix(&bank, &listing.escrow))
+        .unwrap();
+
+    let fields = logged_auction_state();
+    assert_eq!(fields[5], 250u64.to_le_bytes());
+    assert_eq!(fields[7], bidder.wallet.to_bytes());
+    assert_eq!(fields[8], auction.highest_bidder_ft_temp_pubkey.to_bytes());
+    assert_eq!(fields[9], bidder.ft_account.to_bytes());
+    assert_eq!(fields[25], auction.last_bid_at.to_le_bytes());
+    assert_eq!(fields[26], 1u64.to_le_bytes());
+}
+
+#[test]
+fn get_auction_state_of_zeroed_account_is_not_found() {
+    let mut bank = Bank::new();
+    let program_id = bank.program_id;
+    let escrow = bank.create_account(&program_id, Auction::LEN);
+
+    assert_eq!(
+        bank.process(get_auction_state_ix(&bank, &escrow)),
+        Err(AuctionError::AuctionNotFound.into())
+    );
+}
+
+fn validate_auction_ix(
+    bank: &Bank,
+    bidder: &Pubkey,
+    escrow: &Pubkey,
+    expected_price: u64,
+) -> Instruction {
+    let mut data = vec![32];
+    data.extend_from_slice(&expected_price.to_le_bytes());
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*bidder, true),
+            AccountMeta::new_readonly(*escrow, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+    )
+}
+
+#[test]
+fn validate_auction_passes_a_biddable_auction() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    let escrow_data = bank.account(&listing.escrow).unwrap().data.clone();
+
+    bank.process(validate_auction_ix(
+        &bank,
+        &bidder.wallet,
+        &listing.escrow,
+        150,
+    ))
+    .unwrap();
+    assert_eq!(
+        Bank::events(),
+        vec![vec![
+            ValidationPassedEvent::NAME.to_vec(),
+            listing.escrow.to_bytes().to_vec(),
+            bidder.wallet.to_bytes().to_vec(),
+            150u64.to_le_bytes().to_vec(),
+        ]]
+    );
+    assert_eq!(bank.account(&listing.escrow).unwrap().data, escrow_data);
+}
+
+#[test]
+fn validate_auction_reports_what_blocks_the_bid() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    let validate = |bank: &mut Bank, expected_price| {
+        bank.process(validate_auction_ix(
+            bank,
+            &bidder.wallet,
+            &listing.escrow,
+            expected_price,
+        ))
+    };
+
+    assert_eq!(
+        validate(&mut bank, INITIAL_PRICE),
+        Err(AuctionError::InsufficientBidPrice.into())
+    );
+    listing.update_auction(&mut bank, |auction| auction.floor_price = 500);
+    assert_eq!(
+        validate(&mut bank, 300),
+        Err(AuctionError::BelowFloorPrice.into())
+    );
+    listing.update_auction(&mut bank, |auction| auction.floor_price = 0);
+
+    listing.update_auction(&mut bank, |auction| auction.terms_accepted = false);
+    assert_eq!(
+        validate(&mut bank, 300),
+        Err(AuctionError::TermsNotAccepted.into())
+    );
+    listing.accept_terms(&mut bank);
+
+    listing.bid(&mut bank, &bidder, 200).unwrap();
+    assert_eq!(
+        validate(&mut bank, 300),
+        Err(AuctionError::AlreadyBid.into())
+    );
+    listing.update_auction(&mut bank, |auction| auction.advanced_amount = 50);
+    assert_eq!(
+        validate(&mut bank, 300),
+        Err(AuctionError::ProceedsAdvanced.into())
+    );
+
+    listing.expire(&mut bank);
+    assert_eq!(
+        validate(&mut bank, 300),
+        Err(AuctionError::InactiveAuction.into())
+    );
+}
+
+#[test]
+fn validate_auction_of_zeroed_account_is_not_found() {
+    let mut bank = Bank::new();
+    let program_id = bank.program_id;
+    let escrow = bank.create_account(&program_id, Auction::LEN);
+    let bidder = bank.create_wallet();
+
+    assert_eq!(
+        bank.process(validate_auction_ix(&bank, &bidder, &escrow, 150)),
+        Err(AuctionError::AuctionNotFound.into())
+    );
+}
+
+fn set_bid_visible_ix(bank: &Bank, listing: &Listing, visible: bool) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[33, visible as u8],
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+fn reveal_current_price_ix(bank: &Bank, listing: &Listing, requester: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[34],
+        vec![
+            AccountMeta::new_readonly(*requester, true),
+            AccountMeta::new_readonly(listing.escrow, false),
+        ],
+    )
+}
+
+#[test]
+fn hidden_bids_do_not_log_the_price() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    assert!(listing.auction(&bank).bids_visible);
+    bank.process(set_bid_visible_ix(&bank, &listing, false))
+        .unwrap();
+    let bidder = listing.bidder(&mut bank);
+
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    assert_eq!(listing.auction(&bank).price, 250);
+    let bid_event = Bank::events()
+        .into_iter()
+        .find(|fields| fields[0] == BidEvent::NAME)
+        .expect("bid logged");
+    assert_eq!(bid_event[3], 0u64.to_le_bytes());
+    assert!(!Bank::logs().iter().any(|log| log.contains("250")));
+
+    bank.process(get_auction_state_ix(&bank, &listing.escrow))
+        .unwrap();
+    assert_eq!(logged_auction_state()[5], 0u64.to_le_bytes());
+}
+
+#[test]
+fn exhibitor_and_highest_bidder_reveal_the_hidden_price() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_bid_visible_ix(&bank, &listing, false))
+        .unwrap();
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+
+    for requester in [listing.exhibitor, bidder.wallet] {
+        bank.process(reveal_current_price_ix(&bank, &listing, &requester))
+            .unwrap();
+        assert_eq!(logged_auction_state()[5], 250u64.to_le_bytes());
+    }
+    let outsider = bank.create_wallet();
+    assert_eq!(
+        bank.process(reveal_current_price_ix(&bank, &listing, &outsider)),
+        Err(AuctionError::Unauthorized.into())
+    );
+}
+
+fn get_next_bid_info_ix(bank: &Bank, listing: &Listing) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[38],
+        vec![
+            AccountMeta::new_readonly(listing.escrow, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(
+                pda(&[b"bid_history", listing.escrow.as_ref()], &bank.program_id),
+                false,
+            ),
+        ],
+    )
+}
+
+#[test]
+fn next_bid_info_logs_what_the_next_bid_requires() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let now = bank.clock().unix_timestamp;
+
+    bank.process(get_next_bid_info_ix(&bank, &listing)).unwrap();
+    let auction = listing.auction(&bank);
+    assert_eq!(
+        Bank::events(),
+        vec![vec![
+            NextBidInfoEvent::NAME.to_vec(),
+            listing.escrow.to_bytes().to_vec(),
+            auction.min_next_bid(now).unwrap().to_le_bytes().to_vec(),
+            (AUCTION_DURATION_SEC as i64).to_le_bytes().to_vec(),
+            0u64.to_le_bytes().to_vec(),
+            vec![0],
+            vec![1],
+        ]]
+    );
+
+    let first = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &first, 150).unwrap();
+    let second = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &second, 200).unwrap();
+    listing.update_auction(&mut bank, |auction| auction.floor_price = 1_000);
+    bank.warp_to_timestamp(now + 100);
+
+    bank.process(get_next_bid_info_ix(&bank, &listing)).unwrap();
+    let auction = listing.auction(&bank);
+    assert!(auction.min_next_bid(now + 100).unwrap() < 1_000);
+    assert_eq!(
+        Bank::events(),
+        vec![vec![
+            NextBidInfoEvent::NAME.to_vec(),
+            listing.escrow.to_bytes().to_vec(),
+            1_000u64.to_le_bytes().to_vec(),
+            (auction.end_at - now - 100).to_le_bytes().to_vec(),
+            2u64.to_le_bytes().to_vec(),
+            vec![1],
+            vec![0],
+        ]]
+    );
+}
+
+fn get_twap_ix(bank: &Bank, listing: &Listing) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[87],
+        vec![AccountMeta::new_readonly(listing.escrow, false)],
+    )
+}
+
+fn logged_twap() -> Vec<Vec<u8>> {
+    Bank::events()
+        .into_iter()
+        .find(|fields| fields[0] == TWAPEvent::NAME)
+        .expect("TWAP logged")
+}
+
+#[test]
+fn twap_weights_each_bid_by_the_time_it_stayed_the_highest() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let now = bank.clock().unix_timestamp;
+    let first = listing.bidder(&mut bank);
+    let second = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &first, 150).unwrap();
+    bank.process(get_twap_ix(&bank, &listing)).unwrap();
+    assert_eq!(logged_twap()[2], 0u64.to_le_bytes());
+
+    bank.warp_to_timestamp(now + 100);
+    listing.bid(&mut bank, &second, 200).unwrap();
+    bank.warp_to_timestamp(now + 400);
+    listing.bid(&mut bank, &first, 300).unwrap();
+
+    bank.process(get_twap_ix(&bank, &listing)).unwrap();
+    assert_eq!(
+        logged_twap(),
+        vec![
+            TWAPEvent::NAME.to_vec(),
+            listing.escrow.to_bytes().to_vec(),
+            ((150u64 * 100 + 200 * 300) / 400).to_le_bytes().to_vec(),
+            400u64.to_le_bytes().to_vec(),
+        ]
+    );
+}
+
+#[test]
+fn twap_of_hidden_bids_is_not_logged() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    bank.process(set_bid_visible_ix(&bank, &listing, false))
+        .unwrap();
+    let now = bank.clock().unix_timestamp;
+    let first = listing.bidder(&mut bank);
+    let second = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &first, 150).unwrap();
+    bank.warp_to_timestamp(now + 100);
+    listing.bid(&mut bank, &second, 200).unwrap();
+
+    bank.process(get_twap_ix(&bank, &listing)).unwrap();
+    assert_eq!(logged_twap()[2], 0u64.to_le_bytes());
+    assert_eq!(listing.auction(&bank).twap(), Some(150));
+}
+
+fn reveal_end_time_ix(bank: &Bank, listing: &Listing, signer: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[111],
+        vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(listing.escrow, false),
+        ],
+    )
+}
+
+/// End time logged by `GetAuctionState`
+fn logged_end_at(bank: &mut Bank, listing: &Listing) -> i64 {
+    bank.process(get_auction_state_ix(bank, &listing.escrow))
+        .unwrap();
+    i64::from_le_bytes(logged_auction_state()[6].as_slice().try_into().unwrap())
+}
+
+#[test]
+fn sealed_end_time_is_logged_from_its_reveal_time() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let end_at = listing.auction(&bank).end_at;
+    let reveal_at = end_at - 60;
+    bank.process(listing.set_sealed_end_time_ix(&bank, reveal_at))
+        .unwrap();
+    assert_eq!(logged_end_at(&mut bank, &listing), i64::MAX);
+
+    // Bids are placed as usual while the end time is sealed
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    bank.warp_to_timestamp(reveal_at - 1);
+    assert_eq!(logged_end_at(&mut bank, &listing), i64::MAX);
+    bank.warp_to_timestamp(reveal_at);
+    assert_eq!(logged_end_at(&mut bank, &listing), end_at);
+}
+
+#[test]
+fn revealed_price_keeps_the_end_time_sealed() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let end_at = listing.auction(&bank).end_at;
+    bank.process(listing.set_sealed_end_time_ix(&bank, end_at - 60))
+        .unwrap();
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+
+    bank.process(reveal_current_price_ix(&bank, &listing, &bidder.wallet))
+        .unwrap();
+    let auction_state = logged_auction_state();
+    assert_eq!(auction_state[5], 250u64.to_le_bytes());
+    assert_eq!(auction_state[6], i64::MAX.to_le_bytes());
+
+    bank.warp_to_timestamp(end_at - 60);
+    bank.process(reveal_current_price_ix(&bank, &listing, &bidder.wallet))
+        .unwrap();
+    assert_eq!(logged_auction_state()[6], end_at.to_le_bytes());
+}
+
+#[test]
+fn exhibitor_reveals_the_sealed_end_time_early() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let end_at = listing.auction(&bank).end_at;
+    assert_eq!(
+        bank.process(listing.set_sealed_end_time_ix(&bank, -1)),
+        Err(AuctionError::InvalidExpiry.into())
+    );
+    bank.process(listing.set_sealed_end_time_ix(&bank, end_at))
+        .unwrap();
+
+    let intruder = bank.create_wallet();
+    assert_eq!(
+        bank.process(reveal_end_time_ix(&bank, &listing, &intruder)),
+        Err(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(logged_end_at(&mut bank, &listing), i64::MAX);
+    bank.process(reveal_end_time_ix(&bank, &listing, &listing.exhibitor))
+        .unwrap();
+    assert_eq!(logged_end_at(&mut bank, &listing), end_at);
+}
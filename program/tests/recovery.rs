@@ -0,0 +1,382 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use common::auction::{pda, setup, Listing, BIDDER_BALANCE};
+use common::{Account, Bank};
+use solana_program::bpf_loader_upgradeable;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::sysvar;
+
+/// Program id of a redeployed program and the upgrade authority recorded in its program data
+struct Redeployment {
+    program_id: Pubkey,
+    upgrade_authority: Pubkey,
+}
+
+impl Redeployment {
+    fn deploy(bank: &mut Bank) -> Self {
+        let program_id = Pubkey::new_unique();
+        let upgrade_authority = Pubkey::new_unique();
+        // Serialized `UpgradeableLoaderState::ProgramData` metadata, without the program bytes
+        let mut data = 3u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(upgrade_authority.as_ref());
+        bank.set_account(
+            Self::program_data(&program_id),
+            Account {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: bpf_loader_upgradeable::id(),
+                executable: false,
+            },
+        );
+        Redeployment {
+            program_id,
+            upgrade_authority,
+        }
+    }
+
+    fn program_data(program_id: &Pubkey) -> Pubkey {
+        pda(&[program_id.as_ref()], &bpf_loader_upgradeable::id())
+    }
+
+    fn escrow_pda(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"escrow"], &self.program_id)
+    }
+}
+
+fn set_escrow_authority_ix(
+    bank: &Bank,
+    listing: &Listing,
+    authority: &Pubkey,
+    old_program_id: &Pubkey,
+    redeployment: &Redeployment,
+    new_authority: &Pubkey,
+    (new_pda, new_pda_bump): (Pubkey, u8),
+) -> Instruction {
+    let program_id = &bank.program_id;
+    let auction = listing.auction(bank);
+    let mut data = vec![59];
+    data.extend_from_slice(old_program_id.as_ref());
+    data.extend_from_slice(redeployment.program_id.as_ref());
+    data.extend_from_slice(new_pda.as_ref());
+    data.push(new_pda_bump);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(pda(&[b"global_config"], program_id), false),
+        AccountMeta::new(listing.escrow, false),
+        AccountMeta::new_readonly(pda(&[b"escrow"], program_id), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(*new_authority, true),
+        AccountMeta::new_readonly(Redeployment::program_data(&redeployment.program_id), false),
+        AccountMeta::new(listing.nft_temp, false),
+    ];
+    if auction.highest_bidder_pubkey != Pubkey::default() {
+        accounts.push(AccountMeta::new(
+            auction.highest_bidder_ft_temp_pubkey,
+            false,
+        ));
+    }
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+#[test]
+fn authority_hands_the_escrowed_accounts_to_a_new_pda() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let redeployment = Redeployment::deploy(&mut bank);
+    let (new_pda, _) = redeployment.escrow_pda();
+    let program_id = bank.program_id;
+
+    bank.process(set_escrow_authority_ix(
+        &bank,
+        &listing,
+        &authority,
+        &program_id,
+        &redeployment,
+        &redeployment.upgrade_authority,
+        redeployment.escrow_pda(),
+    ))
+    .unwrap();
+
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.migrated_to, redeployment.program_id);
+    assert_eq!(bank.token_account(&listing.nft_temp).owner, new_pda);
+    assert_eq!(
+        bank.token_account(&auction.highest_bidder_ft_temp_pubkey)
+            .owner,
+        new_pda
+    );
+    assert_eq!(bank.token_balance(&listing.nft_temp), 1);
+    assert_eq!(
+        bank.token_balance(&auction.highest_bidder_ft_temp_pubkey),
+        250
+    );
+}
+
+#[test]
+fn escrow_authority_is_reassigned_by_the_authority_of_this_program() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let redeployment = Redeployment::deploy(&mut bank);
+    let program_id = bank.program_id;
+    let escrow_pda = pda(&[b"escrow"], &program_id);
+
+    assert_eq!(
+        bank.process(set_escrow_authority_ix(
+            &bank,
+            &listing,
+            &listing.exhibitor,
+            &program_id,
+            &redeployment,
+            &redeployment.upgrade_authority,
+            redeployment.escrow_pda()
+        )),
+        Err(AuctionError::Unauthorized.into())
+    );
+    assert_eq!(
+        bank.process(set_escrow_authority_ix(
+            &bank,
+            &listing,
+            &authority,
+            &Pubkey::new_unique(),
+            &redeployment,
+            &redeployment.upgrade_authority,
+            redeployment.escrow_pda()
+        )),
+        Err(ProgramError::IncorrectProgramId)
+    );
+    assert_eq!(bank.token_account(&listing.nft_temp).owner, escrow_pda);
+}
+
+#[test]
+fn upgrade_authority_of_the_new_program_consents_to_the_migration() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let redeployment = Redeployment::deploy(&mut bank);
+    let program_id = bank.program_id;
+    let mut unsigned = set_escrow_authority_ix(
+        &bank,
+        &listing,
+        &authority,
+        &program_id,
+        &redeployment,
+        &redeployment.upgrade_authority,
+        redeployment.escrow_pda(),
+    );
+    unsigned.accounts[5].is_signer = false;
+
+    assert_eq!(
+        bank.process(unsigned),
+        Err(ProgramError::MissingRequiredSignature)
+    );
+    assert_eq!(
+        bank.process(set_escrow_authority_ix(
+            &bank,
+            &listing,
+            &authority,
+            &program_id,
+            &redeployment,
+            &Pubkey::new_unique(),
+            redeployment.escrow_pda()
+        )),
+        Err(AuctionError::Unauthorized.into())
+    );
+    assert!(!listing.auction(&bank).is_migrated());
+}
+
+#[test]
+fn new_pda_is_derived_from_the_new_program() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let redeployment = Redeployment::deploy(&mut bank);
+    let program_id = bank.program_id;
+    let (new_pda, new_pda_bump) = redeployment.escrow_pda();
+    let escrow_pda = pda(&[b"escrow"], &program_id);
+
+    for wrong_pda in [
+        (Pubkey::new_unique(), new_pda_bump),
+        (new_pda, new_pda_bump.wrapping_sub(1)),
+        Pubkey::find_program_address(&[b"escrow"], &Pubkey::new_unique()),
+    ] {
+        assert_eq!(
+            bank.process(set_escrow_authority_ix(
+                &bank,
+                &listing,
+                &authority,
+                &program_id,
+                &redeployment,
+                &redeployment.upgrade_authority,
+                wrong_pda
+            )),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+    assert_eq!(bank.token_account(&listing.nft_temp).owner, escrow_pda);
+    assert!(!listing.auction(&bank).is_migrated());
+}
+
+#[test]
+fn migrated_auction_is_no_longer_managed_by_this_program() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let redeployment = Redeployment::deploy(&mut bank);
+    let program_id = bank.program_id;
+    bank.process(set_escrow_authority_ix(
+        &bank,
+        &listing,
+        &authority,
+        &program_id,
+        &redeployment,
+        &redeployment.upgrade_authority,
+        redeployment.escrow_pda(),
+    ))
+    .unwrap();
+
+    let bidder = listing.bidder(&mut bank);
+    assert_eq!(
+        listing.bid(&mut bank, &bidder, 250),
+        Err(AuctionError::EscrowMigrated.into())
+    );
+    // The escrowed accounts are owned by the new PDA, so the escrow is migrated only once
+    let next = Redeployment::deploy(&mut bank);
+    for old_program_id in [program_id, redeployment.program_id] {
+        assert_eq!(
+            bank.process(set_escrow_authority_ix(
+                &bank,
+                &listing,
+                &authority,
+                &old_program_id,
+                &next,
+                &next.upgrade_authority,
+                next.escrow_pda()
+            )),
+            Err(AuctionError::EscrowMigrated.into())
+        );
+    }
+    assert_eq!(listing.auction(&bank).migrated_to, redeployment.program_id);
+}
+
+#[test]
+fn sponsors_reclaim_the_bonus_of_a_migrated_auction() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let sponsor = listing.bidder(&mut bank);
+    bank.process(listing.deposit_bonus_ix(&bank, &sponsor, 40))
+        .unwrap();
+    let redeployment = Redeployment::deploy(&mut bank);
+    let program_id = bank.program_id;
+
+    assert_eq!(
+        bank.process(listing.reclaim_bonus_ix(&bank, &sponsor)),
+        Err(AuctionError::ActiveAuction.into())
+    );
+    bank.process(set_escrow_authority_ix(
+        &bank,
+        &listing,
+        &authority,
+        &program_id,
+        &redeployment,
+        &redeployment.upgrade_authority,
+        redeployment.escrow_pda(),
+    ))
+    .unwrap();
+    bank.process(listing.reclaim_bonus_ix(&bank, &sponsor))
+        .unwrap();
+
+    assert_eq!(bank.token_balance(&sponsor.ft_account), BIDDER_BALANCE);
+    assert_eq!(bank.token_balance(&listing.bonus_vault(&bank)), 0);
+    assert_eq!(
+        bank.lamports(&listing.bonus_deposit(&bank, &sponsor.wallet)),
+        0
+    );
+}
+
+/// The auction end after which the authority moves a stuck NFT, as in the program
+const EMERGENCY_THRESHOLD_SEC: i64 = 30 * 86_400;
+
+fn emergency_transfer_nft_ix(
+    bank: &Bank,
+    listing: &Listing,
+    authority: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    let program_id = &bank.program_id;
+    let mut data = vec![83];
+    data.extend_from_slice(destination.as_ref());
+    Instruction::new_with_bytes(
+        *program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(pda(&[b"global_config"], program_id), false),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new(listing.exhibitor, false),
+            AccountMeta::new(listing.nft_temp, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(pda(&[b"escrow"], program_id), false),
+            AccountMeta::new(pda(&[b"stats"], program_id), false),
+        ],
+    )
+}
+
+#[test]
+fn authority_moves_the_nft_out_of_a_stuck_escrow() {
+    let (mut bank, authority) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let destination = listing.nft_account(&mut bank, &listing.exhibitor);
+    let end_at = listing.auction(&bank).end_at;
+
+    bank.warp_to_timestamp(end_at + EMERGENCY_THRESHOLD_SEC - 1);
+    assert_eq!(
+        bank.process(emergency_transfer_nft_ix(
+            &bank,
+            &listing,
+            &authority,
+            &destination
+        )),
+        Err(AuctionError::GracePeriodActive.into())
+    );
+
+    bank.warp_to_timestamp(end_at + EMERGENCY_THRESHOLD_SEC);
+    bank.process(emergency_transfer_nft_ix(
+        &bank,
+        &listing,
+        &authority,
+        &destination,
+    ))
+    .unwrap();
+    assert_eq!(bank.token_balance(&destination), 1);
+    assert!(bank.account(&listing.escrow).is_none());
+    assert!(bank.account(&listing.nft_temp).is_none());
+}
+
+#[test]
+fn only_the_authority_moves_a_stuck_nft() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let destination = listing.nft_account(&mut bank, &listing.exhibitor);
+    let intruder = bank.create_wallet();
+    bank.warp_to_timestamp(listing.auction(&bank).end_at + EMERGENCY_THRESHOLD_SEC);
+
+    assert_eq!(
+        bank.process(emergency_transfer_nft_ix(
+            &bank,
+            &listing,
+            &intruder,
+            &destination
+        )),
+        Err(AuctionError::Unauthorized.into())
+    );
+    assert_eq!(bank.token_balance(&listing.nft_temp), 1);
+}
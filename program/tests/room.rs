@@ -0,0 +1,163 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::error::AuctionError;
+use auction_pal::state::{AuctionRoom, ROOM_DESCRIPTION_LEN};
+use common::auction::{pda, setup, Listing, AUCTION_DURATION_SEC, INITIAL_PRICE};
+use common::Bank;
+use solana_program::hash::{hash, hashv};
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::pubkey::Pubkey;
+use solana_program::{system_program, sysvar};
+
+const ROOM_ID: u64 = 7;
+
+fn room(bank: &Bank) -> Pubkey {
+    pda(&[b"room", &ROOM_ID.to_le_bytes()], &bank.program_id)
+}
+
+fn init_auction_room_ix(
+    bank: &Bank,
+    organizer: &Pubkey,
+    fee_bps: u16,
+    whitelist_root: [u8; 32],
+) -> Instruction {
+    let mut data = vec![57];
+    data.extend_from_slice(&ROOM_ID.to_le_bytes());
+    data.extend_from_slice(&fee_bps.to_le_bytes());
+    data.extend_from_slice(&whitelist_root);
+    data.extend_from_slice(&[b'r'; ROOM_DESCRIPTION_LEN]);
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &data,
+        vec![
+            AccountMeta::new(*organizer, true),
+            AccountMeta::new(room(bank), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+fn close_auction_room_ix(bank: &Bank, organizer: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[58],
+        vec![
+            AccountMeta::new(*organizer, true),
+            AccountMeta::new(room(bank), false),
+        ],
+    )
+}
+
+/// `Exhibit` of the auction in the room
+fn exhibit_in_room_ix(bank: &Bank, listing: &Listing) -> Instruction {
+    let mut exhibit_ix = listing.exhibit_ix(bank, INITIAL_PRICE, AUCTION_DURATION_SEC);
+    exhibit_ix
+        .accounts
+        .push(AccountMeta::new_readonly(room(bank), false));
+    exhibit_ix
+}
+
+#[test]
+fn room_fee_is_paid_to_the_organizer() {
+    let (mut bank, _) = setup();
+    let organizer = bank.create_wallet();
+    bank.process(init_auction_room_ix(&bank, &organizer, 500, [0; 32]))
+        .unwrap();
+    let room_state: AuctionRoom = bank.unpack(&room(&bank));
+    assert_eq!(room_state.organizer, organizer);
+    assert_eq!(room_state.fee_bps, 500);
+
+    let listing = Listing::new(&mut bank);
+    bank.process(exhibit_in_room_ix(&bank, &listing)).unwrap();
+    listing.accept_terms(&mut bank);
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.room, room(&bank));
+    assert_eq!(auction.room_organizer, organizer);
+    assert_eq!(auction.room_fee_bps, 500);
+
+    let bidder = listing.bidder(&mut bank);
+    let organizer_ft = bank.create_token_account(&listing.ft_mint, &organizer, 0);
+    let mut bid_ix = listing.bid_ix(&mut bank, &bidder, 200);
+    bid_ix.accounts.push(AccountMeta::new(organizer_ft, false));
+    bank.process(bid_ix).unwrap();
+
+    assert_eq!(bank.token_balance(&organizer_ft), 10);
+    let auction = listing.auction(&bank);
+    assert_eq!(auction.price, 200);
+    assert_eq!(
+        bank.token_balance(&auction.highest_bidder_ft_temp_pubkey),
+        190
+    );
+}
+
+#[test]
+fn room_whitelist_gates_the_bidders() {
+    let (mut bank, _) = setup();
+    let organizer = bank.create_wallet();
+    let listing = Listing::new(&mut bank);
+    let allowed = listing.bidder(&mut bank);
+    let other_allowed = listing.bidder(&mut bank);
+    let outsider = listing.bidder(&mut bank);
+    let leaf = hash(allowed.wallet.as_ref()).to_bytes();
+    let sibling = hash(other_allowed.wallet.as_ref()).to_bytes();
+    let root = if leaf <= sibling {
+        hashv(&[&leaf, &sibling])
+    } else {
+        hashv(&[&sibling, &leaf])
+    }
+    .to_bytes();
+    bank.process(init_auction_room_ix(&bank, &organizer, 0, root))
+        .unwrap();
+    bank.process(exhibit_in_room_ix(&bank, &listing)).unwrap();
+    listing.accept_terms(&mut bank);
+    assert_eq!(listing.auction(&bank).whitelist_root, root);
+
+    let mut outsider_ix = listing.bid_ix(&mut bank, &outsider, 150);
+    outsider_ix.data.extend_from_slice(&sibling);
+    assert_eq!(
+        bank.process(outsider_ix),
+        Err(AuctionError::NotWhitelisted.into())
+    );
+    assert_eq!(
+        listing.bid(&mut bank, &allowed, 150),
+        Err(AuctionError::NotWhitelisted.into())
+    );
+
+    let mut allowed_ix = listing.bid_ix(&mut bank, &allowed, 150);
+    allowed_ix.data.extend_from_slice(&sibling);
+    bank.process(allowed_ix).unwrap();
+    assert_eq!(listing.auction(&bank).highest_bidder_pubkey, allowed.wallet);
+}
+
+#[test]
+fn organizer_closes_the_room_without_affecting_its_auctions() {
+    let (mut bank, _) = setup();
+    let organizer = bank.create_wallet();
+    bank.process(init_auction_room_ix(&bank, &organizer, 500, [0; 32]))
+        .unwrap();
+    let listing = Listing::new(&mut bank);
+    bank.process(exhibit_in_room_ix(&bank, &listing)).unwrap();
+    listing.accept_terms(&mut bank);
+    let stranger = bank.create_wallet();
+
+    assert_eq!(
+        bank.process(close_auction_room_ix(&bank, &stranger)),
+        Err(AuctionError::Unauthorized.into())
+    );
+    let organizer_lamports = bank.lamports(&organizer);
+    let room_lamports = bank.lamports(&room(&bank));
+    bank.process(close_auction_room_ix(&bank, &organizer))
+        .unwrap();
+    assert_eq!(bank.lamports(&room(&bank)), 0);
+    assert_eq!(
+        bank.lamports(&organizer),
+        organizer_lamports + room_lamports
+    );
+
+    assert_eq!(listing.auction(&bank).room_fee_bps, 500);
+    let late = Listing::new(&mut bank);
+    assert!(bank.process(exhibit_in_room_ix(&bank, &late)).is_err());
+}
@@ -0,0 +1,103 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use common::auction::{pda, setup, Listing, AUCTION_DURATION_SEC, INITIAL_PRICE};
+use common::Bank;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use spl_token::error::TokenError;
+use spl_token::state::{AccountState, Mint};
+
+/// Listing whose NFT mint can be frozen by the escrow PDA, exhibited and made soulbound
+fn soulbound_listing(bank: &mut Bank) -> Listing {
+    let listing = Listing::new(bank);
+    let mut nft_mint: Mint = bank.unpack(&listing.nft_mint);
+    nft_mint.freeze_authority = Some(pda(&[b"escrow"], &bank.program_id)).into();
+    bank.pack(listing.nft_mint, spl_token::id(), nft_mint);
+    bank.process(listing.exhibit_ix(bank, INITIAL_PRICE, AUCTION_DURATION_SEC))
+        .unwrap();
+    listing.accept_terms(bank);
+    bank.process(exhibit_soulbound_ix(bank, &listing)).unwrap();
+    listing
+}
+
+fn exhibit_soulbound_ix(bank: &Bank, listing: &Listing) -> Instruction {
+    Instruction::new_with_bytes(
+        bank.program_id,
+        &[50],
+        vec![
+            AccountMeta::new_readonly(listing.exhibitor, true),
+            AccountMeta::new(listing.escrow, false),
+            AccountMeta::new_readonly(listing.nft_temp, false),
+            AccountMeta::new_readonly(listing.nft_mint, false),
+        ],
+    )
+}
+
+#[test]
+fn soulbound_nft_is_frozen_in_the_winner_account() {
+    let (mut bank, _) = setup();
+    let listing = soulbound_listing(&mut bank);
+    assert!(listing.auction(&bank).is_soulbound);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 250).unwrap();
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+
+    listing.expire(&mut bank);
+    let mut close_ix = listing.close_ix(&bank, &winner_nft);
+    close_ix
+        .accounts
+        .push(AccountMeta::new_readonly(listing.nft_mint, false));
+    bank.process(close_ix).unwrap();
+    let winner_nft_account = bank.token_account(&winner_nft);
+    assert_eq!(winner_nft_account.amount, 1);
+    assert_eq!(winner_nft_account.state, AccountState::Frozen);
+    assert_eq!(bank.token_balance(&listing.ft_receiving), 250);
+
+    let buyer = bank.create_wallet();
+    let resale_nft = listing.nft_account(&mut bank, &buyer);
+    assert_eq!(
+        bank.process(
+            spl_token::instruction::transfer(
+                &spl_token::id(),
+                &winner_nft,
+                &resale_nft,
+                &bidder.wallet,
+                &[],
+                1,
+            )
+            .unwrap()
+        ),
+        Err(TokenError::AccountFrozen.into())
+    );
+    assert_eq!(bank.token_balance(&winner_nft), 1);
+}
+
+#[test]
+fn cancelled_soulbound_nft_is_burnt() {
+    let (mut bank, _) = setup();
+    let listing = soulbound_listing(&mut bank);
+
+    let mut cancel_ix = listing.cancel_ix(&bank);
+    cancel_ix
+        .accounts
+        .push(AccountMeta::new(listing.nft_mint, false));
+    bank.process(cancel_ix).unwrap();
+    assert_eq!(bank.unpack::<Mint>(&listing.nft_mint).supply, 0);
+    assert_eq!(bank.token_balance(&listing.exhibitor_nft), 0);
+    assert!(bank.account(&listing.nft_temp).is_none());
+    assert!(bank.account(&listing.escrow).is_none());
+}
+
+#[test]
+fn soulbound_needs_the_pda_as_freeze_authority() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+
+    assert_eq!(
+        bank.process(exhibit_soulbound_ix(&bank, &listing)),
+        Err(ProgramError::InvalidAccountData)
+    );
+    assert!(!listing.auction(&bank).is_soulbound);
+}
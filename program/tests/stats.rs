@@ -0,0 +1,52 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use auction_pal::event::ProgramStatsEvent;
+use auction_pal::state::ProgramStats;
+use common::auction::{pda, setup, Listing};
+use common::Bank;
+use solana_program::instruction::{AccountMeta, Instruction};
+
+#[test]
+fn program_stats_count_every_auction() {
+    let (mut bank, _) = setup();
+    let stats = pda(&[b"stats"], &bank.program_id);
+    let sold = Listing::exhibit(&mut bank);
+    let cancelled = Listing::exhibit(&mut bank);
+    assert_eq!(bank.unpack::<ProgramStats>(&stats).active_auctions, 2);
+
+    let first = sold.bidder(&mut bank);
+    sold.bid(&mut bank, &first, 150).unwrap();
+    let second = sold.bidder(&mut bank);
+    sold.bid(&mut bank, &second, 200).unwrap();
+    sold.bid(&mut bank, &first, 300).unwrap();
+
+    bank.process(cancelled.cancel_ix(&bank)).unwrap();
+    sold.expire(&mut bank);
+    let winner_nft = sold.nft_account(&mut bank, &first.wallet);
+    bank.process(sold.close_ix(&bank, &winner_nft)).unwrap();
+
+    let program_stats: ProgramStats = bank.unpack(&stats);
+    assert_eq!(program_stats.total_auctions, 2);
+    assert_eq!(program_stats.total_bids, 3);
+    assert_eq!(program_stats.total_volume, 650);
+    assert_eq!(program_stats.active_auctions, 0);
+
+    bank.process(Instruction::new_with_bytes(
+        bank.program_id,
+        &[29],
+        vec![AccountMeta::new_readonly(stats, false)],
+    ))
+    .unwrap();
+    assert_eq!(
+        Bank::events(),
+        vec![vec![
+            ProgramStatsEvent::NAME.to_vec(),
+            2u64.to_le_bytes().to_vec(),
+            650u64.to_le_bytes().to_vec(),
+            3u64.to_le_bytes().to_vec(),
+            0u32.to_le_bytes().to_vec(),
+        ]]
+    );
+}
@@ -0,0 +1,68 @@
+extern crate AuctionPal as auction_pal;
+
+mod common;
+
+use common::auction::{setup, Listing, AUCTION_DURATION_SEC, INITIAL_PRICE};
+use common::Bank;
+use solana_program::instruction::Instruction;
+use solana_program::program_error::ProgramError;
+
+/// Asserts `instruction` fails once any account at `indices` is passed read-only
+fn assert_requires_writable(bank: &mut Bank, instruction: &Instruction, indices: &[usize]) {
+    for &index in indices {
+        let mut read_only = instruction.clone();
+        read_only.accounts[index].is_writable = false;
+        assert_eq!(
+            bank.process(read_only),
+            Err(ProgramError::InvalidAccountData),
+            "account {index} passed read-only"
+        );
+    }
+}
+
+#[test]
+fn exhibit_requires_writable_accounts() {
+    let (mut bank, _) = setup();
+    let listing = Listing::new(&mut bank);
+    let exhibit_ix = listing.exhibit_ix(&bank, INITIAL_PRICE, AUCTION_DURATION_SEC);
+
+    assert_requires_writable(&mut bank, &exhibit_ix, &[1, 2, 4]);
+    bank.process(exhibit_ix).unwrap();
+}
+
+#[test]
+fn bid_requires_writable_accounts() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let first = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &first, 150).unwrap();
+    let bidder = listing.bidder(&mut bank);
+    let bid_ix = listing.bid_ix(&mut bank, &bidder, 300);
+
+    assert_requires_writable(&mut bank, &bid_ix, &[2, 3, 4, 5, 6]);
+    bank.process(bid_ix).unwrap();
+}
+
+#[test]
+fn cancel_requires_writable_accounts() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let cancel_ix = listing.cancel_ix(&bank);
+
+    assert_requires_writable(&mut bank, &cancel_ix, &[0, 1, 2, 3]);
+    bank.process(cancel_ix).unwrap();
+}
+
+#[test]
+fn close_requires_writable_accounts() {
+    let (mut bank, _) = setup();
+    let listing = Listing::exhibit(&mut bank);
+    let bidder = listing.bidder(&mut bank);
+    listing.bid(&mut bank, &bidder, 150).unwrap();
+    listing.expire(&mut bank);
+    let winner_nft = listing.nft_account(&mut bank, &bidder.wallet);
+    let close_ix = listing.close_ix(&bank, &winner_nft);
+
+    assert_requires_writable(&mut bank, &close_ix, &[0, 1, 2, 3, 4, 5, 6]);
+    bank.process(close_ix).unwrap();
+}
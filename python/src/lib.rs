@@ -0,0 +1,199 @@
+use auction_pal::event::BidEvent;
+use auction_pal::state::Auction;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+
+/// Decodes the data of an escrow account into a dict keyed by the `Auction` field names
+/// Pubkeys are returned as base58 strings and the category as its variant name
+#[pyfunction]
+fn parse_auction_state<'py>(py: Python<'py>, data: &[u8]) -> PyResult<&'py PyDict> {
+    let auction = Auction::unpack(data).map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+    let state = PyDict::new(py);
+    state.set_item("is_initialized", auction.is_initialized)?;
+    state.set_item("exhibitor_pubkey", auction.exhibitor_pubkey.to_string())?;
+    state.set_item(
+        "exhibiting_nft_temp_pubkey",
+        auction.exhibiting_nft_temp_pubkey.to_string(),
+    )?;
+    state.set_item(
+        "exhibitor_ft_receiving_pubkey",
+        auction.exhibitor_ft_receiving_pubkey.to_string(),
+    )?;
+    state.set_item("price", auction.price)?;
+    state.set_item("end_at", auction.end_at)?;
+    state.set_item(
+        "highest_bidder_pubkey",
+        auction.highest_bidder_pubkey.to_string(),
+    )?;
+    state.set_item(
+        "highest_bidder_ft_temp_pubkey",
+        auction.highest_bidder_ft_temp_pubkey.to_string(),
+    )?;
+    state.set_item(
+        "highest_bidder_ft_returning_pubkey",
+        auction.highest_bidder_ft_returning_pubkey.to_string(),
+    )?;
+    state.set_item("bid_locked", auction.bid_locked)?;
+    state.set_item("pda_bump", auction.pda_bump)?;
+    state.set_item("max_bidder_extension_sec", auction.max_bidder_extension_sec)?;
+    state.set_item("bidder_extended", auction.bidder_extended)?;
+    state.set_item("floor_price", auction.floor_price)?;
+    state.set_item("category", format!("{:?}", auction.category))?;
+    state.set_item("max_bidders", auction.max_bidders)?;
+    state.set_item("unique_bidder_count", auction.unique_bidder_count)?;
+    state.set_item("terms_accepted", auction.terms_accepted)?;
+    state.set_item("terms_hash", PyBytes::new(py, &auction.terms_hash))?;
+    state.set_item("ft_mint", auction.ft_mint.to_string())?;
+    state.set_item("airdrop_winner", auction.airdrop_winner.to_string())?;
+    state.set_item("auto_close", auction.auto_close)?;
+    state.set_item("refund_on_cancel", auction.refund_on_cancel)?;
+    state.set_item("bid_escrow_amount", auction.bid_escrow_amount)?;
+    state.set_item("advanced_amount", auction.advanced_amount)?;
+    state.set_item("bids_visible", auction.bids_visible)?;
+    state.set_item("expected_nft_mint", auction.expected_nft_mint.to_string())?;
+    state.set_item("mediator_pubkey", auction.mediator_pubkey.to_string())?;
+    state.set_item("starts_at", auction.starts_at)?;
+    state.set_item("version", auction.version)?;
+    state.set_item("oracle_pubkey", auction.oracle_pubkey.to_string())?;
+    state.set_item("oracle_min_price", auction.oracle_min_price)?;
+    state.set_item("upfront_royalty_bps", auction.upfront_royalty_bps)?;
+    state.set_item(
+        "royalty_creator_ft_pubkey",
+        auction.royalty_creator_ft_pubkey.to_string(),
+    )?;
+    state.set_item("upfront_royalty_paid", auction.upfront_royalty_paid)?;
+    state.set_item("close_grace_period_sec", auction.close_grace_period_sec)?;
+    state.set_item("close_authority", auction.close_authority.to_string())?;
+    state.set_item("priority_bidder", auction.priority_bidder.to_string())?;
+    state.set_item("is_soulbound", auction.is_soulbound)?;
+    state.set_item("min_bid_increment", auction.min_bid_increment)?;
+    state.set_item("bid_multiplier_bps", auction.bid_multiplier_bps)?;
+    state.set_item("room", auction.room.to_string())?;
+    state.set_item("room_organizer", auction.room_organizer.to_string())?;
+    state.set_item("room_fee_bps", auction.room_fee_bps)?;
+    state.set_item("whitelist_root", PyBytes::new(py, &auction.whitelist_root))?;
+    state.set_item("bonus_amount", auction.bonus_amount)?;
+    state.set_item("cross_program_bid", auction.cross_program_bid)?;
+    state.set_item("cpi_authority", auction.cpi_authority.to_string())?;
+    state.set_item("finality_timeout_sec", auction.finality_timeout_sec)?;
+    state.set_item("finalized_at", auction.finalized_at)?;
+    state.set_item("is_finalized", auction.is_finalized)?;
+    state.set_item("is_disputed", auction.is_disputed)?;
+    let fractional_owners: Vec<(String, u16)> = auction
+        .fractional_owners
+        .iter()
+        .filter(|(_, share_bps)| *share_bps > 0)
+        .map(|(owner_ft_pubkey, share_bps)| (owner_ft_pubkey.to_string(), *share_bps))
+        .collect();
+    state.set_item("fractional_owners", fractional_owners)?;
+    state.set_item(
+        "highest_bidder_vault_program",
+        auction.highest_bidder_vault_program.to_string(),
+    )?;
+    state.set_item("highest_bidder_vault", auction.highest_bidder_vault.to_string())?;
+    state.set_item(
+        "highest_bidder_vault_ft",
+        auction.highest_bidder_vault_ft.to_string(),
+    )?;
+    state.set_item("penalty_bps", auction.penalty_bps)?;
+    state.set_item("payment_grace_period_sec", auction.payment_grace_period_sec)?;
+    state.set_item("gate_token_mint", auction.gate_token_mint.to_string())?;
+    state.set_item("gate_token_min_amount", auction.gate_token_min_amount)?;
+    state.set_item("lot_size", auction.lot_size)?;
+    let accepted_currencies: Vec<(String, String)> = auction
+        .accepted_mints
+        .iter()
+        .zip(auction.accepted_ft_receiving_pubkeys.iter())
+        .filter(|(mint, _)| **mint != Pubkey::default())
+        .map(|(mint, ft_receiving_pubkey)| (mint.to_string(), ft_receiving_pubkey.to_string()))
+        .collect();
+    state.set_item("accepted_currencies", accepted_currencies)?;
+    state.set_item("idle_period_sec", auction.idle_period_sec)?;
+    state.set_item("max_duration_sec", auction.max_duration_sec)?;
+    state.set_item("last_bid_at", auction.last_bid_at)?;
+    state.set_item("winner_override", auction.winner_override.to_string())?;
+    state.set_item("audit_oracle", auction.audit_oracle.to_string())?;
+    state.set_item("audit_pending", auction.audit_pending)?;
+    state.set_item("nft_lock_period_sec", auction.nft_lock_period_sec)?;
+    state.set_item("transaction_limit", auction.transaction_limit)?;
+    state.set_item("burn_on_cancel", auction.burn_on_cancel)?;
+    state.set_item("twap_numerator", auction.twap_numerator)?;
+    state.set_item("twap_denominator", auction.twap_denominator)?;
+    state.set_item(
+        "nft_update_authority",
+        auction.nft_update_authority.to_string(),
+    )?;
+    state.set_item(
+        "allow_updates_during_auction",
+        auction.allow_updates_during_auction,
+    )?;
+    state.set_item("trustline_program", auction.trustline_program.to_string())?;
+    state.set_item("trustline_type", PyBytes::new(py, &auction.trustline_type))?;
+    state.set_item("auction_type", format!("{:?}", auction.auction_type))?;
+    state.set_item("exhibitor_collateral", auction.exhibitor_collateral)?;
+    state.set_item("cancel_penalty_bps", auction.cancel_penalty_bps)?;
+    state.set_item("bid_nonce", auction.bid_nonce)?;
+    state.set_item("clock_drift_tolerance_sec", auction.clock_drift_tolerance_sec)?;
+    state.set_item("requires_approval", auction.requires_approval)?;
+    state.set_item("anti_sandwich", auction.anti_sandwich)?;
+    state.set_item("last_block_bidder", auction.last_block_bidder.to_string())?;
+    state.set_item("last_block_bid_price", auction.last_block_bid_price)?;
+    state.set_item("last_block_bid_slot", auction.last_block_bid_slot)?;
+    let price_curve: Vec<(u64, u16)> = auction
+        .price_curve
+        .iter()
+        .copied()
+        .filter(|(_, increment_bps)| *increment_bps > 0)
+        .collect();
+    state.set_item("price_curve", price_curve)?;
+    state.set_item("early_bird_period_bps", auction.early_bird_period_bps)?;
+    state.set_item("early_bird_increment_bps", auction.early_bird_increment_bps)?;
+    state.set_item("late_increment_bps", auction.late_increment_bps)?;
+    state.set_item("reveal_end_at_time", auction.reveal_end_at_time)?;
+    state.set_item("hidden_end_at_revealed", auction.hidden_end_at_revealed)?;
+    state.set_item("bid_weight_oracle", auction.bid_weight_oracle.to_string())?;
+    state.set_item("highest_bid_weight_bps", auction.highest_bid_weight_bps)?;
+    state.set_item("has_changelog", auction.has_changelog)?;
+    state.set_item("changelog_bump", auction.changelog_bump)?;
+    state.set_item("migrated_to", auction.migrated_to.to_string())?;
+    Ok(state)
+}
+
+/// Decodes the fields of a `Bid` event, as logged by `sol_log_data` and base64 decoded
+/// back to back, into a dict with `escrow`, `bidder` and `price`
+#[pyfunction]
+fn decode_bid_event<'py>(py: Python<'py>, log_bytes: &[u8]) -> PyResult<&'py PyDict> {
+    let fields = log_bytes
+        .strip_prefix(BidEvent::NAME)
+        .filter(|fields| fields.len() == 72)
+        .ok_or_else(|| PyValueError::new_err("not a Bid event"))?;
+    let (escrow, rest) = fields.split_at(32);
+    let (bidder, price) = rest.split_at(32);
+    let invalid_event = |_| PyValueError::new_err("invalid Bid event");
+
+    let event = PyDict::new(py);
+    event.set_item(
+        "escrow",
+        Pubkey::try_from(escrow).map_err(invalid_event)?.to_string(),
+    )?;
+    event.set_item(
+        "bidder",
+        Pubkey::try_from(bidder).map_err(invalid_event)?.to_string(),
+    )?;
+    event.set_item(
+        "price",
+        u64::from_le_bytes(price.try_into().map_err(invalid_event)?),
+    )?;
+    Ok(event)
+}
+
+#[pymodule]
+fn auction_pal_py(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(parse_auction_state, m)?)?;
+    m.add_function(wrap_pyfunction!(decode_bid_event, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,83 @@
+"""Decoding of an escrow account and a Bid event recorded after exhibiting an auction at 100
+for an hour and bidding 250 on it
+
+Build the extension first, e.g. with `maturin develop`, then run `pytest`
+"""
+
+from pathlib import Path
+
+import pytest
+
+from auction_pal_py import decode_bid_event, parse_auction_state
+
+FIXTURES = Path(__file__).parent / "fixtures"
+DEFAULT_PUBKEY = "11111111111111111111111111111111"
+ESCROW = "11111114d3RrygbPdAtMuFnDmzsN8T5fYKVQ7FVr7"
+EXHIBITOR = "11111112D1oxKts8YPdTJRG5FzxTNpMtWmq8hkVx3"
+BIDDER = "111111152P2r5yt6odmBLPsFCLBrFisJ3aS7LqLAT"
+FT_MINT = "11111112cMQwSC9qirWGjZM6gLGwW69X22mqwLLGP"
+BIDDER_FT_TEMP = "11111115q4EpJaTXAZWpCg3J2zppWGSZ46KXozzo9"
+
+
+@pytest.fixture
+def auction_data():
+    return (FIXTURES / "auction.bin").read_bytes()
+
+
+@pytest.fixture
+def bid_event_data():
+    return (FIXTURES / "bid_event.bin").read_bytes()
+
+
+def test_auction_state_after_a_bid(auction_data):
+    state = parse_auction_state(auction_data)
+
+    assert state["is_initialized"] is True
+    assert state["exhibitor_pubkey"] == EXHIBITOR
+    assert state["ft_mint"] == FT_MINT
+    assert state["price"] == 250
+    assert state["starts_at"] == 1_700_000_000
+    assert state["end_at"] == 1_700_003_600
+    assert state["highest_bidder_pubkey"] == BIDDER
+    assert state["highest_bidder_ft_temp_pubkey"] == BIDDER_FT_TEMP
+    assert state["bid_escrow_amount"] == 250
+    assert state["bid_locked"] is True
+    assert state["bid_nonce"] == 1
+    assert state["terms_accepted"] is True
+    assert state["category"] == "Other"
+    assert state["version"] == 5
+
+
+def test_unset_fields_keep_their_defaults(auction_data):
+    state = parse_auction_state(auction_data)
+
+    assert state["oracle_pubkey"] == DEFAULT_PUBKEY
+    assert state["mediator_pubkey"] == DEFAULT_PUBKEY
+    assert state["has_changelog"] is False
+    assert state["migrated_to"] == DEFAULT_PUBKEY
+    assert state["fractional_owners"] == []
+    assert state["accepted_currencies"] == []
+    assert state["price_curve"] == []
+    assert state["terms_hash"] == bytes(32)
+
+
+def test_auction_state_rejects_other_data(auction_data):
+    with pytest.raises(ValueError):
+        parse_auction_state(auction_data[:-1])
+    with pytest.raises(ValueError):
+        parse_auction_state(bytes(len(auction_data)))
+
+
+def test_bid_event(bid_event_data):
+    assert decode_bid_event(bid_event_data) == {
+        "escrow": ESCROW,
+        "bidder": BIDDER,
+        "price": 250,
+    }
+
+
+def test_bid_event_rejects_other_events(bid_event_data):
+    with pytest.raises(ValueError, match="not a Bid event"):
+        decode_bid_event(bid_event_data[:-1])
+    with pytest.raises(ValueError, match="not a Bid event"):
+        decode_bid_event(b"\x00" + bid_event_data[1:])